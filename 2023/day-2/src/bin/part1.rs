@@ -1,7 +1,8 @@
 use aoc2023_day_2::part1;
 
-fn main() {
-    let input = include_str!("input.txt");
-    let result = part1::process(input);
-    println!("Result: {:?}", result.expect("should have a result"));
+fn main() -> miette::Result<()> {
+    let input = aoc_runtime::day!()?.load_input()?;
+    let result = part1::process(&input)?;
+    println!("Result: {}", result);
+    Ok(())
 }
@@ -1,20 +1,18 @@
-use aoc2024_day_1::*;
+use aoc2024_day_1::part2::{self, Strategy};
 
 fn main() {
-    divan::main();
+    // Without a stored input there is nothing to benchmark.
+    if aoc_runtime::bench_input!().is_some() {
+        divan::main();
+    }
 }
 
-#[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!("../input.txt",))).unwrap();
+/// The stored input, which `main` has checked is there.
+fn input() -> &'static str {
+    aoc_runtime::bench_input!().unwrap()
 }
 
-#[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!("../input.txt",))).unwrap();
-}
-
-#[divan::bench]
-fn part2_counter() {
-    part2_counter::process(divan::black_box(include_str!("../input.txt",))).unwrap();
+#[divan::bench(args = Strategy::ALL)]
+fn part2_strategy(strategy: Strategy) {
+    part2::process_with(divan::black_box(input()), strategy).unwrap();
 }
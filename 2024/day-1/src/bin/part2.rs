@@ -1,8 +1,10 @@
-use aoc2024_day_1::part2;
+use aoc_common::solution::Part;
+use aoc_runtime::Args;
+use miette::*;
 
-fn main() {
-    tracing_subscriber::fmt::init();
-    let input = include_str!("../../input.txt");
-    let result = part2::process(input);
-    println!("Result: {}", result.expect("should have a result"));
+fn main() -> Result<()> {
+    aoc_runtime::init();
+    let args = Args::from_env();
+    let input = args.load(&aoc_runtime::day!()?)?;
+    args.solve(&aoc2024_day_1::SOLUTION, Part::Two, &input)
 }
@@ -1,4 +1,32 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+
 pub mod parser;
 pub mod part1;
 pub mod part2;
-pub mod part2_counter;
+
+/// Both parts compare the same two location lists, read as pairs.
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Parsed = Vec<(u32, u32)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        parser::parse_input(input)
+    }
+
+    fn part1(pairs: &Self::Parsed) -> Result<Answer> {
+        part1::solve(pairs)
+    }
+
+    fn part2(pairs: &Self::Parsed) -> Result<Answer> {
+        part2::solve(pairs)
+    }
+
+    fn items(pairs: &Self::Parsed) -> Option<usize> {
+        Some(pairs.len())
+    }
+}
+
+aoc_common::solution!(Day1);
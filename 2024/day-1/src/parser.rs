@@ -1,3 +1,4 @@
+use miette::*;
 use nom::character::complete::{digit1, line_ending};
 use nom::IResult;
 
@@ -12,3 +13,9 @@ pub fn parse_line(input: &str) -> IResult<&str, (u32, u32)> {
 pub fn parse(input: &str) -> IResult<&str, Vec<(u32, u32)>> {
     nom::multi::many1(parse_line)(input)
 }
+
+/// Reads both location lists, one pair per line.
+pub fn parse_input(input: &str) -> Result<Vec<(u32, u32)>> {
+    let (_, pairs) = parse(input).map_err(|e| miette!("Failed to parse input: {}", e))?;
+    Ok(pairs)
+}
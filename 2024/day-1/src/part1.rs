@@ -1,28 +1,25 @@
 use std::vec;
 
+use aoc_common::answer::Answer;
+use aoc_common::par::prelude::*;
 use miette::*;
-use rayon::prelude::*;
 
 use crate::parser::*;
 
 /// Calculate the distance between two numbers
-/// ```
-/// let a: u32 = 3;
-/// let b: u32 = 4;
-/// let result = distance(a, b);
-/// ```
 fn distance(a: &u32, b: &u32) -> u32 {
-    if a > b {
-        a - b
-    } else {
-        b - a
-    }
+    a.abs_diff(*b)
 }
 
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let (_, data) = parse(input).expect("Failed to parse input");
-    let mut vectors = data.into_iter().fold((vec![], vec![]), |mut acc, (a, b)| {
+    Ok(solve(&parse_input(input)?)?.to_string())
+}
+
+/// Sums the distances between the lists once both are sorted.
+#[tracing::instrument(skip(pairs))]
+pub fn solve(pairs: &[(u32, u32)]) -> Result<Answer> {
+    let mut vectors = pairs.iter().fold((vec![], vec![]), |mut acc, &(a, b)| {
         acc.0.push(a);
         acc.1.push(b);
         acc
@@ -38,7 +35,7 @@ pub fn process(input: &str) -> Result<String> {
         .map(|(a, b)| distance(a, b))
         .sum::<u32>();
 
-    Ok(result.to_string())
+    Ok(result.into())
 }
 
 #[cfg(test)]
@@ -1,48 +1,133 @@
 use std::collections::HashMap;
-use std::vec;
+use std::fmt;
 
+use aoc_common::answer::Answer;
+use aoc_common::par::prelude::*;
 use miette::*;
-use rayon::prelude::*;
 
 use crate::parser::*;
 
+/// Below this many location pairs, sorting both lists and merging them beats
+/// building a hash map of occurrences.
+pub const SORTED_THRESHOLD: usize = 4096;
+
+/// How the occurrences of each left-hand number in the right list are counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// Count the right list into a `HashMap`, then look every left-hand
+    /// number up in parallel.
+    HashMap,
+    /// Sort both lists and walk them in lockstep; no hashing at all.
+    Sorted,
+}
+
+impl Strategy {
+    pub const ALL: [Strategy; 2] = [Strategy::HashMap, Strategy::Sorted];
+
+    /// Picks the strategy expected to be fastest for `len` location pairs.
+    pub fn for_len(len: usize) -> Self {
+        if len < SORTED_THRESHOLD {
+            Strategy::Sorted
+        } else {
+            Strategy::HashMap
+        }
+    }
+
+    fn similarity(self, left: Vec<u32>, right: Vec<u32>) -> u32 {
+        match self {
+            Strategy::HashMap => similarity_hash_map(&left, &right),
+            Strategy::Sorted => similarity_sorted(left, right),
+        }
+    }
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Strategy::HashMap => "hash-map",
+            Strategy::Sorted => "sorted",
+        })
+    }
+}
+
+/// Computes the similarity score with the strategy picked from the input size.
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let (_, data) = parse(input).expect("Failed to parse input");
-    let vectors = data.into_iter().fold((vec![], vec![]), |mut acc, (a, b)| {
-        acc.0.push(a);
-        acc.1.push(b);
-        acc
-    });
-
-    let number_occurences = vectors
-        .1
-        .par_iter()
-        .fold(HashMap::new, |mut acc, b| {
-            let counter = acc.entry(b).or_insert(0);
-            *counter += 1;
-            acc
-        })
-        .reduce(HashMap::new, |mut acc, map| {
-            for (key, value) in map {
-                *acc.entry(key).or_insert(0) += value;
-            }
-            acc
-        });
-
-    let result = vectors
-        .0
-        .par_iter()
+    process_inner(input, None)
+}
+
+/// Computes the similarity score with an explicit counting strategy.
+#[tracing::instrument]
+pub fn process_with(input: &str, strategy: Strategy) -> Result<String> {
+    process_inner(input, Some(strategy))
+}
+
+fn process_inner(input: &str, strategy: Option<Strategy>) -> Result<String> {
+    Ok(solve_inner(&parse_input(input)?, strategy)?.to_string())
+}
+
+/// Computes the similarity score with the strategy picked from the input size.
+#[tracing::instrument(skip(pairs))]
+pub fn solve(pairs: &[(u32, u32)]) -> Result<Answer> {
+    solve_inner(pairs, None)
+}
+
+/// Computes the similarity score with an explicit counting strategy.
+#[tracing::instrument(skip(pairs))]
+pub fn solve_with(pairs: &[(u32, u32)], strategy: Strategy) -> Result<Answer> {
+    solve_inner(pairs, Some(strategy))
+}
+
+fn solve_inner(pairs: &[(u32, u32)], strategy: Option<Strategy>) -> Result<Answer> {
+    let (left, right): (Vec<u32>, Vec<u32>) = pairs.iter().copied().unzip();
+
+    let strategy = strategy.unwrap_or_else(|| Strategy::for_len(left.len()));
+    tracing::debug!(%strategy, pairs = left.len(), "counting occurrences");
+
+    Ok(strategy.similarity(left, right).into())
+}
+
+fn similarity_hash_map(left: &[u32], right: &[u32]) -> u32 {
+    let mut number_occurences = HashMap::new();
+    for b in right {
+        *number_occurences.entry(b).or_insert(0) += 1;
+    }
+
+    left.par_iter()
         .map(|a| a * number_occurences.get(a).unwrap_or(&0))
-        .sum::<u32>();
+        .sum::<u32>()
+}
+
+fn similarity_sorted(mut left: Vec<u32>, mut right: Vec<u32>) -> u32 {
+    left.sort_unstable();
+    right.sort_unstable();
 
-    Ok(result.to_string())
+    // Both lists are sorted, so equal values form runs; each run on the left
+    // is matched against the run of the same value on the right.
+    let mut result = 0;
+    let mut j = 0;
+    let mut i = 0;
+    while i < left.len() {
+        let value = left[i];
+        let left_run = left[i..].iter().take_while(|&&a| a == value).count();
+
+        while j < right.len() && right[j] < value {
+            j += 1;
+        }
+        let right_run = right[j..].iter().take_while(|&&b| b == value).count();
+
+        result += value * left_run as u32 * right_run as u32;
+        i += left_run;
+    }
+    result
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use rstest::rstest;
+
     #[test]
     fn it_works() -> miette::Result<()> {
         let input = "3   4
@@ -54,4 +139,23 @@ mod tests {
         assert_eq!("31", process(input)?);
         Ok(())
     }
+
+    #[rstest]
+    #[case("3   4\n4   3\n2   5\n1   3\n3   9\n3   3")]
+    #[case("1   2\n3   4")]
+    #[case("7   7\n7   7\n7   1\n2   7")]
+    #[case("10   1\n1   10\n10   10\n5   10")]
+    fn strategies_agree(#[case] input: &str) -> miette::Result<()> {
+        let expected = process_with(input, Strategy::Sorted)?;
+        for strategy in Strategy::ALL {
+            assert_eq!(process_with(input, strategy)?, expected, "{strategy}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn strategy_follows_input_size() {
+        assert_eq!(Strategy::for_len(10), Strategy::Sorted);
+        assert_eq!(Strategy::for_len(SORTED_THRESHOLD), Strategy::HashMap);
+    }
 }
@@ -1,2 +1,27 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+
 pub mod part1;
 pub mod part2;
+
+/// Both parts walk hiking trails up the same topographic map.
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Parsed = Vec<Vec<u8>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        part1::parse(input)
+    }
+
+    fn part1(map: &Self::Parsed) -> Result<Answer> {
+        part1::solve(map)
+    }
+
+    fn part2(map: &Self::Parsed) -> Result<Answer> {
+        part2::solve(map)
+    }
+}
+
+aoc_common::solution!(Day10);
@@ -1,24 +1,42 @@
+use aoc_common::answer::Answer;
 use miette::*;
 use std::collections::VecDeque;
 
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let map: Vec<Vec<u8>> = input
+    Ok(solve(&parse(input)?)?.to_string())
+}
+
+/// Reads the topographic map, one height per digit.
+pub fn parse(input: &str) -> Result<Vec<Vec<u8>>> {
+    input
         .lines()
-        .map(|line| line.chars().map(|c| c.to_digit(10).unwrap() as u8).collect())
-        .collect();
+        .map(|line| {
+            line.chars()
+                .map(|c| {
+                    c.to_digit(10)
+                        .map(|height| height as u8)
+                        .ok_or_else(|| miette!("Invalid height '{c}'"))
+                })
+                .collect()
+        })
+        .collect()
+}
 
+/// Sums, over every trailhead, how many summits it can reach.
+#[tracing::instrument(skip(map))]
+pub fn solve(map: &[Vec<u8>]) -> Result<Answer> {
     let mut total_score = 0;
 
     for (i, row) in map.iter().enumerate() {
         for (j, &height) in row.iter().enumerate() {
             if height == 0 {
-                total_score += bfs(&map, i, j);
+                total_score += bfs(map, i, j);
             }
         }
     }
 
-    Ok(total_score.to_string())
+    Ok(total_score.into())
 }
 
 fn bfs(map: &[Vec<u8>], start_i: usize, start_j: usize) -> usize {
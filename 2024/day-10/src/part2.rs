@@ -1,27 +1,36 @@
+use aoc_common::answer::Answer;
 use miette::*;
 use std::collections::HashSet;
 
+use crate::part1::parse;
+
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let map: Vec<Vec<u8>> = input
-        .lines()
-        .map(|line| line.chars().map(|c| c.to_digit(10).unwrap() as u8).collect())
-        .collect();
+    Ok(solve(&parse(input)?)?.to_string())
+}
 
+/// Sums, over every trailhead, how many distinct trails lead to a summit.
+#[tracing::instrument(skip(map))]
+pub fn solve(map: &[Vec<u8>]) -> Result<Answer> {
     let mut total_rating = 0;
 
     for (i, row) in map.iter().enumerate() {
         for (j, &height) in row.iter().enumerate() {
             if height == 0 {
-                total_rating += count_distinct_trails(&map, i, j, &mut HashSet::new());
+                total_rating += count_distinct_trails(map, i, j, &mut HashSet::new());
             }
         }
     }
 
-    Ok(total_rating.to_string())
+    Ok(total_rating.into())
 }
 
-fn count_distinct_trails(map: &[Vec<u8>], i: usize, j: usize, visited: &mut HashSet<(usize, usize)>) -> usize {
+fn count_distinct_trails(
+    map: &[Vec<u8>],
+    i: usize,
+    j: usize,
+    visited: &mut HashSet<(usize, usize)>,
+) -> usize {
     if map[i][j] == 9 {
         return 1;
     }
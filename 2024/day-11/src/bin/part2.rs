@@ -1,11 +1,10 @@
+use aoc_common::solution::Part;
+use aoc_runtime::Args;
 use miette::*;
 
-use aoc2024_day_11::part2;
-
 fn main() -> Result<()> {
-    tracing_subscriber::fmt::init();
-    let input = include_str!("../../input2.txt");
-    let result = part2::process(input)?;
-    println!("Result: {}", result);
-    Ok(())
+    aoc_runtime::init();
+    let args = Args::from_env();
+    let input = args.load(&aoc_runtime::day!()?)?;
+    args.solve(&aoc2024_day_11::SOLUTION, Part::Two, &input)
 }
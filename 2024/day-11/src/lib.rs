@@ -1,2 +1,31 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+
 pub mod part1;
 pub mod part2;
+
+/// Both parts blink at the same row of stones, 25 or 75 times.
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Parsed = Vec<u64>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        part1::parse(input)
+    }
+
+    fn part1(stones: &Self::Parsed) -> Result<Answer> {
+        part1::solve(stones)
+    }
+
+    fn part2(stones: &Self::Parsed) -> Result<Answer> {
+        part2::solve(stones)
+    }
+
+    fn items(stones: &Self::Parsed) -> Option<usize> {
+        Some(stones.len())
+    }
+}
+
+aoc_common::solution!(Day11);
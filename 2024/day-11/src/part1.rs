@@ -1,13 +1,23 @@
+use aoc_common::answer::Answer;
 use miette::*;
 
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let mut stones: Vec<Stone> = input
+    Ok(solve(&parse(input)?)?.to_string())
+}
+
+/// Reads the numbers engraved on the stones, in line order.
+pub fn parse(input: &str) -> Result<Vec<u64>> {
+    input
         .split_whitespace()
-        .map(|s| Stone {
-            value: s.parse().unwrap(),
-        })
-        .collect();
+        .map(|s| s.parse().into_diagnostic())
+        .collect()
+}
+
+/// Counts the stones after blinking 25 times, one stone at a time.
+#[tracing::instrument(skip(values))]
+pub fn solve(values: &[u64]) -> Result<Answer> {
+    let mut stones: Vec<Stone> = values.iter().map(|&value| Stone { value }).collect();
 
     for _ in 0..25 {
         let mut new_stones = Vec::new();
@@ -19,7 +29,7 @@ pub fn process(input: &str) -> Result<String> {
         stones = new_stones;
     }
 
-    Ok(stones.len().to_string())
+    Ok(stones.len().into())
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
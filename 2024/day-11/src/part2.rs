@@ -1,20 +1,28 @@
-use indicatif::ProgressIterator;
+use aoc_common::answer::Answer;
 use miette::*;
 use std::collections::HashMap;
 
+use crate::part1::parse;
+
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let mut stone_counts: HashMap<Stone, u64> = input
-        .split_whitespace()
-        .map(|s| Stone {
-            value: s.parse().unwrap(),
-        })
-        .fold(HashMap::new(), |mut acc, stone| {
-            *acc.entry(stone).or_insert(0) += 1;
-            acc
-        });
+    Ok(solve(&parse(input)?)?.to_string())
+}
+
+/// Counts the stones after blinking 75 times, tracking how many stones share
+/// each number.
+#[tracing::instrument(skip(values))]
+pub fn solve(values: &[u64]) -> Result<Answer> {
+    let mut stone_counts: HashMap<Stone, u64> =
+        values
+            .iter()
+            .map(|&value| Stone { value })
+            .fold(HashMap::new(), |mut acc, stone| {
+                *acc.entry(stone).or_insert(0) += 1;
+                acc
+            });
 
-    for _ in (0..75).progress() {
+    for _ in 0..75 {
         let mut new_stone_counts = HashMap::new();
         for (stone, count) in stone_counts.iter() {
             let transformed = stone.transform();
@@ -27,7 +35,7 @@ pub fn process(input: &str) -> Result<String> {
     }
 
     let total_stones: u64 = stone_counts.values().sum();
-    Ok(total_stones.to_string())
+    Ok(total_stones.into())
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -1,11 +1,10 @@
+use aoc_common::solution::Part;
+use aoc_runtime::Args;
 use miette::*;
 
-use aoc2024_day_12::part1;
-
 fn main() -> Result<()> {
-    tracing_subscriber::fmt::init();
-    let input = include_str!("../../input1.txt");
-    let result = part1::process(input)?;
-    println!("Result: {}", result);
-    Ok(())
+    aoc_runtime::init();
+    let args = Args::from_env();
+    let input = args.load(&aoc_runtime::day!()?)?;
+    args.solve(&aoc2024_day_12::SOLUTION, Part::One, &input)
 }
@@ -1,24 +1,35 @@
+use aoc_common::answer::Answer;
 use miette::*;
 use std::collections::HashSet;
 
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let map: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+    Ok(solve(&parse(input)?)?.to_string())
+}
+
+/// Reads the garden plots, one row of plant types per line.
+pub fn parse(input: &str) -> Result<Vec<Vec<char>>> {
+    Ok(input.lines().map(|line| line.chars().collect()).collect())
+}
+
+/// Prices every region by its area times its perimeter.
+#[tracing::instrument(skip(map))]
+pub fn solve(map: &[Vec<char>]) -> Result<Answer> {
     let mut visited = vec![vec![false; map[0].len()]; map.len()];
     let mut total_price = 0;
 
     for i in 0..map.len() {
         for j in 0..map[0].len() {
             if !visited[i][j] {
-                let region = explore_region(&map, &mut visited, i, j);
+                let region = explore_region(map, &mut visited, i, j);
                 let area = region.len();
-                let perimeter = calculate_perimeter(&map, &region);
+                let perimeter = calculate_perimeter(map, &region);
                 total_price += area * perimeter; // Assuming some logic to calculate price
             }
         }
     }
 
-    Ok(total_price.to_string())
+    Ok(total_price.into())
 }
 
 fn explore_region(
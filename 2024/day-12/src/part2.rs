@@ -1,12 +1,19 @@
+use aoc_common::answer::Answer;
 use miette::*;
 use std::collections::{HashMap, HashSet, VecDeque};
 use strum::{EnumIter, IntoEnumIterator};
 
+use crate::part1::parse;
+
 pub fn process(input: &str) -> Result<String> {
-    let map = parse_map(input);
-    let grid = Grid::new(&map);
-    let total_price = calculate_total_price(&grid);
-    Ok(total_price.to_string())
+    Ok(solve(&parse(input)?)?.to_string())
+}
+
+/// Prices every region by its area times its number of sides.
+#[tracing::instrument(skip(map))]
+pub fn solve(map: &[Vec<char>]) -> Result<Answer> {
+    let grid = Grid::new(map);
+    Ok(calculate_total_price(&grid).into())
 }
 
 #[derive(Clone)]
@@ -79,10 +86,6 @@ impl std::ops::Add for Point {
     }
 }
 
-pub fn parse_map(input: &str) -> Vec<Vec<char>> {
-    input.lines().map(|line| line.chars().collect()).collect()
-}
-
 ///
 /// Performs a Breadth-First Search (BFS) on the grid starting from the given point and searching for the specified plant type.
 ///
@@ -127,16 +130,16 @@ pub fn bfs(grid: &Grid, start: Point, plant_type: char) -> (HashSet<Point>, Vec<
 /// 1. For each cell in the grid:
 ///    - 1.1. If the cell has not been visited:
 ///        - 1.1.1. Perform a Breadth-First Search ([`bfs`]) starting from the cell and searching for the plant type.
-///               - The [`bfs`] function explores all connected cells of the same plant type starting from the given cell.
-///               - It returns a set of visited points and a vector of points in the region.
+///          - The [`bfs`] function explores all connected cells of the same plant type starting from the given cell.
+///          - It returns a set of visited points and a vector of points in the region.
 ///        - 1.1.2. Calculate the perimeter of the region using the [`bfs`] result.
-///               - For each point in the region, check its neighbors in all directions.
-///               - If a neighbor is not part of the region, it contributes to the perimeter.
-///               - Use a HashMap to count the number of times each perimeter point is encountered.
+///          - For each point in the region, check its neighbors in all directions.
+///          - If a neighbor is not part of the region, it contributes to the perimeter.
+///          - Use a HashMap to count the number of times each perimeter point is encountered.
 ///        - 1.1.3. Calculate the area of the region.
-///               - The area is simply the number of points in the region.
+///          - The area is simply the number of points in the region.
 ///        - 1.1.4. Calculate the price of the region (area * perimeter).
-///               - Multiply the area by the perimeter to get the price of the region.
+///          - Multiply the area by the perimeter to get the price of the region.
 /// 2. Return the total price of all regions.
 ///    - Sum the prices of all regions to get the total price.
 ///
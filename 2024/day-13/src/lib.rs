@@ -1,2 +1,34 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use machine::ClawMachine;
+use miette::Result;
+
+pub mod machine;
 pub mod part1;
 pub mod part2;
+
+/// Both parts play the same claw machines; the second moves every prize far
+/// away.
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Parsed = Vec<ClawMachine>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        machine::parse(input)
+    }
+
+    fn part1(machines: &Self::Parsed) -> Result<Answer> {
+        part1::solve(machines)
+    }
+
+    fn part2(machines: &Self::Parsed) -> Result<Answer> {
+        part2::solve(machines)
+    }
+
+    fn items(machines: &Self::Parsed) -> Option<usize> {
+        Some(machines.len())
+    }
+}
+
+aoc_common::solution!(Day13);
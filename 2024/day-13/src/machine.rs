@@ -0,0 +1,224 @@
+use miette::*;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{digit1, multispace1},
+    combinator::map_res,
+    sequence::{preceded, tuple},
+    IResult,
+};
+
+#[derive(Debug)]
+pub struct ButtonConfig {
+    pub x: i64,
+    pub y: i64,
+}
+
+#[derive(Debug)]
+pub struct Prize {
+    pub x: i64,
+    pub y: i64,
+}
+
+#[derive(Debug)]
+pub struct ClawMachine {
+    pub button_a: ButtonConfig,
+    pub button_b: ButtonConfig,
+    pub prize: Prize,
+}
+
+impl ClawMachine {
+    /// The cheapest presses of A and B, at most `max_presses` each, that land
+    /// the claw exactly on the prize moved `offset` further along both axes.
+    ///
+    /// The two axes form a 2x2 linear system, solved with Cramer's rule. When
+    /// both buttons move along the same line the system has many solutions,
+    /// and [`Self::collinear_presses`] picks the cheapest.
+    pub fn presses(&self, offset: i64, max_presses: i64) -> Option<(i64, i64)> {
+        let (ax, ay) = (self.button_a.x, self.button_a.y);
+        let (bx, by) = (self.button_b.x, self.button_b.y);
+        let (px, py) = (self.prize.x + offset, self.prize.y + offset);
+
+        let det = ax * by - ay * bx;
+        if det == 0 {
+            return self.collinear_presses(px, py, max_presses);
+        }
+
+        let a_det = px * by - py * bx;
+        let b_det = ax * py - ay * px;
+        if a_det % det != 0 || b_det % det != 0 {
+            return None;
+        }
+
+        let (a, b) = (a_det / det, b_det / det);
+        let in_range = |presses| (0..=max_presses).contains(&presses);
+        (in_range(a) && in_range(b)).then_some((a, b))
+    }
+
+    /// The cheapest presses when both buttons move along the same line.
+    ///
+    /// Along that line the presses satisfy a single equation `a*u + b*v = w`,
+    /// whose integer solutions follow from the extended gcd as
+    /// `(a0 + k*v/g, b0 - k*u/g)`. The cost `3a + b` is linear in `k`, so the
+    /// cheapest solution sits at one end of the range of `k` that keeps both
+    /// counts within `0..=max_presses`. The prize must also lie on the line,
+    /// which the final check against both axes enforces.
+    fn collinear_presses(&self, px: i64, py: i64, max_presses: i64) -> Option<(i64, i64)> {
+        let (ax, ay) = (self.button_a.x as i128, self.button_a.y as i128);
+        let (bx, by) = (self.button_b.x as i128, self.button_b.y as i128);
+        let (px, py, max) = (px as i128, py as i128, max_presses as i128);
+
+        let (u, v, w) = if ax != 0 || bx != 0 {
+            (ax, bx, px)
+        } else {
+            (ay, by, py)
+        };
+        if u == 0 && v == 0 {
+            // Neither button moves the claw at all.
+            return (px == 0 && py == 0).then_some((0, 0));
+        }
+
+        let (g, x, y) = extended_gcd(u, v);
+        if w % g != 0 {
+            return None;
+        }
+        let (a0, b0) = (x * (w / g), y * (w / g));
+        let (step_a, step_b) = (v / g, u / g);
+
+        // Bounds on k from 0 <= a0 + k*step_a <= max and
+        // 0 <= b0 - k*step_b <= max; at least one step is non-zero.
+        let (mut lo, mut hi) = (i128::MIN, i128::MAX);
+        if step_a == 0 {
+            if !(0..=max).contains(&a0) {
+                return None;
+            }
+        } else {
+            lo = lo.max(div_ceil(-a0, step_a));
+            hi = hi.min((max - a0).div_euclid(step_a));
+        }
+        if step_b == 0 {
+            if !(0..=max).contains(&b0) {
+                return None;
+            }
+        } else {
+            lo = lo.max(div_ceil(b0 - max, step_b));
+            hi = hi.min(b0.div_euclid(step_b));
+        }
+        if lo > hi {
+            return None;
+        }
+
+        let k = if 3 * step_a >= step_b { lo } else { hi };
+        let (a, b) = (a0 + k * step_a, b0 - k * step_b);
+        (a * ax + b * bx == px && a * ay + b * by == py).then_some((a as i64, b as i64))
+    }
+}
+
+/// Returns `(g, x, y)` with `u*x + v*y = g = gcd(u, v)`.
+fn extended_gcd(u: i128, v: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (u, v);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    (old_r, old_x, old_y)
+}
+
+fn div_ceil(n: i128, d: i128) -> i128 {
+    -(-n).div_euclid(d)
+}
+
+fn parse_i64(input: &str) -> IResult<&str, i64> {
+    map_res(digit1, str::parse)(input)
+}
+
+fn parse_button_config(input: &str) -> IResult<&str, ButtonConfig> {
+    let (input, (_, x, _, y)) = tuple((tag("X+"), parse_i64, tag(", Y+"), parse_i64))(input)?;
+    Ok((input, ButtonConfig { x, y }))
+}
+
+fn parse_prize(input: &str) -> IResult<&str, Prize> {
+    let (input, (_, x, _, y)) =
+        tuple((tag("Prize: X="), parse_i64, tag(", Y="), parse_i64))(input)?;
+    Ok((input, Prize { x, y }))
+}
+
+fn parse_claw_machine(input: &str) -> IResult<&str, ClawMachine> {
+    let (input, (button_a, _, button_b, _, prize)) = tuple((
+        preceded(tag("Button A: "), parse_button_config),
+        multispace1,
+        preceded(tag("Button B: "), parse_button_config),
+        multispace1,
+        parse_prize,
+    ))(input)?;
+    Ok((
+        input,
+        ClawMachine {
+            button_a,
+            button_b,
+            prize,
+        },
+    ))
+}
+
+fn parse_input(input: &str) -> IResult<&str, Vec<ClawMachine>> {
+    let mut machines = Vec::new();
+    let mut remaining_input = input;
+    while !remaining_input.is_empty() {
+        let (input, machine) = parse_claw_machine(remaining_input)?;
+        machines.push(machine);
+        remaining_input = input.trim_start();
+    }
+    Ok((remaining_input, machines))
+}
+
+/// Reads every claw machine, separated by blank lines.
+pub fn parse(input: &str) -> Result<Vec<ClawMachine>> {
+    let (_, machines) =
+        parse_input(input).map_err(|e| miette!("Failed to parse input: {:?}", e))?;
+    Ok(machines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn machine(a: (i64, i64), b: (i64, i64), prize: (i64, i64)) -> ClawMachine {
+        ClawMachine {
+            button_a: ButtonConfig { x: a.0, y: a.1 },
+            button_b: ButtonConfig { x: b.0, y: b.1 },
+            prize: Prize {
+                x: prize.0,
+                y: prize.1,
+            },
+        }
+    }
+
+    #[test]
+    fn dependent_buttons() {
+        // B is cheaper per step, so A is never pressed.
+        assert_eq!(
+            Some((0, 10)),
+            machine((2, 2), (1, 1), (10, 10)).presses(0, 100)
+        );
+        // A covers four steps for three tokens, so it is pressed as much as
+        // possible.
+        assert_eq!(
+            Some((2, 2)),
+            machine((4, 4), (1, 1), (10, 10)).presses(0, 100)
+        );
+        assert_eq!(
+            Some((250, 0)),
+            machine((4, 4), (1, 1), (1000, 1000)).presses(0, i64::MAX)
+        );
+        // Every solution presses B more than 100 times.
+        assert_eq!(None, machine((4, 4), (1, 1), (1000, 1000)).presses(0, 100));
+        // Off the shared line.
+        assert_eq!(None, machine((2, 2), (1, 1), (10, 11)).presses(0, 100));
+        // On the line, but between reachable points.
+        assert_eq!(None, machine((2, 2), (4, 4), (5, 5)).presses(0, 100));
+    }
+}
@@ -1,155 +1,33 @@
+use aoc_common::answer::Answer;
 use miette::*;
-use nom::{
-    bytes::complete::tag,
-    character::complete::{digit1, multispace1},
-    combinator::map_res,
-    sequence::{preceded, tuple},
-    IResult,
-};
-use std::ops::{Add, Mul};
-use z3::{ast::Ast, Config, Context, Optimize, SatResult};
 
-#[derive(Debug)]
-struct ButtonConfig {
-    x: i32,
-    y: i32,
-}
-
-#[derive(Debug)]
-struct Prize {
-    x: i64,
-    y: i64,
-}
-
-#[derive(Debug)]
-struct ClawMachine {
-    button_a: ButtonConfig,
-    button_b: ButtonConfig,
-    prize: Prize,
-}
-
-fn parse_i32(input: &str) -> IResult<&str, i32> {
-    map_res(digit1, str::parse)(input)
-}
+use crate::machine::{parse, ClawMachine};
 
-fn parse_i64(input: &str) -> IResult<&str, i64> {
-    map_res(digit1, str::parse)(input)
-}
-
-fn parse_button_config(input: &str) -> IResult<&str, ButtonConfig> {
-    let (input, (_, x, _, y)) = tuple((tag("X+"), parse_i32, tag(", Y+"), parse_i32))(input)?;
-    Ok((input, ButtonConfig { x, y }))
-}
-
-fn parse_prize(input: &str) -> IResult<&str, Prize> {
-    let (input, (_, x, _, y)) =
-        tuple((tag("Prize: X="), parse_i64, tag(", Y="), parse_i64))(input)?;
-    Ok((input, Prize { x, y }))
-}
-
-fn parse_claw_machine(input: &str) -> IResult<&str, ClawMachine> {
-    let (input, (button_a, _, button_b, _, prize)) = tuple((
-        preceded(tag("Button A: "), parse_button_config),
-        multispace1,
-        preceded(tag("Button B: "), parse_button_config),
-        multispace1,
-        parse_prize,
-    ))(input)?;
-    Ok((
-        input,
-        ClawMachine {
-            button_a,
-            button_b,
-            prize,
-        },
-    ))
-}
-
-fn parse_input(input: &str) -> IResult<&str, Vec<ClawMachine>> {
-    let mut machines = Vec::new();
-    let mut remaining_input = input;
-    while !remaining_input.is_empty() {
-        let (input, machine) = parse_claw_machine(remaining_input)?;
-        machines.push(machine);
-        remaining_input = input.trim_start();
-    }
-    Ok((remaining_input, machines))
-}
+/// How much further away the prizes really are, along both axes.
+const PRIZE_OFFSET: i64 = 10_000_000_000_000;
 
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let (_, machines) =
-        parse_input(input).map_err(|e| miette!("Failed to parse input: {:?}", e))?;
-
-    let cfg = Config::new();
-    let ctx = Context::new(&cfg);
-    let opt = Optimize::new(&ctx);
-
-    let p0 = z3::ast::Int::new_const(&ctx, "P0");
-    let p1 = z3::ast::Int::new_const(&ctx, "P1");
-
-    opt.assert(&p0.ge(&z3::ast::Int::from_i64(&ctx, 0)));
-    opt.assert(&p1.ge(&z3::ast::Int::from_i64(&ctx, 0)));
-
-    let mut total_tokens = 0;
-
-    for machine in machines {
-        let ClawMachine {
-            button_a,
-            button_b,
-            prize,
-        } = machine;
-        let (ax, ay) = (button_a.x as i64, button_a.y as i64);
-        let (bx, by) = (button_b.x as i64, button_b.y as i64);
-        let (px, py) = (prize.x + 10_000_000_000_000, prize.y + 10_000_000_000_000);
-        let value = p1.clone().mul(&z3::ast::Int::from_i64(&ctx, bx));
-
-        opt.push();
-        opt.assert(
-            &p0.clone()
-                .mul(&z3::ast::Int::from_i64(&ctx, ax))
-                .add(&value)
-                ._eq(&z3::ast::Int::from_i64(&ctx, px)),
-        );
-        opt.assert(
-            &p0.clone()
-                .mul(&z3::ast::Int::from_i64(&ctx, ay))
-                .add(&p1.clone().mul(&z3::ast::Int::from_i64(&ctx, by)))
-                ._eq(&z3::ast::Int::from_i64(&ctx, py)),
-        );
-        opt.minimize(
-            &p0.clone()
-                .mul(&z3::ast::Int::from_i64(&ctx, 3))
-                .add(&p1.clone()),
-        );
-
-        if opt.check(&[]) == SatResult::Sat {
-            let model = opt.get_model().unwrap();
-            let tokens = model
-                .eval(
-                    &p0.clone()
-                        .mul(&z3::ast::Int::from_i64(&ctx, 3))
-                        .add(&p1.clone()),
-                    true,
-                )
-                .unwrap()
-                .as_i64()
-                .unwrap();
-            total_tokens += tokens;
-        }
+    Ok(solve(&parse(input)?)?.to_string())
+}
 
-        opt.pop();
-    }
+/// Sums the fewest tokens that win every winnable prize, once the prizes are
+/// moved to their real positions.
+#[tracing::instrument(skip(machines))]
+pub fn solve(machines: &[ClawMachine]) -> Result<Answer> {
+    let total_tokens: i64 = machines
+        .iter()
+        .filter_map(|machine| machine.presses(PRIZE_OFFSET, i64::MAX))
+        .map(|(a, b)| a * 3 + b)
+        .sum();
 
-    Ok(total_tokens.to_string())
+    Ok(total_tokens.into())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use rstest::rstest;
-
     #[test]
     fn it_works() -> Result<()> {
         let input = "Button A: X+94, Y+34
@@ -167,7 +45,7 @@ Prize: X=7870, Y=6450
 Button A: X+69, Y+23
 Button B: X+27, Y+71
 Prize: X=18641, Y=10279";
-        assert_eq!("480", process(input)?);
+        assert_eq!("875318608908", process(input)?);
         Ok(())
     }
 }
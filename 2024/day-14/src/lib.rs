@@ -1,2 +1,32 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+use part1::Robot;
+
 pub mod part1;
 pub mod part2;
+
+/// Both parts watch the same robots patrol the bathroom.
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Parsed = Vec<Robot>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        part1::parse(input)
+    }
+
+    fn part1(robots: &Self::Parsed) -> Result<Answer> {
+        part1::solve(robots)
+    }
+
+    fn part2(robots: &Self::Parsed) -> Result<Answer> {
+        part2::solve(robots)
+    }
+
+    fn items(robots: &Self::Parsed) -> Option<usize> {
+        Some(robots.len())
+    }
+}
+
+aoc_common::solution!(Day14);
@@ -1,3 +1,4 @@
+use aoc_common::answer::Answer;
 use glam::IVec2;
 use miette::*;
 use nom::{
@@ -10,9 +11,9 @@ use nom::{
 };
 
 #[derive(Debug, Clone, Copy)]
-struct Robot {
-    position: IVec2,
-    velocity: IVec2,
+pub struct Robot {
+    pub position: IVec2,
+    pub velocity: IVec2,
 }
 
 impl Robot {
@@ -38,11 +39,15 @@ fn parse_robot(input: &str) -> IResult<&str, Robot> {
     )(input)
 }
 
-fn parse(input: &str) -> Vec<Robot> {
-    separated_list1(newline, parse_robot)(input).unwrap().1
+/// Reads every robot's position and velocity, one robot per line.
+pub fn parse(input: &str) -> Result<Vec<Robot>> {
+    let (_, robots) = separated_list1(newline, parse_robot)(input)
+        .map_err(|e| miette!("Failed to parse robots: {}", e))?;
+    Ok(robots)
 }
 
-fn simulate(robots: &[Robot], width: i32, height: i32) -> Vec<Robot> {
+/// Moves every robot one second forward, wrapping around the edges.
+pub fn simulate(robots: &[Robot], width: i32, height: i32) -> Vec<Robot> {
     robots
         .iter()
         .map(|robot| {
@@ -54,9 +59,15 @@ fn simulate(robots: &[Robot], width: i32, height: i32) -> Vec<Robot> {
 }
 
 pub fn process(input: &str) -> Result<String> {
+    Ok(solve(&parse(input)?)?.to_string())
+}
+
+/// Multiplies how many robots sit in each quadrant after 100 seconds.
+#[tracing::instrument(skip(robots))]
+pub fn solve(robots: &[Robot]) -> Result<Answer> {
     let width = 101;
     let height = 103;
-    let mut robots = parse(input);
+    let mut robots = robots.to_vec();
 
     for _ in 0..100 {
         robots = simulate(&robots, width, height);
@@ -77,7 +88,7 @@ pub fn process(input: &str) -> Result<String> {
     }
 
     let safety_factor = quadrants.iter().product::<i32>();
-    Ok(safety_factor.to_string())
+    Ok(safety_factor.into())
 }
 
 #[cfg(test)]
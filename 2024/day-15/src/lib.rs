@@ -1,2 +1,33 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+use part1::{Moves, Warehouse};
+
 pub mod part1;
 pub mod part2;
+
+/// Both parts replay the same robot moves, in the warehouse as drawn or
+/// twice as wide.
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Parsed = (Warehouse, Moves);
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        part1::parse(input)
+    }
+
+    fn part1(puzzle: &Self::Parsed) -> Result<Answer> {
+        part1::solve(puzzle)
+    }
+
+    fn part2(puzzle: &Self::Parsed) -> Result<Answer> {
+        part2::solve(puzzle)
+    }
+
+    fn items((_, moves): &Self::Parsed) -> Option<usize> {
+        Some(moves.sequence.len())
+    }
+}
+
+aoc_common::solution!(Day15);
@@ -1,3 +1,4 @@
+use aoc_common::answer::Answer;
 use miette::*;
 use nom::{
     character::complete::{char, line_ending},
@@ -9,21 +10,27 @@ use std::collections::HashMap;
 
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let (_, (mut warehouse, moves)) =
-        parse_input(input).map_err(|e| miette::miette!(e.to_string()))?;
-    simulate_robot(&mut warehouse, &moves);
+    Ok(solve(&parse(input)?)?.to_string())
+}
+
+/// Sums the GPS coordinates of the boxes once the robot has made every move.
+#[tracing::instrument(skip(puzzle))]
+pub fn solve(puzzle: &(Warehouse, Moves)) -> Result<Answer> {
+    let (warehouse, moves) = puzzle;
+    let mut warehouse = warehouse.clone();
+    simulate_robot(&mut warehouse, moves);
     let gps_sum = calculate_gps_sum(&warehouse);
-    Ok(gps_sum.to_string())
+    Ok(gps_sum.into())
 }
 
-#[derive(Debug)]
-struct Warehouse {
-    grid: Vec<Vec<char>>,
+#[derive(Debug, Clone)]
+pub struct Warehouse {
+    pub grid: Vec<Vec<char>>,
 }
 
 #[derive(Debug)]
-struct Moves {
-    sequence: Vec<char>,
+pub struct Moves {
+    pub sequence: Vec<char>,
 }
 
 impl std::fmt::Display for Warehouse {
@@ -38,6 +45,12 @@ impl std::fmt::Display for Warehouse {
     }
 }
 
+/// Reads the warehouse map and the robot's moves below it.
+pub fn parse(input: &str) -> Result<(Warehouse, Moves)> {
+    let (_, parsed) = parse_input(input).map_err(|e| miette!(e.to_string()))?;
+    Ok(parsed)
+}
+
 fn parse_input(input: &str) -> IResult<&str, (Warehouse, Moves)> {
     let (input, grid) = parse_grid(input)?;
     let (input, _) = many0(line_ending)(input)?; // Handle optional newline
@@ -125,7 +138,7 @@ fn simulate_robot(warehouse: &mut Warehouse, moves: &Moves) {
     }
 }
 
-fn find_robot(grid: &[Vec<char>]) -> (usize, usize) {
+pub fn find_robot(grid: &[Vec<char>]) -> (usize, usize) {
     for (i, row) in grid.iter().enumerate() {
         if let Some(j) = row.iter().position(|&c| c == '@') {
             return (i, j);
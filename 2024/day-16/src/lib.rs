@@ -1,2 +1,28 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+use part1::CellType;
+
 pub mod part1;
 pub mod part2;
+
+/// Both parts race the reindeer through the same maze.
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Parsed = Vec<Vec<CellType>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Ok(part1::parse_map(input))
+    }
+
+    fn part1(grid: &Self::Parsed) -> Result<Answer> {
+        part1::solve(grid)
+    }
+
+    fn part2(grid: &Self::Parsed) -> Result<Answer> {
+        part2::solve(grid)
+    }
+}
+
+aoc_common::solution!(Day16);
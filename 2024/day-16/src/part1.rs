@@ -1,15 +1,19 @@
+use aoc_common::answer::Answer;
 use glam::IVec2;
 use miette::*;
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashSet};
 
 pub fn process(input: &str) -> Result<String> {
-    let grid: Vec<Vec<CellType>> = parse_map(input);
-    let start = find_position(&grid, CellType::Start).unwrap();
-    let end = find_position(&grid, CellType::End).unwrap();
+    Ok(solve(&parse_map(input))?.to_string())
+}
 
-    let result = a_star(&grid, start, end).ok_or_else(|| miette!("No path found"))?;
-    Ok(result.to_string())
+/// Finds the lowest score a reindeer can get from the start to the end.
+#[tracing::instrument(skip(grid))]
+pub fn solve(grid: &[Vec<CellType>]) -> Result<Answer> {
+    let (start, end) = endpoints(grid)?;
+    let result = a_star(grid, start, end).ok_or_else(|| miette!("No path found"))?;
+    Ok(result.into())
 }
 
 #[derive(Copy, Clone, Eq, PartialEq)]
@@ -37,7 +41,7 @@ impl PartialOrd for State {
 }
 
 #[derive(Copy, Clone, PartialEq, Eq)]
-enum CellType {
+pub enum CellType {
     Wall,
     Path,
     Start,
@@ -102,13 +106,21 @@ impl From<Direction> for IVec2 {
     }
 }
 
-fn parse_map(input: &str) -> Vec<Vec<CellType>> {
+/// Reads the maze, one row of cells per line.
+pub fn parse_map(input: &str) -> Vec<Vec<CellType>> {
     input
         .lines()
         .map(|line| line.chars().filter_map(CellType::from_char).collect())
         .collect()
 }
 
+/// Where the maze starts and ends.
+pub fn endpoints(grid: &[Vec<CellType>]) -> Result<(IVec2, IVec2)> {
+    let start = find_position(grid, CellType::Start).ok_or_else(|| miette!("No start tile"))?;
+    let end = find_position(grid, CellType::End).ok_or_else(|| miette!("No end tile"))?;
+    Ok((start, end))
+}
+
 fn find_position(grid: &[Vec<CellType>], target: CellType) -> Option<IVec2> {
     for (y, row) in grid.iter().enumerate() {
         for (x, &cell) in row.iter().enumerate() {
@@ -124,13 +136,12 @@ fn a_star(grid: &[Vec<CellType>], start: IVec2, end: IVec2) -> Option<usize> {
     let mut heap = BinaryHeap::new();
     let mut visited = HashSet::new();
 
-    for &direction in Direction::all().iter() {
-        heap.push(State {
-            cost: 0,
-            position: start,
-            direction,
-        });
-    }
+    // The reindeer starts facing east.
+    heap.push(State {
+        cost: 0,
+        position: start,
+        direction: Direction::East,
+    });
 
     while let Some(State {
         cost,
@@ -195,7 +206,7 @@ mod tests {
 #S..#.....#...#
 ###############";
         let result = process(input)?;
-        assert_eq!(result, "6036");
+        assert_eq!(result, "7036");
         Ok(())
     }
 }
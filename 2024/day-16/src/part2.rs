@@ -1,46 +1,24 @@
+use aoc_common::answer::Answer;
 use glam::IVec2;
 use miette::*;
 use std::collections::{HashMap, HashSet, VecDeque};
 
-pub fn process(input: &str) -> Result<String> {
-    let grid: Vec<Vec<CellType>> = parse_map(input);
-    let start = find_position(&grid, CellType::Start).unwrap();
-    let end = find_position(&grid, CellType::End).unwrap();
-
-    let result = find_best_paths(&grid, start, end);
-    Ok(result.len().to_string())
-}
+use crate::part1::{endpoints, parse_map, CellType};
 
-#[derive(Copy, Clone, PartialEq, Eq)]
-enum CellType {
-    Wall,
-    Path,
-    Start,
-    End,
-}
+/// Index of [`Direction::East`] in [`Direction::from_index`], the way the
+/// reindeer starts facing.
+const EAST: usize = 1;
 
-impl CellType {
-    fn from_char(c: char) -> Option<Self> {
-        match c {
-            '#' => Some(CellType::Wall),
-            '.' => Some(CellType::Path),
-            'S' => Some(CellType::Start),
-            'E' => Some(CellType::End),
-            _ => None,
-        }
-    }
+pub fn process(input: &str) -> Result<String> {
+    Ok(solve(&parse_map(input))?.to_string())
 }
 
-impl From<char> for CellType {
-    fn from(c: char) -> Self {
-        match c {
-            '#' => CellType::Wall,
-            '.' => CellType::Path,
-            'S' => CellType::Start,
-            'E' => CellType::End,
-            _ => panic!("Invalid cell type"),
-        }
-    }
+/// Counts the tiles that lie on at least one of the best paths.
+#[tracing::instrument(skip(grid))]
+pub fn solve(grid: &[Vec<CellType>]) -> Result<Answer> {
+    let (start, end) = endpoints(grid)?;
+    let result = find_best_paths(grid, start, end);
+    Ok(result.len().into())
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Ord, PartialOrd)]
@@ -78,24 +56,6 @@ impl From<Direction> for IVec2 {
     }
 }
 
-fn parse_map(input: &str) -> Vec<Vec<CellType>> {
-    input
-        .lines()
-        .map(|line| line.chars().filter_map(CellType::from_char).collect())
-        .collect()
-}
-
-fn find_position(grid: &[Vec<CellType>], target: CellType) -> Option<IVec2> {
-    for (y, row) in grid.iter().enumerate() {
-        for (x, &cell) in row.iter().enumerate() {
-            if cell == target {
-                return Some(IVec2::new(x as i32, y as i32));
-            }
-        }
-    }
-    None
-}
-
 fn find_best_paths(grid: &[Vec<CellType>], start: IVec2, end: IVec2) -> HashSet<IVec2> {
     let adj = |state: &(IVec2, usize)| -> Vec<((IVec2, usize), usize)> {
         let (position, direction) = *state;
@@ -122,8 +82,8 @@ fn find_best_paths(grid: &[Vec<CellType>], start: IVec2, end: IVec2) -> HashSet<
     let mut prevs: HashMap<(IVec2, usize), Vec<(IVec2, usize)>> = HashMap::new();
     let mut queue = VecDeque::new();
 
-    queue.push_back(((start, 0), 0));
-    distances.insert((start, 0), 0);
+    queue.push_back(((start, EAST), 0));
+    distances.insert((start, EAST), 0);
 
     while let Some(((current, direction), current_cost)) = queue.pop_front() {
         for (next_state, edge_cost) in adj(&(current, direction)) {
@@ -133,12 +93,16 @@ fn find_best_paths(grid: &[Vec<CellType>], start: IVec2, end: IVec2) -> HashSet<
                 prevs.insert(next_state, vec![(current, direction)]);
                 queue.push_back((next_state, new_cost));
             } else if new_cost == distances[&next_state] {
-                prevs.get_mut(&next_state).unwrap().push((current, direction));
+                prevs
+                    .get_mut(&next_state)
+                    .unwrap()
+                    .push((current, direction));
             }
         }
     }
 
-    let min_cost = distances.iter()
+    let min_cost = distances
+        .iter()
         .filter(|((position, _), _)| *position == end)
         .map(|(_, &cost)| cost)
         .min()
@@ -0,0 +1,94 @@
+use miette::*;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{digit1, multispace0, space0},
+    combinator::map_res,
+    multi::separated_list1,
+    sequence::{preceded, separated_pair},
+    IResult,
+};
+
+/// The 3-bit computer: its three registers and the program it runs.
+#[derive(Debug, Clone)]
+pub struct Computer {
+    pub registers: [u64; 3],
+    pub program: Vec<u8>,
+}
+
+impl Computer {
+    /// Runs the program from the start with register A set to `a`, returning
+    /// everything it outputs.
+    pub fn run(&self, a: u64) -> Vec<u8> {
+        let [_, mut b, mut c] = self.registers;
+        let mut a = a;
+        let mut output = Vec::new();
+        let mut pc = 0;
+
+        while pc + 1 < self.program.len() {
+            let (opcode, operand) = (self.program[pc], self.program[pc + 1]);
+            let combo = || match operand {
+                0..=3 => operand as u64,
+                4 => a,
+                5 => b,
+                6 => c,
+                _ => unreachable!("combo operand 7 is reserved"),
+            };
+
+            match opcode {
+                0 => a >>= combo(),
+                1 => b ^= operand as u64,
+                2 => b = combo() % 8,
+                3 if a != 0 => {
+                    pc = operand as usize;
+                    continue;
+                }
+                3 => {}
+                4 => b ^= c,
+                5 => output.push((combo() % 8) as u8),
+                6 => b = a >> combo(),
+                7 => c = a >> combo(),
+                _ => unreachable!("opcodes are 3-bit"),
+            }
+            pc += 2;
+        }
+
+        output
+    }
+}
+
+/// Reads the registers and the program.
+pub fn parse(input: &str) -> Result<Computer> {
+    let (_, computer) =
+        parse_all(input.trim()).map_err(|e| miette!("Failed to parse input {e}"))?;
+    if let Some(&value) = computer.program.iter().find(|&&value| value > 7) {
+        bail!("Program value {value} is not 3-bit");
+    }
+    Ok(computer)
+}
+
+fn parse_all(input: &str) -> IResult<&str, Computer> {
+    let (input, register_a) = parse_register("Register A")(input)?;
+    let (input, register_b) = parse_register("Register B")(input)?;
+    let (input, register_c) = parse_register("Register C")(input)?;
+    let (input, program) = preceded(tag("Program: "), parse_program)(input)?;
+    Ok((
+        input,
+        Computer {
+            registers: [register_a, register_b, register_c],
+            program,
+        },
+    ))
+}
+
+fn parse_register<'a>(label: &'a str) -> impl Fn(&'a str) -> IResult<&'a str, u64> {
+    move |input: &'a str| {
+        let (input, (_, value)) =
+            separated_pair(tag(label), tag(": "), map_res(digit1, str::parse))(input)?;
+        let (input, _) = multispace0(input)?;
+        Ok((input, value))
+    }
+}
+
+fn parse_program(input: &str) -> IResult<&str, Vec<u8>> {
+    separated_list1(preceded(space0, tag(",")), map_res(digit1, str::parse))(input)
+}
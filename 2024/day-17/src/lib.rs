@@ -1,2 +1,33 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use computer::Computer;
+use miette::Result;
+
+pub mod computer;
 pub mod part1;
 pub mod part2;
+
+/// Both parts run the same program on the 3-bit computer.
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Parsed = Computer;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        computer::parse(input)
+    }
+
+    fn part1(computer: &Self::Parsed) -> Result<Answer> {
+        part1::solve(computer)
+    }
+
+    fn part2(computer: &Self::Parsed) -> Result<Answer> {
+        part2::solve(computer)
+    }
+
+    fn items(computer: &Self::Parsed) -> Option<usize> {
+        Some(computer.program.len())
+    }
+}
+
+aoc_common::solution!(Day17);
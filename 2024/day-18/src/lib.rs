@@ -1,2 +1,32 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+use part1::Point;
+
 pub mod part1;
 pub mod part2;
+
+/// Both parts drop the same bytes onto the memory grid.
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Parsed = Vec<Point>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        part1::parse(input)
+    }
+
+    fn part1(points: &Self::Parsed) -> Result<Answer> {
+        part1::solve(points)
+    }
+
+    fn part2(points: &Self::Parsed) -> Result<Answer> {
+        part2::solve(points)
+    }
+
+    fn items(points: &Self::Parsed) -> Option<usize> {
+        Some(points.len())
+    }
+}
+
+aoc_common::solution!(Day18);
@@ -1,12 +1,14 @@
+use aoc_common::answer::Answer;
 use miette::*;
 use std::collections::{HashSet, VecDeque};
 
-const GRID_SIZE: usize = 71;
+pub const GRID_SIZE: usize = 71;
+pub const FALLEN_BYTES: usize = 1024;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Point {
-    x: usize,
-    y: usize,
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
 }
 
 impl Point {
@@ -40,20 +42,27 @@ impl Point {
     }
 }
 
-fn parse_input(input: &str) -> Vec<Point> {
+/// Reads the falling byte positions, in the order they fall.
+pub fn parse(input: &str) -> Result<Vec<Point>> {
     input
         .lines()
         .map(|line| {
-            let parts: Vec<usize> = line.split(',').map(|s| s.parse().unwrap()).collect();
-            Point {
-                x: parts[0],
-                y: parts[1],
+            let (x, y) = line
+                .split_once(',')
+                .ok_or_else(|| miette!("Invalid byte position {line:?}"))?;
+            let point = Point {
+                x: x.trim().parse().into_diagnostic()?,
+                y: y.trim().parse().into_diagnostic()?,
+            };
+            if point.x >= GRID_SIZE || point.y >= GRID_SIZE {
+                bail!("Byte position {line:?} is outside the memory space");
             }
+            Ok(point)
         })
         .collect()
 }
 
-fn is_path_possible(grid: &[Vec<bool>], start: Point, end: Point) -> Option<usize> {
+pub fn is_path_possible(grid: &[Vec<bool>], start: Point, end: Point) -> Option<usize> {
     let mut queue = VecDeque::new();
     let mut visited = HashSet::new();
     queue.push_back((start, 0));
@@ -75,11 +84,16 @@ fn is_path_possible(grid: &[Vec<bool>], start: Point, end: Point) -> Option<usiz
     None
 }
 
+#[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let points = parse_input(input);
+    Ok(solve(&parse(input)?)?.to_string())
+}
 
+/// Counts the steps to the exit once the first kilobyte has fallen.
+#[tracing::instrument(skip(points))]
+pub fn solve(points: &[Point]) -> Result<Answer> {
     let mut grid = vec![vec![false; GRID_SIZE]; GRID_SIZE];
-    for point in points.iter().take(1024) {
+    for point in points.iter().take(FALLEN_BYTES) {
         grid[point.x][point.y] = true;
     }
 
@@ -92,5 +106,5 @@ pub fn process(input: &str) -> Result<String> {
     let result = is_path_possible(&grid, start, end)
         .ok_or_else(|| miette!("No path found from start to end"))?;
 
-    Ok(result.to_string())
+    Ok(result.into())
 }
@@ -1,86 +1,18 @@
-use indicatif::ProgressIterator;
+use aoc_common::answer::Answer;
 use miette::*;
-use std::collections::{HashSet, VecDeque};
 
-const GRID_SIZE: usize = 71;
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Point {
-    x: usize,
-    y: usize,
-}
-
-impl Point {
-    fn neighbors(&self) -> Vec<Point> {
-        let mut neighbors = Vec::new();
-        if self.x > 0 {
-            neighbors.push(Point {
-                x: self.x - 1,
-                y: self.y,
-            });
-        }
-        if self.x < GRID_SIZE - 1 {
-            neighbors.push(Point {
-                x: self.x + 1,
-                y: self.y,
-            });
-        }
-        if self.y > 0 {
-            neighbors.push(Point {
-                x: self.x,
-                y: self.y - 1,
-            });
-        }
-        if self.y < GRID_SIZE - 1 {
-            neighbors.push(Point {
-                x: self.x,
-                y: self.y + 1,
-            });
-        }
-        neighbors
-    }
-}
-
-fn parse_input(input: &str) -> Vec<Point> {
-    input
-        .lines()
-        .map(|line| {
-            let parts: Vec<usize> = line.split(',').map(|s| s.parse().unwrap()).collect();
-            Point {
-                x: parts[0],
-                y: parts[1],
-            }
-        })
-        .collect()
-}
-
-fn is_path_possible(grid: &[Vec<bool>], start: Point, end: Point) -> Option<usize> {
-    let mut queue = VecDeque::new();
-    let mut visited = HashSet::new();
-    queue.push_back((start, 0));
-    visited.insert(start);
-
-    while let Some((current, steps)) = queue.pop_front() {
-        if current == end {
-            return Some(steps);
-        }
-
-        for neighbor in current.neighbors() {
-            if !visited.contains(&neighbor) && !grid[neighbor.x][neighbor.y] {
-                visited.insert(neighbor);
-                queue.push_back((neighbor, steps + 1));
-            }
-        }
-    }
-
-    None
-}
+use crate::part1::{is_path_possible, parse, Point, FALLEN_BYTES, GRID_SIZE};
 
+#[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let points = parse_input(input);
+    Ok(solve(&parse(input)?)?.to_string())
+}
 
+/// Finds the first byte after the first kilobyte that cuts off the exit.
+#[tracing::instrument(skip(points))]
+pub fn solve(points: &[Point]) -> Result<Answer> {
     let mut grid = vec![vec![false; GRID_SIZE]; GRID_SIZE];
-    for point in points.iter().take(1024) {
+    for point in points.iter().take(FALLEN_BYTES) {
         grid[point.x][point.y] = true;
     }
 
@@ -90,20 +22,12 @@ pub fn process(input: &str) -> Result<String> {
         y: GRID_SIZE - 1,
     };
 
-    let mut result = None;
-    for point in points[1024..].iter().progress() {
+    for point in points.iter().skip(FALLEN_BYTES) {
         grid[point.x][point.y] = true;
         if is_path_possible(&grid, start, end).is_none() {
-            result = Some(point);
-            break;
+            return Ok(format!("{},{}", point.x, point.y).into());
         }
     }
 
-    let formatted_result = if let Some(point) = result {
-        format!("{},{}", point.x, point.y)
-    } else {
-        "No failure point found".to_string()
-    };
-
-    Ok(formatted_result)
+    bail!("No failure point found")
 }
@@ -1,2 +1,32 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+use part1::Onsen;
+
 pub mod part1;
 pub mod part2;
+
+/// Both parts arrange the same towel patterns into the same designs.
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Parsed = Onsen;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        part1::parse(input)
+    }
+
+    fn part1(onsen: &Self::Parsed) -> Result<Answer> {
+        part1::solve(onsen)
+    }
+
+    fn part2(onsen: &Self::Parsed) -> Result<Answer> {
+        part2::solve(onsen)
+    }
+
+    fn items(onsen: &Self::Parsed) -> Option<usize> {
+        Some(onsen.designs.len())
+    }
+}
+
+aoc_common::solution!(Day19);
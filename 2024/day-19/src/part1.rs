@@ -1,32 +1,53 @@
+use aoc_common::answer::Answer;
 use miette::*;
 use std::collections::HashMap;
 
+/// The available towel patterns and the designs to arrange with them.
+#[derive(Debug, Clone)]
+pub struct Onsen {
+    pub patterns: Vec<String>,
+    pub designs: Vec<String>,
+}
+
+/// Reads the towel patterns and the designs below them.
+pub fn parse(input: &str) -> Result<Onsen> {
+    let input = input.replace("\r\n", "\n");
+    let (patterns, designs) = input
+        .trim()
+        .split_once("\n\n")
+        .ok_or_else(|| miette!("Missing the blank line between patterns and designs"))?;
+    Ok(Onsen {
+        patterns: patterns.split(", ").map(|p| p.trim().to_string()).collect(),
+        designs: designs.lines().map(|d| d.trim().to_string()).collect(),
+    })
+}
+
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let mut sections = input.split("\n\n");
-    let patterns: Vec<&str> = sections.next().unwrap().split(", ").collect();
-    let designs: Vec<&str> = sections.next().unwrap().lines().collect();
+    Ok(solve(&parse(input)?)?.to_string())
+}
 
+/// Counts the designs some arrangement of towels can make.
+#[tracing::instrument(skip(onsen))]
+pub fn solve(onsen: &Onsen) -> Result<Answer> {
     let mut memo = HashMap::new();
-    let mut count = 0;
-
-    for design in designs {
-        if can_construct(design, &patterns, &mut memo) {
-            count += 1;
-        }
-    }
+    let count = onsen
+        .designs
+        .iter()
+        .filter(|design| can_construct(design, &onsen.patterns, &mut memo))
+        .count();
 
-    Ok(count.to_string())
+    Ok(count.into())
 }
 
-fn can_construct(design: &str, patterns: &[&str], memo: &mut HashMap<String, bool>) -> bool {
+fn can_construct(design: &str, patterns: &[String], memo: &mut HashMap<String, bool>) -> bool {
     if design.is_empty() {
         return true;
     }
     if let Some(&result) = memo.get(design) {
         return result;
     }
-    for &pattern in patterns {
+    for pattern in patterns {
         if design.starts_with(pattern) {
             let remaining = design.strip_prefix(pattern).unwrap();
             if can_construct(remaining, patterns, memo) {
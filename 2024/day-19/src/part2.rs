@@ -1,23 +1,28 @@
+use aoc_common::answer::Answer;
 use miette::*;
 use std::collections::HashMap;
 
+use crate::part1::{parse, Onsen};
+
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let mut sections = input.split("\n\n");
-    let patterns: Vec<&str> = sections.next().unwrap().split(", ").collect();
-    let designs: Vec<&str> = sections.next().unwrap().lines().collect();
+    Ok(solve(&parse(input)?)?.to_string())
+}
 
+/// Sums the number of towel arrangements for every design.
+#[tracing::instrument(skip(onsen))]
+pub fn solve(onsen: &Onsen) -> Result<Answer> {
     let mut memo = HashMap::new();
-    let mut total_ways = 0;
-
-    for design in designs {
-        total_ways += count_ways(design, &patterns, &mut memo);
-    }
+    let total_ways: usize = onsen
+        .designs
+        .iter()
+        .map(|design| count_ways(design, &onsen.patterns, &mut memo))
+        .sum();
 
-    Ok(total_ways.to_string())
+    Ok(total_ways.into())
 }
 
-fn count_ways(design: &str, patterns: &[&str], memo: &mut HashMap<String, usize>) -> usize {
+fn count_ways(design: &str, patterns: &[String], memo: &mut HashMap<String, usize>) -> usize {
     if design.is_empty() {
         return 1;
     }
@@ -25,7 +30,7 @@ fn count_ways(design: &str, patterns: &[&str], memo: &mut HashMap<String, usize>
         return result;
     }
     let mut ways = 0;
-    for &pattern in patterns {
+    for pattern in patterns {
         if design.starts_with(pattern) {
             let remaining = design.strip_prefix(pattern).unwrap();
             ways += count_ways(remaining, patterns, memo);
@@ -1,3 +1,32 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+
 pub mod parser;
 pub mod part1;
 pub mod part2;
+
+/// Both parts judge the same reports; the second tolerates one bad level.
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Parsed = Vec<Vec<usize>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        parser::parse_input(input)
+    }
+
+    fn part1(reports: &Self::Parsed) -> Result<Answer> {
+        part1::solve(reports)
+    }
+
+    fn part2(reports: &Self::Parsed) -> Result<Answer> {
+        part2::solve(reports)
+    }
+
+    fn items(reports: &Self::Parsed) -> Option<usize> {
+        Some(reports.len())
+    }
+}
+
+aoc_common::solution!(Day2);
@@ -1,3 +1,4 @@
+use miette::*;
 use nom::{
     bytes::complete::tag,
     character::complete::{digit1, line_ending},
@@ -15,3 +16,10 @@ pub fn parse_line(line: &str) -> IResult<&str, Vec<usize>> {
 pub fn parse_reports(input: &str) -> IResult<&str, Vec<Vec<usize>>> {
     separated_list1(line_ending, parse_line)(input)
 }
+
+/// Reads every report, one per line.
+pub fn parse_input(input: &str) -> Result<Vec<Vec<usize>>> {
+    let (_, reports) =
+        parse_reports(input).map_err(|e| miette!("Failed to parse reports: {}", e))?;
+    Ok(reports)
+}
@@ -1,13 +1,18 @@
+use aoc_common::answer::Answer;
 use miette::*;
-use tracing::*;
 
 use crate::parser::*;
 
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let reports = parse_reports(input).expect("failed to parse reports").1;
+    Ok(solve(&parse_input(input)?)?.to_string())
+}
+
+/// Counts the reports whose levels change safely.
+#[tracing::instrument(skip(reports))]
+pub fn solve(reports: &[Vec<usize>]) -> Result<Answer> {
     let safe_reports = reports.iter().filter(|r| is_safe_report(r)).count();
-    Ok(safe_reports.to_string())
+    Ok(safe_reports.into())
 }
 
 fn is_safe_report(report: &[usize]) -> bool {
@@ -52,10 +57,10 @@ mod tests {
 
     #[test]
     fn is_safe_report_works() {
-        assert_eq!(is_safe_report(&[1, 2, 3, 4, 5]), true);
-        assert_eq!(is_safe_report(&[5, 4, 3, 2, 1]), true);
-        assert_eq!(is_safe_report(&[1, 2, 3, 3, 3]), false);
-        assert_eq!(is_safe_report(&[1, 2, 3, 5, 5]), false);
-        assert_eq!(is_safe_report(&[1, 2, 3, 5, 4]), false);
+        assert!(is_safe_report(&[1, 2, 3, 4, 5]));
+        assert!(is_safe_report(&[5, 4, 3, 2, 1]));
+        assert!(!is_safe_report(&[1, 2, 3, 3, 3]));
+        assert!(!is_safe_report(&[1, 2, 3, 5, 5]));
+        assert!(!is_safe_report(&[1, 2, 3, 5, 4]));
     }
 }
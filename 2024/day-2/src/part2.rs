@@ -1,13 +1,18 @@
+use aoc_common::answer::Answer;
 use miette::*;
-use tracing::*;
 
 use crate::parser::*;
 
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let reports = parse_reports(input).expect("failed to parse reports").1;
+    Ok(solve(&parse_input(input)?)?.to_string())
+}
+
+/// Counts the reports that are safe once at most one level is removed.
+#[tracing::instrument(skip(reports))]
+pub fn solve(reports: &[Vec<usize>]) -> Result<Answer> {
     let safe_reports = reports.iter().filter(|r| is_safe_report(r)).count();
-    Ok(safe_reports.to_string())
+    Ok(safe_reports.into())
 }
 
 fn is_safe_report(report: &[usize]) -> bool {
@@ -32,8 +37,6 @@ fn is_strictly_increasing_or_decreasing(report: &[usize], skip_index: Option<usi
     let mut increasing = true;
     let mut decreasing = true;
 
-    let mut fail_count = 0;
-
     for i in 0..report.len() - 1 {
         if Some(i) == skip_index {
             continue;
@@ -57,10 +60,7 @@ fn is_strictly_increasing_or_decreasing(report: &[usize], skip_index: Option<usi
             decreasing = false;
         }
         if !increasing && !decreasing {
-            fail_count += 1;
-            if fail_count > 1 {
-                return false;
-            }
+            return false;
         }
     }
 
@@ -82,4 +82,11 @@ mod tests {
         assert_eq!("4", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn turning_back_is_unsafe() -> miette::Result<()> {
+        // No single removal leaves a report that only rises or only falls.
+        assert_eq!("0", process("1 2 3 4 3 2")?);
+        Ok(())
+    }
 }
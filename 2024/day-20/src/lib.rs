@@ -1,2 +1,28 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+use part1::Racetrack;
+
 pub mod part1;
 pub mod part2;
+
+/// Both parts look for cheats on the same racetrack.
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Parsed = Racetrack;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        part1::parse(input)
+    }
+
+    fn part1(track: &Self::Parsed) -> Result<Answer> {
+        part1::solve(track)
+    }
+
+    fn part2(track: &Self::Parsed) -> Result<Answer> {
+        part2::solve(track)
+    }
+}
+
+aoc_common::solution!(Day20);
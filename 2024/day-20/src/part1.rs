@@ -1,31 +1,63 @@
+use aoc_common::answer::Answer;
 use glam::IVec2;
 use miette::*;
 use std::collections::{hash_map, HashMap, HashSet, VecDeque};
 
-#[tracing::instrument]
-pub fn process(input: &str) -> Result<String> {
+/// The racetrack map with its start and end positions.
+#[derive(Debug, Clone)]
+pub struct Racetrack {
+    pub grid: Vec<Vec<char>>,
+    pub start: Position,
+    pub end: Position,
+}
+
+/// Reads the racetrack and locates `S` and `E`.
+pub fn parse(input: &str) -> Result<Racetrack> {
     let grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
 
-    let mut start = Position::ZERO;
-    let mut end = Position::ZERO;
+    let mut start = None;
+    let mut end = None;
 
     for (y, row) in grid.iter().enumerate() {
         for (x, &cell) in row.iter().enumerate() {
             if cell == 'S' {
-                start = Position::new(x as i32, y as i32);
+                start = Some(Position::new(x as i32, y as i32));
             } else if cell == 'E' {
-                end = Position::new(x as i32, y as i32);
+                end = Some(Position::new(x as i32, y as i32));
             }
         }
     }
 
-    let start_distance = bfs(&grid, start);
-    let end_distance = bfs(&grid, end);
-    let final_distance = start_distance[&end];
+    Ok(Racetrack {
+        grid,
+        start: start.ok_or_else(|| miette!("No start position found"))?,
+        end: end.ok_or_else(|| miette!("No end position found"))?,
+    })
+}
+
+#[tracing::instrument]
+pub fn process(input: &str) -> Result<String> {
+    Ok(solve(&parse(input)?)?.to_string())
+}
+
+/// Counts the two-picosecond cheats that save at least 100 picoseconds.
+#[tracing::instrument(skip(track))]
+pub fn solve(track: &Racetrack) -> Result<Answer> {
+    count_cheats(track, 2, 100).map(Answer::from)
+}
+
+/// Counts the cheats of at most `max_skip` picoseconds that save at least
+/// `min_saved` picoseconds.
+pub fn count_cheats(track: &Racetrack, max_skip: i32, min_saved: i32) -> Result<usize> {
+    let start_distance = bfs(&track.grid, track.start);
+    let end_distance = bfs(&track.grid, track.end);
+    let final_distance = *start_distance
+        .get(&track.end)
+        .ok_or_else(|| miette!("The end is not reachable from the start"))?;
 
     let mut skips = 0;
-    let mut saved_counts = HashMap::new();
-    let walkable_cells: Vec<Position> = grid
+    let walkable_cells: Vec<Position> = track
+        .grid
         .iter()
         .enumerate()
         .flat_map(|(y, row)| {
@@ -34,29 +66,26 @@ pub fn process(input: &str) -> Result<String> {
                 .filter(|&(_, &cell)| cell != '#')
                 .map(move |(x, _)| Position::new(x as i32, y as i32))
         })
+        .filter(|point| start_distance.contains_key(point))
         .collect();
 
     for &point1 in walkable_cells.iter() {
         for &point2 in walkable_cells.iter() {
             let skip_distance = manhattan_distance(point1, point2);
-            if skip_distance > 2 {
+            if skip_distance > max_skip {
                 continue;
             }
             let nd = start_distance[&point1] + skip_distance + end_distance[&point2];
-            let saved = final_distance - nd;
-            if saved > 0 {
-                *saved_counts.entry(saved).or_insert(0) += 1;
-                if saved >= 100 {
-                    skips += 1;
-                }
+            if final_distance - nd >= min_saved {
+                skips += 1;
             }
         }
     }
 
-    Ok(skips.to_string())
+    Ok(skips)
 }
 
-type Position = IVec2;
+pub type Position = IVec2;
 
 fn manhattan_distance(a: Position, b: Position) -> i32 {
     (a.x - b.x).abs() + (a.y - b.y).abs()
@@ -135,6 +164,7 @@ mod tests {
 #...#...#...###
 ###############";
         assert_eq!("0", process(input)?);
+        assert_eq!(44, count_cheats(&parse(input)?, 2, 1)?);
         Ok(())
     }
 }
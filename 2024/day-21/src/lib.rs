@@ -1,2 +1,31 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+
 pub mod part1;
 pub mod part2;
+
+/// Both parts type the same door codes through a chain of keypads.
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Parsed = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        part1::parse(input)
+    }
+
+    fn part1(codes: &Self::Parsed) -> Result<Answer> {
+        part1::solve(codes)
+    }
+
+    fn part2(codes: &Self::Parsed) -> Result<Answer> {
+        part2::solve(codes)
+    }
+
+    fn items(codes: &Self::Parsed) -> Option<usize> {
+        Some(codes.len())
+    }
+}
+
+aoc_common::solution!(Day21);
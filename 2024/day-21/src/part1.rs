@@ -1,3 +1,4 @@
+use aoc_common::answer::Answer;
 use itertools::Itertools;
 use miette::*;
 use std::collections::{HashMap, VecDeque};
@@ -5,7 +6,38 @@ use std::collections::{HashMap, VecDeque};
 type Grid = Vec<Vec<Option<char>>>;
 type Sequences = HashMap<(char, char), Vec<String>>;
 
+/// Reads the door codes, one per line.
+pub fn parse(input: &str) -> Result<Vec<String>> {
+    input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let valid = line
+                .strip_suffix('A')
+                .is_some_and(|digits| digits.chars().all(|c| c.is_ascii_digit()));
+            if !valid {
+                bail!("Invalid door code {line:?}");
+            }
+            Ok(line.to_string())
+        })
+        .collect()
+}
+
+#[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
+    Ok(solve(&parse(input)?)?.to_string())
+}
+
+/// Sums the complexities with two robots on directional keypads.
+#[tracing::instrument(skip(codes))]
+pub fn solve(codes: &[String]) -> Result<Answer> {
+    Ok(total_complexity(codes, 2).into())
+}
+
+/// Sums each code's numeric part times the length of the shortest sequence
+/// typed through `robots` directional keypads.
+pub fn total_complexity(codes: &[String], robots: i32) -> u64 {
     let num_keypad = vec![
         vec![Some('7'), Some('8'), Some('9')],
         vec![Some('4'), Some('5'), Some('6')],
@@ -26,22 +58,19 @@ pub fn process(input: &str) -> Result<String> {
         .collect();
 
     let mut cache = HashMap::new();
-    let total: i32 = input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(|line| {
-            let numeric = line[..line.len() - 1].parse::<i32>().unwrap_or(0);
-            let inputs = solve(line, &num_sequences);
+    codes
+        .iter()
+        .map(|code| {
+            let numeric = code[..code.len() - 1].parse::<u64>().unwrap_or(0);
+            let inputs = expand_code(code, &num_sequences);
             let length = inputs
                 .iter()
-                .map(|seq| compute_length(seq, 2, &dir_sequences, &dir_lengths, &mut cache))
+                .map(|seq| compute_length(seq, robots, &dir_sequences, &dir_lengths, &mut cache))
                 .min()
                 .unwrap_or(0);
             numeric * length
         })
-        .sum();
-
-    Ok(total.to_string())
+        .sum()
 }
 
 fn compute_sequences(keypad: &Grid) -> Sequences {
@@ -103,7 +132,7 @@ fn compute_sequences(keypad: &Grid) -> Sequences {
     sequences
 }
 
-fn solve(string: &str, sequences: &Sequences) -> Vec<String> {
+fn expand_code(string: &str, sequences: &Sequences) -> Vec<String> {
     let pairs: Vec<_> = std::iter::once('A')
         .chain(string.chars())
         .tuple_windows()
@@ -111,50 +140,36 @@ fn solve(string: &str, sequences: &Sequences) -> Vec<String> {
 
     let options: Vec<_> = pairs.iter().map(|(x, y)| &sequences[&(*x, *y)]).collect();
 
-    cartesian_product(&options)
-        .into_iter()
-        .map(|v| v.join(""))
+    options
+        .iter()
+        .map(|v| v.iter())
+        .multi_cartesian_product()
+        .map(|v| v.iter().flat_map(|s| s.chars()).collect::<String>())
         .collect()
 }
 
-fn cartesian_product(vectors: &[&Vec<String>]) -> Vec<Vec<String>> {
-    if vectors.is_empty() {
-        return vec![vec![]];
-    }
-
-    let mut result = Vec::new();
-    for item in vectors[0].iter() {
-        for mut sub_result in cartesian_product(&vectors[1..]) {
-            let mut new_result = vec![item.clone()];
-            new_result.append(&mut sub_result);
-            result.push(new_result);
-        }
-    }
-    result
-}
-
 fn compute_length(
     sequence: &str,
     depth: i32,
     dir_sequences: &Sequences,
     dir_lengths: &HashMap<(char, char), usize>,
-    cache: &mut HashMap<String, i32>,
-) -> i32 {
-    if let Some(&cached) = cache.get(sequence) {
+    cache: &mut HashMap<(String, i32), u64>,
+) -> u64 {
+    if let Some(&cached) = cache.get(&(sequence.to_string(), depth)) {
         return cached;
     }
 
     if depth == 1 {
-        let length: i32 = std::iter::once('A')
+        let length: u64 = std::iter::once('A')
             .chain(sequence.chars())
             .tuple_windows()
-            .map(|(x, y)| dir_lengths[&(x, y)] as i32)
+            .map(|(x, y)| dir_lengths[&(x, y)] as u64)
             .sum();
-        cache.insert(sequence.to_string(), length);
+        cache.insert((sequence.to_string(), depth), length);
         return length;
     }
 
-    let length: i32 = std::iter::once('A')
+    let length: u64 = std::iter::once('A')
         .chain(sequence.chars())
         .tuple_windows()
         .map(|(x, y)| {
@@ -166,7 +181,7 @@ fn compute_length(
         })
         .sum();
 
-    cache.insert(sequence.to_string(), length);
+    cache.insert((sequence.to_string(), depth), length);
     length
 }
 
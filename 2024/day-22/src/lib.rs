@@ -1,2 +1,31 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+
 pub mod part1;
 pub mod part2;
+
+/// Both parts evolve the same buyers' secret numbers.
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Parsed = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        part1::parse(input)
+    }
+
+    fn part1(initial_secrets: &Self::Parsed) -> Result<Answer> {
+        part1::solve(initial_secrets)
+    }
+
+    fn part2(initial_secrets: &Self::Parsed) -> Result<Answer> {
+        part2::solve(initial_secrets)
+    }
+
+    fn items(initial_secrets: &Self::Parsed) -> Option<usize> {
+        Some(initial_secrets.len())
+    }
+}
+
+aoc_common::solution!(Day22);
@@ -1,6 +1,7 @@
+use aoc_common::answer::Answer;
 use miette::*;
 
-fn transform_secret(mut secret: i64) -> i64 {
+pub fn transform_secret(mut secret: i64) -> i64 {
     let modulo_operand = 16777216;
     let mut result = secret * 64;
     secret ^= result;
@@ -17,15 +18,24 @@ fn transform_secret(mut secret: i64) -> i64 {
     secret
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> Result<String> {
-    let initial_secrets: Vec<i64> = input
+/// Reads the initial secret of every buyer.
+pub fn parse(input: &str) -> Result<Vec<i64>> {
+    input
         .lines()
         .filter(|line| !line.is_empty())
-        .map(|line| line.parse::<i64>())
+        .map(|line| line.trim().parse::<i64>())
         .collect::<std::result::Result<_, _>>()
-        .map_err(|e| miette!("Failed to parse input: {}", e))?;
+        .map_err(|e| miette!("Failed to parse input: {}", e))
+}
+
+#[tracing::instrument]
+pub fn process(input: &str) -> Result<String> {
+    Ok(solve(&parse(input)?)?.to_string())
+}
 
+/// Sums every buyer's 2000th secret number.
+#[tracing::instrument(skip(initial_secrets))]
+pub fn solve(initial_secrets: &[i64]) -> Result<Answer> {
     let sum: i64 = initial_secrets
         .iter()
         .map(|&secret| {
@@ -37,7 +47,7 @@ pub fn process(input: &str) -> Result<String> {
         })
         .sum();
 
-    Ok(sum.to_string())
+    Ok(sum.into())
 }
 
 #[cfg(test)]
@@ -47,10 +57,10 @@ mod tests {
     #[test]
     fn it_works() -> Result<()> {
         let input = "1
-2
-3
+10
+100
 2024";
-        assert_eq!("23", process(input)?);
+        assert_eq!("37327623", process(input)?);
         Ok(())
     }
 }
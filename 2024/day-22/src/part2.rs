@@ -1,23 +1,9 @@
 use std::collections::{HashMap, HashSet};
 
+use aoc_common::answer::Answer;
 use miette::*;
 
-fn transform_secret(mut secret: i64) -> i64 {
-    let modulo_operand = 16777216;
-    let mut result = secret * 64;
-    secret ^= result;
-    secret %= modulo_operand;
-
-    result = secret / 32;
-    secret ^= result;
-    secret %= modulo_operand;
-
-    result = secret * 2048;
-    secret ^= result;
-    secret %= modulo_operand;
-
-    secret
-}
+use crate::part1::{parse, transform_secret};
 
 fn get_price(secret: i64) -> i64 {
     secret.abs() % 10
@@ -37,16 +23,15 @@ fn get_buyer_prices(initial: i64) -> Vec<i64> {
 
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let initial_secrets: Vec<i64> = input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(|line| line.parse::<i64>())
-        .collect::<std::result::Result<_, _>>()
-        .map_err(|e| miette!("Failed to parse input: {}", e))?;
+    Ok(solve(&parse(input)?)?.to_string())
+}
 
+/// Finds the four price changes that earn the most bananas across all buyers.
+#[tracing::instrument(skip(initial_secrets))]
+pub fn solve(initial_secrets: &[i64]) -> Result<Answer> {
     let mut sequence_totals: HashMap<[i64; 4], i64> = HashMap::new();
 
-    for &initial in &initial_secrets {
+    for &initial in initial_secrets {
         let prices = get_buyer_prices(initial);
         let mut seen_sequences = HashSet::new();
 
@@ -64,5 +49,20 @@ pub fn process(input: &str) -> Result<String> {
         }
     }
 
-    Ok(sequence_totals.values().max().unwrap_or(&0).to_string())
+    Ok(sequence_totals.values().max().copied().unwrap_or(0).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() -> Result<()> {
+        let input = "1
+2
+3
+2024";
+        assert_eq!("23", process(input)?);
+        Ok(())
+    }
 }
@@ -1,11 +1,13 @@
+use aoc_runtime::Args;
 use miette::*;
 
 use aoc2024_day_23::part2_petgraph;
 
 fn main() -> Result<()> {
-    tracing_subscriber::fmt::init();
-    let input = include_str!("../../input2.txt");
-    let result = part2_petgraph::process(input)?;
-    println!("Result: {}", result);
+    aoc_runtime::init();
+    let args = Args::from_env();
+    let input = args.load(&aoc_runtime::day!()?)?;
+    let result = part2_petgraph::process(&input)?;
+    args.report(2, &result);
     Ok(())
 }
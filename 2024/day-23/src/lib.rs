@@ -1,3 +1,33 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+use part1::Network;
+
 pub mod part1;
 pub mod part2;
 pub mod part2_petgraph;
+
+/// Both parts search the same network map for cliques.
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Parsed = Network;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        part1::parse(input)
+    }
+
+    fn part1(network: &Self::Parsed) -> Result<Answer> {
+        part1::solve(network)
+    }
+
+    fn part2(network: &Self::Parsed) -> Result<Answer> {
+        part2::solve(network)
+    }
+
+    fn items(network: &Self::Parsed) -> Option<usize> {
+        Some(network.len())
+    }
+}
+
+aoc_common::solution!(Day23);
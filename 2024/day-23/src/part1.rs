@@ -1,18 +1,41 @@
+use aoc_common::answer::Answer;
 use miette::*;
 use std::collections::{HashMap, HashSet};
 
-#[tracing::instrument]
-pub fn process(input: &str) -> Result<String> {
-    let mut network: HashMap<&str, HashSet<&str>> = HashMap::new();
+/// Every computer mapped to the computers it is directly connected to.
+pub type Network = HashMap<String, HashSet<String>>;
+
+/// Reads the connections, one `a-b` pair per line.
+pub fn parse(input: &str) -> Result<Network> {
+    let mut network: Network = HashMap::new();
 
-    for line in input.lines() {
-        let (comp1, comp2) = line.split_once('-').unwrap();
-        network.entry(comp1).or_default().insert(comp2);
-        network.entry(comp2).or_default().insert(comp1);
+    for line in input.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let (comp1, comp2) = line
+            .split_once('-')
+            .ok_or_else(|| miette!("Invalid connection {line:?}"))?;
+        network
+            .entry(comp1.to_string())
+            .or_default()
+            .insert(comp2.to_string());
+        network
+            .entry(comp2.to_string())
+            .or_default()
+            .insert(comp1.to_string());
     }
 
+    Ok(network)
+}
+
+#[tracing::instrument]
+pub fn process(input: &str) -> Result<String> {
+    Ok(solve(&parse(input)?)?.to_string())
+}
+
+/// Counts the sets of three interconnected computers with a name starting in `t`.
+#[tracing::instrument(skip(network))]
+pub fn solve(network: &Network) -> Result<Answer> {
     let mut triplet_count = 0;
-    let computers: Vec<&str> = network.keys().copied().collect();
+    let computers: Vec<&str> = network.keys().map(String::as_str).collect();
 
     for i in 0..computers.len() {
         for j in (i + 1)..computers.len() {
@@ -32,7 +55,7 @@ pub fn process(input: &str) -> Result<String> {
         }
     }
 
-    Ok(triplet_count.to_string())
+    Ok(triplet_count.into())
 }
 
 #[cfg(test)]
@@ -1,57 +1,87 @@
+use aoc_common::answer::Answer;
 use miette::*;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
+
+use crate::part1::{parse, Network};
 
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let mut network: HashMap<&str, HashSet<&str>> = HashMap::new();
-    for line in input.lines() {
-        let (comp1, comp2) = line.split_once('-').unwrap();
-        network.entry(comp1).or_default().insert(comp2);
-        network.entry(comp2).or_default().insert(comp1);
-    }
-
-    let computers: Vec<&str> = network.keys().copied().collect();
-    let mut largest_clique: Vec<&str> = Vec::new();
-
-    for &start in &computers {
-        let mut current = vec![start];
-        let mut candidates: Vec<&str> = computers
-            .iter()
-            .filter(|&&c| c > start && network[start].contains(c))
-            .copied()
-            .collect();
-
-        while !candidates.is_empty() {
-            let next = candidates[0];
-            candidates.remove(0);
+    Ok(solve(&parse(input)?)?.to_string())
+}
 
-            current.push(next);
-            if is_clique(&current, &network) {
-                if current.len() > largest_clique.len() {
-                    largest_clique = current.clone();
-                }
-
-                candidates.retain(|&c| network[next].contains(c));
-            } else {
-                current.pop();
-            }
-        }
-    }
+/// Finds the password to the LAN party: the largest clique, sorted and joined.
+#[tracing::instrument(skip(network))]
+pub fn solve(network: &Network) -> Result<Answer> {
+    let candidates = network.keys().map(String::as_str).collect();
+    let mut largest_clique = Vec::new();
+    bron_kerbosch(
+        network,
+        &mut Vec::new(),
+        candidates,
+        HashSet::new(),
+        &mut largest_clique,
+    );
 
     largest_clique.sort();
-    Ok(largest_clique.join(","))
+    Ok(largest_clique.join(",").into())
 }
 
-/// Finds the largest fully connected subgraph containing the given computers
-fn is_clique(computers: &[&str], network: &HashMap<&str, HashSet<&str>>) -> bool {
-    for i in 0..computers.len() {
-        for j in (i + 1)..computers.len() {
-            if !network[computers[i]].contains(computers[j]) {
-                return false;
-            }
+/// Extends `clique` with every maximal clique drawn from `candidates`, keeping
+/// the largest in `largest`.
+///
+/// Computers in `excluded` were already tried at this depth, so any clique
+/// they could join has been seen. Branching only on the candidates that are
+/// not neighbors of a pivot skips cliques the pivot's branch also reaches,
+/// which keeps the search exact without depending on the order of the map.
+fn bron_kerbosch<'a>(
+    network: &'a Network,
+    clique: &mut Vec<&'a str>,
+    mut candidates: HashSet<&'a str>,
+    mut excluded: HashSet<&'a str>,
+    largest: &mut Vec<&'a str>,
+) {
+    let Some(pivot) = candidates
+        .iter()
+        .chain(&excluded)
+        .max_by_key(|&&computer| {
+            network[computer]
+                .iter()
+                .filter(|neighbor| candidates.contains(neighbor.as_str()))
+                .count()
+        })
+        .copied()
+    else {
+        if clique.len() > largest.len() {
+            *largest = clique.clone();
         }
+        return;
+    };
+
+    let branches: Vec<&str> = candidates
+        .iter()
+        .filter(|&&computer| !network[pivot].contains(computer))
+        .copied()
+        .collect();
+    for computer in branches {
+        let neighbors = &network[computer];
+        let connected = |set: &HashSet<&'a str>| {
+            set.iter()
+                .filter(|&&other| neighbors.contains(other))
+                .copied()
+                .collect()
+        };
+        clique.push(computer);
+        bron_kerbosch(
+            network,
+            clique,
+            connected(&candidates),
+            connected(&excluded),
+            largest,
+        );
+        clique.pop();
+        candidates.remove(computer);
+        excluded.insert(computer);
     }
-    true
 }
 
 #[cfg(test)]
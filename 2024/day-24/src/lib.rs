@@ -1,2 +1,32 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+use part1::Device;
+
 pub mod part1;
 pub mod part2;
+
+/// Both parts read the same wires and gates.
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Parsed = Device;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        part1::parse(input)
+    }
+
+    fn part1(device: &Self::Parsed) -> Result<Answer> {
+        part1::solve(device)
+    }
+
+    fn part2(device: &Self::Parsed) -> Result<Answer> {
+        part2::solve(device)
+    }
+
+    fn items(device: &Self::Parsed) -> Option<usize> {
+        Some(device.gates.len())
+    }
+}
+
+aoc_common::solution!(Day24);
@@ -1,58 +1,74 @@
+use aoc_common::answer::Answer;
 use miette::*;
 use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
-enum Gate {
+pub enum Gate {
     And(String, String, String),
     Or(String, String, String),
     Xor(String, String, String),
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> Result<String> {
+/// The initial wire values and the gates wired between them.
+#[derive(Debug, Clone)]
+pub struct Device {
+    pub wires: HashMap<String, u8>,
+    pub gates: Vec<Gate>,
+}
+
+/// Reads the initial wire values and, after the blank line, the gates.
+pub fn parse(input: &str) -> Result<Device> {
     let mut wires: HashMap<String, u8> = HashMap::new();
     let mut gates: Vec<Gate> = Vec::new();
     let mut is_parsing_gates = false;
 
-    for line in input.lines() {
+    for line in input.lines().map(str::trim) {
         if line.is_empty() {
             is_parsing_gates = true;
             continue;
         }
 
         if !is_parsing_gates {
-            let parts: Vec<&str> = line.split(": ").collect();
-            wires.insert(parts[0].to_string(), parts[1].parse::<u8>().unwrap());
+            let (wire, value) = line
+                .split_once(": ")
+                .ok_or_else(|| miette!("Invalid wire value {line:?}"))?;
+            wires.insert(wire.to_string(), value.parse::<u8>().into_diagnostic()?);
         } else {
-            let parts: Vec<&str> = line.split(" -> ").collect();
-            let gate_parts: Vec<&str> = parts[0].split(' ').collect();
-            let output = parts[1].to_string();
-
-            match gate_parts.len() {
-                3 => {
-                    let gate = match gate_parts[1] {
-                        "AND" => {
-                            Gate::And(gate_parts[0].to_string(), gate_parts[2].to_string(), output)
-                        }
-                        "OR" => {
-                            Gate::Or(gate_parts[0].to_string(), gate_parts[2].to_string(), output)
-                        }
-                        "XOR" => {
-                            Gate::Xor(gate_parts[0].to_string(), gate_parts[2].to_string(), output)
-                        }
-                        _ => continue,
-                    };
-                    gates.push(gate);
-                }
-                _ => continue,
-            }
+            let (gate, output) = line
+                .split_once(" -> ")
+                .ok_or_else(|| miette!("Invalid gate {line:?}"))?;
+            let gate_parts: Vec<&str> = gate.split(' ').collect();
+            let [in1, op, in2] = gate_parts[..] else {
+                bail!("Invalid gate {line:?}");
+            };
+            let (in1, in2, output) = (in1.to_string(), in2.to_string(), output.to_string());
+            gates.push(match op {
+                "AND" => Gate::And(in1, in2, output),
+                "OR" => Gate::Or(in1, in2, output),
+                "XOR" => Gate::Xor(in1, in2, output),
+                _ => bail!("Unknown gate {op:?}"),
+            });
         }
     }
 
+    Ok(Device { wires, gates })
+}
+
+#[tracing::instrument]
+pub fn process(input: &str) -> Result<String> {
+    Ok(solve(&parse(input)?)?.to_string())
+}
+
+/// Simulates the gates and reads the number on the `z` wires.
+#[tracing::instrument(skip(device))]
+pub fn solve(device: &Device) -> Result<Answer> {
+    let mut wires = device.wires.clone();
+    let gates = &device.gates;
+
     let mut changed = true;
     while changed {
         changed = false;
-        for gate in &gates {
+        for gate in gates {
             match gate {
                 Gate::And(in1, in2, out) => {
                     if let (Some(&v1), Some(&v2)) = (wires.get(in1), wires.get(in2)) {
@@ -94,7 +110,7 @@ pub fn process(input: &str) -> Result<String> {
         result = (result << 1) | (value as u64);
     }
 
-    Ok(result.to_string())
+    Ok(result.into())
 }
 
 #[cfg(test)]
@@ -1,40 +1,51 @@
+use aoc_common::answer::Answer;
 use itertools::Itertools;
 use miette::*;
 use std::collections::HashMap;
 
+use crate::part1::{parse, Device, Gate};
+
+const SWAPPED_PAIRS: usize = 4;
+
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let mut formulas = HashMap::new();
-    let mut is_parsing_gates = false;
+    Ok(solve(&parse(input)?)?.to_string())
+}
 
-    for line in input.lines() {
-        if line.is_empty() {
-            is_parsing_gates = true;
-            continue;
-        }
+/// Names the eight wires whose outputs must be swapped back to make the
+/// device a ripple-carry adder.
+#[tracing::instrument(skip(device))]
+pub fn solve(device: &Device) -> Result<Answer> {
+    let swaps = find_swaps(device, SWAPPED_PAIRS)?;
+    Ok(swaps.iter().sorted().join(",").into())
+}
 
-        if is_parsing_gates {
-            let parts: Vec<&str> = line.split(" -> ").collect();
-            let gate_parts: Vec<&str> = parts[0].split(' ').collect();
-            let output = parts[1].to_string();
-
-            if gate_parts.len() == 3 {
-                formulas.insert(
-                    output,
-                    Formula {
-                        op: gate_parts[1].to_string(),
-                        x: gate_parts[0].to_string(),
-                        y: gate_parts[2].to_string(),
-                    },
-                );
-            }
-        }
-    }
+/// Greedily swaps the pair of gate outputs that lets the adder verify the
+/// most `z` bits, `pairs` times over.
+pub fn find_swaps(device: &Device, pairs: usize) -> Result<Vec<String>> {
+    let mut formulas: HashMap<String, Formula> = device
+        .gates
+        .iter()
+        .map(|gate| {
+            let (op, x, y, output) = match gate {
+                Gate::And(x, y, output) => ("AND", x, y, output),
+                Gate::Or(x, y, output) => ("OR", x, y, output),
+                Gate::Xor(x, y, output) => ("XOR", x, y, output),
+            };
+            let formula = Formula {
+                op: op.to_string(),
+                x: x.clone(),
+                y: y.clone(),
+            };
+            (output.clone(), formula)
+        })
+        .collect();
 
     let mut swaps = Vec::new();
-    for _ in 0..4 {
+    for _ in 0..pairs {
         let baseline = progress(&formulas);
-        let keys: Vec<_> = formulas.keys().cloned().collect();
+        let keys: Vec<_> = formulas.keys().cloned().sorted().collect();
+        let mut swapped = false;
         'outer: for x in &keys {
             for y in &keys {
                 if x == y {
@@ -48,15 +59,19 @@ pub fn process(input: &str) -> Result<String> {
                 if progress(&formulas) > baseline {
                     swaps.push(x.clone());
                     swaps.push(y.clone());
+                    swapped = true;
                     break 'outer;
                 }
                 formulas.insert(x.clone(), x_formula);
                 formulas.insert(y.clone(), y_formula);
             }
         }
+        if !swapped {
+            bail!("No swap fixes bit {baseline} of the adder");
+        }
     }
 
-    Ok(swaps.iter().sorted().join(","))
+    Ok(swaps)
 }
 
 #[derive(Debug, Clone)]
@@ -76,10 +91,7 @@ fn verify_z(formulas: &HashMap<String, Formula>, wire: &str, num: u32) -> bool {
             return false;
         }
         if num == 0 {
-            return [&formula.x, &formula.y]
-                .iter()
-                .sorted()
-                .collect::<Vec<_>>()
+            return [&formula.x, &formula.y].iter().sorted().collect::<Vec<_>>()
                 == vec![&"x00", &"y00"];
         }
         (verify_intermediate_xor(formulas, &formula.x, num)
@@ -112,10 +124,7 @@ fn verify_carry_bit(formulas: &HashMap<String, Formula>, wire: &str, num: u32) -
             if formula.op != "AND" {
                 return false;
             }
-            return [&formula.x, &formula.y]
-                .iter()
-                .sorted()
-                .collect::<Vec<_>>()
+            return [&formula.x, &formula.y].iter().sorted().collect::<Vec<_>>()
                 == vec![&"x00", &"y00"];
         }
         if formula.op != "OR" {
@@ -135,10 +144,7 @@ fn verify_direct_carry(formulas: &HashMap<String, Formula>, wire: &str, num: u32
         if formula.op != "AND" {
             return false;
         }
-        [&formula.x, &formula.y]
-            .iter()
-            .sorted()
-            .collect::<Vec<_>>()
+        [&formula.x, &formula.y].iter().sorted().collect::<Vec<_>>()
             == vec![&make_wire("x", num).as_str(), &make_wire("y", num).as_str()]
     } else {
         false
@@ -173,26 +179,28 @@ mod tests {
 
     #[test]
     fn it_works() -> Result<()> {
+        // A three-bit ripple-carry adder with the outputs of `a01` and `b01` swapped.
         let input = "x00: 0
 x01: 1
 x02: 0
-x03: 1
-x04: 0
-x05: 1
-y00: 0
-y01: 0
-y02: 1
-y03: 1
-y04: 0
-y05: 1
-
-x00 AND y00 -> z05
-x01 AND y01 -> z02
-x02 AND y02 -> z01
-x03 AND y03 -> z03
-x04 AND y04 -> z04
-x05 AND y05 -> z00";
-        assert_eq!("aaa,aoc,bbb,ccc,eee,ooo,z24,z99", process(input)?);
+y00: 1
+y01: 1
+y02: 0
+
+x00 XOR y00 -> z00
+x00 AND y00 -> c00
+x01 XOR y01 -> b01
+x01 AND y01 -> a01
+a01 XOR c00 -> z01
+a01 AND c00 -> d01
+b01 OR d01 -> c01
+x02 XOR y02 -> a02
+x02 AND y02 -> b02
+a02 XOR c01 -> z02
+a02 AND c01 -> d02
+b02 OR d02 -> z03";
+        let swaps = find_swaps(&parse(input)?, 1)?;
+        assert_eq!("a01,b01", swaps.iter().sorted().join(","));
         Ok(())
     }
 }
@@ -1 +1,32 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+use part1::Schematic;
+
 pub mod part1;
+
+/// The lock and key schematics of the North Pole's office door.
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Parsed = Vec<Schematic>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        part1::parse(input)
+    }
+
+    fn part1(schematics: &Self::Parsed) -> Result<Answer> {
+        part1::solve(schematics)
+    }
+
+    /// The last day has no second puzzle.
+    fn part2(_schematics: &Self::Parsed) -> Result<Answer> {
+        Ok(Answer::Text(String::new()))
+    }
+
+    fn items(schematics: &Self::Parsed) -> Option<usize> {
+        Some(schematics.len())
+    }
+}
+
+aoc_common::solution!(Day25);
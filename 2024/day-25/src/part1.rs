@@ -1,9 +1,24 @@
+use aoc_common::answer::Answer;
 use miette::*;
 
+/// Reads the lock and key schematics, separated by blank lines.
+pub fn parse(input: &str) -> Result<Vec<Schematic>> {
+    input
+        .replace("\r\n", "\n")
+        .trim()
+        .split("\n\n")
+        .map(Schematic::from_str)
+        .collect()
+}
+
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let schematics: Vec<Schematic> = input.split("\n\n").map(Schematic::from_str).collect();
+    Ok(solve(&parse(input)?)?.to_string())
+}
 
+/// Counts the lock and key pairs that fit together without overlapping.
+#[tracing::instrument(skip(schematics))]
+pub fn solve(schematics: &[Schematic]) -> Result<Answer> {
     let locks: Vec<&Schematic> = schematics.iter().filter(|s| s.is_lock).collect();
     let keys: Vec<&Schematic> = schematics.iter().filter(|s| !s.is_lock).collect();
 
@@ -12,18 +27,21 @@ pub fn process(input: &str) -> Result<String> {
         .flat_map(|lock| keys.iter().filter(|key| can_fit(lock, key)))
         .count();
 
-    Ok(valid_pairs.to_string())
+    Ok(valid_pairs.into())
 }
 
-#[derive(Debug)]
-struct Schematic {
-    heights: Vec<i32>,
-    is_lock: bool,
+#[derive(Debug, Clone)]
+pub struct Schematic {
+    pub heights: Vec<i32>,
+    pub is_lock: bool,
 }
 
 impl Schematic {
-    fn from_str(s: &str) -> Self {
+    fn from_str(s: &str) -> Result<Self> {
         let lines: Vec<&str> = s.lines().collect();
+        if lines.is_empty() {
+            bail!("Empty schematic");
+        }
         let is_lock = lines[0].chars().all(|c| c == '#');
         let width = lines[0].len();
 
@@ -37,7 +55,7 @@ impl Schematic {
             *height = count - 1;
         }
 
-        Self { heights, is_lock }
+        Ok(Self { heights, is_lock })
     }
 }
 
@@ -1,20 +1,18 @@
-use aoc2024_day_3::*;
+use aoc2024_day_3::part2::{self, ParserBackend};
 
 fn main() {
-    divan::main();
+    // Without a stored input there is nothing to benchmark.
+    if aoc_runtime::bench_input!().is_some() {
+        divan::main();
+    }
 }
 
-#[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!("../input1.txt",))).unwrap();
+/// The stored input, which `main` has checked is there.
+fn input() -> &'static str {
+    aoc_runtime::bench_input!().unwrap()
 }
 
-#[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!("../input2.txt",))).unwrap();
-}
-
-#[divan::bench]
-fn part2_pest() {
-    part2_pest::process(divan::black_box(include_str!("../input2.txt",))).unwrap();
+#[divan::bench(args = ParserBackend::ALL)]
+fn part2(backend: ParserBackend) {
+    part2::process_with(divan::black_box(input()), backend).unwrap();
 }
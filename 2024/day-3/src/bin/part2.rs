@@ -1,11 +1,16 @@
 use miette::*;
 
-use aoc2024_day_3::part2;
+use aoc2024_day_3::part2::{self, ParserBackend};
 
+/// Takes an optional parser backend (`regex`, `pest`, `chumsky`, `hand-rolled`).
 fn main() -> Result<()> {
-    tracing_subscriber::fmt::init();
-    let input = include_str!("../../input2.txt");
-    let result = part2::process(input)?;
+    aoc_runtime::init();
+    let backend = match std::env::args().nth(1) {
+        Some(name) => name.parse()?,
+        None => ParserBackend::default(),
+    };
+    let input = aoc_runtime::day!()?.load_input()?;
+    let result = part2::process_with(&input, backend)?;
     println!("Result: {}", result);
     Ok(())
 }
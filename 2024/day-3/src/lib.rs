@@ -1,3 +1,33 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+use part2::{Instruction, ParserBackend};
+
 pub mod part1;
 pub mod part2;
-pub mod part2_pest;
+
+/// Both parts run the instructions read out of the same corrupted memory;
+/// only the second obeys `do()` and `don't()`.
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Parsed = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        ParserBackend::default().parse(input)
+    }
+
+    fn part1(instructions: &Self::Parsed) -> Result<Answer> {
+        part1::solve(instructions)
+    }
+
+    fn part2(instructions: &Self::Parsed) -> Result<Answer> {
+        part2::solve(instructions)
+    }
+
+    fn items(instructions: &Self::Parsed) -> Option<usize> {
+        Some(instructions.len())
+    }
+}
+
+aoc_common::solution!(Day3);
@@ -1,37 +1,23 @@
+use aoc_common::answer::Answer;
 use miette::*;
-use nom::{bytes::complete::tag, character::complete::digit1, IResult};
-use regex::Regex;
+
+use crate::part2::{Instruction, ParserBackend};
 
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let multiplications = parse(input)?;
-    let result = multiplications.iter().fold(0, |acc, (a, b)| acc + a * b);
-    Ok(result.to_string())
-}
-
-type Multiplication = (u32, u32);
-
-fn parse_multiplication(input: &str) -> IResult<&str, Multiplication> {
-    let (input, _) = tag("mul(")(input)?;
-    let (input, a) = digit1(input)?;
-    let (input, _) = tag(",")(input)?;
-    let (input, b) = digit1(input)?;
-    let (input, _) = tag(")")(input)?;
-    Ok((input, (a.parse().unwrap(), b.parse().unwrap())))
+    Ok(solve(&ParserBackend::default().parse(input)?)?.to_string())
 }
 
-fn parse(input: &str) -> Result<Vec<Multiplication>> {
-    let regex = Regex::new(r"mul\(\d+,\d+\)").unwrap();
-    let mut multiplications = Vec::new();
-
-    for r#match in regex.find_iter(input) {
-        let substring = r#match.as_str();
-        let (_, multiplication) = parse_multiplication(substring)
-            .map_err(|_| miette::Error::msg("Failed to parse multiplication"))?;
-        multiplications.push(multiplication);
-    }
-
-    Ok(multiplications)
+/// Sums the products of every multiplication, ignoring `do()` and `don't()`.
+#[tracing::instrument(skip(instructions))]
+pub fn solve(instructions: &[Instruction]) -> Result<Answer> {
+    let result = instructions
+        .iter()
+        .fold(0, |acc, instruction| match instruction {
+            Instruction::Multiplication((a, b)) => acc + a * b,
+            _ => acc,
+        });
+    Ok(result.into())
 }
 
 #[cfg(test)]
@@ -1,32 +1,133 @@
+use std::fmt;
+use std::str::FromStr;
+
+use aoc_common::answer::Answer;
+use chumsky::prelude::*;
 use miette::*;
-use nom::{bytes::complete::tag, character::complete::digit1, IResult};
+use nom::{bytes::complete::tag, character::complete::digit1, combinator::map_res, IResult};
+use pest::Parser as _;
+use pest_derive::Parser;
 use regex::Regex;
 
+/// The interchangeable parsers for the corrupted memory.
+///
+/// Every backend extracts the same list of instructions, so they can be
+/// swapped at runtime and compared against each other. They all reject a
+/// multiplication whose operands do not fit in a `u32`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParserBackend {
+    /// Regex tokenizer with nom parsing each match.
+    #[default]
+    Regex,
+    /// PEG grammar in `part2.pest`.
+    Pest,
+    /// Chumsky combinators skipping unrecognized characters.
+    Chumsky,
+    /// Single pass over the bytes with no parsing library.
+    HandRolled,
+}
+
+impl ParserBackend {
+    pub const ALL: [ParserBackend; 4] = [
+        ParserBackend::Regex,
+        ParserBackend::Pest,
+        ParserBackend::Chumsky,
+        ParserBackend::HandRolled,
+    ];
+
+    /// Reads the `do()`, `don't()` and `mul(a,b)` instructions out of the
+    /// corrupted memory, in order.
+    pub fn parse(self, input: &str) -> Result<Vec<Instruction>> {
+        match self {
+            ParserBackend::Regex => parse_regex(input),
+            ParserBackend::Pest => parse_pest(input),
+            ParserBackend::Chumsky => parse_chumsky(input),
+            ParserBackend::HandRolled => parse_hand_rolled(input),
+        }
+    }
+}
+
+impl fmt::Display for ParserBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ParserBackend::Regex => "regex",
+            ParserBackend::Pest => "pest",
+            ParserBackend::Chumsky => "chumsky",
+            ParserBackend::HandRolled => "hand-rolled",
+        })
+    }
+}
+
+impl FromStr for ParserBackend {
+    type Err = miette::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        ParserBackend::ALL
+            .into_iter()
+            .find(|backend| backend.to_string() == s)
+            .ok_or_else(|| miette!("Unknown parser backend '{s}' (expected one of regex, pest, chumsky, hand-rolled)"))
+    }
+}
+
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let multiplications = parse(input)?;
-    let result = multiplications.iter().fold(0, |acc, (a, b)| acc + a * b);
-    Ok(result.to_string())
+    process_with(input, ParserBackend::default())
+}
+
+#[tracing::instrument]
+pub fn process_with(input: &str, backend: ParserBackend) -> Result<String> {
+    Ok(solve(&backend.parse(input)?)?.to_string())
+}
+
+/// Sums the products of the multiplications left enabled.
+#[tracing::instrument(skip(instructions))]
+pub fn solve(instructions: &[Instruction]) -> Result<Answer> {
+    let result: u32 = enabled_multiplications(instructions.iter().copied())
+        .iter()
+        .map(|(a, b)| a * b)
+        .sum();
+    Ok(result.into())
 }
 
-enum Instruction {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
     Do,
     Dont,
     Multiplication(Multiplication),
 }
 
-type Multiplication = (u32, u32);
+pub type Multiplication = (u32, u32);
+
+/// Keeps the multiplications that are enabled by the preceding `do()` / `don't()`.
+fn enabled_multiplications(
+    instructions: impl IntoIterator<Item = Instruction>,
+) -> Vec<Multiplication> {
+    let mut enabled = true;
+    let mut multiplications = Vec::new();
+
+    for instruction in instructions {
+        match instruction {
+            Instruction::Do => enabled = true,
+            Instruction::Dont => enabled = false,
+            Instruction::Multiplication(mul) if enabled => multiplications.push(mul),
+            Instruction::Multiplication(_) => {}
+        }
+    }
+
+    multiplications
+}
+
+// -----------------------------------------------------------------------------
+// Regex + nom
+// -----------------------------------------------------------------------------
 
 fn parse_multiplication(input: &str) -> IResult<&str, Instruction> {
     let (input, _) = tag("mul(")(input)?;
-    let (input, a) = digit1(input)?;
+    let (input, a) = map_res(digit1, str::parse)(input)?;
     let (input, _) = tag(",")(input)?;
-    let (input, b) = digit1(input)?;
+    let (input, b) = map_res(digit1, str::parse)(input)?;
     let (input, _) = tag(")")(input)?;
-    Ok((
-        input,
-        Instruction::Multiplication((a.parse().unwrap(), b.parse().unwrap())),
-    ))
+    Ok((input, Instruction::Multiplication((a, b))))
 }
 
 fn parse_do(input: &str) -> IResult<&str, Instruction> {
@@ -39,37 +140,193 @@ fn parse_dont(input: &str) -> IResult<&str, Instruction> {
     Ok((input, Instruction::Dont))
 }
 
-fn parse(input: &str) -> Result<Vec<Multiplication>> {
+fn parse_regex(input: &str) -> Result<Vec<Instruction>> {
     let regex = Regex::new(r"(do\(\)|don't\(\)|mul\(\d+,\d+\))").unwrap();
-    let mut multiplications = Vec::new();
-    let mut enabled = true;
 
-    for r#match in regex.find_iter(input) {
-        let substring = r#match.as_str();
-        if let Ok((_, _)) = parse_do(substring) {
-            enabled = true;
-        } else if let Ok((_, _)) = parse_dont(substring) {
-            enabled = false;
-        } else if enabled {
-            let (_, multiplication) = parse_multiplication(substring)
-                .map_err(|_| miette::Error::msg("Failed to parse multiplication"))?;
-            if let Instruction::Multiplication(mul) = multiplication {
-                multiplications.push(mul);
+    regex
+        .find_iter(input)
+        .map(|r#match| {
+            let substring = r#match.as_str();
+            parse_do(substring)
+                .or_else(|_| parse_dont(substring))
+                .or_else(|_| parse_multiplication(substring))
+                .map(|(_, instruction)| instruction)
+                .map_err(|_| miette::Error::msg("Failed to parse multiplication"))
+        })
+        .collect()
+}
+
+// -----------------------------------------------------------------------------
+// Pest
+// -----------------------------------------------------------------------------
+
+#[derive(Parser)]
+#[grammar = "part2.pest"]
+struct Part2Parser;
+
+fn parse_pest(input: &str) -> Result<Vec<Instruction>> {
+    let pairs = Part2Parser::parse(Rule::input, input).into_diagnostic()?;
+
+    let mut instructions = Vec::new();
+    for pair in pairs {
+        match pair.as_rule() {
+            Rule::do_instruction => instructions.push(Instruction::Do),
+            Rule::dont_instruction => instructions.push(Instruction::Dont),
+            Rule::mul_instruction => {
+                let mut numbers = pair
+                    .into_inner()
+                    .map(|p| p.as_str().parse::<u32>().into_diagnostic());
+                let (Some(a), Some(b)) = (numbers.next(), numbers.next()) else {
+                    return Err(miette!("Invalid multiplication instruction"));
+                };
+                instructions.push(Instruction::Multiplication((a?, b?)));
             }
+            _ => continue,
         }
     }
 
-    Ok(multiplications)
+    Ok(instructions)
+}
+
+// -----------------------------------------------------------------------------
+// Chumsky
+// -----------------------------------------------------------------------------
+
+fn chumsky_parser<'a>(
+) -> impl chumsky::Parser<'a, &'a str, Vec<Instruction>, extra::Err<Rich<'a, char>>> {
+    // An operand too large for a `u32` is still a multiplication, so it is
+    // reported instead of skipped as corrupted memory.
+    let number = text::digits(10)
+        .to_slice()
+        .validate(|digits: &str, e, emitter| {
+            digits.parse::<u32>().unwrap_or_else(|error| {
+                emitter.emit(Rich::custom(e.span(), error));
+                0
+            })
+        });
+
+    let mul = just("mul(")
+        .ignore_then(number)
+        .then_ignore(just(','))
+        .then(number)
+        .then_ignore(just(')'))
+        .map(Instruction::Multiplication);
+
+    let instruction = choice((
+        just("do()").to(Instruction::Do),
+        just("don't()").to(Instruction::Dont),
+        mul,
+    ));
+
+    // Anything that does not start a valid instruction is corrupted memory;
+    // skip a single character and try again from the next one.
+    instruction
+        .map(Some)
+        .or(any().to(None))
+        .repeated()
+        .collect::<Vec<_>>()
+        .map(|instructions| instructions.into_iter().flatten().collect())
+}
+
+fn parse_chumsky(input: &str) -> Result<Vec<Instruction>> {
+    chumsky::Parser::parse(&chumsky_parser(), input)
+        .into_result()
+        .map_err(|e| miette!("Parse failed: {:?}", e))
+}
+
+// -----------------------------------------------------------------------------
+// Hand-rolled
+// -----------------------------------------------------------------------------
+
+/// Reads `digits` followed by `terminator`, returning the digits and the
+/// remaining bytes.
+fn digits_then(bytes: &[u8], terminator: u8) -> Option<(&[u8], &[u8])> {
+    let len = bytes.iter().take_while(|b| b.is_ascii_digit()).count();
+    if len == 0 || bytes.get(len) != Some(&terminator) {
+        return None;
+    }
+    Some((&bytes[..len], &bytes[len + 1..]))
+}
+
+/// The value of `digits`, or an error if it does not fit in a `u32`.
+fn number(digits: &[u8]) -> Result<u32> {
+    digits
+        .iter()
+        .try_fold(0u32, |acc, &b| {
+            acc.checked_mul(10)?.checked_add((b - b'0') as u32)
+        })
+        .ok_or_else(|| miette!("{} does not fit in a u32", String::from_utf8_lossy(digits)))
+}
+
+fn parse_hand_rolled(input: &str) -> Result<Vec<Instruction>> {
+    let mut bytes = input.as_bytes();
+    let mut instructions = Vec::new();
+
+    while !bytes.is_empty() {
+        if let Some(rest) = bytes.strip_prefix(b"do()") {
+            instructions.push(Instruction::Do);
+            bytes = rest;
+        } else if let Some(rest) = bytes.strip_prefix(b"don't()") {
+            instructions.push(Instruction::Dont);
+            bytes = rest;
+        } else if let Some((a, b, rest)) = bytes.strip_prefix(b"mul(").and_then(|rest| {
+            let (a, rest) = digits_then(rest, b',')?;
+            let (b, rest) = digits_then(rest, b')')?;
+            Some((a, b, rest))
+        }) {
+            instructions.push(Instruction::Multiplication((number(a)?, number(b)?)));
+            bytes = rest;
+        } else {
+            bytes = &bytes[1..];
+        }
+    }
+
+    Ok(instructions)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use rstest::rstest;
+
     #[test]
     fn it_works() {
         let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
         let result = process(input).unwrap();
         assert_eq!(result, "48");
     }
+
+    #[rstest]
+    #[case("xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))")]
+    #[case("mul(1,2)mul(3,4don't()mul(5,6)do()do()mul(7,8)")]
+    #[case("mulmul(12,34)mul( 1,2)mul(1, 2)don'tmul(9,9)do(mul(2,2))")]
+    #[case("don't()do()don't()mul(100,100)")]
+    #[case("")]
+    #[case("mul(4294967295,1)")]
+    fn backends_agree(#[case] input: &str) -> Result<()> {
+        let expected = ParserBackend::Regex.parse(input)?;
+        for backend in ParserBackend::ALL {
+            assert_eq!(backend.parse(input)?, expected, "{backend} on {input:?}");
+        }
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("mul(4294967296,1)")]
+    #[case("xmul(2,4)mul(1,99999999999)")]
+    fn backends_reject_operands_past_u32(#[case] input: &str) {
+        for backend in ParserBackend::ALL {
+            assert!(backend.parse(input).is_err(), "{backend} on {input:?}");
+        }
+    }
+
+    #[test]
+    fn backend_names_round_trip() -> Result<()> {
+        for backend in ParserBackend::ALL {
+            assert_eq!(backend.to_string().parse::<ParserBackend>()?, backend);
+        }
+        assert!("nom".parse::<ParserBackend>().is_err());
+        Ok(())
+    }
 }
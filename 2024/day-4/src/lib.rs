@@ -1,2 +1,27 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+
 pub mod part1;
 pub mod part2;
+
+/// Both parts search the same grid of letters.
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Parsed = Vec<Vec<char>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        part1::parse(input)
+    }
+
+    fn part1(grid: &Self::Parsed) -> Result<Answer> {
+        part1::solve(grid)
+    }
+
+    fn part2(grid: &Self::Parsed) -> Result<Answer> {
+        part2::solve(grid)
+    }
+}
+
+aoc_common::solution!(Day4);
@@ -1,3 +1,4 @@
+use aoc_common::answer::Answer;
 use miette::*;
 use nom::{
     character::complete::{alpha1, line_ending},
@@ -10,16 +11,24 @@ use nom_locate::LocatedSpan;
 
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let grid = parse_input(input)
-        .map_err(|_| miette!("Failed to parse input"))?
-        .1;
-    let count = count_xmas(&grid);
-    Ok(count.to_string())
+    Ok(solve(&parse(input)?)?.to_string())
+}
+
+/// Counts every `XMAS` in the word search, in all eight directions.
+#[tracing::instrument(skip(grid))]
+pub fn solve(grid: &[Vec<char>]) -> Result<Answer> {
+    Ok(count_xmas(grid).into())
 }
 
 type Span<'a> = LocatedSpan<&'a str>;
 
-fn parse_input(input: &str) -> IResult<Span, Vec<Vec<char>>> {
+/// Reads the word search into rows of letters.
+pub fn parse(input: &str) -> Result<Vec<Vec<char>>> {
+    let (_, grid) = parse_input(input).map_err(|_| miette!("Failed to parse input"))?;
+    Ok(grid)
+}
+
+fn parse_input(input: &str) -> IResult<Span<'_>, Vec<Vec<char>>> {
     let parse_line = map(alpha1, |s: Span| s.chars().collect::<Vec<_>>());
     let parse_grid = separated_list1(line_ending, parse_line);
     let (remaining, grid) = terminated(parse_grid, opt(line_ending))(Span::new(input))?;
@@ -1,27 +1,17 @@
+use aoc_common::answer::Answer;
 use miette::*;
-use nom::{
-    character::complete::{alpha1, line_ending},
-    combinator::{map, opt},
-    multi::separated_list1,
-    sequence::terminated,
-    IResult,
-};
-use nom_locate::LocatedSpan;
+
+use crate::part1::parse;
 
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let (_, grid) = parse_input(input).map_err(|_| miette::miette!("Failed to parse input"))?;
-    let count = count_x_mas(&grid);
-    Ok(count.to_string())
+    Ok(solve(&parse(input)?)?.to_string())
 }
 
-type Span<'a> = LocatedSpan<&'a str>;
-
-fn parse_input(input: &str) -> IResult<Span, Vec<Vec<char>>> {
-    let parse_line = map(alpha1, |s: Span| s.chars().collect::<Vec<_>>());
-    let parse_grid = separated_list1(line_ending, parse_line);
-    let (remaining, grid) = terminated(parse_grid, opt(line_ending))(Span::new(input))?;
-    Ok((remaining, grid))
+/// Counts the two `MAS` crossing in an X around each `A`.
+#[tracing::instrument(skip(grid))]
+pub fn solve(grid: &[Vec<char>]) -> Result<Answer> {
+    Ok(count_x_mas(grid).into())
 }
 
 fn count_x_mas(grid: &[Vec<char>]) -> usize {
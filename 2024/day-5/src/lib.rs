@@ -1,4 +1,34 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+use safety_manual::ProductionInstructions;
+
 pub mod parser;
 pub mod part1;
 pub mod part2;
 pub mod safety_manual;
+
+/// Both parts check the same updates against the same ordering rules.
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Parsed = ProductionInstructions;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        parser::parse(input)
+    }
+
+    fn part1(safety_manual: &Self::Parsed) -> Result<Answer> {
+        part1::solve(safety_manual)
+    }
+
+    fn part2(safety_manual: &Self::Parsed) -> Result<Answer> {
+        part2::solve(safety_manual)
+    }
+
+    fn items(safety_manual: &Self::Parsed) -> Option<usize> {
+        Some(safety_manual.pages().len())
+    }
+}
+
+aoc_common::solution!(Day5);
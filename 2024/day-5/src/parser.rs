@@ -1,3 +1,4 @@
+use miette::*;
 use nom::{
     bytes::complete::tag,
     character::complete::{digit1, newline},
@@ -7,7 +8,13 @@ use nom::{
     IResult,
 };
 
-use crate::safety_manual::{Page, Rule};
+use crate::safety_manual::{Page, ProductionInstructions, Rule};
+
+/// Reads the ordering rules and the updates into the safety manual.
+pub fn parse(input: &str) -> Result<ProductionInstructions> {
+    let (_, (rules, updates)) = parse_input(input).map_err(|_| miette!("Failed to parse input"))?;
+    Ok(ProductionInstructions::new(rules, updates))
+}
 
 pub fn parse_input(input: &str) -> IResult<&str, (Vec<Rule>, Vec<Page>)> {
     let (input, rules) = parse_rules(input)?;
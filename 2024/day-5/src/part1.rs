@@ -1,21 +1,23 @@
+use aoc_common::answer::Answer;
 use miette::*;
 
-use crate::{parser::parse_input, safety_manual::ProductionInstructions};
+use crate::{parser::parse, safety_manual::ProductionInstructions};
 
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let (_, (rules, updates)) = parse_input(input).map_err(|_| miette!("Failed to parse input"))?;
-    let safety_manual = ProductionInstructions::new(rules, updates);
-    let sum = safety_manual.sum_of_middle_page_numbers();
-    Ok(sum.to_string())
+    Ok(solve(&parse(input)?)?.to_string())
+}
+
+/// Sums the middle page of every update already in order.
+#[tracing::instrument(skip(safety_manual))]
+pub fn solve(safety_manual: &ProductionInstructions) -> Result<Answer> {
+    Ok(safety_manual.sum_of_middle_page_numbers().into())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use rstest::rstest;
-
     #[test]
     fn it_works() -> Result<()> {
         let input = "47|53
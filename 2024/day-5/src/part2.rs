@@ -1,30 +1,39 @@
+use aoc_common::answer::Answer;
 use miette::*;
 
-use crate::{parser::parse_input, safety_manual::Page};
+use crate::{
+    parser::parse,
+    safety_manual::{Page, ProductionInstructions},
+};
 
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let (_, (rules, pages)) = parse_input(input).map_err(|_| miette!("Failed to parse input"))?;
+    Ok(solve(&parse(input)?)?.to_string())
+}
 
-    let incorrect_updates: Vec<Page> = pages
-        .into_iter()
-        .filter(|page| !page.is_correctly_ordered(&rules))
+/// Sums the middle page of every update that was out of order, once fixed.
+#[tracing::instrument(skip(safety_manual))]
+pub fn solve(safety_manual: &ProductionInstructions) -> Result<Answer> {
+    let rules = safety_manual.rules();
+
+    let incorrect_updates: Vec<&Page> = safety_manual
+        .pages()
+        .iter()
+        .filter(|page| !page.is_correctly_ordered(rules))
         .collect();
 
     let corrected_sum: u32 = incorrect_updates
         .into_iter()
-        .map(|page| page.correct_order(&rules).middle_page_number())
+        .map(|page| page.correct_order(rules).middle_page_number())
         .sum();
 
-    Ok(corrected_sum.to_string())
+    Ok(corrected_sum.into())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use rstest::rstest;
-
     #[test]
     fn it_works() -> Result<()> {
         let input = "47|53
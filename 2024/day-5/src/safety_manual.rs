@@ -10,6 +10,14 @@ impl ProductionInstructions {
         Self { rules, pages }
     }
 
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    pub fn pages(&self) -> &[Page] {
+        &self.pages
+    }
+
     pub fn sum_of_middle_page_numbers(&self) -> u32 {
         self.pages
             .iter()
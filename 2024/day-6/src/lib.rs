@@ -1,2 +1,27 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+
 pub mod part1;
 pub mod part2;
+
+/// Both parts follow the guard around the same lab.
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Parsed = Vec<Vec<char>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        part1::parse(input)
+    }
+
+    fn part1(map: &Self::Parsed) -> Result<Answer> {
+        part1::solve(map)
+    }
+
+    fn part2(map: &Self::Parsed) -> Result<Answer> {
+        part2::solve(map)
+    }
+}
+
+aoc_common::solution!(Day6);
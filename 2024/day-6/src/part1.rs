@@ -1,9 +1,19 @@
-use indicatif::ProgressBar;
+use aoc_common::answer::Answer;
 use miette::*;
 use std::collections::HashSet;
 
 pub fn process(input: &str) -> Result<String> {
-    let map: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+    Ok(solve(&parse(input)?)?.to_string())
+}
+
+/// Reads the lab's map, one row of cells per line.
+pub fn parse(input: &str) -> Result<Vec<Vec<char>>> {
+    Ok(input.lines().map(|line| line.chars().collect()).collect())
+}
+
+/// Counts the cells the guard visits before leaving the map.
+#[tracing::instrument(skip(map))]
+pub fn solve(map: &[Vec<char>]) -> Result<Answer> {
     let mut visited: HashSet<(usize, usize)> = HashSet::new();
     let mut position = (0, 0);
     let mut direction = Direction::Up;
@@ -18,7 +28,6 @@ pub fn process(input: &str) -> Result<String> {
         }
     }
 
-    let pb = ProgressBar::new_spinner();
     loop {
         visited.insert(position);
         let next_position = direction.move_forward(position);
@@ -32,12 +41,9 @@ pub fn process(input: &str) -> Result<String> {
         } else {
             position = next_position;
         }
-
-        pb.inc(1);
     }
-    pb.finish_with_message("Simulation complete");
 
-    Ok(visited.len().to_string())
+    Ok(visited.len().into())
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -72,8 +78,6 @@ impl Direction {
 mod tests {
     use super::*;
 
-    use rstest::rstest;
-
     #[test]
     fn it_works() -> Result<()> {
         let input = "....#.....
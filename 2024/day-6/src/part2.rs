@@ -1,10 +1,18 @@
-use indicatif::ParallelProgressIterator;
+use aoc_common::answer::Answer;
+use aoc_common::par::prelude::*;
 use miette::*;
-use rayon::prelude::*;
 use std::collections::HashSet;
 
+use crate::part1::parse;
+
 pub fn process(input: &str) -> Result<String> {
-    let map: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+    Ok(solve(&parse(input)?)?.to_string())
+}
+
+/// Counts the cells on the guard's path where one obstruction traps them in
+/// a loop.
+#[tracing::instrument(skip(map))]
+pub fn solve(map: &[Vec<char>]) -> Result<Answer> {
     let (position, direction) = map
         .iter()
         .enumerate()
@@ -18,17 +26,15 @@ pub fn process(input: &str) -> Result<String> {
         .ok_or_else(|| miette!("Initial position not found"))?;
 
     let start_position = position;
-    let (_, visited) = simulate(&map, position, direction);
+    let (_, visited) = simulate(map, position, direction);
 
-    let map_clone = map.clone();
     let loop_amount: usize = visited
         .iter()
         .cloned()
         .collect::<Vec<_>>()
         .par_iter()
-        .progress_count(visited.len() as u64)
         .map(|&(x, y)| {
-            let mut map_clone = map_clone.clone();
+            let mut map_clone = map.to_vec();
             map_clone[y as usize][x as usize] = 'O';
             let (looped, _) = simulate(&map_clone, start_position, direction);
             map_clone[y as usize][x as usize] = '.';
@@ -40,7 +46,7 @@ pub fn process(input: &str) -> Result<String> {
         })
         .sum();
 
-    Ok(loop_amount.to_string())
+    Ok(loop_amount.into())
 }
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
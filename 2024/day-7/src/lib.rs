@@ -1,2 +1,32 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+use part1::Equation;
+
 pub mod part1;
 pub mod part2;
+
+/// Both parts try to balance the same calibration equations.
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Parsed = Vec<Equation>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        part1::parse(input)
+    }
+
+    fn part1(equations: &Self::Parsed) -> Result<Answer> {
+        part1::solve(equations)
+    }
+
+    fn part2(equations: &Self::Parsed) -> Result<Answer> {
+        part2::solve(equations)
+    }
+
+    fn items(equations: &Self::Parsed) -> Option<usize> {
+        Some(equations.len())
+    }
+}
+
+aoc_common::solution!(Day7);
@@ -1,3 +1,4 @@
+use aoc_common::answer::Answer;
 use miette::*;
 use nom::{
     bytes::complete::tag,
@@ -13,17 +14,23 @@ use strum::{EnumIter, IntoEnumIterator};
 
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let equations = parse(input)?;
+    Ok(solve(&parse(input)?)?.to_string())
+}
+
+/// Sums the targets that some mix of `+` and `*` reaches.
+#[tracing::instrument(skip(equations))]
+pub fn solve(equations: &[Equation]) -> Result<Answer> {
     let total: usize = equations
-        .into_iter()
+        .iter()
         .filter(|eq| evaluate(&eq.numbers, eq.target))
         .map(|eq| eq.target)
         .sum();
 
-    Ok(total.to_string())
+    Ok(total.into())
 }
 
-fn parse(input: &str) -> Result<Vec<Equation>> {
+/// Reads one calibration equation per line.
+pub fn parse(input: &str) -> Result<Vec<Equation>> {
     let input = LocatedSpan::new(input);
     let (_, equations) =
         separated_list1(newline, parse_equation)(input).map_err(|e| miette!(e.to_string()))?;
@@ -63,9 +70,9 @@ fn evaluate(numbers: &[usize], target: usize) -> bool {
 }
 
 #[derive(Debug)]
-struct Equation {
-    target: usize,
-    numbers: Vec<usize>,
+pub struct Equation {
+    pub target: usize,
+    pub numbers: Vec<usize>,
 }
 
 #[derive(Debug, EnumIter)]
@@ -1,46 +1,24 @@
+use aoc_common::answer::Answer;
 use miette::*;
-use nom::{
-    bytes::complete::tag,
-    character::complete::{digit1, newline, space1},
-    combinator::map_res,
-    multi::separated_list1,
-    sequence::separated_pair,
-    IResult,
-};
-use nom_locate::LocatedSpan;
-use std::str::FromStr;
 use strum::{EnumIter, IntoEnumIterator};
 
+use crate::part1::{parse, Equation};
+
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let equations = parse(input)?;
+    Ok(solve(&parse(input)?)?.to_string())
+}
+
+/// Sums the targets that some mix of `+`, `*` and `||` reaches.
+#[tracing::instrument(skip(equations))]
+pub fn solve(equations: &[Equation]) -> Result<Answer> {
     let total: usize = equations
-        .into_iter()
+        .iter()
         .filter(|eq| evaluate(&eq.numbers, eq.target))
         .map(|eq| eq.target)
         .sum();
 
-    Ok(total.to_string())
-}
-
-fn parse(input: &str) -> Result<Vec<Equation>> {
-    let input = LocatedSpan::new(input);
-    let (_, equations) =
-        separated_list1(newline, parse_equation)(input).map_err(|e| miette!(e.to_string()))?;
-    Ok(equations)
-}
-
-fn parse_equation(input: LocatedSpan<&str>) -> IResult<LocatedSpan<&str>, Equation> {
-    let (input, (target, numbers)) = separated_pair(
-        map_res(digit1, |s: LocatedSpan<&str>| usize::from_str(*s)),
-        tag(": "),
-        separated_list1(
-            space1,
-            map_res(digit1, |s: LocatedSpan<&str>| usize::from_str(*s)),
-        ),
-    )(input)?;
-
-    Ok((input, Equation { target, numbers }))
+    Ok(total.into())
 }
 
 fn evaluate(numbers: &[usize], target: usize) -> bool {
@@ -62,12 +40,6 @@ fn evaluate(numbers: &[usize], target: usize) -> bool {
     helper(&numbers[1..], numbers[0], target)
 }
 
-#[derive(Debug)]
-struct Equation {
-    target: usize,
-    numbers: Vec<usize>,
-}
-
 #[derive(Debug, EnumIter)]
 enum Operation {
     Add,
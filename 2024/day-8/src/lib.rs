@@ -1,2 +1,32 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+use part1::AntennaMap;
+
 pub mod part1;
 pub mod part2;
+
+/// Both parts look for antinodes between the same antennas.
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Parsed = AntennaMap;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        part1::parse_map(input)
+    }
+
+    fn part1(map: &Self::Parsed) -> Result<Answer> {
+        part1::solve(map)
+    }
+
+    fn part2(map: &Self::Parsed) -> Result<Answer> {
+        part2::solve(map)
+    }
+
+    fn items(map: &Self::Parsed) -> Option<usize> {
+        Some(map.antennas.len())
+    }
+}
+
+aoc_common::solution!(Day8);
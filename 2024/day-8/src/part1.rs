@@ -1,3 +1,4 @@
+use aoc_common::answer::Answer;
 use glam::IVec2;
 use miette::*;
 use nom::{
@@ -9,25 +10,38 @@ use std::collections::{HashMap, HashSet};
 type Span<'a> = LocatedSpan<&'a str>;
 
 #[derive(Debug)]
-struct Antenna {
-    position: IVec2,
-    frequency: char,
+pub struct Antenna {
+    pub position: IVec2,
+    pub frequency: char,
+}
+
+/// The antennas on the roof and the size of the map around them.
+#[derive(Debug)]
+pub struct AntennaMap {
+    pub antennas: Vec<Antenna>,
+    pub size: IVec2,
 }
 
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let (antennas, map_size) = parse_map(input)?;
-    let antinodes = calculate_antinodes(&antennas, &map_size);
-    Ok(antinodes.len().to_string())
+    Ok(solve(&parse_map(input)?)?.to_string())
+}
+
+/// Counts the cells holding an antinode one spacing beyond a pair of
+/// same-frequency antennas.
+#[tracing::instrument(skip(map))]
+pub fn solve(map: &AntennaMap) -> Result<Answer> {
+    let antinodes = calculate_antinodes(&map.antennas, &map.size);
+    Ok(antinodes.len().into())
 }
 
-fn parse_map(input: &str) -> Result<(Vec<Antenna>, IVec2)> {
-    let (_, antennas) =
+pub fn parse_map(input: &str) -> Result<AntennaMap> {
+    let (_, (antennas, size)) =
         map_parser(Span::new(input)).map_err(|_| Error::msg("Failed to parse map"))?;
-    Ok(antennas)
+    Ok(AntennaMap { antennas, size })
 }
 
-fn map_parser(input: Span) -> IResult<Span, (Vec<Antenna>, IVec2)> {
+fn map_parser(input: Span<'_>) -> IResult<Span<'_>, (Vec<Antenna>, IVec2)> {
     let (input, lines) = separated_list1(line_ending, take_while(|c| c != '\n'))(input)?;
     let lines: Vec<_> = lines
         .into_iter()
@@ -1,59 +1,20 @@
+use aoc_common::answer::Answer;
 use glam::IVec2;
 use miette::*;
-use nom::{
-    bytes::complete::take_while, character::complete::line_ending, multi::separated_list1, IResult,
-};
-use nom_locate::LocatedSpan;
 use std::collections::{HashMap, HashSet};
 
-type Span<'a> = LocatedSpan<&'a str>;
-
-#[derive(Debug)]
-struct Antenna {
-    position: IVec2,
-    frequency: char,
-}
+use crate::part1::{parse_map, Antenna, AntennaMap};
 
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let (antennas, map_size) = parse_map(input)?;
-    let antinodes = calculate_antinodes(&antennas, &map_size);
-    Ok(antinodes.len().to_string())
-}
-
-fn parse_map(input: &str) -> Result<(Vec<Antenna>, IVec2)> {
-    let (_, antennas) =
-        map_parser(Span::new(input)).map_err(|_| Error::msg("Failed to parse map"))?;
-    Ok(antennas)
+    Ok(solve(&parse_map(input)?)?.to_string())
 }
 
-fn map_parser(input: Span) -> IResult<Span, (Vec<Antenna>, IVec2)> {
-    let (input, lines) = separated_list1(line_ending, take_while(|c| c != '\n'))(input)?;
-    let lines: Vec<_> = lines
-        .into_iter()
-        .filter(|line| !line.fragment().is_empty())
-        .collect();
-    let mut antennas = Vec::new();
-    let mut max_x = 0;
-    let max_y = lines.len() as i32;
-
-    for (y, line) in lines.iter().enumerate() {
-        for (x, c) in line.fragment().chars().enumerate() {
-            if c != '.' {
-                antennas.push(Antenna {
-                    position: IVec2::new(x as i32, y as i32),
-                    frequency: c,
-                });
-            }
-            if x as i32 > max_x {
-                max_x = x as i32;
-            }
-        }
-    }
-
-    let map_size = IVec2::new(max_x + 1, max_y);
-
-    Ok((input, (antennas, map_size)))
+/// Counts the cells in line with any pair of same-frequency antennas.
+#[tracing::instrument(skip(map))]
+pub fn solve(map: &AntennaMap) -> Result<Answer> {
+    let antinodes = calculate_antinodes(&map.antennas, &map.size);
+    Ok(antinodes.len().into())
 }
 
 fn calculate_antinodes(antennas: &[Antenna], map_size: &IVec2) -> HashSet<IVec2> {
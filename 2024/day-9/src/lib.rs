@@ -1,2 +1,32 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+use part1::Block;
+
 pub mod part1;
 pub mod part2;
+
+/// Both parts compact the same disk, block by block or file by file.
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Parsed = Vec<Block>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        part1::parse(input)
+    }
+
+    fn part1(disk_map: &Self::Parsed) -> Result<Answer> {
+        part1::solve(disk_map)
+    }
+
+    fn part2(disk_map: &Self::Parsed) -> Result<Answer> {
+        part2::solve(disk_map)
+    }
+
+    fn items(disk_map: &Self::Parsed) -> Option<usize> {
+        Some(disk_map.len())
+    }
+}
+
+aoc_common::solution!(Day9);
@@ -1,20 +1,33 @@
+use aoc_common::answer::Answer;
 use miette::*;
 use nom::{bytes::complete::take, combinator::map_res, multi::many1, IResult};
 
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let (_, mut disk_map) = parse_disk_map(input).map_err(|_| miette!("Error parsing input"))?;
-    disk_map = compact_disk(disk_map);
+    Ok(solve(&parse(input)?)?.to_string())
+}
+
+/// Moves file blocks one at a time into the leftmost free block and returns
+/// the checksum of the compacted disk.
+#[tracing::instrument(skip(disk_map))]
+pub fn solve(disk_map: &[Block]) -> Result<Answer> {
+    let disk_map = compact_disk(disk_map.to_vec());
     let checksum = calculate_checksum(&disk_map);
-    Ok(checksum.to_string())
+    Ok(checksum.into())
 }
 
-#[derive(Debug, Clone)]
-enum Block {
+#[derive(Debug, Clone, PartialEq)]
+pub enum Block {
     File(usize),
     Free,
 }
 
+/// Expands the dense disk map into one entry per block.
+pub fn parse(input: &str) -> Result<Vec<Block>> {
+    let (_, disk_map) = parse_disk_map(input).map_err(|_| miette!("Error parsing input"))?;
+    Ok(disk_map)
+}
+
 fn parse_disk_map(input: &str) -> IResult<&str, Vec<Block>> {
     let (input, digits) = many1(map_res(take(1usize), |s: &str| s.parse::<usize>()))(input)?;
 
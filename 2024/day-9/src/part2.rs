@@ -1,39 +1,20 @@
+use aoc_common::answer::Answer;
 use miette::*;
-use nom::{bytes::complete::take, combinator::map_res, multi::many1, IResult};
+
+use crate::part1::{parse, Block};
 
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let (_, mut disk_map) = parse_disk_map(input).map_err(|_| miette!("Error parsing input"))?;
-    disk_map = compact_disk(disk_map);
-    let checksum = calculate_checksum(&disk_map);
-    Ok(checksum.to_string())
-}
-
-#[derive(Debug, Clone, PartialEq)]
-enum Block {
-    File(usize),
-    Free,
+    Ok(solve(&parse(input)?)?.to_string())
 }
 
-fn parse_disk_map(input: &str) -> IResult<&str, Vec<Block>> {
-    let (input, digits) = many1(map_res(take(1usize), |s: &str| s.parse::<usize>()))(input)?;
-
-    let mut disk_map = Vec::new();
-    let mut iter = digits.into_iter();
-    let mut file_id = 0;
-
-    while let Some(file_len) = iter.next() {
-        let free_len = iter.next().unwrap_or(0);
-        for _ in 0..file_len {
-            disk_map.push(Block::File(file_id));
-        }
-        for _ in 0..free_len {
-            disk_map.push(Block::Free);
-        }
-        file_id += 1;
-    }
-
-    Ok((input, disk_map))
+/// Moves whole files into the leftmost free span that fits them and returns
+/// the checksum of the compacted disk.
+#[tracing::instrument(skip(disk_map))]
+pub fn solve(disk_map: &[Block]) -> Result<Answer> {
+    let disk_map = compact_disk(disk_map.to_vec());
+    let checksum = calculate_checksum(&disk_map);
+    Ok(checksum.into())
 }
 
 fn compact_disk(disk_map: Vec<Block>) -> Vec<Block> {
@@ -0,0 +1,217 @@
+use aoc_common::diagnostics;
+use chumsky::prelude::*;
+use miette::*;
+
+/// Number of positions on the dial.
+pub const SIZE: u32 = 100;
+/// Where the dial points before the first rotation.
+pub const START: u32 = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Left(u32),
+    Right(u32),
+}
+
+/// Defines the parser using Chumsky combinators.
+///
+/// We specify the error type `extra::Err<Rich<'a, char>>` to get detailed diagnostics,
+/// although we just flatten them for the result here.
+fn parser<'a>() -> impl Parser<'a, &'a str, Vec<Instruction>, extra::Err<Rich<'a, char>>> {
+    let amount = diagnostics::number::<u32>();
+    let instruction = one_of("LR").then(amount).map(|(dir, amount)| match dir {
+        'L' => Instruction::Left(amount),
+        'R' => Instruction::Right(amount),
+        _ => unreachable!("one_of ensures only L or R are parsed"),
+    });
+
+    instruction
+        .separated_by(text::newline())
+        .allow_trailing()
+        .collect()
+}
+
+/// Parses one rotation per line.
+pub fn parse(input: &str) -> Result<Vec<Instruction>> {
+    Ok(diagnostics::parse(parser(), "input", input)?)
+}
+
+/// The dial right after applying an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct State {
+    pub instruction: Instruction,
+    /// Where the dial points once the rotation ends.
+    pub position: u32,
+    /// Clicks during the rotation that left the dial at 0, including the
+    /// final one.
+    pub zero_clicks: u64,
+}
+
+/// The shape of a dial: how many positions it has and where it starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DialConfig {
+    /// Number of positions, numbered from 0.
+    pub size: u32,
+    /// Where the dial points before the first rotation.
+    pub start: u32,
+}
+
+impl Default for DialConfig {
+    /// The puzzle's dial: [`SIZE`] positions, starting at [`START`].
+    fn default() -> Self {
+        Self {
+            size: SIZE,
+            start: START,
+        }
+    }
+}
+
+/// A dial being turned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dial {
+    size: i64,
+    position: i64,
+}
+
+impl Default for Dial {
+    fn default() -> Self {
+        Self {
+            size: SIZE as i64,
+            position: START as i64,
+        }
+    }
+}
+
+impl Dial {
+    /// A dial pointing at `config.start`, or an error if `config` is not a
+    /// dial.
+    pub fn new(config: DialConfig) -> Result<Self> {
+        if config.size == 0 {
+            bail!("A dial needs at least one position");
+        }
+        if config.start >= config.size {
+            bail!(
+                "A dial with {} positions cannot start at {}",
+                config.size,
+                config.start
+            );
+        }
+        Ok(Self {
+            size: config.size as i64,
+            position: config.start as i64,
+        })
+    }
+
+    /// Where the dial points.
+    pub fn position(&self) -> u32 {
+        self.position as u32
+    }
+
+    /// Applies `instruction` and returns how many of its clicks left the
+    /// dial at 0, including the final one.
+    pub fn rotate(&mut self, instruction: Instruction) -> u64 {
+        let (pos, modulus) = (self.position, self.size);
+        let (new_pos, zero_clicks) = match instruction {
+            Instruction::Left(amount) => {
+                let amount = amount as i64;
+
+                // Moving Left means subtracting.
+                // We cover the interval of integers [pos - amount, pos - 1].
+                // The number of multiples of the size in an interval [A, B] is:
+                // floor(B / size) - floor((A - 1) / size)
+                let upper = pos - 1;
+                let lower_minus_1 = pos - amount - 1;
+
+                let hits = upper.div_euclid(modulus) - lower_minus_1.div_euclid(modulus);
+                ((pos - amount).rem_euclid(modulus), hits)
+            }
+            Instruction::Right(amount) => {
+                let amount = amount as i64;
+
+                // Moving Right means adding.
+                // We cover the interval (pos, pos + amount].
+                // Since 'pos' is always normalized (0 <= pos < size),
+                // the formula simplifies to just integer division.
+                ((pos + amount) % modulus, (pos + amount) / modulus)
+            }
+        };
+
+        self.position = new_pos;
+        zero_clicks as u64
+    }
+}
+
+/// Yields the state of the puzzle's dial after every instruction, starting
+/// from [`START`].
+pub fn trace(instructions: &[Instruction]) -> impl Iterator<Item = State> + '_ {
+    trace_with(instructions, Dial::default())
+}
+
+/// Yields the state of `dial` after every instruction.
+pub fn trace_with(instructions: &[Instruction], dial: Dial) -> impl Iterator<Item = State> + '_ {
+    instructions.iter().scan(dial, |dial, &instruction| {
+        let zero_clicks = dial.rotate(instruction);
+        Some(State {
+            instruction,
+            position: dial.position(),
+            zero_clicks,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trace_follows_the_dial() -> Result<()> {
+        let instructions = parse("L68\nL30\nR48\nR200\nL5")?;
+        let states = trace(&instructions)
+            .map(|s| (s.position, s.zero_clicks))
+            .collect::<Vec<_>>();
+        assert_eq!(states, vec![(82, 1), (52, 0), (0, 1), (0, 2), (95, 0)]);
+        Ok(())
+    }
+
+    #[test]
+    fn rotations_match_clicking_one_step_at_a_time() -> Result<()> {
+        let instructions = parse("R7\nL3\nL20\nR1\nL1\nR33\nL9\nR0\nL0")?;
+        for size in 1..=12 {
+            for start in 0..size {
+                let mut dial = Dial::new(DialConfig { size, start })?;
+                let mut position = start as i64;
+                for &instruction in &instructions {
+                    let (step, amount) = match instruction {
+                        Instruction::Left(amount) => (-1, amount),
+                        Instruction::Right(amount) => (1, amount),
+                    };
+                    let mut expected = 0;
+                    for _ in 0..amount {
+                        position = (position + step).rem_euclid(size as i64);
+                        expected += u64::from(position == 0);
+                    }
+                    assert_eq!(dial.rotate(instruction), expected, "{size}/{start}");
+                    assert_eq!(dial.position() as i64, position, "{size}/{start}");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_impossible_dials() {
+        let dial = |size, start| Dial::new(DialConfig { size, start });
+        assert!(dial(0, 0).is_err());
+        assert!(dial(10, 10).is_err());
+        assert_eq!(Dial::new(DialConfig::default()).unwrap(), Dial::default());
+    }
+
+    #[test]
+    fn parse_errors_point_into_the_input() {
+        let report = parse("L68\nX30\nR48").unwrap_err();
+        let error = report
+            .downcast_ref::<diagnostics::ParseError>()
+            .expect("parse failures carry the input");
+        assert_eq!(error.labels()[0].offset(), 4);
+    }
+}
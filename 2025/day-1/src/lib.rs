@@ -1,2 +1,33 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use dial::Instruction;
+use miette::Result;
+
+pub mod dial;
 pub mod part1;
 pub mod part2;
+
+/// Both parts replay the same rotations on the dial.
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Parsed = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        dial::parse(input)
+    }
+
+    fn part1(instructions: &Self::Parsed) -> Result<Answer> {
+        part1::solve(instructions)
+    }
+
+    fn part2(instructions: &Self::Parsed) -> Result<Answer> {
+        part2::solve(instructions)
+    }
+
+    fn items(instructions: &Self::Parsed) -> Option<usize> {
+        Some(instructions.len())
+    }
+}
+
+aoc_common::solution!(Day1);
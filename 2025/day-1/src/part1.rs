@@ -1,62 +1,35 @@
+use aoc_common::answer::Answer;
 use miette::*;
-use chumsky::prelude::*;
 
-#[derive(Debug, Clone, Copy)]
-enum Instruction {
-    Left(u32),
-    Right(u32),
-}
-
-/// Defines the parser using Chumsky combinators.
-///
-/// We specify the error type `extra::Err<Rich<'a, char>>` to get detailed diagnostics,
-/// although we just flatten them for the result here.
-fn parser<'a>() -> impl Parser<'a, &'a str, Vec<Instruction>, extra::Err<Rich<'a, char>>> {
-    let instruction = one_of("LR")
-        .then(text::int(10).from_str::<u32>().unwrapped())
-        .map(|(dir, amount)| match dir {
-            'L' => Instruction::Left(amount),
-            'R' => Instruction::Right(amount),
-            _ => unreachable!("one_of ensures only L or R are parsed"),
-        });
+use crate::dial::{self, Dial, DialConfig, Instruction};
 
-    instruction
-        .separated_by(text::newline())
-        .allow_trailing()
-        .collect()
+#[tracing::instrument]
+pub fn process(input: &str) -> Result<String> {
+    process_with(input, DialConfig::default())
 }
 
+/// Solves the puzzle for a dial other than the puzzle's.
 #[tracing::instrument]
-pub fn process(input: &str) -> Result<String> {
-    let instructions = parser()
-        .parse(input)
-        .into_result()
-        .map_err(|e| miette!("Parse failed with errors: {:?}", e))?;
+pub fn process_with(input: &str, config: DialConfig) -> Result<String> {
+    Ok(solve_with(&dial::parse(input)?, config)?.to_string())
+}
 
-    let start_position = 50;
-    let modulus = 100;
+/// Counts the rotations that leave the puzzle's dial at 0.
+#[tracing::instrument(skip(instructions))]
+pub fn solve(instructions: &[Instruction]) -> Result<Answer> {
+    solve_with(instructions, DialConfig::default())
+}
+
+/// Like [`solve`], for a dial other than the puzzle's.
+#[tracing::instrument(skip(instructions))]
+pub fn solve_with(instructions: &[Instruction], config: DialConfig) -> Result<Answer> {
+    let dial = Dial::new(config)?;
 
-    let zero_hits = instructions
-        .iter()
-        // scan maintains the state (current dial position) through the iterator
-        .scan(start_position, |position, instruction| {
-            match instruction {
-                // For Left: (current - amount) % 100
-                // We use rem_euclid to handle negative wrapping correctly (e.g., -10 % 100 should be 90)
-                Instruction::Left(amount) => {
-                    *position = (*position as i32 - *amount as i32).rem_euclid(modulus) as u32;
-                }
-                // For Right: (current + amount) % 100
-                Instruction::Right(amount) => {
-                    *position = (*position + *amount) % modulus as u32;
-                }
-            }
-            Some(*position)
-        })
-        .filter(|&pos| pos == 0)
+    let zero_hits = dial::trace_with(instructions, dial)
+        .filter(|state| state.position == 0)
         .count();
 
-    Ok(zero_hits.to_string())
+    Ok(zero_hits.into())
 }
 
 #[cfg(test)]
@@ -78,4 +51,12 @@ L82";
         assert_eq!("3", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn other_dials() -> Result<()> {
+        let input = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
+        let dial = DialConfig { size: 10, start: 0 };
+        assert_eq!("3", process_with(input, dial)?);
+        Ok(())
+    }
 }
@@ -1,79 +1,36 @@
+use aoc_common::answer::Answer;
 use miette::*;
-use chumsky::prelude::*;
 
-#[derive(Debug, Clone, Copy)]
-enum Instruction {
-    Left(u32),
-    Right(u32),
-}
-
-/// Defines the parser using Chumsky combinators.
-///
-/// We specify the error type `extra::Err<Rich<'a, char>>` to get detailed diagnostics,
-/// although we just flatten them for the result here.
-fn parser<'a>() -> impl Parser<'a, &'a str, Vec<Instruction>, extra::Err<Rich<'a, char>>> {
-    let instruction = one_of("LR")
-        .then(text::int(10).from_str::<u32>().unwrapped())
-        .map(|(dir, amount)| match dir {
-            'L' => Instruction::Left(amount),
-            'R' => Instruction::Right(amount),
-            _ => unreachable!("one_of ensures only L or R are parsed"),
-        });
-
-    instruction
-        .separated_by(text::newline())
-        .allow_trailing()
-        .collect()
-}
+use crate::dial::{self, Dial, DialConfig, Instruction};
 
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let instructions = parser()
-        .parse(input)
-        .into_result()
-        .map_err(|e| miette!("Parse failed with errors: {:?}", e))?;
-
-    let start_pos: i64 = 50;
-    let modulus: i64 = 100;
-
-    // We use fold to maintain the current position and accumulate the total count of '0' hits.
-    let (_, total_hits) = instructions
-        .iter()
-        .fold((start_pos, 0), |(pos, count), instruction| {
-            match instruction {
-                Instruction::Left(amount) => {
-                    let amount = *amount as i64;
-
-                    // Moving Left means subtracting.
-                    // We cover the interval of integers [pos - amount, pos - 1].
-                    // The number of multiples of 100 in an interval [A, B] is:
-                    // floor(B / 100) - floor((A - 1) / 100)
-
-                    let upper = pos - 1;
-                    let lower_minus_1 = pos - amount - 1;
-
-                    let hits = upper.div_euclid(modulus) - lower_minus_1.div_euclid(modulus);
-                    let new_pos = (pos - amount).rem_euclid(modulus);
+    process_with(input, DialConfig::default())
+}
 
-                    (new_pos, count + hits)
-                }
-                Instruction::Right(amount) => {
-                    let amount = *amount as i64;
+/// Solves the puzzle for a dial other than the puzzle's.
+#[tracing::instrument]
+pub fn process_with(input: &str, config: DialConfig) -> Result<String> {
+    Ok(solve_with(&dial::parse(input)?, config)?.to_string())
+}
 
-                    // Moving Right means adding.
-                    // We cover the interval (pos, pos + amount].
-                    // Since 'pos' is always normalized (0 <= pos < 100),
-                    // the formula simplifies to just integer division.
+/// Counts every click that passes the puzzle's dial over 0.
+#[tracing::instrument(skip(instructions))]
+pub fn solve(instructions: &[Instruction]) -> Result<Answer> {
+    solve_with(instructions, DialConfig::default())
+}
 
-                    let hits = (pos + amount) / modulus;
-                    let new_pos = (pos + amount) % modulus;
+/// Like [`solve`], for a dial other than the puzzle's.
+#[tracing::instrument(skip(instructions))]
+pub fn solve_with(instructions: &[Instruction], config: DialConfig) -> Result<Answer> {
+    let dial = Dial::new(config)?;
 
-                    (new_pos, count + hits)
-                }
-            }
-        });
+    // Every click that lands on 0 counts, not just where rotations end.
+    let total_hits: u64 = dial::trace_with(instructions, dial)
+        .map(|state| state.zero_clicks)
+        .sum();
 
-    Ok(total_hits.to_string())
+    Ok(total_hits.into())
 }
 
 #[cfg(test)]
@@ -95,4 +52,12 @@ L82";
         assert_eq!("6", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn other_dials() -> Result<()> {
+        let input = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
+        let dial = DialConfig { size: 10, start: 0 };
+        assert_eq!("45", process_with(input, dial)?);
+        Ok(())
+    }
 }
@@ -1,15 +1,29 @@
 use aoc2025_day_10::*;
 
 fn main() {
-    divan::main();
+    // Without a stored input there is nothing to benchmark.
+    if aoc_runtime::bench_input!().is_some() {
+        divan::main();
+    }
+}
+
+/// The stored input, which `main` has checked is there.
+fn input() -> &'static str {
+    aoc_runtime::bench_input!().unwrap()
+}
+
+#[divan::bench]
+fn part2_exact() {
+    part2::process_exact(divan::black_box(input())).unwrap();
 }
 
 #[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!("../input1.txt",))).unwrap();
+fn part2_lattice() {
+    part2::process_lattice(divan::black_box(input())).unwrap();
 }
 
+#[cfg(feature = "good_lp")]
 #[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!("../input2.txt",))).unwrap();
+fn part2_good_lp() {
+    part2::process_with(divan::black_box(input()), &part2::GoodLp).unwrap();
 }
@@ -1,2 +1,44 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+use part1::Machine;
+use part2::LinearSystem;
+
 pub mod part1;
 pub mod part2;
+
+/// The manual as each part reads it: part 1 only looks at the lights and
+/// part 2 only at the joltage counters.
+#[derive(Debug, Clone)]
+pub struct Manual {
+    pub machines: Vec<Machine>,
+    pub systems: Vec<LinearSystem>,
+}
+
+/// Both parts press the buttons of the same machines.
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Parsed = Manual;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Ok(Manual {
+            machines: part1::parse(input)?,
+            systems: part2::parse(input)?,
+        })
+    }
+
+    fn part1(manual: &Self::Parsed) -> Result<Answer> {
+        part1::solve(&manual.machines)
+    }
+
+    fn part2(manual: &Self::Parsed) -> Result<Answer> {
+        part2::solve(&manual.systems)
+    }
+
+    fn items(manual: &Self::Parsed) -> Option<usize> {
+        Some(manual.machines.len())
+    }
+}
+
+aoc_common::solution!(Day10);
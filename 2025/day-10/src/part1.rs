@@ -1,196 +1,80 @@
-use bitvec::prelude::*;
+use aoc_common::answer::Answer;
+use aoc_common::diagnostics;
 use chumsky::prelude::*;
+use gf2::{Gf2Matrix, SolutionSpace};
 use miette::*;
+use std::fmt::{self, Write};
 
-/// A bit vector backed by `usize` words with Least Significant Bit first ordering.
-/// This aligns with standard CPU integer operations for maximum performance.
-type Row = BitVec<usize, Lsb0>;
+pub use gf2::Row;
 
-#[derive(Debug)]
-struct Machine {
+/// One line of the manual: the light pattern and what each button toggles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Machine {
     /// Target configuration (b vector)
     target: Row,
     /// Button configurations (A matrix columns)
     buttons: Vec<Row>,
 }
 
-struct LinearSystem {
-    /// Augmented matrix [A | b] in Reduced Row Echelon Form
-    matrix: Vec<Row>,
-    num_vars: usize,
-    num_eqs: usize,
-    /// Maps column index (variable) to pivot row index
-    col_to_pivot: Vec<Option<usize>>,
-    /// Indices of free variables (columns without pivots)
-    free_vars: Vec<usize>,
-}
-
-impl LinearSystem {
-    fn new(machine: &Machine) -> Self {
-        let num_eqs = machine.target.len();
-        let num_vars = machine.buttons.len();
-
-        // Build Augmented Matrix [A | b]
-        // Each inner BitVec is a row (equation).
-        // Matrix dimensions: num_eqs x (num_vars + 1)
-        let mut matrix = vec![Row::repeat(false, num_vars + 1); num_eqs];
-
-        for (r, row) in matrix.iter_mut().enumerate() {
-            // Fill A matrix part
-            for (c, button) in machine.buttons.iter().enumerate() {
-                // Safety: Parser ensures button length matches target length
-                if unsafe { *button.get_unchecked(r) } {
-                    row.set(c, true);
-                }
-            }
-            // Fill b vector part (augmented column)
-            if unsafe { *machine.target.get_unchecked(r) } {
-                row.set(num_vars, true);
-            }
-        }
-
-        Self {
-            matrix,
-            num_vars,
-            num_eqs,
-            col_to_pivot: vec![None; num_vars],
-            free_vars: Vec::new(),
-        }
+impl Machine {
+    /// The light pattern to reach, one bit per light.
+    pub fn lights(&self) -> &Row {
+        &self.target
     }
 
-    /// Performs Gaussian Elimination to transform the matrix into Reduced Row Echelon Form (RREF).
-    fn rref(&mut self) -> bool {
-        let mut pivot_row = 0;
-
-        for c in 0..self.num_vars {
-            if pivot_row >= self.num_eqs {
-                self.free_vars.push(c);
-                continue;
-            }
-
-            // Find a row with a 1 in the current column (pivot)
-            let mut pivot_found = None;
-            for r in pivot_row..self.num_eqs {
-                if self.matrix[r][c] {
-                    pivot_found = Some(r);
-                    break;
-                }
-            }
-
-            if let Some(r) = pivot_found {
-                self.matrix.swap(pivot_row, r);
-                self.col_to_pivot[c] = Some(pivot_row);
-
-                // Clone pivot row to avoid multiple mutable borrows
-                let pivot_vec = self.matrix[pivot_row].clone();
-
-                // XOR eliminate other rows (both below AND above for RREF)
-                for i in 0..self.num_eqs {
-                    if i != pivot_row && self.matrix[i][c] {
-                        let row = &mut self.matrix[i];
-                        *row ^= &pivot_vec;
-                    }
-                }
-                pivot_row += 1;
-            } else {
-                self.free_vars.push(c);
-            }
-        }
+    /// The machine's lights as a system of equations over GF(2), with one
+    /// equation per light and one variable per button.
+    pub fn system(&self) -> Gf2Matrix {
+        Gf2Matrix::from_columns(self.target.len(), &self.buttons)
+    }
 
-        // Check for consistency: 0 = 1?
-        // If a row is all zeros except the augmented column, no solution exists.
-        for r in pivot_row..self.num_eqs {
-            if self.matrix[r][self.num_vars] {
-                return false;
-            }
-        }
+    /// Every set of button presses that produces the target, as bit vectors
+    /// indexed by button, or `None` when the target is unreachable.
+    pub fn solutions(&self) -> Option<SolutionSpace> {
+        self.system().solve(&self.target)
+    }
 
-        true
+    /// The fewest buttons to press, one bit per button, or `None` when the
+    /// target is unreachable.
+    ///
+    /// Fails when the machine has too many redundant buttons to search.
+    pub fn solve_min_presses(&self) -> Result<Option<Row>> {
+        self.solutions()
+            .map(|space| space.min_weight_solution())
+            .transpose()
+            .into_diagnostic()
     }
 
-    /// Extracts the particular solution and the basis of the null space.
-    fn extract_solution_space(&self) -> (Row, Vec<Row>) {
-        // Particular Solution (x_p)
-        // Set all free variables to 0. Since matrix is in RREF,
-        // the pivot variables simply take the value of the augmented column.
-        let mut x_p = Row::repeat(false, self.num_vars);
-        for (c, &pivot_row) in self.col_to_pivot.iter().enumerate() {
-            if let Some(r) = pivot_row {
-                if self.matrix[r][self.num_vars] {
-                    x_p.set(c, true);
-                }
-            }
+    /// Whether pressing the buttons set in `presses` lights up the target.
+    pub fn verify(&self, presses: &Row) -> bool {
+        if presses.len() != self.buttons.len() {
+            return false;
         }
-
-        // Null Space Basis
-        // For each free variable f, set x_f = 1, others = 0, and solve for pivots.
-        let mut basis = Vec::with_capacity(self.free_vars.len());
-
-        for &f in &self.free_vars {
-            let mut v = Row::repeat(false, self.num_vars);
-            v.set(f, true);
-
-            // Back-substitute to find dependent pivot variables
-            // Start from rightmost pivot columns to handle dependencies correctly
-            for c in (0..f).rev() {
-                if let Some(r) = self.col_to_pivot[c] {
-                    // x_c = sum(A_ck * x_k) for k > c
-                    // Check dot product of row `r` and current vector `v`
-                    let mut dot = false;
-                    for k in (c + 1)..self.num_vars {
-                        if self.matrix[r][k] && v[k] {
-                            dot = !dot;
-                        }
-                    }
-                    if dot {
-                        v.set(c, true);
-                    }
-                }
-            }
-            basis.push(v);
+        let mut lights = Row::repeat(false, self.target.len());
+        for button in presses.iter_ones() {
+            lights ^= &self.buttons[button];
         }
-
-        (x_p, basis)
+        lights == self.target
     }
+}
 
-    /// Solves for the minimum Hamming weight (fewest button presses).
-    /// Uses Gray Codes to iterate the null space efficiently.
-    fn solve_min_weight(&mut self) -> Option<usize> {
-        if !self.rref() {
-            return None;
-        }
-
-        let (mut current_sol, null_basis) = self.extract_solution_space();
-        let k = null_basis.len();
-
-        // If no free variables, unique solution
-        if k == 0 {
-            return Some(current_sol.count_ones());
+/// Writes the machine back as a manual line, without the joltages part 1
+/// ignores.
+impl fmt::Display for Machine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char('[')?;
+        for light in self.target.iter().by_vals() {
+            f.write_char(if light { '#' } else { '.' })?;
         }
-
-        let mut min_weight = current_sol.count_ones();
-
-        // Explicitly typed as usize to prevent "ambiguous numeric type" error
-        let num_combinations: usize = 1 << k;
-
-        // Gray Code Iteration:
-        // iterate i from 1 to 2^k. The bit that changes between gray(i-1) and gray(i)
-        // is the position of the lowest set bit in i (0-indexed).
-        // This allows us to update the current solution with a single XOR.
-        for i in 1..num_combinations {
-            // Find index of the bit that flipped (trailing zeros of i)
-            let basis_idx = i.trailing_zeros() as usize;
-
-            // Update solution: x_new = x_old XOR basis[idx]
-            current_sol ^= &null_basis[basis_idx];
-
-            let weight = current_sol.count_ones();
-            if weight < min_weight {
-                min_weight = weight;
-            }
+        f.write_char(']')?;
+        for button in &self.buttons {
+            let lights = button
+                .iter_ones()
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>();
+            write!(f, " ({})", lights.join(","))?;
         }
-
-        Some(min_weight)
+        Ok(())
     }
 }
 
@@ -210,7 +94,7 @@ fn parser<'a>() -> impl Parser<'a, &'a str, Vec<Machine>, extra::Err<Rich<'a, ch
     // (0,2,3)
     let indices = text::int(10)
         .from_str::<usize>()
-        .unwrapped()
+        .try_map(|index, span| index.map_err(|e| Rich::custom(span, e)))
         .separated_by(just(','))
         .collect::<Vec<usize>>()
         .delimited_by(just('('), just(')'));
@@ -251,23 +135,32 @@ fn parser<'a>() -> impl Parser<'a, &'a str, Vec<Machine>, extra::Err<Rich<'a, ch
         .collect()
 }
 
+/// Parses the manual, one machine per line.
+pub fn parse(input: &str) -> Result<Vec<Machine>> {
+    Ok(diagnostics::parse(parser(), "input", input)?)
+}
+
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let machines = parser()
-        .parse(input)
-        .into_result()
-        .map_err(|e| miette!("Parse failed: {:?}", e))?;
+    Ok(solve(&parse(input)?)?.to_string())
+}
 
+/// The fewest button presses that light up every machine.
+#[tracing::instrument(skip(machines))]
+pub fn solve(machines: &[Machine]) -> Result<Answer> {
     let total_presses: usize = machines
         .iter()
-        .map(|m| {
-            LinearSystem::new(m)
-                .solve_min_weight()
-                .expect("Machine configuration should be solvable")
+        .enumerate()
+        .map(|(index, m)| {
+            let presses = m
+                .solve_min_presses()?
+                .ok_or_else(|| miette!("Machine {index} cannot light up {m}"))?;
+            debug_assert!(m.verify(&presses), "{presses} does not light up {m}");
+            Ok(presses.count_ones())
         })
-        .sum();
+        .sum::<Result<_>>()?;
 
-    Ok(total_presses.to_string())
+    Ok(total_presses.into())
 }
 
 #[cfg(test)]
@@ -282,4 +175,102 @@ mod tests {
         assert_eq!("7", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn solutions_cover_the_null_space() -> Result<()> {
+        let machines = parse("[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}")?;
+        let machine = &machines[0];
+        let space = machine.solutions().unwrap();
+        let solutions = space.iter().into_diagnostic()?.collect::<Vec<_>>();
+
+        // 6 buttons over 4 independent lights leave 2 free variables.
+        assert_eq!(solutions.len(), 4);
+        for x in &solutions {
+            let mut lights = Row::repeat(false, machine.target.len());
+            for button in x.iter_ones() {
+                lights ^= &machine.buttons[button];
+            }
+            assert_eq!(lights, machine.target);
+        }
+
+        // A custom objective: buttons 3 and 5 are expensive, so the cheapest
+        // solution is no longer the one with the fewest presses.
+        let cost = |x: &Row| {
+            x.iter_ones()
+                .map(|b| if b == 3 || b == 5 { 10 } else { 1 })
+                .sum::<usize>()
+        };
+        assert_eq!(solutions.iter().map(cost).min(), Some(3));
+        Ok(())
+    }
+
+    #[test]
+    fn large_null_spaces_meet_in_the_middle() -> Result<()> {
+        // 40 buttons over 3 lights leave 37 free variables, far too many to
+        // walk, yet one press of a light-0 button does it.
+        let buttons = (0..40).map(|i| format!("({})", i % 3)).collect::<Vec<_>>();
+        assert_eq!("1", process(&format!("[#..] {}", buttons.join(" ")))?);
+        Ok(())
+    }
+
+    #[test]
+    fn unreachable_targets_have_no_solutions() -> Result<()> {
+        let machines = parse("[#.] (1)")?;
+        assert!(machines[0].solutions().is_none());
+        assert!(machines[0].solve_min_presses()?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn unsolvable_machines_are_reported() {
+        let error = process("[.#] (1)\n[#.] (1)").unwrap_err();
+        assert_eq!(error.to_string(), "Machine 1 cannot light up [#.] (1)");
+    }
+
+    #[test]
+    fn min_presses_light_up_the_target() -> Result<()> {
+        let machines = parse(
+            "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+[#..] (0) (1) (2) (0,1) (0,1,2) (1) (2) (0) (1) (2) (0) (1) (2) (0) (1) (2) (0) (1) (2) (0) (1) (2) (0) (1) (2)",
+        )?;
+        let presses = machines[0].solve_min_presses()?.unwrap();
+        assert_eq!(presses.count_ones(), 2);
+        assert!(machines[0].verify(&presses));
+
+        // 25 buttons over 3 lights meet in the middle.
+        let presses = machines[1].solve_min_presses()?.unwrap();
+        assert_eq!(presses.count_ones(), 1);
+        assert!(machines[1].verify(&presses));
+
+        assert!(!machines[0].verify(&Row::repeat(false, 6)));
+        assert!(!machines[0].verify(&Row::repeat(false, 2)));
+        Ok(())
+    }
+
+    #[test]
+    fn parse_errors_point_into_the_input() {
+        let input = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+[...#.] (0,2,3,4 (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}";
+        let report = process(input).unwrap_err();
+        let error = report
+            .downcast_ref::<diagnostics::ParseError>()
+            .expect("parse failures carry the input");
+        let offset = error.labels()[0].offset();
+        assert_eq!(input[..offset].lines().count(), 2);
+    }
+
+    #[test]
+    fn machines_round_trip_through_display() -> Result<()> {
+        let machines = parse("[.##.] (3,1) () (7) {3,5,4,7}\n[]")?;
+        let lines = machines.iter().map(Machine::to_string).collect::<Vec<_>>();
+        assert_eq!(lines, ["[.##.] (1,3) () ()", "[]"]);
+        assert_eq!(parse(&lines.join("\n"))?, machines);
+        Ok(())
+    }
+
+    #[test]
+    fn oversized_indices_are_parse_errors() {
+        let report = parse("[.#] (99999999999999999999999)").unwrap_err();
+        assert!(report.downcast_ref::<diagnostics::ParseError>().is_some());
+    }
 }
@@ -1,7 +1,9 @@
+use aoc_common::answer::Answer;
+use aoc_common::par::prelude::*;
+use aoc_common::{diagnostics, explain, explain_span};
 use chumsky::prelude::*;
-use miette::{miette, Result};
+use miette::Result;
 use nalgebra::{DMatrix, DVector};
-use rayon::prelude::*;
 
 /// Numerical epsilon for comparing floating point values to zero.
 const EPSILON: f64 = 1e-9;
@@ -16,6 +18,10 @@ const INTEGRALITY_TOLERANCE: f64 = 1e-3;
 /// Tolerance for pruning branches in B&B.
 const PRUNING_TOLERANCE: f64 = 1e-5;
 
+/// How far below zero a basic variable may drift before the dual simplex
+/// treats it as infeasible.
+const PRIMAL_TOLERANCE: f64 = 1e-6;
+
 #[derive(Clone, Debug)]
 pub struct LinearSystem {
     pub a: DMatrix<f64>,
@@ -25,326 +31,241 @@ pub struct LinearSystem {
     pub original_b: DVector<f64>,
 }
 
+impl LinearSystem {
+    /// The system of pressing the buttons, the columns of `a`, to bring the
+    /// counters to `b`, every press costing one.
+    pub fn new(a: DMatrix<f64>, b: DVector<f64>) -> Self {
+        Self {
+            c: DVector::from_element(a.ncols(), 1.0),
+            a,
+            b: b.clone(),
+            original_b: b,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Solution {
     pub x: DVector<f64>,
     pub cost: f64,
 }
 
-mod simplex {
-    use super::*;
-
-    /// Solves the Linear Programming relaxation of the system.
-    pub fn solve(sys: &LinearSystem) -> Option<Solution> {
-        // Phase 1: check feasibility and find initial BFS
-        let (mut tableau, m, n) = setup_phase_one(sys);
+mod cuts;
+mod exact;
+mod lattice;
+mod lp;
+mod simplex;
 
-        let phase1_obj_col = tableau.ncols() - 1;
-        if !run_pivot_loop(&mut tableau, m, phase1_obj_col) {
-            return None; // Unbounded (should not happen in Phase 1)
-        }
-
-        // Check Phase 1 objective (Minimize sum of artificials)
-        let phase1_cost = tableau[(m, tableau.ncols() - 1)];
-        if phase1_cost.abs() > PHASE1_TOLERANCE {
-            return None; // Infeasible
-        }
+#[cfg(feature = "good_lp")]
+pub use lp::GoodLp;
+pub use lp::{LpBackend, Simplex, Tighten};
 
-        // Phase 2: Optimize original objective
-        let (mut phase2_tableau, active_rows) = prepare_phase_two(&tableau, m, n);
-        setup_phase_two_objective(&mut phase2_tableau, &sys.c, active_rows, n);
+mod milp {
+    use std::cmp::Ordering;
+    use std::collections::BinaryHeap;
+    use std::sync::atomic::{self, AtomicU64};
+    use std::sync::Arc;
 
-        if !run_pivot_loop(&mut phase2_tableau, active_rows, n) {
-            return None; // Unbounded
-        }
+    use super::*;
 
-        extract_solution(&phase2_tableau, active_rows, n)
+    struct BranchNode<W> {
+        lower_bounds: Vec<f64>,
+        upper_bounds: Vec<Option<f64>>,
+        /// Relaxation cost of the parent, which nothing below this node can
+        /// beat.
+        bound: f64,
+        /// The parent's solved relaxation and the bound this node adds to
+        /// it, when the backend can re-solve from there.
+        parent: Option<(Arc<W>, Tighten)>,
     }
 
-    fn setup_phase_one(sys: &LinearSystem) -> (DMatrix<f64>, usize, usize) {
-        let m = sys.a.nrows();
-        let n = sys.a.ncols();
-        let width = n + m + 1; // Vars + Artificials + RHS
-        let height = m + 1; // Constraints + Objective
-
-        let mut tableau = DMatrix::zeros(height, width);
-
-        // Setup constraints (handling negative RHS by flipping signs)
-        for r in 0..m {
-            let sign = if sys.b[r] < 0.0 { -1.0 } else { 1.0 };
-
-            for c in 0..n {
-                tableau[(r, c)] = sys.a[(r, c)] * sign;
-            }
-            tableau[(r, n + r)] = 1.0; // Artificial variable identity
-            tableau[(r, width - 1)] = sys.b[r] * sign;
+    // Ordered so that `BinaryHeap`, a max-heap, pops the lowest bound first.
+    impl<W> Ord for BranchNode<W> {
+        fn cmp(&self, other: &Self) -> Ordering {
+            other.bound.total_cmp(&self.bound)
         }
+    }
 
-        // Setup Phase 1 Objective: Maximize -Sum(Artificials)
-        // Algebraically eliminate artificials from the objective row immediately.
-        // Obj = -Sum(Row_i) for all i
-        for c in 0..width {
-            let col_sum: f64 = (0..m).map(|r| tableau[(r, c)]).sum();
-            tableau[(m, c)] = -col_sum;
+    impl<W> PartialOrd for BranchNode<W> {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
         }
+    }
 
-        // Zero out the artificial columns in the objective row (canonical form)
-        for i in 0..m {
-            tableau[(m, n + i)] = 0.0;
+    impl<W> PartialEq for BranchNode<W> {
+        fn eq(&self, other: &Self) -> bool {
+            self.cmp(other) == Ordering::Equal
         }
-
-        (tableau, m, n)
     }
 
-    fn prepare_phase_two(tableau: &DMatrix<f64>, m: usize, n: usize) -> (DMatrix<f64>, usize) {
-        let width = tableau.ncols();
-        // Identify which column is basic for each row
-        let mut basis_col_for_row = vec![None; m];
+    impl<W> Eq for BranchNode<W> {}
 
-        for r in 0..m {
-            basis_col_for_row[r] = find_basis_col(tableau, r, m, width - 1);
-        }
+    /// Cost of the best integer solution found so far, shared by the nodes
+    /// being evaluated in parallel. `u64::MAX` until there is one.
+    struct Incumbent(AtomicU64);
 
-        // Basis Repair: If Artificial variable is basic, try to pivot it out
-        let mut repaired_tableau = tableau.clone();
-        for r in 0..m {
-            if let Some(bc) = basis_col_for_row[r] {
-                if bc >= n {
-                    // Artificial is basic. Try to find a non-artificial pivot.
-                    if let Some(pc) = (0..n).find(|&c| repaired_tableau[(r, c)].abs() > EPSILON) {
-                        pivot(&mut repaired_tableau, r, pc, m, width - 1);
-                        basis_col_for_row[r] = Some(pc);
-                    } else {
-                        // Row is 0=0 (redundant). Mark for removal.
-                        basis_col_for_row[r] = None;
-                    }
-                }
+    impl Incumbent {
+        fn get(&self) -> f64 {
+            match self.0.load(atomic::Ordering::Relaxed) {
+                u64::MAX => f64::MAX,
+                cost => cost as f64,
             }
         }
 
-        // Filter out redundant rows
-        let active_row_indices: Vec<usize> =
-            (0..m).filter(|&r| basis_col_for_row[r].is_some()).collect();
-
-        let new_m = active_row_indices.len();
-        let mut phase2 = DMatrix::zeros(new_m + 1, n + 1);
-
-        for (new_r, &old_r) in active_row_indices.iter().enumerate() {
-            for c in 0..n {
-                phase2[(new_r, c)] = repaired_tableau[(old_r, c)];
-            }
-            phase2[(new_r, n)] = repaired_tableau[(old_r, width - 1)]; // Copy RHS
+        /// Whether a node whose relaxation costs `bound` cannot improve on
+        /// the incumbent.
+        fn prunes(&self, bound: f64) -> bool {
+            bound >= self.get() - PRUNING_TOLERANCE
         }
 
-        (phase2, new_m)
-    }
-
-    fn setup_phase_two_objective(
-        phase2: &mut DMatrix<f64>,
-        c_vec: &DVector<f64>,
-        m: usize,
-        n: usize,
-    ) {
-        // Start with original costs
-        for c in 0..n {
-            phase2[(m, c)] = c_vec[c];
+        /// Records `cost`, returning whether it is the new best.
+        fn offer(&self, cost: u64) -> bool {
+            self.0.fetch_min(cost, atomic::Ordering::Relaxed) > cost
         }
-
-        // Canonicalize: Eliminate basic variables from objective row
-        for r in 0..m {
-            // Find the basic column in this row (it will be a unit vector)
-            if let Some(bc) = find_basis_col(phase2, r, m, n) {
-                let factor = phase2[(m, bc)];
-                if factor.abs() > EPSILON {
-                    for c in 0..=n {
-                        phase2[(m, c)] -= factor * phase2[(r, c)];
-                    }
-                }
-            }
-        }
-    }
-
-    fn extract_solution(tableau: &DMatrix<f64>, m: usize, n: usize) -> Option<Solution> {
-        let mut x = DVector::zeros(n);
-
-        for c in 0..n {
-            // Check if column c is basic
-            let mut basic_row = None;
-            let mut non_zeros = 0;
-
-            for r in 0..m {
-                let val = tableau[(r, c)];
-                if val.abs() > EPSILON {
-                    non_zeros += 1;
-                    if (val - 1.0).abs() < EPSILON {
-                        basic_row = Some(r);
-                    }
-                }
-            }
-
-            if non_zeros == 1 {
-                if let Some(r) = basic_row {
-                    x[c] = tableau[(r, n)];
-                }
-            }
-        }
-
-        Some(Solution {
-            x,
-            cost: -tableau[(m, n)], // Objective maximization adjustment
-        })
     }
 
-    fn pivot(mat: &mut DMatrix<f64>, pr: usize, pc: usize, m: usize, n: usize) {
-        let pivot_val = mat[(pr, pc)];
-        let inv = 1.0 / pivot_val;
+    /// Cuts the root relaxation down with [`cuts::tighten`], then branches
+    /// best-first.
+    ///
+    /// Only the root is solved from scratch. Every other node re-solves its
+    /// parent's relaxation with [`LpBackend::resolve`] after adding the one
+    /// bound it branched on, unless the backend keeps nothing to restart
+    /// from.
+    ///
+    /// Open nodes wait in a queue ordered by their parent's relaxation cost.
+    /// Each step takes as many of the most promising ones as rayon has
+    /// threads and evaluates them in parallel, all pruning against the shared
+    /// [`Incumbent`]. The number of cuts and of nodes explored are recorded
+    /// on the span.
+    #[tracing::instrument(skip_all, fields(cuts, nodes))]
+    pub fn solve(sys: &LinearSystem, lp: &impl LpBackend) -> Option<usize> {
+        let (sys, cuts) = cuts::tighten(sys);
+        let sys = &sys;
+        tracing::Span::current().record("cuts", cuts);
 
-        // Normalize pivot row
-        for c in 0..=n {
-            mat[(pr, c)] *= inv;
+        let n = sys.a.ncols();
+        let incumbent = Incumbent(AtomicU64::new(u64::MAX));
+        let mut queue = BinaryHeap::from([BranchNode {
+            lower_bounds: vec![0.0; n],
+            upper_bounds: vec![None; n],
+            bound: f64::MIN,
+            parent: None,
+        }]);
+
+        let mut nodes = 0;
+        while !queue.is_empty() {
+            let batch = std::iter::from_fn(|| queue.pop())
+                .take(aoc_common::par::current_num_threads())
+                .filter(|node| !incumbent.prunes(node.bound))
+                .collect::<Vec<_>>();
+            nodes += batch.len();
+            let children = batch
+                .into_par_iter()
+                .flat_map_iter(|node| evaluate(sys, lp, &node, &incumbent))
+                .collect::<Vec<_>>();
+            queue.extend(children);
         }
 
-        // Eliminate other rows
-        for r in 0..=m {
-            if r != pr {
-                let factor = mat[(r, pc)];
-                if factor.abs() > EPSILON {
-                    for c in 0..=n {
-                        mat[(r, c)] -= factor * mat[(pr, c)];
-                    }
-                }
-            }
+        tracing::Span::current().record("nodes", nodes);
+        match incumbent.0.into_inner() {
+            u64::MAX => None,
+            cost => Some(cost as usize),
         }
     }
 
-    fn run_pivot_loop(mat: &mut DMatrix<f64>, m: usize, n: usize) -> bool {
-        let max_iters = 5000;
-
-        for _ in 0..max_iters {
-            // Bland's Rule: First column with negative reduced cost
-            let pivot_col = (0..n).find(|&c| mat[(m, c)] < -EPSILON);
-
-            match pivot_col {
-                None => return true, // Optimal
-                Some(pc) => {
-                    // Min Ratio Test
-                    let mut pivot_row = None;
-                    let mut min_ratio = f64::MAX;
-
-                    for r in 0..m {
-                        let val = mat[(r, pc)];
-                        if val > EPSILON {
-                            let ratio = mat[(r, n)] / val;
-                            if ratio < min_ratio {
-                                min_ratio = ratio;
-                                pivot_row = Some(r);
-                            }
-                        }
-                    }
-
-                    match pivot_row {
-                        None => return false, // Unbounded
-                        Some(pr) => pivot(mat, pr, pc, m, n),
-                    }
-                }
+    /// Solves the relaxation of `node`, offering an integer solution to the
+    /// incumbent or returning the two nodes that split on a fractional
+    /// variable.
+    fn evaluate<L: LpBackend>(
+        sys: &LinearSystem,
+        lp: &L,
+        node: &BranchNode<L::Warm>,
+        incumbent: &Incumbent,
+    ) -> Vec<BranchNode<L::Warm>> {
+        let (sol, shift_cost, warm) = match &node.parent {
+            Some((warm, bound)) => {
+                let Some((sol, warm)) = lp.resolve(warm, *bound) else {
+                    explain!("drop node: relaxation is infeasible");
+                    return Vec::new();
+                };
+                let shift_cost = (0..sys.a.ncols())
+                    .map(|c| node.lower_bounds[c] * sys.c[c])
+                    .sum::<f64>();
+                (sol, shift_cost, Some(warm))
             }
-        }
-        false // Iteration limit exceeded
-    }
-
-    fn find_basis_col(mat: &DMatrix<f64>, r: usize, m: usize, total_cols: usize) -> Option<usize> {
-        for c in 0..total_cols {
-            // Look for 1.0
-            if (mat[(r, c)] - 1.0).abs() < EPSILON {
-                // Ensure it's a unit vector (zeros elsewhere)
-                let is_unit =
-                    (0..m).all(|other_r| other_r == r || mat[(other_r, c)].abs() < EPSILON);
-                if is_unit {
-                    return Some(c);
-                }
+            None => {
+                let Some((lp_sys, shift_cost)) = build_relaxed_system(sys, node) else {
+                    explain!("drop node: bounds are infeasible");
+                    return Vec::new();
+                };
+                let Some((sol, warm)) = lp.solve_warm(&lp_sys) else {
+                    explain!("drop node: relaxation is infeasible");
+                    return Vec::new();
+                };
+                (sol, shift_cost, warm)
             }
+        };
+
+        let total_cost = sol.cost + shift_cost;
+        if incumbent.prunes(total_cost) {
+            explain!(
+                "prune node: relaxation costs {total_cost:.3}, best is {}",
+                incumbent.get()
+            );
+            return Vec::new();
         }
-        None
-    }
-}
-
-mod milp {
-    use super::*;
-
-    struct BranchNode {
-        lower_bounds: Vec<f64>,
-        upper_bounds: Vec<Option<f64>>,
-    }
-
-    pub fn solve(sys: &LinearSystem) -> Option<usize> {
-        let n = sys.a.ncols();
-        let mut best_int_cost = f64::MAX;
-
-        let mut best_sol: Option<Vec<usize>> = None;
 
-        let mut stack = vec![BranchNode {
-            lower_bounds: vec![0.0; n],
-            upper_bounds: vec![None; n],
-        }];
-
-        while let Some(node) = stack.pop() {
-            // Construct the relaxed LP system for this node
-            let (lp_sys, shift_cost) = match build_relaxed_system(sys, &node) {
-                Some(res) => res,
-                None => continue, // Infeasible bounds
-            };
-
-            // Solve Relaxed LP
-            if let Some(sol) = simplex::solve(&lp_sys) {
-                let total_cost = sol.cost + shift_cost;
-
-                // Pruning: Bound check
-                if total_cost >= best_int_cost - PRUNING_TOLERANCE {
-                    continue;
-                }
-
-                // Check Integrality
-                let (full_x, first_fractional) = map_solution_to_original(&sol, &node);
-
-                if let Some((idx, val)) = first_fractional {
-                    // Branching: Split on the fractional variable
-                    let floor_val = val.floor();
-                    let ceil_val = val.ceil();
-
-                    // Branch 1: x <= floor
-                    let mut left = BranchNode {
-                        lower_bounds: node.lower_bounds.clone(),
-                        upper_bounds: node.upper_bounds.clone(),
-                    };
-                    let current_ub = left.upper_bounds[idx].unwrap_or(f64::MAX);
-                    left.upper_bounds[idx] = Some(current_ub.min(floor_val));
-
-                    // Branch 2: x >= ceil
-                    let mut right = BranchNode {
-                        lower_bounds: node.lower_bounds.clone(),
-                        upper_bounds: node.upper_bounds.clone(),
-                    };
-                    right.lower_bounds[idx] = right.lower_bounds[idx].max(ceil_val);
-
-                    stack.push(left);
-                    stack.push(right);
-                } else {
-                    // Integer Solution Found
-                    if verify_strict(sys, &full_x) {
-                        let cost: usize = full_x.iter().map(|&x| x.round() as usize).sum();
-                        if (cost as f64) < best_int_cost {
-                            best_int_cost = cost as f64;
-                            best_sol = Some(full_x.iter().map(|&x| x.round() as usize).collect());
-                        }
-                    }
+        let (full_x, first_fractional) = map_solution_to_original(&sol, node);
+        let Some((idx, val)) = first_fractional else {
+            if verify_strict(sys, &full_x) {
+                // Cut slacks cost nothing, so only the presses count.
+                let cost = full_x
+                    .iter()
+                    .zip(sys.c.iter())
+                    .map(|(&x, &c)| x.round() * c)
+                    .sum::<f64>() as u64;
+                if incumbent.offer(cost) {
+                    explain!("new best integer solution costs {cost}");
                 }
             }
-        }
-
-        best_sol.map(|s| s.iter().sum())
+            return Vec::new();
+        };
+
+        let floor_val = val.floor();
+        let ceil_val = val.ceil();
+        explain!("branch on x{idx} = {val:.3}: x{idx} <= {floor_val} or x{idx} >= {ceil_val}");
+
+        let warm = warm.map(Arc::new);
+        let lower = node.lower_bounds[idx];
+
+        // Branch 1: x <= floor
+        let mut left = BranchNode {
+            lower_bounds: node.lower_bounds.clone(),
+            upper_bounds: node.upper_bounds.clone(),
+            bound: total_cost,
+            parent: warm
+                .clone()
+                .map(|warm| (warm, Tighten::AtMost(idx, floor_val - lower))),
+        };
+        let current_ub = left.upper_bounds[idx].unwrap_or(f64::MAX);
+        left.upper_bounds[idx] = Some(current_ub.min(floor_val));
+
+        // Branch 2: x >= ceil
+        let mut right = BranchNode {
+            lower_bounds: node.lower_bounds.clone(),
+            upper_bounds: node.upper_bounds.clone(),
+            bound: total_cost,
+            parent: warm.map(|warm| (warm, Tighten::AtLeast(idx, ceil_val - lower))),
+        };
+        right.lower_bounds[idx] = right.lower_bounds[idx].max(ceil_val);
+
+        vec![left, right]
     }
 
-    fn build_relaxed_system(sys: &LinearSystem, node: &BranchNode) -> Option<(LinearSystem, f64)> {
+    fn build_relaxed_system<W>(
+        sys: &LinearSystem,
+        node: &BranchNode<W>,
+    ) -> Option<(LinearSystem, f64)> {
         let mut work_sys = sys.clone();
         let mut shift_cost = 0.0;
         let n = sys.a.ncols();
@@ -396,17 +317,17 @@ mod milp {
         Some((work_sys, shift_cost))
     }
 
-    fn map_solution_to_original(
+    fn map_solution_to_original<W>(
         sol: &Solution,
-        node: &BranchNode,
+        node: &BranchNode<W>,
     ) -> (Vec<f64>, Option<(usize, f64)>) {
         let n = node.lower_bounds.len();
         let mut full_x = vec![0.0; n];
         let mut first_fractional = None;
 
-        for c in 0..n {
+        for (c, slot) in full_x.iter_mut().enumerate() {
             let val = sol.x[c] + node.lower_bounds[c];
-            full_x[c] = val;
+            *slot = val;
 
             // Only check fractional if we haven't found one yet
             if first_fractional.is_none() {
@@ -432,60 +353,123 @@ mod milp {
         }
         true
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn queue_pops_the_lowest_bound_first() {
+            let node = |bound| BranchNode::<()> {
+                lower_bounds: Vec::new(),
+                upper_bounds: Vec::new(),
+                bound,
+                parent: None,
+            };
+            let mut queue = BinaryHeap::from([node(3.5), node(1.0), node(2.0)]);
+            let order = std::iter::from_fn(|| queue.pop().map(|node| node.bound));
+            assert_eq!(order.collect::<Vec<_>>(), [1.0, 2.0, 3.5]);
+        }
+
+        #[test]
+        fn incumbent_keeps_the_cheapest_cost() {
+            let incumbent = Incumbent(AtomicU64::new(u64::MAX));
+            assert!(!incumbent.prunes(1e12));
+            assert!(incumbent.offer(12));
+            assert!(!incumbent.offer(15));
+            assert!(incumbent.offer(10));
+            assert!(incumbent.prunes(10.0) && !incumbent.prunes(9.5));
+        }
+    }
+}
+
+/// A machine as written in the manual, with the spans [`parse`] points at
+/// when its parts disagree.
+#[derive(Debug, Clone)]
+struct RawMachine {
+    /// The number of lights in the diagram.
+    lights: usize,
+    diagram: SimpleSpan,
+    /// The counters each button bumps.
+    buttons: Vec<Vec<(usize, SimpleSpan)>>,
+    targets: Vec<f64>,
+}
+
+impl RawMachine {
+    /// Every way the machine contradicts itself, as errors naming `line`.
+    fn errors<'a>(&self, line: usize) -> Vec<Rich<'a, char>> {
+        let counters = self.targets.len();
+        let mut errors = Vec::new();
+        if self.lights != counters {
+            errors.push(Rich::custom(
+                self.diagram,
+                format!(
+                    "line {line}: the diagram has {} lights but there are {counters} joltage targets",
+                    self.lights
+                ),
+            ));
+        }
+        for &(index, span) in self.buttons.iter().flatten() {
+            if index >= counters {
+                errors.push(Rich::custom(
+                    span,
+                    format!(
+                        "line {line}: button index {index} is out of range for {counters} counters"
+                    ),
+                ));
+            }
+        }
+        errors
+    }
+
+    fn system(&self) -> LinearSystem {
+        let m = self.targets.len();
+        let n = self.buttons.len();
+
+        let mut a_mat = DMatrix::zeros(m, n);
+        for (col, rows) in self.buttons.iter().enumerate() {
+            for &(row, _) in rows {
+                a_mat[(row, col)] = 1.0;
+            }
+        }
+        LinearSystem::new(a_mat, DVector::from_column_slice(&self.targets))
+    }
 }
 
-fn parser<'a>() -> impl Parser<'a, &'a str, Vec<LinearSystem>, extra::Err<Rich<'a, char>>> {
+fn parser<'a>() -> impl Parser<'a, &'a str, Vec<RawMachine>, extra::Err<Rich<'a, char>>> {
     let hspace = one_of(" \t").repeated();
 
     // Example: [...] (A) (B) {Targets}
-    let diagram = none_of("]")
+    let diagram = one_of(".#")
         .repeated()
+        .count()
         .delimited_by(just('['), just(']'))
-        .ignored();
+        .map_with(|lights, e| (lights, e.span()));
 
-    let num_list = text::int(10)
-        .from_str::<f64>()
-        .unwrapped()
-        .separated_by(just(','))
-        .collect::<Vec<f64>>();
+    let index = text::int(10)
+        .from_str::<usize>()
+        .try_map(|index, span| index.map_err(|e| Rich::custom(span, e)))
+        .map_with(|index, e| (index, e.span()));
 
-    let buttons = num_list
-        .delimited_by(just('('), just(')'))
-        .map(|v| v.into_iter().map(|x| x as usize).collect::<Vec<_>>());
+    let buttons = index
+        .separated_by(just(','))
+        .collect::<Vec<_>>()
+        .delimited_by(just('('), just(')'));
 
-    let targets = num_list.delimited_by(just('{'), just('}'));
+    let targets = diagnostics::number::<f64>()
+        .separated_by(just(','))
+        .collect::<Vec<f64>>()
+        .delimited_by(just('{'), just('}'));
 
     let machine = diagram
         .then_ignore(hspace)
-        .ignore_then(buttons.padded_by(hspace).repeated().collect::<Vec<_>>())
+        .then(buttons.padded_by(hspace).repeated().collect::<Vec<_>>())
         .then(targets)
-        .map(|(buttons, targets)| {
-            let m = targets.len();
-            let n = buttons.len();
-
-            let mut a_mat = DMatrix::zeros(m, n);
-            let mut b_vec = DVector::zeros(m);
-            let c_vec = DVector::from_element(n, 1.0); // Cost = 1 per press
-
-            for (col, rows) in buttons.into_iter().enumerate() {
-                for row in rows {
-                    if row < m {
-                        a_mat[(row, col)] = 1.0;
-                    }
-                }
-            }
-            for (row, &val) in targets.iter().enumerate() {
-                if row < m {
-                    b_vec[row] = val;
-                }
-            }
-
-            LinearSystem {
-                a: a_mat,
-                b: b_vec.clone(),
-                c: c_vec,
-                original_b: b_vec,
-            }
+        .map(|(((lights, diagram), buttons), targets)| RawMachine {
+            lights,
+            diagram,
+            buttons,
+            targets,
         });
 
     machine
@@ -494,30 +478,138 @@ fn parser<'a>() -> impl Parser<'a, &'a str, Vec<LinearSystem>, extra::Err<Rich<'
         .collect()
 }
 
+/// Parses the manual into one system per machine.
+///
+/// Every machine must light as many lights as it has joltage targets, and
+/// its buttons may only bump those counters; any mismatch is reported
+/// against the input with the line it is on.
+pub fn parse(input: &str) -> Result<Vec<LinearSystem>> {
+    let machines = diagnostics::parse(parser(), "input", input)?;
+    let errors = machines
+        .iter()
+        .flat_map(|machine| {
+            let line = input[..machine.diagram.start].matches('\n').count() + 1;
+            machine.errors(line)
+        })
+        .collect::<Vec<_>>();
+    if !errors.is_empty() {
+        return Err(diagnostics::ParseError::new("input", input, errors).into());
+    }
+    Ok(machines.iter().map(RawMachine::system).collect())
+}
+
+/// The fewest presses that bring `sys`'s counters to their targets, using
+/// `lp` for the relaxations inside branch and bound, or `None` when no
+/// combination of presses does.
+pub fn min_presses(sys: &LinearSystem, lp: &impl LpBackend) -> Option<usize> {
+    milp::solve(sys, lp)
+}
+
+#[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let systems = parser()
-        .parse(input)
-        .into_result()
-        .map_err(|e| miette!("Parse failed: {:?}", e))?;
+    Ok(solve(&parse(input)?)?.to_string())
+}
+
+/// The fewest presses that bring every machine's counters to their targets,
+/// with the solver the `exact` and `lattice` features pick.
+#[tracing::instrument(skip(systems))]
+pub fn solve(systems: &[LinearSystem]) -> Result<Answer> {
+    if cfg!(feature = "exact") {
+        solve_exact(systems)
+    } else if cfg!(feature = "lattice") {
+        solve_lattice(systems)
+    } else {
+        solve_with(systems, &Simplex)
+    }
+}
+
+/// Solves every machine in exact rational arithmetic, so none of the
+/// tolerances above come into play. Slower; the `exact` feature makes
+/// [`process`] use it.
+pub fn process_exact(input: &str) -> Result<String> {
+    Ok(solve_exact(&parse(input)?)?.to_string())
+}
+
+/// Like [`process_exact`], on already parsed systems.
+pub fn solve_exact(systems: &[LinearSystem]) -> Result<Answer> {
+    Ok(exact::solve_all(systems)?.into())
+}
+
+/// Solves every machine over the lattice of its integer solutions, found by
+/// bringing the buttons to Hermite normal form, with no relaxations and no
+/// floats. The `lattice` feature makes [`process`] use it.
+pub fn process_lattice(input: &str) -> Result<String> {
+    Ok(solve_lattice(&parse(input)?)?.to_string())
+}
+
+/// Like [`process_lattice`], on already parsed systems.
+pub fn solve_lattice(systems: &[LinearSystem]) -> Result<Answer> {
+    Ok(lattice::solve_all(systems)?.into())
+}
+
+/// Solves every machine, using `lp` for the relaxations inside branch and bound.
+pub fn process_with(input: &str, lp: &impl LpBackend) -> Result<String> {
+    Ok(solve_with(&parse(input)?, lp)?.to_string())
+}
 
+/// Like [`process_with`], on already parsed systems.
+pub fn solve_with(systems: &[LinearSystem], lp: &impl LpBackend) -> Result<Answer> {
     let total: usize = systems
         .par_iter()
-        .map(|sys| milp::solve(sys).unwrap_or(0))
+        .enumerate()
+        .map(|(machine, sys)| {
+            let _machine = explain_span!("machine", machine).entered();
+            milp::solve(sys, lp).unwrap_or(0)
+        })
         .sum();
 
-    Ok(total.to_string())
+    Ok(total.into())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn it_works() -> Result<()> {
-        let input = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+    const EXAMPLE: &str = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
 [...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
 [.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}";
-        assert_eq!("33", process(input)?);
+
+    #[test]
+    fn it_works() -> Result<()> {
+        assert_eq!("33", process(EXAMPLE)?);
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_machines_that_disagree() {
+        let err = parse("[.#] (0) {1,2}\n[##] (0,2) {1,2}").unwrap_err();
+        let report = format!("{err:?}");
+        assert!(report.contains("line 2: button index 2 is out of range for 2 counters"));
+        assert!(!report.contains("line 1"));
+
+        let err = parse("[.#] (0) {1,2}\n[###] (1) {1,2}").unwrap_err();
+        let report = format!("{err:?}");
+        assert!(report.contains("line 2: the diagram has 3 lights but there are 2 joltage targets"));
+    }
+
+    #[test]
+    fn linear_systems_snapshot() -> Result<()> {
+        let systems = parse(EXAMPLE)?;
+        let out: String = systems
+            .iter()
+            .enumerate()
+            .map(|(machine, sys)| {
+                format!(
+                    "machine {machine}: {}x{}\nA ={}b ={}c ={}",
+                    sys.a.nrows(),
+                    sys.a.ncols(),
+                    sys.a,
+                    sys.b.transpose(),
+                    sys.c.transpose(),
+                )
+            })
+            .collect();
+        insta::assert_snapshot!(out);
         Ok(())
     }
 }
@@ -0,0 +1,140 @@
+use super::*;
+
+/// Rounds of cuts added at the root before branching.
+const MAX_ROUNDS: usize = 8;
+
+/// Tableau entries this close to an integer are snapped to it before
+/// rounding down, so float drift cannot produce a cut that removes an
+/// integer solution.
+const SNAP_TOLERANCE: f64 = 1e-6;
+
+/// Rounds `value` down, treating values within [`SNAP_TOLERANCE`] of an
+/// integer as that integer.
+fn snapped_floor(value: f64) -> f64 {
+    let rounded = value.round();
+    if (value - rounded).abs() < SNAP_TOLERANCE {
+        rounded
+    } else {
+        value.floor()
+    }
+}
+
+/// The Gomory fractional cut of tableau row `r`, if its basic variable is
+/// fractional, as `(coefficients, rhs)`.
+///
+/// The row reads `Σ ā_j x_j = b̄` over every column, basic ones included.
+/// Every variable is a non-negative integer, so `Σ ⌊ā_j⌋ x_j` is an integer
+/// no larger than `b̄`, giving `Σ ⌊ā_j⌋ x_j <= ⌊b̄⌋`. The current vertex
+/// violates it, as its basic variable sits at the fractional `b̄`.
+fn cut(tableau: &DMatrix<f64>, r: usize, n: usize) -> Option<(Vec<f64>, f64)> {
+    let rhs = tableau[(r, n)];
+    if (rhs - rhs.round()).abs() <= INTEGRALITY_TOLERANCE {
+        return None;
+    }
+    let coefficients = (0..n).map(|c| snapped_floor(tableau[(r, c)])).collect();
+    Some((coefficients, snapped_floor(rhs)))
+}
+
+/// Adds `Σ a_j x_j <= rhs` to `sys` as an equality with a new slack column.
+///
+/// The slack is an integer wherever the other variables are, because the
+/// cut's coefficients are, so later cuts stay valid.
+fn add_row(sys: &mut LinearSystem, coefficients: &[f64], rhs: f64) {
+    let (m, n) = sys.a.shape();
+    sys.a = sys.a.clone().resize(m + 1, n + 1, 0.0);
+    for (c, &coefficient) in coefficients.iter().enumerate() {
+        sys.a[(m, c)] = coefficient;
+    }
+    sys.a[(m, n)] = 1.0;
+    sys.b = sys.b.clone().resize_vertically(m + 1, rhs);
+    sys.original_b = sys.original_b.clone().resize_vertically(m + 1, rhs);
+    sys.c = sys.c.clone().resize_vertically(n + 1, 0.0);
+}
+
+/// Tightens the root relaxation of `sys` with rounds of Gomory fractional
+/// cuts, returning the tightened system and how many cuts it gained.
+///
+/// The cuts come from the in-crate simplex's final tableau, whatever backend
+/// branch and bound uses afterwards. Each cut adds a slack column at zero
+/// cost, so integer solutions keep their cost.
+pub fn tighten(sys: &LinearSystem) -> (LinearSystem, usize) {
+    let mut sys = sys.clone();
+    let mut added = 0;
+    for _ in 0..MAX_ROUNDS {
+        let Some(optimal) = simplex::solve_tableau(&sys) else {
+            break;
+        };
+        let n = sys.a.ncols();
+        let cuts = (0..optimal.rows)
+            .filter_map(|r| cut(&optimal.tableau, r, n))
+            .collect::<Vec<_>>();
+        if cuts.is_empty() {
+            break;
+        }
+        explain!(
+            "add {} gomory cuts, relaxation costs {:.3}",
+            cuts.len(),
+            optimal.solution.cost
+        );
+        added += cuts.len();
+        for (coefficients, rhs) in cuts {
+            add_row(&mut sys, &coefficients, rhs);
+        }
+    }
+    (sys, added)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cuts_close_the_integrality_gap() {
+        // x0 + 2x1 + 3x2 = 4 relaxes to 4/3 presses on x2 alone, but takes
+        // 2 whole ones.
+        let sys = LinearSystem {
+            a: DMatrix::from_row_slice(1, 3, &[1.0, 2.0, 3.0]),
+            b: DVector::from_element(1, 4.0),
+            c: DVector::from_element(3, 1.0),
+            original_b: DVector::from_element(1, 4.0),
+        };
+        let (tight, cuts) = tighten(&sys);
+        assert!(cuts > 0);
+        let relaxed = simplex::solve(&tight).unwrap();
+        assert!(relaxed.cost > simplex::solve(&sys).unwrap().cost);
+        assert!(relaxed.cost <= 2.0 + 1e-9);
+        assert_eq!(milp::solve(&sys, &Simplex), Some(2));
+    }
+
+    #[test]
+    fn cuts_keep_every_integer_solution() {
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(512);
+        for _ in 0..200 {
+            let m = rng.random_range(1..=4);
+            let n = rng.random_range(m..=7);
+            let a = DMatrix::from_fn(m, n, |_, _| rng.random_range(0..=1) as f64);
+            let x0 = DVector::from_fn(n, |_, _| rng.random_range(0..=20) as f64);
+            let b = &a * &x0;
+            let sys = LinearSystem {
+                a,
+                b: b.clone(),
+                c: DVector::from_element(n, 1.0),
+                original_b: b,
+            };
+
+            // The point b was built from must survive every cut, with the
+            // slacks the cuts leave it.
+            let (tight, _) = tighten(&sys);
+            let mut x = x0.as_slice().to_vec();
+            for r in sys.a.nrows()..tight.a.nrows() {
+                let lhs = (0..x.len()).map(|c| tight.a[(r, c)] * x[c]).sum::<f64>();
+                let slack = tight.b[r] - lhs;
+                assert!(slack > -1e-9, "cut {r} removes {x0}");
+                x.push(slack);
+            }
+        }
+    }
+}
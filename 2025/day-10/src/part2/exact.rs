@@ -0,0 +1,409 @@
+use super::*;
+
+use num::{BigInt, BigRational, One, Signed, ToPrimitive, Zero};
+
+/// A [`LinearSystem`] with integer coefficients, solved without rounding.
+///
+/// Every quantity the simplex produces is a [`BigRational`], so the
+/// feasibility, integrality and pruning decisions are exact comparisons and
+/// need no tolerances.
+#[derive(Clone, Debug)]
+pub struct ExactSystem {
+    a: Vec<Vec<BigInt>>,
+    b: Vec<BigInt>,
+    c: Vec<BigInt>,
+}
+
+impl ExactSystem {
+    /// Reads the coefficients of `sys` back as integers.
+    ///
+    /// The parser builds them from integer literals, which `f64` holds exactly
+    /// up to 2^53, so this loses nothing on puzzle inputs.
+    pub fn from_float(sys: &LinearSystem) -> Option<Self> {
+        let int = |v: f64| (v.fract() == 0.0).then(|| BigInt::from(v as i64));
+        Some(Self {
+            a: (0..sys.a.nrows())
+                .map(|r| (0..sys.a.ncols()).map(|c| int(sys.a[(r, c)])).collect())
+                .collect::<Option<_>>()?,
+            b: sys.b.iter().map(|&v| int(v)).collect::<Option<_>>()?,
+            c: sys.c.iter().map(|&v| int(v)).collect::<Option<_>>()?,
+        })
+    }
+}
+
+/// An optimal vertex of a relaxation.
+pub(super) struct Vertex {
+    pub(super) x: Vec<BigRational>,
+    pub(super) cost: BigRational,
+}
+
+/// A simplex tableau: one row per constraint, then the objective row; the
+/// last column is the right-hand side.
+struct Tableau {
+    rows: Vec<Vec<BigRational>>,
+    /// The basic column of every constraint row.
+    basis: Vec<usize>,
+}
+
+impl Tableau {
+    fn rhs(&self) -> usize {
+        self.rows[0].len() - 1
+    }
+
+    fn objective(&self) -> &[BigRational] {
+        self.rows.last().expect("the tableau has an objective row")
+    }
+
+    fn pivot(&mut self, pr: usize, pc: usize) {
+        let inv = self.rows[pr][pc].recip();
+        for value in self.rows[pr].iter_mut() {
+            *value *= &inv;
+        }
+        let pivot_row = self.rows[pr].clone();
+        for (r, row) in self.rows.iter_mut().enumerate() {
+            if r == pr || row[pc].is_zero() {
+                continue;
+            }
+            let factor = row[pc].clone();
+            for (value, p) in row.iter_mut().zip(&pivot_row) {
+                *value -= &factor * p;
+            }
+        }
+        self.basis[pr] = pc;
+    }
+
+    /// Pivots on the first `columns` until no reduced cost is negative.
+    ///
+    /// Bland's rule, entering on the lowest column and leaving on the lowest
+    /// basic column among the tied ratios, guarantees termination. Returns
+    /// `false` when the objective is unbounded.
+    fn optimize(&mut self, columns: usize) -> bool {
+        let m = self.basis.len();
+        let rhs = self.rhs();
+        loop {
+            let Some(pc) = (0..columns).find(|&c| self.objective()[c].is_negative()) else {
+                return true;
+            };
+            let mut leaving: Option<(usize, BigRational)> = None;
+            for r in 0..m {
+                if !self.rows[r][pc].is_positive() {
+                    continue;
+                }
+                let ratio = &self.rows[r][rhs] / &self.rows[r][pc];
+                let better = match &leaving {
+                    None => true,
+                    Some((best, min)) => {
+                        ratio < *min || (ratio == *min && self.basis[r] < self.basis[*best])
+                    }
+                };
+                if better {
+                    leaving = Some((r, ratio));
+                }
+            }
+            match leaving {
+                Some((pr, _)) => self.pivot(pr, pc),
+                None => return false,
+            }
+        }
+    }
+}
+
+/// Minimises `c·x` subject to `Ax = b` and `x >= 0` with the two-phase
+/// simplex. Returns `None` when the system is infeasible or unbounded.
+pub(super) fn simplex(a: &[Vec<BigInt>], b: &[BigInt], c: &[BigInt]) -> Option<Vertex> {
+    let (m, n) = (b.len(), c.len());
+    let q = |v: &BigInt| BigRational::from_integer(v.clone());
+
+    // Phase 1: one artificial per row, after flipping rows to a non-negative
+    // right-hand side, and minimise their sum.
+    let mut rows = Vec::with_capacity(m + 1);
+    for r in 0..m {
+        let sign = if b[r].is_negative() {
+            -BigInt::one()
+        } else {
+            BigInt::one()
+        };
+        let mut row = vec![BigRational::zero(); n + m + 1];
+        for (col, value) in a[r].iter().enumerate() {
+            row[col] = q(&(value * &sign));
+        }
+        row[n + r] = BigRational::one();
+        row[n + m] = q(&(&b[r] * &sign));
+        rows.push(row);
+    }
+    let mut objective = vec![BigRational::zero(); n + m + 1];
+    for row in &rows {
+        for (col, value) in objective.iter_mut().enumerate() {
+            if !(n..n + m).contains(&col) {
+                *value -= &row[col];
+            }
+        }
+    }
+    rows.push(objective);
+    let mut tableau = Tableau {
+        rows,
+        basis: (n..n + m).collect(),
+    };
+    if !tableau.optimize(n + m) || !tableau.objective()[n + m].is_zero() {
+        return None;
+    }
+
+    // Pivot the artificials still basic at zero out of the basis, dropping
+    // the rows they cannot leave, which are redundant.
+    let mut r = 0;
+    while r < tableau.basis.len() {
+        if tableau.basis[r] < n {
+            r += 1;
+        } else if let Some(pc) = (0..n).find(|&col| !tableau.rows[r][col].is_zero()) {
+            tableau.pivot(r, pc);
+            r += 1;
+        } else {
+            tableau.rows.remove(r);
+            tableau.basis.remove(r);
+        }
+    }
+
+    // Phase 2: drop the artificial columns and price out the basic columns
+    // of the original objective.
+    for row in tableau.rows.iter_mut() {
+        row.drain(n..n + m);
+    }
+    let m = tableau.basis.len();
+    let mut objective = c.iter().map(q).collect::<Vec<_>>();
+    objective.push(BigRational::zero());
+    for r in 0..m {
+        let factor = objective[tableau.basis[r]].clone();
+        if !factor.is_zero() {
+            for (value, p) in objective.iter_mut().zip(&tableau.rows[r]) {
+                *value -= &factor * p;
+            }
+        }
+    }
+    tableau.rows[m] = objective;
+    if !tableau.optimize(n) {
+        return None;
+    }
+
+    let mut x = vec![BigRational::zero(); n];
+    for (r, &col) in tableau.basis.iter().enumerate() {
+        x[col] = tableau.rows[r][n].clone();
+    }
+    Some(Vertex {
+        x,
+        cost: -tableau.objective()[n].clone(),
+    })
+}
+
+struct BranchNode {
+    lower_bounds: Vec<BigInt>,
+    upper_bounds: Vec<Option<BigInt>>,
+}
+
+/// Solves the relaxation of `node`: the lower bounds are shifted out of `x`
+/// and every upper bound becomes a row with its own slack column.
+///
+/// Returns the vertex in the original variables together with its cost, or
+/// `None` when the node is infeasible.
+fn relax(sys: &ExactSystem, node: &BranchNode) -> Option<(Vec<BigRational>, BigRational)> {
+    let n = sys.c.len();
+    let bounded = (0..n)
+        .filter_map(|col| Some((col, node.upper_bounds[col].as_ref()?)))
+        .collect::<Vec<_>>();
+    if bounded
+        .iter()
+        .any(|&(col, upper)| *upper < node.lower_bounds[col])
+    {
+        explain!("drop node: bounds are infeasible");
+        return None;
+    }
+
+    let width = n + bounded.len();
+    let mut a = Vec::with_capacity(sys.a.len() + bounded.len());
+    let mut b = Vec::with_capacity(a.capacity());
+    for (row, rhs) in sys.a.iter().zip(&sys.b) {
+        let shifted = row
+            .iter()
+            .zip(&node.lower_bounds)
+            .fold(rhs.clone(), |rhs, (coeff, lower)| rhs - coeff * lower);
+        let mut row = row.clone();
+        row.resize(width, BigInt::zero());
+        a.push(row);
+        b.push(shifted);
+    }
+    for (slack, &(col, upper)) in bounded.iter().enumerate() {
+        let mut row = vec![BigInt::zero(); width];
+        row[col] = BigInt::one();
+        row[n + slack] = BigInt::one();
+        a.push(row);
+        b.push(upper - &node.lower_bounds[col]);
+    }
+    let mut c = sys.c.clone();
+    c.resize(width, BigInt::zero());
+
+    let Some(vertex) = simplex(&a, &b, &c) else {
+        explain!("drop node: relaxation is infeasible");
+        return None;
+    };
+    let shift = sys
+        .c
+        .iter()
+        .zip(&node.lower_bounds)
+        .map(|(cost, lower)| cost * lower)
+        .sum::<BigInt>();
+    let x = (0..n)
+        .map(|col| &vertex.x[col] + BigRational::from_integer(node.lower_bounds[col].clone()))
+        .collect();
+    Some((x, vertex.cost + BigRational::from_integer(shift)))
+}
+
+/// Branch and bound over exact relaxations. Returns the fewest presses that
+/// reach the targets, or `None` when they cannot be reached.
+///
+/// Integer costs make any relaxation whose cost rounds up to the best known
+/// solution unable to improve on it, so those nodes are pruned.
+pub fn solve(sys: &ExactSystem) -> Option<BigInt> {
+    let n = sys.c.len();
+    let mut best: Option<BigInt> = None;
+    let mut stack = vec![BranchNode {
+        lower_bounds: vec![BigInt::zero(); n],
+        upper_bounds: vec![None; n],
+    }];
+
+    while let Some(node) = stack.pop() {
+        let Some((x, cost)) = relax(sys, &node) else {
+            continue;
+        };
+        if let Some(best) = &best {
+            if cost.ceil().to_integer() >= *best {
+                explain!("prune node: relaxation costs {cost}, best is {best}");
+                continue;
+            }
+        }
+
+        match x.iter().position(|value| !value.is_integer()) {
+            Some(idx) => {
+                let (floor, ceil) = (x[idx].floor().to_integer(), x[idx].ceil().to_integer());
+                explain!(
+                    "branch on x{idx} = {}: x{idx} <= {floor} or x{idx} >= {ceil}",
+                    x[idx]
+                );
+
+                let mut left = BranchNode {
+                    lower_bounds: node.lower_bounds.clone(),
+                    upper_bounds: node.upper_bounds.clone(),
+                };
+                left.upper_bounds[idx] = Some(match &node.upper_bounds[idx] {
+                    Some(upper) => upper.min(&floor).clone(),
+                    None => floor,
+                });
+
+                let mut right = node;
+                right.lower_bounds[idx] = right.lower_bounds[idx].clone().max(ceil);
+
+                stack.push(left);
+                stack.push(right);
+            }
+            None => {
+                let cost = cost.to_integer();
+                explain!("new best integer solution costs {cost}");
+                best = Some(cost);
+            }
+        }
+    }
+
+    best
+}
+
+/// Solves every machine with [`solve`], returning the total presses.
+pub fn solve_all(systems: &[LinearSystem]) -> Result<usize> {
+    let total = systems
+        .par_iter()
+        .enumerate()
+        .map(|(machine, sys)| {
+            let _machine = explain_span!("machine", machine).entered();
+            let sys = ExactSystem::from_float(sys)
+                .ok_or_else(|| miette::miette!("machine {machine} has a non-integer target"))?;
+            Ok(solve(&sys).map_or(0, |presses| presses.to_usize().unwrap_or(0)))
+        })
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .sum::<usize>();
+
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn int(values: &[i64]) -> Vec<BigInt> {
+        values.iter().map(|&v| BigInt::from(v)).collect()
+    }
+
+    #[test]
+    fn simplex_solves_exactly() {
+        // Beale's cycling example with the costs scaled by 4 to integers; the
+        // optimum is 4 * -5/4.
+        let a = [
+            int(&[4, 0, 0, 1, -32, -4, 36]),
+            int(&[0, 2, 0, 1, -24, -1, 6]),
+            int(&[0, 0, 1, 0, 0, 1, 0]),
+        ];
+        let c = int(&[0, 0, 0, -3, 80, -2, 24]);
+        let vertex = simplex(&a, &int(&[0, 0, 1]), &c).expect("feasible and bounded");
+        assert_eq!(vertex.cost, BigRational::from_integer((-5).into()));
+
+        assert!(simplex(&[int(&[1, 1])], &int(&[-1]), &int(&[1, 1])).is_none());
+        assert!(simplex(&[int(&[1, -1])], &int(&[1]), &int(&[-1, 0])).is_none());
+        let redundant = simplex(&[int(&[1, 1]), int(&[2, 2])], &int(&[2, 4]), &int(&[1, 3]));
+        assert_eq!(
+            redundant.map(|v| v.cost),
+            Some(BigRational::from_integer(2.into()))
+        );
+    }
+
+    #[test]
+    fn huge_targets_stay_exact() {
+        // x0 + x1 = t and x1 + x2 = t + 1 take exactly t + 1 presses.
+        let t = 10_i64.pow(13) + 7;
+        let sys = ExactSystem {
+            a: vec![int(&[1, 1, 0]), int(&[0, 1, 1])],
+            b: int(&[t, t + 1]),
+            c: int(&[1, 1, 1]),
+        };
+        assert_eq!(solve(&sys), Some(BigInt::from(t + 1)));
+    }
+
+    #[test]
+    fn matches_the_float_solver() {
+        let mut rng = StdRng::seed_from_u64(511);
+        for _ in 0..200 {
+            let m = rng.random_range(1..=4);
+            let n = rng.random_range(m..=7);
+            let a = DMatrix::from_fn(m, n, |_, _| rng.random_range(0..=1) as f64);
+            let x0 = DVector::from_fn(n, |_, _| rng.random_range(0..=20) as f64);
+            let b = &a * x0;
+            let sys = LinearSystem {
+                a,
+                b: b.clone(),
+                c: DVector::from_element(n, 1.0),
+                original_b: b,
+            };
+
+            let exact = solve(&ExactSystem::from_float(&sys).unwrap()).map(|v| v.to_usize());
+            assert_eq!(exact, milp::solve(&sys, &Simplex).map(Some), "{sys:?}");
+        }
+    }
+
+    #[test]
+    fn it_works() -> Result<()> {
+        let input = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}";
+        assert_eq!(33, solve_all(&parse(input)?)?);
+        Ok(())
+    }
+}
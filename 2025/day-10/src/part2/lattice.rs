@@ -0,0 +1,530 @@
+use super::*;
+
+use num::{BigInt, BigRational, ToPrimitive, Zero};
+
+use super::exact::{self, Vertex};
+
+/// A [`LinearSystem`] with non-negative integer coefficients, solved over
+/// the lattice of its integer solutions instead of by branching on
+/// relaxations.
+#[derive(Clone, Debug)]
+pub struct IntegerSystem {
+    a: Vec<Vec<i128>>,
+    b: Vec<i128>,
+    c: Vec<i128>,
+}
+
+impl IntegerSystem {
+    /// Reads the coefficients of `sys` back as integers, or `None` unless
+    /// every one is a non-negative integer.
+    ///
+    /// Buttons only ever add to counters, which is what bounds how often
+    /// each can be pressed; [`solve`] relies on it.
+    pub fn from_float(sys: &LinearSystem) -> Option<Self> {
+        let int = |v: f64| (v.fract() == 0.0 && v >= 0.0).then_some(v as i128);
+        Some(Self {
+            a: (0..sys.a.nrows())
+                .map(|r| (0..sys.a.ncols()).map(|c| int(sys.a[(r, c)])).collect())
+                .collect::<Option<_>>()?,
+            b: sys.b.iter().map(|&v| int(v)).collect::<Option<_>>()?,
+            c: sys.c.iter().map(|&v| int(v)).collect::<Option<_>>()?,
+        })
+    }
+
+    /// The most times each button can be pressed: no more than any counter
+    /// it bumps allows, and never for a button that bumps none.
+    fn upper_bounds(&self) -> Vec<i128> {
+        (0..self.c.len())
+            .map(|col| {
+                self.a
+                    .iter()
+                    .zip(&self.b)
+                    .filter(|(row, _)| row[col] > 0)
+                    .map(|(row, &b)| b.div_euclid(row[col]))
+                    .min()
+                    .unwrap_or(0)
+            })
+            .collect()
+    }
+}
+
+/// A matrix as rows, worked on a column at a time.
+type Matrix = Vec<Vec<i128>>;
+
+/// Subtracts `q` times column `from` from column `to` of every matrix.
+fn sub_column(matrices: &mut [&mut Matrix], to: usize, from: usize, q: i128) {
+    for matrix in matrices.iter_mut() {
+        for row in matrix.iter_mut() {
+            row[to] = q
+                .checked_mul(row[from])
+                .and_then(|v| row[to].checked_sub(v))
+                .expect("lattice coefficients overflow i128");
+        }
+    }
+}
+
+fn swap_columns(matrices: &mut [&mut Matrix], i: usize, j: usize) {
+    for matrix in matrices.iter_mut() {
+        for row in matrix.iter_mut() {
+            row.swap(i, j);
+        }
+    }
+}
+
+/// Brings the `n` columns of `h` to column-style Hermite normal form with
+/// unimodular column operations, repeating each on `u`. Returns the rows
+/// holding the pivots, one per column of the rank, in order.
+///
+/// Afterwards pivot `k` is positive, every entry of its row left of it is
+/// reduced modulo it, and column `k` is zero above its row. The columns past
+/// the rank are zero.
+fn hermite(h: &mut Matrix, u: &mut Matrix, n: usize) -> Vec<usize> {
+    let mut pivots = Vec::new();
+    for row in 0..h.len() {
+        let col = pivots.len();
+        if col == n {
+            break;
+        }
+        // Euclid's algorithm across the row, on the columns still free.
+        while let Some(k) = (col..n)
+            .filter(|&c| h[row][c] != 0)
+            .min_by_key(|&c| h[row][c].unsigned_abs())
+        {
+            swap_columns(&mut [h, u], col, k);
+            let mut reduced = true;
+            for c in col + 1..n {
+                let q = h[row][c] / h[row][col];
+                sub_column(&mut [h, u], c, col, q);
+                reduced &= h[row][c] == 0;
+            }
+            if reduced {
+                break;
+            }
+        }
+        if h[row][col] == 0 {
+            continue;
+        }
+        if h[row][col] < 0 {
+            // Negates the column.
+            sub_column(&mut [h, u], col, col, 2);
+        }
+        for c in 0..col {
+            let q = h[row][c].div_euclid(h[row][col]);
+            sub_column(&mut [h, u], c, col, q);
+        }
+        pivots.push(row);
+    }
+    pivots
+}
+
+/// Every integer solution of `Ax = b`, as `base` plus any integer
+/// combination of the `kernel` columns.
+#[derive(Clone, Debug)]
+pub struct Lattice {
+    base: Vec<i128>,
+    /// One column per degree of freedom, in the echelon form [`hermite`]
+    /// leaves: column `k` is zero above row `pivots[k]` and positive there.
+    kernel: Matrix,
+    pivots: Vec<usize>,
+}
+
+impl Lattice {
+    /// The solutions of `sys`'s equations, whatever their sign, or `None`
+    /// when not even a negative number of presses reaches the targets.
+    pub fn new(sys: &IntegerSystem) -> Option<Self> {
+        let n = sys.c.len();
+        let identity = |n| -> Matrix {
+            (0..n)
+                .map(|r| (0..n).map(|c| i128::from(r == c)).collect())
+                .collect()
+        };
+
+        // A·U = H with U unimodular, so Ax = b exactly when x = U·y for an
+        // integer y with H·y = b. H is lower triangular on its pivots.
+        let mut h = sys.a.clone();
+        let mut u = identity(n);
+        let pivots = hermite(&mut h, &mut u, n);
+        let rank = pivots.len();
+
+        let mut y = vec![0; n];
+        for (k, &row) in pivots.iter().enumerate() {
+            let rest = (0..k).map(|j| h[row][j] * y[j]).sum::<i128>();
+            let rhs = sys.b[row] - rest;
+            if rhs % h[row][k] != 0 {
+                return None;
+            }
+            y[k] = rhs / h[row][k];
+        }
+        let consistent = h
+            .iter()
+            .zip(&sys.b)
+            .all(|(row, &b)| (0..rank).map(|j| row[j] * y[j]).sum::<i128>() == b);
+        if !consistent {
+            return None;
+        }
+
+        // The columns of U past the rank span the integer kernel; put them
+        // in echelon form too, so the search can fix one at a time.
+        let base = u
+            .iter()
+            .map(|row| row.iter().zip(&y).map(|(u, y)| u * y).sum())
+            .collect();
+        let mut kernel: Matrix = u.iter().map(|row| row[rank..].to_vec()).collect();
+        let d = n - rank;
+        let pivots = hermite(&mut kernel, &mut identity(d), d);
+        debug_assert_eq!(pivots.len(), d, "the kernel columns are independent");
+        Some(Self {
+            base,
+            kernel,
+            pivots,
+        })
+    }
+
+    /// The dimension of the lattice.
+    pub fn rank(&self) -> usize {
+        self.pivots.len()
+    }
+}
+
+/// Walks the lattice one kernel coefficient at a time, keeping every press
+/// count within `0..=upper`.
+struct Search<'a> {
+    sys: &'a IntegerSystem,
+    lattice: &'a Lattice,
+    upper: &'a [i128],
+    best: Option<i128>,
+}
+
+impl Search<'_> {
+    /// The rows whose value the first `k + 1` coefficients settle.
+    fn rows(&self, k: usize) -> std::ops::Range<usize> {
+        let end = self.lattice.pivots.get(k + 1).copied();
+        self.lattice.pivots[k]..end.unwrap_or(self.upper.len())
+    }
+
+    fn in_bounds(&self, x: &[i128], mut rows: std::ops::Range<usize>) -> bool {
+        rows.all(|i| (0..=self.upper[i]).contains(&x[i]))
+    }
+
+    /// The values of coefficient `k` keeping the rows it settles in bounds,
+    /// given `x` from the coefficients before it.
+    fn range(&self, k: usize, x: &[i128]) -> Option<(i128, i128)> {
+        let (mut low, mut high) = (i128::MIN, i128::MAX);
+        for i in self.rows(k) {
+            let a = self.lattice.kernel[i][k];
+            let (from, to) = (-x[i], self.upper[i] - x[i]);
+            match a.signum() {
+                0 if from > 0 || to < 0 => return None,
+                0 => {}
+                1 => {
+                    low = low.max(div_ceil(from, a));
+                    high = high.min(to.div_euclid(a));
+                }
+                _ => {
+                    low = low.max(div_ceil(-to, -a));
+                    high = high.min((-from).div_euclid(-a));
+                }
+            }
+        }
+        (low <= high).then_some((low, high))
+    }
+
+    /// The linear relaxation of the rows from `from` on, with the rows
+    /// before it fixed as in `x`: what they cost at least and the value it
+    /// gives each, or `None` when not even fractional presses reach the
+    /// targets.
+    ///
+    /// Fixing those rows fixes exactly the coefficients that settle them,
+    /// since each kernel column starts at its pivot, so this bounds every
+    /// lattice point below the node. It is solved with [`exact::simplex`],
+    /// so the bound is never rounded either.
+    fn relax(&self, x: &[i128], from: usize) -> Option<Vertex> {
+        let n = self.upper.len();
+        let rest = self
+            .sys
+            .a
+            .iter()
+            .zip(&self.sys.b)
+            .map(|(row, &b)| b - (0..from).map(|i| row[i] * x[i]).sum::<i128>())
+            .collect::<Vec<_>>();
+        if rest.iter().any(|&r| r < 0) {
+            return None;
+        }
+        if from == n {
+            let vertex = Vertex {
+                x: Vec::new(),
+                cost: BigRational::zero(),
+            };
+            return rest.iter().all(|&r| r == 0).then_some(vertex);
+        }
+
+        let a = self
+            .sys
+            .a
+            .iter()
+            .map(|row| row[from..].iter().map(|&v| BigInt::from(v)).collect())
+            .collect::<Vec<_>>();
+        let b = rest.into_iter().map(BigInt::from).collect::<Vec<_>>();
+        let c = self.sys.c[from..]
+            .iter()
+            .map(|&v| BigInt::from(v))
+            .collect::<Vec<_>>();
+        exact::simplex(&a, &b, &c)
+    }
+
+    /// Whether a node that costs `settled` so far and `relaxed` at least for
+    /// the rest cannot beat the best solution so far. Costs are integers,
+    /// so the relaxation's cost rounds up.
+    fn prunes(&self, settled: i128, relaxed: &Vertex) -> bool {
+        let bound = (BigRational::from_integer(settled.into()) + &relaxed.cost).ceil();
+        self.best
+            .is_some_and(|best| bound >= BigRational::from_integer(best.into()))
+    }
+
+    /// Tries the values of coefficient `k` and those after it, given `x`
+    /// from the coefficients before it and `relaxed`, the relaxation of the
+    /// rows they leave open. The rows above `rows(k)` are already final and
+    /// cost `settled`.
+    fn visit(&mut self, k: usize, x: &[i128], settled: i128, relaxed: &Vertex) {
+        if k == self.lattice.rank() {
+            debug_assert!(self.in_bounds(x, 0..x.len()));
+            if self.best.is_none_or(|best| settled < best) {
+                explain!("new best integer solution costs {settled}");
+                self.best = Some(settled);
+            }
+            return;
+        }
+        let Some((low, high)) = self.range(k, x) else {
+            return;
+        };
+        let column = self
+            .lattice
+            .kernel
+            .iter()
+            .map(|row| row[k])
+            .collect::<Vec<_>>();
+        let at = |t: i128| {
+            x.iter()
+                .zip(&column)
+                .map(|(x, a)| x + t * a)
+                .collect::<Vec<_>>()
+        };
+
+        if k + 1 == self.lattice.rank() {
+            // Every row is settled now and the cost moves linearly with the
+            // coefficient, so one end of its range is the cheapest.
+            let slope = self
+                .rows(k)
+                .map(|i| column[i] * self.sys.c[i])
+                .sum::<i128>();
+            let next = at(if slope < 0 { high } else { low });
+            let cost = self.settle(k, &next, settled);
+            let done = Vertex {
+                x: Vec::new(),
+                cost: BigRational::zero(),
+            };
+            return self.visit(k + 1, &next, cost, &done);
+        }
+
+        // The cheapest relaxation with the coefficient fixed is convex in
+        // it, so the bound only grows walking away from the coefficient's
+        // relaxed value. Both walks stop at the first value that cannot
+        // beat the best so far or leaves the relaxation infeasible, which
+        // usually means a handful of values however wide the range is. The
+        // pivot row is the first one `relaxed` covers, and no later
+        // coefficient moves it.
+        let pivot = self.lattice.pivots[k];
+        let relaxed_t = (&relaxed.x[0] - BigRational::from_integer(x[pivot].into()))
+            / BigRational::from_integer(column[pivot].into());
+        let start = relaxed_t
+            .ceil()
+            .to_integer()
+            .to_i128()
+            .map_or(low, |t| t.clamp(low, high.saturating_add(1)));
+        for t in start..=high {
+            if !self.try_value(k, &at(t), settled) {
+                break;
+            }
+        }
+        for t in (low..start).rev() {
+            if !self.try_value(k, &at(t), settled) {
+                break;
+            }
+        }
+    }
+
+    /// Visits the node that fixes coefficient `k` to give `next`, returning
+    /// whether its relaxation was still worth following.
+    fn try_value(&mut self, k: usize, next: &[i128], settled: i128) -> bool {
+        let cost = self.settle(k, next, settled);
+        let Some(relaxed) = self.relax(next, self.lattice.pivots[k + 1]) else {
+            return false;
+        };
+        if self.prunes(cost, &relaxed) {
+            return false;
+        }
+        self.visit(k + 1, next, cost, &relaxed);
+        true
+    }
+
+    /// `settled` plus the cost of the rows coefficient `k` settles.
+    fn settle(&self, k: usize, x: &[i128], settled: i128) -> i128 {
+        settled + self.rows(k).map(|i| x[i] * self.sys.c[i]).sum::<i128>()
+    }
+}
+
+fn div_ceil(a: i128, b: i128) -> i128 {
+    -(-a).div_euclid(b)
+}
+
+/// The fewest presses, weighted by `sys`'s costs, that reach the targets,
+/// or `None` when they cannot be reached.
+///
+/// Solves the equations once over the integers with [`Lattice::new`], then
+/// searches the lattice's points with every press count between zero and
+/// its bound, fixing one kernel coefficient at a time. Exact linear
+/// relaxations of what each choice leaves open steer and prune the search,
+/// so it only tries the values near the relaxed optimum. Nothing is rounded,
+/// so the answer is exact for targets that fit an `i128`, but how long it
+/// takes depends on how far the relaxation is from the integer optimum,
+/// not on the rank alone: a machine whose relaxation is much cheaper over a
+/// wide range of values still walks that range.
+///
+/// # Panics
+///
+/// If reducing the equations overflows an `i128`, which the 0/1 button
+/// matrices of the puzzle are far from.
+pub fn solve(sys: &IntegerSystem) -> Option<i128> {
+    let lattice = Lattice::new(sys)?;
+    let upper = sys.upper_bounds();
+    // The rows above the first pivot take no part in the kernel.
+    let fixed = lattice.pivots.first().copied().unwrap_or(upper.len());
+    let mut search = Search {
+        sys,
+        lattice: &lattice,
+        upper: &upper,
+        best: None,
+    };
+    if search.in_bounds(&lattice.base, 0..fixed) {
+        let relaxed = search.relax(&lattice.base, fixed)?;
+        let settled = (0..fixed).map(|i| lattice.base[i] * sys.c[i]).sum();
+        search.visit(0, &lattice.base, settled, &relaxed);
+    }
+    search.best
+}
+
+/// Solves every machine with [`solve`], returning the total presses.
+pub fn solve_all(systems: &[LinearSystem]) -> Result<usize> {
+    let total = systems
+        .par_iter()
+        .enumerate()
+        .map(|(machine, sys)| {
+            let _machine = explain_span!("machine", machine).entered();
+            let sys = IntegerSystem::from_float(sys)
+                .ok_or_else(|| miette::miette!("machine {machine} has a non-integer target"))?;
+            Ok(solve(&sys).map_or(0, |presses| presses as usize))
+        })
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .sum::<usize>();
+
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn system(a: &[&[i128]], b: &[i128]) -> IntegerSystem {
+        IntegerSystem {
+            a: a.iter().map(|row| row.to_vec()).collect(),
+            b: b.to_vec(),
+            c: vec![1; a[0].len()],
+        }
+    }
+
+    #[test]
+    fn kernel_spans_the_solutions() {
+        let sys = system(&[&[1, 1, 0, 1], &[0, 1, 1, 1]], &[4, 6]);
+        let lattice = Lattice::new(&sys).unwrap();
+        assert_eq!(lattice.rank(), 2);
+        let times = |x: &[i128]| -> Vec<i128> {
+            sys.a
+                .iter()
+                .map(|row| row.iter().zip(x).map(|(a, x)| a * x).sum())
+                .collect()
+        };
+        assert_eq!(times(&lattice.base), sys.b);
+        for k in 0..lattice.rank() {
+            let column = lattice.kernel.iter().map(|row| row[k]).collect::<Vec<_>>();
+            assert_eq!(times(&column), [0, 0]);
+        }
+    }
+
+    #[test]
+    fn rejects_targets_off_the_lattice() {
+        // 2x = 3 has a rational solution but no integer one.
+        assert!(Lattice::new(&system(&[&[2, 2]], &[3])).is_none());
+        assert!(Lattice::new(&system(&[&[1, 1], &[1, 1]], &[1, 2])).is_none());
+        // Integer, but only with negative presses.
+        assert_eq!(solve(&system(&[&[1, 0], &[1, 1]], &[3, 1])), None);
+    }
+
+    #[test]
+    fn huge_targets_stay_exact() {
+        // x0 + x1 = t and x1 + x2 = t + 1 take exactly t + 1 presses.
+        let t = 10_i128.pow(13) + 7;
+        let sys = system(&[&[1, 1, 0], &[0, 1, 1]], &[t, t + 1]);
+        assert_eq!(solve(&sys), Some(t + 1));
+    }
+
+    #[test]
+    fn huge_targets_with_several_free_buttons() {
+        // Pressing x1 t times and one of x2, x4 once is cheapest, but the
+        // lattice leaves three coefficients free, each ranging over ~t values.
+        let t = 10_i128.pow(13) + 7;
+        let sys = system(&[&[1, 1, 0, 1, 0], &[0, 1, 1, 0, 1]], &[t, t + 1]);
+        assert_eq!(Lattice::new(&sys).unwrap().rank(), 3);
+        assert_eq!(solve(&sys), Some(t + 1));
+
+        let sys = system(
+            &[&[1, 1, 0, 0, 1], &[0, 1, 1, 0, 0], &[0, 0, 1, 1, 1]],
+            &[t, 2 * t, t + 3],
+        );
+        // Costs 2t + 3 - x4, and x4 can be at most 1.
+        assert_eq!(Lattice::new(&sys).unwrap().rank(), 2);
+        assert_eq!(solve(&sys), Some(2 * t + 2));
+    }
+
+    #[test]
+    fn matches_the_float_solver() {
+        let mut rng = StdRng::seed_from_u64(575);
+        for _ in 0..200 {
+            // Machines leave a few buttons free at most, as do these.
+            let m = rng.random_range(1..=4);
+            let n = rng.random_range(m..=m + 3);
+            let a = DMatrix::from_fn(m, n, |_, _| rng.random_range(0..=1) as f64);
+            let x0 = DVector::from_fn(n, |_, _| rng.random_range(0..=20) as f64);
+            let sys = LinearSystem::new(a.clone(), &a * x0);
+
+            let lattice = solve(&IntegerSystem::from_float(&sys).unwrap());
+            assert_eq!(
+                lattice.map(|v| v as usize),
+                milp::solve(&sys, &Simplex),
+                "{sys:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn it_works() -> Result<()> {
+        let input = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}";
+        assert_eq!(33, solve_all(&parse(input)?)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,155 @@
+use super::*;
+
+/// Solves the linear relaxation of a [`LinearSystem`]: minimise `c·x`
+/// subject to `Ax = b` and `x >= 0`.
+///
+/// Branch and bound only talks to the LP through this trait, so the in-crate
+/// simplex can be swapped for an external solver to cross-check results.
+pub trait LpBackend: Sync {
+    /// What a solve leaves behind to re-solve from after a bound change.
+    type Warm: Send + Sync;
+
+    /// Returns `None` when the system is infeasible or unbounded.
+    fn solve(&self, sys: &LinearSystem) -> Option<Solution>;
+
+    /// Like [`LpBackend::solve`], also returning the state to warm start
+    /// [`LpBackend::resolve`] from, when the backend keeps one.
+    fn solve_warm(&self, sys: &LinearSystem) -> Option<(Solution, Option<Self::Warm>)>;
+
+    /// Re-solves the relaxation `warm` was left by with `bound` added, or
+    /// returns `None` when that makes it infeasible.
+    fn resolve(&self, warm: &Self::Warm, bound: Tighten) -> Option<(Solution, Self::Warm)>;
+}
+
+/// A bound branch and bound adds to one variable of a relaxation it has
+/// solved, measured from that relaxation's lower bounds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Tighten {
+    /// The variable is at most this much.
+    AtMost(usize, f64),
+    /// The variable is at least this much.
+    AtLeast(usize, f64),
+}
+
+/// The in-crate two-phase simplex with Bland's rule, re-solving after bound
+/// changes with the dual simplex.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Simplex;
+
+impl LpBackend for Simplex {
+    type Warm = simplex::Warm;
+
+    fn solve(&self, sys: &LinearSystem) -> Option<Solution> {
+        simplex::solve(sys)
+    }
+
+    fn solve_warm(&self, sys: &LinearSystem) -> Option<(Solution, Option<Self::Warm>)> {
+        let optimal = simplex::solve_tableau(sys)?;
+        let solution = optimal.solution.clone();
+        Some((solution, simplex::Warm::new(sys, optimal)))
+    }
+
+    fn resolve(&self, warm: &Self::Warm, bound: Tighten) -> Option<(Solution, Self::Warm)> {
+        let mut warm = warm.clone();
+        match bound {
+            Tighten::AtMost(var, limit) => warm.cap(var, limit),
+            Tighten::AtLeast(var, shift) => warm.raise(var, shift),
+        }
+        warm.reoptimize().then(|| (warm.solution(), warm))
+    }
+}
+
+/// Adapter to the solvers behind `good_lp`: `microlp` by default, or HiGHS
+/// with the `highs` feature.
+#[cfg(feature = "good_lp")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GoodLp;
+
+#[cfg(feature = "good_lp")]
+impl LpBackend for GoodLp {
+    /// The external solvers always start over.
+    type Warm = std::convert::Infallible;
+
+    fn solve_warm(&self, sys: &LinearSystem) -> Option<(Solution, Option<Self::Warm>)> {
+        self.solve(sys).map(|solution| (solution, None))
+    }
+
+    fn resolve(&self, warm: &Self::Warm, _: Tighten) -> Option<(Solution, Self::Warm)> {
+        match *warm {}
+    }
+
+    fn solve(&self, sys: &LinearSystem) -> Option<Solution> {
+        use good_lp::{variable, Expression, ProblemVariables, SolverModel};
+
+        let (m, n) = sys.a.shape();
+        let mut vars = ProblemVariables::new();
+        let x = (0..n)
+            .map(|_| vars.add(variable().min(0.0)))
+            .collect::<Vec<_>>();
+        let objective = (0..n).map(|c| sys.c[c] * x[c]).sum::<Expression>();
+
+        #[cfg(feature = "highs")]
+        let mut model = vars.minimise(objective.clone()).using(good_lp::highs);
+        #[cfg(not(feature = "highs"))]
+        let mut model = vars.minimise(objective.clone()).using(good_lp::microlp);
+
+        for r in 0..m {
+            let lhs = (0..n)
+                .filter(|&c| sys.a[(r, c)] != 0.0)
+                .map(|c| sys.a[(r, c)] * x[c])
+                .sum::<Expression>();
+            model = model.with(lhs.eq(sys.b[r]));
+        }
+
+        let solution = model.solve().ok()?;
+        let values =
+            DVector::from_iterator(n, x.iter().map(|&v| good_lp::Solution::value(&solution, v)));
+        let cost = good_lp::Solution::eval(&solution, &objective);
+        Some(Solution { x: values, cost })
+    }
+}
+
+#[cfg(all(test, feature = "good_lp"))]
+mod tests {
+    use super::*;
+
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn backends_agree_on_random_relaxations() {
+        let mut rng = StdRng::seed_from_u64(487);
+        for _ in 0..200 {
+            let m = rng.random_range(1..=4);
+            let n = rng.random_range(m..=8);
+            let a = DMatrix::from_fn(m, n, |_, _| rng.random_range(0..=1) as f64);
+            let x0 = DVector::from_fn(n, |_, _| rng.random_range(0..=9) as f64);
+            let b = &a * x0;
+            let sys = LinearSystem {
+                a,
+                b: b.clone(),
+                c: DVector::from_element(n, 1.0),
+                original_b: b,
+            };
+
+            let ours = Simplex.solve(&sys).map(|s| s.cost);
+            let theirs = GoodLp.solve(&sys).map(|s| s.cost);
+            match (ours, theirs) {
+                (Some(ours), Some(theirs)) => assert!((ours - theirs).abs() < 1e-6),
+                (ours, theirs) => panic!("simplex {ours:?} vs good_lp {theirs:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn backends_agree_on_the_example() -> Result<()> {
+        let input = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}";
+        assert_eq!(
+            process_with(input, &Simplex)?,
+            process_with(input, &GoodLp)?
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,642 @@
+use super::*;
+
+/// Solves the Linear Programming relaxation of the system.
+pub fn solve(sys: &LinearSystem) -> Option<Solution> {
+    solve_tableau(sys).map(|optimal| optimal.solution)
+}
+
+/// An optimal solution together with the final phase 2 tableau it was read
+/// from.
+pub struct Optimal {
+    pub solution: Solution,
+    /// `B⁻¹A | B⁻¹b` for each of the first `rows` rows, then the reduced
+    /// costs. Rows found redundant in phase 1 are gone.
+    pub tableau: DMatrix<f64>,
+    pub rows: usize,
+}
+
+/// Like [`solve`], keeping the final tableau, which cutting planes are
+/// derived from.
+pub fn solve_tableau(sys: &LinearSystem) -> Option<Optimal> {
+    // Phase 1: check feasibility and find initial BFS
+    let (mut tableau, m, n) = setup_phase_one(sys);
+
+    let phase1_obj_col = tableau.ncols() - 1;
+    if !run_pivot_loop(&mut tableau, m, phase1_obj_col) {
+        return None; // Unbounded (should not happen in Phase 1)
+    }
+
+    // Check Phase 1 objective (Minimize sum of artificials)
+    let phase1_cost = tableau[(m, tableau.ncols() - 1)];
+    if phase1_cost.abs() > PHASE1_TOLERANCE {
+        return None; // Infeasible
+    }
+
+    // Phase 2: Optimize original objective
+    let (mut phase2_tableau, active_rows) = prepare_phase_two(&tableau, m, n);
+    setup_phase_two_objective(&mut phase2_tableau, &sys.c, active_rows, n);
+
+    if !run_pivot_loop(&mut phase2_tableau, active_rows, n) {
+        return None; // Unbounded
+    }
+
+    let solution = extract_solution(&phase2_tableau, active_rows, n)?;
+    Some(Optimal {
+        solution,
+        tableau: phase2_tableau,
+        rows: active_rows,
+    })
+}
+
+/// A relaxation solved to optimality, kept so that branch and bound can
+/// re-solve it after tightening one bound instead of starting over.
+///
+/// Tightening a bound leaves every reduced cost as it was, so the basis
+/// stays dual feasible and only the values of the basic variables can go
+/// negative. [`Warm::reoptimize`] pivots those out with the dual simplex,
+/// usually in a handful of steps where a cold start repeats both phases.
+#[derive(Clone, Debug)]
+pub struct Warm {
+    /// `B⁻¹A | B⁻¹b` for each constraint row, then the reduced costs. The
+    /// system's own columns come first, then a slack per added bound.
+    tableau: DMatrix<f64>,
+    /// The basic column of each constraint row.
+    basis: Vec<usize>,
+    /// The cost of each column, zero for the slacks.
+    costs: DVector<f64>,
+}
+
+impl Warm {
+    /// Keeps the final tableau of `optimal`, the solution to `sys`. `None`
+    /// when float drift hides the basic column of some row.
+    pub fn new(sys: &LinearSystem, optimal: Optimal) -> Option<Self> {
+        let Optimal { tableau, rows, .. } = optimal;
+        // Same reading of the basis as `extract_solution`.
+        let mut basis = vec![None; rows];
+        for c in 0..sys.a.ncols() {
+            let Some(r) = (0..rows).find(|&r| (tableau[(r, c)] - 1.0).abs() < EPSILON) else {
+                continue;
+            };
+            let unit = (0..rows).all(|other| other == r || tableau[(other, c)].abs() < EPSILON);
+            if unit && basis[r].is_none() && tableau[(rows, c)].abs() < EPSILON {
+                basis[r] = Some(c);
+            }
+        }
+        Some(Self {
+            basis: basis.into_iter().collect::<Option<_>>()?,
+            tableau,
+            costs: sys.c.clone(),
+        })
+    }
+
+    fn rows(&self) -> usize {
+        self.basis.len()
+    }
+
+    fn rhs(&self) -> usize {
+        self.tableau.ncols() - 1
+    }
+
+    /// The current vertex, slacks of the added bounds included.
+    pub fn solution(&self) -> Solution {
+        let mut x = DVector::zeros(self.costs.len());
+        for (r, &c) in self.basis.iter().enumerate() {
+            x[c] = self.tableau[(r, self.rhs())];
+        }
+        Solution {
+            cost: self.costs.dot(&x),
+            x,
+        }
+    }
+
+    /// Raises the lower bound of `var` by `shift`, substituting
+    /// `x + shift` for it.
+    pub fn raise(&mut self, var: usize, shift: f64) {
+        let rhs = self.rhs();
+        for r in 0..self.rows() {
+            self.tableau[(r, rhs)] -= shift * self.tableau[(r, var)];
+        }
+    }
+
+    /// Adds `x_var <= limit` as a row with a new basic slack.
+    pub fn cap(&mut self, var: usize, limit: f64) {
+        let (rows, rhs) = (self.rows(), self.rhs());
+        let mut tableau = std::mem::take(&mut self.tableau)
+            .insert_column(rhs, 0.0)
+            .insert_row(rows, 0.0);
+        tableau[(rows, var)] = 1.0;
+        tableau[(rows, rhs)] = 1.0;
+        tableau[(rows, rhs + 1)] = limit;
+        // Keep the new row in terms of the non-basic columns.
+        if let Some(r) = self.basis.iter().position(|&c| c == var) {
+            for c in 0..=rhs + 1 {
+                tableau[(rows, c)] -= tableau[(r, c)];
+            }
+        }
+        self.tableau = tableau;
+        self.basis.push(rhs);
+        let slacks = self.costs.len() + 1;
+        self.costs = std::mem::take(&mut self.costs).resize_vertically(slacks, 0.0);
+    }
+
+    /// Restores feasibility with the dual simplex, returning `false` when
+    /// the tightened relaxation is infeasible.
+    pub fn reoptimize(&mut self) -> bool {
+        let (m, n) = (self.rows(), self.rhs());
+        for _ in 0..5000 {
+            let leaving = (0..m)
+                .filter(|&r| self.tableau[(r, n)] < -PRIMAL_TOLERANCE)
+                .min_by(|&a, &b| self.tableau[(a, n)].total_cmp(&self.tableau[(b, n)]));
+            let Some(pr) = leaving else {
+                return true;
+            };
+            // Dual ratio test, keeping every reduced cost non-negative.
+            let entering = (0..n)
+                .filter(|&c| self.tableau[(pr, c)] < -EPSILON)
+                .map(|c| (c, self.tableau[(m, c)].max(0.0) / -self.tableau[(pr, c)]))
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+            let Some((pc, _)) = entering else {
+                return false;
+            };
+            pivot(&mut self.tableau, pr, pc, m, n);
+            self.basis[pr] = pc;
+        }
+        false
+    }
+}
+
+fn setup_phase_one(sys: &LinearSystem) -> (DMatrix<f64>, usize, usize) {
+    let m = sys.a.nrows();
+    let n = sys.a.ncols();
+    let width = n + m + 1; // Vars + Artificials + RHS
+    let height = m + 1; // Constraints + Objective
+
+    let mut tableau = DMatrix::zeros(height, width);
+
+    // Setup constraints (handling negative RHS by flipping signs)
+    for r in 0..m {
+        let sign = if sys.b[r] < 0.0 { -1.0 } else { 1.0 };
+
+        for c in 0..n {
+            tableau[(r, c)] = sys.a[(r, c)] * sign;
+        }
+        tableau[(r, n + r)] = 1.0; // Artificial variable identity
+        tableau[(r, width - 1)] = sys.b[r] * sign;
+    }
+
+    // Setup Phase 1 Objective: Maximize -Sum(Artificials)
+    // Algebraically eliminate artificials from the objective row immediately.
+    // Obj = -Sum(Row_i) for all i
+    for c in 0..width {
+        let col_sum: f64 = (0..m).map(|r| tableau[(r, c)]).sum();
+        tableau[(m, c)] = -col_sum;
+    }
+
+    // Zero out the artificial columns in the objective row (canonical form)
+    for i in 0..m {
+        tableau[(m, n + i)] = 0.0;
+    }
+
+    (tableau, m, n)
+}
+
+fn prepare_phase_two(tableau: &DMatrix<f64>, m: usize, n: usize) -> (DMatrix<f64>, usize) {
+    let width = tableau.ncols();
+    // Identify which column is basic for each row
+    let mut basis_col_for_row: Vec<Option<usize>> = (0..m)
+        .map(|r| find_basis_col(tableau, r, m, width - 1))
+        .collect();
+
+    // Basis Repair: If Artificial variable is basic, try to pivot it out
+    let mut repaired_tableau = tableau.clone();
+    for r in 0..m {
+        if let Some(bc) = basis_col_for_row[r] {
+            if bc >= n {
+                // Artificial is basic. Try to find a non-artificial pivot.
+                if let Some(pc) = (0..n).find(|&c| repaired_tableau[(r, c)].abs() > EPSILON) {
+                    pivot(&mut repaired_tableau, r, pc, m, width - 1);
+                    basis_col_for_row[r] = Some(pc);
+                } else {
+                    // Row is 0=0 (redundant). Mark for removal.
+                    basis_col_for_row[r] = None;
+                }
+            }
+        }
+    }
+
+    // Filter out redundant rows
+    let active_row_indices: Vec<usize> =
+        (0..m).filter(|&r| basis_col_for_row[r].is_some()).collect();
+
+    let new_m = active_row_indices.len();
+    let mut phase2 = DMatrix::zeros(new_m + 1, n + 1);
+
+    for (new_r, &old_r) in active_row_indices.iter().enumerate() {
+        for c in 0..n {
+            phase2[(new_r, c)] = repaired_tableau[(old_r, c)];
+        }
+        phase2[(new_r, n)] = repaired_tableau[(old_r, width - 1)]; // Copy RHS
+    }
+
+    (phase2, new_m)
+}
+
+fn setup_phase_two_objective(phase2: &mut DMatrix<f64>, c_vec: &DVector<f64>, m: usize, n: usize) {
+    // Start with original costs
+    for c in 0..n {
+        phase2[(m, c)] = c_vec[c];
+    }
+
+    // Canonicalize: Eliminate basic variables from objective row
+    for r in 0..m {
+        // Find the basic column in this row (it will be a unit vector)
+        if let Some(bc) = find_basis_col(phase2, r, m, n) {
+            let factor = phase2[(m, bc)];
+            if factor.abs() > EPSILON {
+                for c in 0..=n {
+                    phase2[(m, c)] -= factor * phase2[(r, c)];
+                }
+            }
+        }
+    }
+}
+
+fn extract_solution(tableau: &DMatrix<f64>, m: usize, n: usize) -> Option<Solution> {
+    let mut x = DVector::zeros(n);
+    // A row can hold several unit columns when constraint columns are
+    // proportional. Basic columns also have a zero reduced cost, and each row
+    // has exactly one basic column; the rest are non-basic and stay at zero.
+    let mut row_taken = vec![false; m];
+
+    for c in 0..n {
+        // Check if column c is basic
+        let mut basic_row = None;
+        let mut non_zeros = 0;
+
+        for r in 0..m {
+            let val = tableau[(r, c)];
+            if val.abs() > EPSILON {
+                non_zeros += 1;
+                if (val - 1.0).abs() < EPSILON {
+                    basic_row = Some(r);
+                }
+            }
+        }
+
+        if non_zeros == 1 {
+            let reduced_cost = tableau[(m, c)];
+            if let Some(r) = basic_row.filter(|&r| !row_taken[r] && reduced_cost.abs() < EPSILON) {
+                row_taken[r] = true;
+                x[c] = tableau[(r, n)];
+            }
+        }
+    }
+
+    Some(Solution {
+        x,
+        cost: -tableau[(m, n)], // Objective maximization adjustment
+    })
+}
+
+fn pivot(mat: &mut DMatrix<f64>, pr: usize, pc: usize, m: usize, n: usize) {
+    let pivot_val = mat[(pr, pc)];
+    let inv = 1.0 / pivot_val;
+
+    // Normalize pivot row
+    for c in 0..=n {
+        mat[(pr, c)] *= inv;
+    }
+
+    // Eliminate other rows
+    for r in 0..=m {
+        if r != pr {
+            let factor = mat[(r, pc)];
+            if factor.abs() > EPSILON {
+                for c in 0..=n {
+                    mat[(r, c)] -= factor * mat[(pr, c)];
+                }
+            }
+        }
+    }
+}
+
+fn run_pivot_loop(mat: &mut DMatrix<f64>, m: usize, n: usize) -> bool {
+    let max_iters = 5000;
+
+    for _ in 0..max_iters {
+        // Bland's Rule: First column with negative reduced cost
+        let pivot_col = (0..n).find(|&c| mat[(m, c)] < -EPSILON);
+
+        match pivot_col {
+            None => return true, // Optimal
+            Some(pc) => {
+                // Min Ratio Test
+                let mut pivot_row = None;
+                let mut min_ratio = f64::MAX;
+
+                for r in 0..m {
+                    let val = mat[(r, pc)];
+                    if val > EPSILON {
+                        let ratio = mat[(r, n)] / val;
+                        if ratio < min_ratio {
+                            min_ratio = ratio;
+                            pivot_row = Some(r);
+                        }
+                    }
+                }
+
+                match pivot_row {
+                    None => return false, // Unbounded
+                    Some(pr) => pivot(mat, pr, pc, m, n),
+                }
+            }
+        }
+    }
+    false // Iteration limit exceeded
+}
+
+fn find_basis_col(mat: &DMatrix<f64>, r: usize, m: usize, total_cols: usize) -> Option<usize> {
+    for c in 0..total_cols {
+        // Look for 1.0
+        if (mat[(r, c)] - 1.0).abs() < EPSILON {
+            // Ensure it's a unit vector (zeros elsewhere)
+            let is_unit = (0..m).all(|other_r| other_r == r || mat[(other_r, c)].abs() < EPSILON);
+            if is_unit {
+                return Some(c);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use itertools::Itertools;
+    use num::rational::Ratio;
+    use num::{Signed, Zero};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    type Rational = Ratio<i128>;
+
+    fn system(a: &[&[f64]], b: &[f64], c: &[f64]) -> LinearSystem {
+        let b = DVector::from_column_slice(b);
+        LinearSystem {
+            a: DMatrix::from_fn(a.len(), c.len(), |r, col| a[r][col]),
+            b: b.clone(),
+            c: DVector::from_column_slice(c),
+            original_b: b,
+        }
+    }
+
+    /// Checks that `solution` is feasible for `sys` and reaches `cost`.
+    fn assert_optimal(sys: &LinearSystem, solution: &Solution, cost: f64) {
+        assert!(
+            (solution.cost - cost).abs() < 1e-6,
+            "cost {} != {}",
+            solution.cost,
+            cost
+        );
+        assert!(solution.x.iter().all(|&x| x > -1e-9), "{}", solution.x);
+        let residual = &sys.a * &solution.x - &sys.b;
+        assert!(residual.amax() < 1e-6, "residual {}", residual);
+        let objective = sys.c.dot(&solution.x);
+        assert!((objective - solution.cost).abs() < 1e-6);
+    }
+
+    #[test]
+    fn dantzig_production_problem() {
+        // max 3x + 5y s.t. x <= 4, 2y <= 12, 3x + 2y <= 18; optimum 36 at (2, 6).
+        let sys = system(
+            &[
+                &[1.0, 0.0, 1.0, 0.0, 0.0],
+                &[0.0, 2.0, 0.0, 1.0, 0.0],
+                &[3.0, 2.0, 0.0, 0.0, 1.0],
+            ],
+            &[4.0, 12.0, 18.0],
+            &[-3.0, -5.0, 0.0, 0.0, 0.0],
+        );
+        let solution = solve(&sys).expect("feasible and bounded");
+        assert_optimal(&sys, &solution, -36.0);
+        assert!((solution.x[0] - 2.0).abs() < 1e-9 && (solution.x[1] - 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn beale_cycling_example() {
+        // Cycles under the textbook pivoting rule; the optimum is -5/4.
+        let sys = system(
+            &[
+                &[1.0, 0.0, 0.0, 0.25, -8.0, -1.0, 9.0],
+                &[0.0, 1.0, 0.0, 0.5, -12.0, -0.5, 3.0],
+                &[0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+            ],
+            &[0.0, 0.0, 1.0],
+            &[0.0, 0.0, 0.0, -0.75, 20.0, -0.5, 6.0],
+        );
+        let solution = solve(&sys).expect("feasible and bounded");
+        assert_optimal(&sys, &solution, -1.25);
+    }
+
+    #[test]
+    fn redundant_rows_are_dropped() {
+        let sys = system(&[&[1.0, 1.0], &[2.0, 2.0]], &[2.0, 4.0], &[1.0, 3.0]);
+        let solution = solve(&sys).expect("feasible and bounded");
+        assert_optimal(&sys, &solution, 2.0);
+    }
+
+    #[test]
+    fn negative_right_hand_sides_are_flipped() {
+        let sys = system(&[&[-1.0, -2.0]], &[-4.0], &[1.0, 1.0]);
+        let solution = solve(&sys).expect("feasible and bounded");
+        assert_optimal(&sys, &solution, 2.0);
+    }
+
+    #[test]
+    fn infeasible_systems_have_no_solution() {
+        assert!(solve(&system(&[&[1.0, 1.0]], &[-1.0], &[1.0, 1.0])).is_none());
+        assert!(solve(&system(&[&[1.0], &[1.0]], &[1.0, 2.0], &[1.0])).is_none());
+    }
+
+    #[test]
+    fn unbounded_systems_have_no_solution() {
+        assert!(solve(&system(&[&[1.0, -1.0]], &[1.0], &[-1.0, 0.0])).is_none());
+    }
+
+    /// Exact optimum of `min c·x, Ax = b, x >= 0` by enumerating every basis
+    /// in rational arithmetic. Only valid for feasible, bounded systems.
+    fn reference_optimum(a: &[Vec<i64>], b: &[i64], c: &[i64]) -> Rational {
+        let n = c.len();
+        let mut rows: Vec<Vec<Rational>> = a
+            .iter()
+            .zip(b)
+            .map(|(row, &rhs)| {
+                row.iter()
+                    .chain(std::iter::once(&rhs))
+                    .map(|&v| Rational::from_integer(v as i128))
+                    .collect()
+            })
+            .collect();
+
+        // Reduce to row echelon form so dependent rows can be discarded.
+        let mut rank = 0;
+        for col in 0..n {
+            let Some(pivot) = (rank..rows.len()).find(|&r| !rows[r][col].is_zero()) else {
+                continue;
+            };
+            rows.swap(rank, pivot);
+            let pivot_row = rows[rank].clone();
+            for row in rows.iter_mut().skip(rank + 1) {
+                let factor = row[col] / pivot_row[col];
+                for (value, &p) in row.iter_mut().zip(&pivot_row).skip(col) {
+                    *value -= factor * p;
+                }
+            }
+            rank += 1;
+        }
+        rows.truncate(rank);
+
+        (0..n)
+            .combinations(rank)
+            .filter_map(|basis| {
+                let x_basis = solve_exact(&rows, &basis)?;
+                x_basis.iter().all(|x| !x.is_negative()).then(|| {
+                    basis
+                        .iter()
+                        .zip(&x_basis)
+                        .map(|(&col, &x)| Rational::from_integer(c[col] as i128) * x)
+                        .sum::<Rational>()
+                })
+            })
+            .min()
+            .expect("generated systems are feasible")
+    }
+
+    /// Solves the square system formed by the `basis` columns, if nonsingular.
+    fn solve_exact(rows: &[Vec<Rational>], basis: &[usize]) -> Option<Vec<Rational>> {
+        let m = rows.len();
+        let rhs = rows[0].len() - 1;
+        let mut mat: Vec<Vec<Rational>> = rows
+            .iter()
+            .map(|row| {
+                basis
+                    .iter()
+                    .map(|&col| row[col])
+                    .chain(std::iter::once(row[rhs]))
+                    .collect()
+            })
+            .collect();
+        for col in 0..m {
+            let pivot = (col..m).find(|&r| !mat[r][col].is_zero())?;
+            mat.swap(col, pivot);
+            let inv = mat[col][col].recip();
+            for value in mat[col].iter_mut() {
+                *value *= inv;
+            }
+            let pivot_row = mat[col].clone();
+            for (r, row) in mat.iter_mut().enumerate() {
+                if r != col && !row[col].is_zero() {
+                    let factor = row[col];
+                    for (value, &p) in row.iter_mut().zip(&pivot_row) {
+                        *value -= factor * p;
+                    }
+                }
+            }
+        }
+        Some(mat.iter().map(|row| row[m]).collect())
+    }
+
+    #[test]
+    fn random_instances_match_rational_reference() {
+        let mut rng = StdRng::seed_from_u64(10);
+        for _ in 0..500 {
+            let m = rng.random_range(1..=3);
+            let n = rng.random_range(m + 1..=6);
+            // A positive first row keeps the feasible region bounded, and
+            // deriving b from a non-negative point keeps it non-empty.
+            let a: Vec<Vec<i64>> = (0..m)
+                .map(|r| {
+                    (0..n)
+                        .map(|_| rng.random_range(if r == 0 { 1..=4 } else { 0..=4 }))
+                        .collect()
+                })
+                .collect();
+            let x0: Vec<i64> = (0..n).map(|_| rng.random_range(0..=3)).collect();
+            let b: Vec<i64> = a
+                .iter()
+                .map(|row| row.iter().zip(&x0).map(|(a, x)| a * x).sum())
+                .collect();
+            let c: Vec<i64> = (0..n).map(|_| rng.random_range(-3..=5)).collect();
+
+            let as_f64 = |v: &[i64]| v.iter().map(|&x| x as f64).collect::<Vec<_>>();
+            let rows = a.iter().map(|row| as_f64(row)).collect::<Vec<_>>();
+            let sys = system(
+                &rows.iter().map(Vec::as_slice).collect::<Vec<_>>(),
+                &as_f64(&b),
+                &as_f64(&c),
+            );
+
+            let expected = reference_optimum(&a, &b, &c);
+            let expected = *expected.numer() as f64 / *expected.denom() as f64;
+            let solution =
+                solve(&sys).unwrap_or_else(|| panic!("no solution for A={a:?} b={b:?} c={c:?}"));
+            assert_optimal(&sys, &solution, expected);
+        }
+    }
+
+    /// `sys` with `bound` applied the way a cold solve sees it: a shifted
+    /// right-hand side or a slack row.
+    fn tightened(sys: &LinearSystem, bound: Tighten) -> LinearSystem {
+        let mut sys = sys.clone();
+        match bound {
+            Tighten::AtLeast(var, shift) => sys.b -= sys.a.column(var) * shift,
+            Tighten::AtMost(var, limit) => {
+                let (m, n) = sys.a.shape();
+                sys.a = sys.a.clone().resize(m + 1, n + 1, 0.0);
+                sys.a[(m, var)] = 1.0;
+                sys.a[(m, n)] = 1.0;
+                sys.b = sys.b.clone().resize_vertically(m + 1, limit);
+                sys.c = sys.c.clone().resize_vertically(n + 1, 0.0);
+            }
+        }
+        sys
+    }
+
+    #[test]
+    fn warm_restarts_match_cold_solves() {
+        let mut rng = StdRng::seed_from_u64(574);
+        for _ in 0..300 {
+            let m = rng.random_range(1..=4);
+            let n = rng.random_range(m..=8);
+            let a = DMatrix::from_fn(m, n, |_, _| rng.random_range(0..=1) as f64);
+            let x0 = DVector::from_fn(n, |_, _| rng.random_range(0..=9) as f64);
+            let mut sys = LinearSystem::new(a.clone(), &a * x0);
+            let Some((_, Some(mut warm))) = Simplex.solve_warm(&sys) else {
+                continue;
+            };
+
+            // Keep branching on random variables, as far as both agree.
+            for _ in 0..6 {
+                let x = warm.solution().x;
+                let var = rng.random_range(0..n);
+                let bound = match rng.random_bool(0.5) {
+                    true => Tighten::AtMost(var, (x[var] - 1.0).max(0.0).floor()),
+                    false => Tighten::AtLeast(var, 1.0),
+                };
+                sys = tightened(&sys, bound);
+                let cold = solve(&sys);
+                let Some((warm_solution, next)) = Simplex.resolve(&warm, bound) else {
+                    assert!(cold.is_none(), "{bound:?} is feasible for {sys:?}");
+                    break;
+                };
+                let cold = cold.unwrap_or_else(|| panic!("{bound:?} is infeasible for {sys:?}"));
+                assert!((warm_solution.cost - cold.cost).abs() < 1e-6);
+                let residual = &sys.a * &warm_solution.x - &sys.b;
+                assert!(residual.amax() < 1e-6, "residual {residual}");
+                assert!(warm_solution.x.iter().all(|&x| x > -1e-6));
+                warm = next;
+            }
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use miette::*;
+
+use aoc2025_day_11::part2::Solver;
+
+/// Prints the devices between two endpoints (`svr` and `out` by default) as a
+/// Graphviz DOT graph.
+fn main() -> Result<()> {
+    aoc_runtime::init();
+    let mut args = std::env::args().skip(1);
+    let start = args.next().unwrap_or_else(|| "svr".to_string());
+    let end = args.next().unwrap_or_else(|| "out".to_string());
+
+    let input = aoc_runtime::day!()?.load_input()?;
+    let solver = Solver::parse(&input)?;
+    let pruned = solver
+        .subgraph_between(&start, &end)
+        .ok_or_else(|| miette!("No path from {} to {}", start, end))?;
+    print!("{}", pruned.to_dot());
+    Ok(())
+}
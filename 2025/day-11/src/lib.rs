@@ -1,2 +1,34 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+use part2::Solver;
+
 pub mod part1;
+pub mod part1_bytes;
 pub mod part2;
+pub mod part2_bytes;
+
+/// Both parts count paths through the same device graph.
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Parsed = Solver;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Solver::parse(input)
+    }
+
+    fn part1(solver: &Self::Parsed) -> Result<Answer> {
+        part1::solve(solver)
+    }
+
+    fn part2(solver: &Self::Parsed) -> Result<Answer> {
+        part2::solve(solver)
+    }
+
+    fn items(solver: &Self::Parsed) -> Option<usize> {
+        Some(solver.len())
+    }
+}
+
+aoc_common::solution!(Day11);
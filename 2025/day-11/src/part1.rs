@@ -1,142 +1,36 @@
-use chumsky::prelude::*;
+use aoc_common::answer::Answer;
 use miette::*;
-use std::collections::{HashMap, VecDeque};
 
-#[derive(Debug)]
-struct GraphRaw {
-    edges: Vec<(String, Vec<String>)>,
-}
-
-fn parser<'a>() -> impl Parser<'a, &'a str, GraphRaw, extra::Err<Rich<'a, char>>> {
-    let ident = text::ident().map(ToString::to_string);
-
-    let dests = ident.separated_by(just(' ')).collect();
-
-    let line = ident.then_ignore(just(':').padded()).then(dests);
-
-    line.separated_by(text::newline())
-        .allow_trailing()
-        .collect()
-        .map(|edges| GraphRaw { edges })
-}
-
-struct Solver {
-    adj: Vec<Vec<usize>>,
-    start_node: usize,
-    end_node: usize,
-    num_nodes: usize,
-}
-
-impl Solver {
-    fn new(raw: GraphRaw) -> Result<Self> {
-        let mut name_to_id: HashMap<String, usize> = HashMap::new();
-        let mut get_id = |name: String| {
-            let len = name_to_id.len();
-            *name_to_id.entry(name).or_insert(len)
-        };
-
-        // First pass: Assign IDs to all source nodes and collect edges
-        // We use a temporary list because we might encounter destination nodes
-        // that don't appear as source nodes (like "out" in the example).
-        let mut temp_edges = Vec::new();
-        for (src, dsts) in raw.edges {
-            let u = get_id(src);
-            for dst in dsts {
-                let v = get_id(dst);
-                temp_edges.push((u, v));
-            }
-        }
-
-        let start_node = name_to_id
-            .get("you")
-            .ok_or(miette!("Node 'you' not found"))?;
-        let end_node = name_to_id
-            .get("out")
-            .ok_or(miette!("Node 'out' not found"))?;
-        let num_nodes = name_to_id.len();
-
-        // Build adjacency list
-        let mut adj = vec![Vec::new(); num_nodes];
-        for (u, v) in temp_edges {
-            adj[u].push(v);
-        }
-
-        Ok(Self {
-            adj,
-            start_node: *start_node,
-            end_node: *end_node,
-            num_nodes,
-        })
-    }
-
-    /// Counts paths using DP on Topological Order (Kahn's Algorithm).
-    /// This works because the problem guarantees data flows one way (DAG).
-    fn count_paths(&self) -> u128 {
-        let mut in_degree = vec![0; self.num_nodes];
-        for u in 0..self.num_nodes {
-            for &v in &self.adj[u] {
-                in_degree[v] += 1;
-            }
-        }
-
-        // Initialize queue with nodes having 0 in-degree
-        let mut queue = VecDeque::new();
-        for (i, &degree) in in_degree.iter().enumerate().take(self.num_nodes) {
-            if degree == 0 {
-                queue.push_back(i);
-            }
-        }
-
-        // DP array to store number of paths from 'you' to node 'i'
-        // We use u128 to prevent overflow on complex graphs
-        let mut paths = vec![0u128; self.num_nodes];
-        paths[self.start_node] = 1;
-
-        // Process nodes in topological order
-        while let Some(u) = queue.pop_front() {
-            // Propagate path counts to neighbors
-            // If paths[u] is 0 (unreachable from 'you'), it adds 0, which is correct.
-            let count_u = paths[u];
-
-            if count_u > 0 {
-                for &v in &self.adj[u] {
-                    paths[v] += count_u;
-                }
-            }
-
-            // Standard Kahn's update
-            for &v in &self.adj[u] {
-                in_degree[v] -= 1;
-                if in_degree[v] == 0 {
-                    queue.push_back(v);
-                }
-            }
-        }
-
-        paths[self.end_node]
-    }
-}
-
-// -----------------------------------------------------------------------------
-// Main Process
-// -----------------------------------------------------------------------------
+use crate::part2::Solver;
 
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let raw_graph = parser()
-        .parse(input)
-        .into_result()
-        .map_err(|e| miette!("Parse failed: {:?}", e))?;
+    Ok(solve(&Solver::parse(input)?)?.to_string())
+}
 
-    let solver = Solver::new(raw_graph)?;
-    let total_paths = solver.count_paths();
+/// Counts the paths from `you` to `out`, in a `u128` unless there are too
+/// many for one.
+///
+/// The devices are read with [`Solver::parse`], the same parser as part 2,
+/// so weighted outputs such as `bbb*3` are accepted and count as one path
+/// each.
+#[tracing::instrument(skip(solver))]
+pub fn solve(solver: &Solver) -> Result<Answer> {
+    for node in ["you", "out"] {
+        ensure!(solver.contains(node), "Node '{node}' not found");
+    }
+    let total_paths = match solver.count_paths("you", "out") {
+        Some(total) => total.into(),
+        None => Answer::Text(solver.count_paths_big("you", "out").to_string()),
+    };
 
-    Ok(total_paths.to_string())
+    Ok(total_paths)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use num_bigint::BigUint;
 
     #[test]
     fn it_works() -> Result<()> {
@@ -153,4 +47,42 @@ iii: out";
         assert_eq!("5", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn counts_past_u128() -> Result<()> {
+        // 68 layers of 4 devices, each wired to all of the next: 4^68 = 2^136
+        // paths.
+        let layer = |i: usize| (0..4).map(move |j| format!("l{i}x{j}"));
+        let mut input = format!("you: {}\n", layer(0).collect::<Vec<_>>().join(" "));
+        for i in 0..68 {
+            let next = match i {
+                67 => "out".to_string(),
+                _ => layer(i + 1).collect::<Vec<_>>().join(" "),
+            };
+            for device in layer(i) {
+                input += &format!("{device}: {next}\n");
+            }
+        }
+        assert_eq!(BigUint::from(2u8).pow(136).to_string(), process(&input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn both_parts_read_weighted_edges() -> Result<()> {
+        let input = "svr: you*2 aaa
+you: fft*3 out
+aaa: fft
+fft: dac*2
+dac: out*5";
+        assert_eq!("2", process(input)?);
+        assert_eq!("2", crate::part2::process(input)?);
+        assert_eq!(process(input)?, crate::part1_bytes::process(input)?);
+        assert!(process(
+            "you: aaa
+aaa: you out"
+        )
+        .is_err());
+        assert!(process("svr: out").is_err());
+        Ok(())
+    }
 }
@@ -0,0 +1,38 @@
+//! [`part1`](crate::part1) with the devices scanned straight from the bytes
+//! instead of through chumsky, to compare the two in the benchmarks.
+
+use crate::part2::Solver;
+use miette::*;
+
+#[tracing::instrument]
+pub fn process(input: &str) -> Result<String> {
+    let solver = Solver::new(crate::part2_bytes::parse(input)?, input)?;
+    Ok(crate::part1::solve(&solver)?.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn agrees_with_the_combinator_parser() -> Result<()> {
+        let input = "aaa: you hhh
+you: bbb ccc
+bbb: ddd eee
+ccc: ddd eee fff
+ddd: ggg
+eee: out
+fff: out
+ggg: out
+hhh: ccc fff iii
+iii: out
+";
+        assert_eq!(process(input)?, crate::part1::process(input)?);
+        assert_eq!(process(input)?, "5");
+        assert!(process("you: out \n").is_err());
+        // Neither parser skips blank lines.
+        assert!(crate::part1::process("you: aaa\n\naaa: out\n").is_err());
+        assert!(process("you: aaa\n\naaa: out\n").is_err());
+        Ok(())
+    }
+}
@@ -1,35 +1,160 @@
+use aoc_common::answer::Answer;
+use aoc_common::diagnostics::{self, ParseMode, Recovered};
+use aoc_common::explain;
 use chumsky::prelude::*;
 use miette::*;
+use num_bigint::BigUint;
 use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::ops::Range;
+
+/// A number of paths: a `u128`, whose sums can overflow, or a [`BigUint`],
+/// whose sums cannot.
+pub trait PathCount: Clone + PartialEq + From<u8> {
+    /// Adds `other`, returning `false` when the sum does not fit.
+    fn accumulate(&mut self, other: &Self) -> bool;
+
+    /// `self` times `factor`, or `None` when the product does not fit.
+    fn scaled(&self, factor: u64) -> Option<Self>;
+}
+
+impl PathCount for u128 {
+    fn accumulate(&mut self, other: &Self) -> bool {
+        match self.checked_add(*other) {
+            Some(sum) => {
+                *self = sum;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn scaled(&self, factor: u64) -> Option<Self> {
+        self.checked_mul(factor as u128)
+    }
+}
+
+impl PathCount for BigUint {
+    fn accumulate(&mut self, other: &Self) -> bool {
+        *self += other;
+        true
+    }
+
+    fn scaled(&self, factor: u64) -> Option<Self> {
+        Some(self * factor)
+    }
+}
 
 #[derive(Debug)]
-struct GraphRaw {
-    edges: Vec<(String, Vec<String>)>,
+pub(crate) struct GraphRaw {
+    pub(crate) edges: Vec<(String, Vec<RawEdge>)>,
+}
+
+/// An output as listed in the input.
+#[derive(Debug, Clone)]
+pub(crate) struct RawEdge {
+    pub(crate) to: String,
+    pub(crate) weight: u64,
+    /// Where the output's name is in the input.
+    pub(crate) span: Range<usize>,
 }
 
 fn parser<'a>() -> impl Parser<'a, &'a str, GraphRaw, extra::Err<Rich<'a, char>>> {
     let ident = text::ident().map(ToString::to_string);
 
-    // Parse target list: "bbb ccc"
-    let dests = ident.separated_by(just(' ')).collect();
+    // Parse an optional edge weight: "bbb*3" (unweighted edges weigh 1)
+    let weight = just('*')
+        .ignore_then(diagnostics::number::<u64>())
+        .or_not()
+        .map(|w| w.unwrap_or(1));
 
-    // Parse line: "aaa: bbb ccc"
+    // Parse target list: "bbb*3 ccc"
+    let dests = ident
+        .map_with(|name, e| {
+            let span: SimpleSpan = e.span();
+            (name, span.into_range())
+        })
+        .then(weight)
+        .map(|((to, span), weight)| RawEdge { to, weight, span })
+        .separated_by(just(' '))
+        .collect();
+
+    // Parse line: "aaa: bbb*3 ccc"
     let line = ident.then_ignore(just(':').padded()).then(dests);
 
-    line.separated_by(text::newline())
+    diagnostics::skip_malformed(line, "\r\n")
+        .separated_by(text::newline())
         .allow_trailing()
-        .collect()
-        .map(|edges| GraphRaw { edges })
+        .collect::<Vec<_>>()
+        .map(|lines| GraphRaw {
+            edges: lines.into_iter().flatten().collect(),
+        })
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Edge {
+    to: usize,
+    weight: u64,
 }
 
-struct Solver {
-    adj: Vec<Vec<usize>>,
+/// A cycle among the devices, which leaves the number of paths unbounded.
+///
+/// Every edge around the cycle is labelled where it is listed in the input.
+#[derive(Debug, Diagnostic)]
+#[diagnostic(
+    code(aoc::day11::cycle),
+    help("paths can only be counted when data flows one way; remove one of these edges")
+)]
+pub struct CycleError {
+    /// Device names around the cycle, in order, starting from any of them.
+    pub cycle: Vec<String>,
+    #[source_code]
+    source_code: NamedSource<String>,
+    #[label(collection)]
+    labels: Vec<LabeledSpan>,
+}
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Graph contains a cycle: ")?;
+        for name in &self.cycle {
+            write!(f, "{name} -> ")?;
+        }
+        write!(f, "{}", self.cycle[0])
+    }
+}
+
+impl std::error::Error for CycleError {}
+
+/// The device graph, interned and topologically sorted once so that every
+/// query is a single linear DP pass.
+pub struct Solver {
+    adj: Vec<Vec<Edge>>,
     name_to_id: HashMap<String, usize>,
     topo_order: Vec<usize>,
 }
 
 impl Solver {
-    fn new(raw: GraphRaw) -> Result<Self> {
+    /// Parses the device list (`aaa: bbb*3 ccc`) and builds the solver.
+    pub fn parse(input: &str) -> Result<Self> {
+        Ok(Self::parse_with(input, ParseMode::Strict)?.value)
+    }
+
+    /// Like [`Solver::parse`], but [`ParseMode::Lenient`] leaves malformed
+    /// lines out of the graph and returns them as warnings.
+    pub fn parse_with(input: &str, mode: ParseMode) -> Result<Recovered<Self>> {
+        let parsed = diagnostics::parse_with(parser(), "input", input, mode)?;
+
+        Ok(Recovered {
+            value: Self::new(parsed.value, input)?,
+            warnings: parsed.warnings,
+        })
+    }
+
+    /// Builds the solver from the parsed graph, reporting a cycle against
+    /// `input`, the text it was parsed from.
+    pub(crate) fn new(raw: GraphRaw, input: &str) -> Result<Self> {
+        let _phase = tracing::info_span!("build").entered();
         let mut name_to_id: HashMap<String, usize> = HashMap::new();
         let mut get_id = |name: String| {
             let len = name_to_id.len();
@@ -40,9 +165,9 @@ impl Solver {
         let mut temp_edges = Vec::new();
         for (src, dsts) in raw.edges {
             let u = get_id(src);
-            for dst in dsts {
-                let v = get_id(dst);
-                temp_edges.push((u, v));
+            for RawEdge { to, weight, span } in dsts {
+                let v = get_id(to);
+                temp_edges.push((u, Edge { to: v, weight }, span));
             }
         }
 
@@ -50,9 +175,9 @@ impl Solver {
         let mut adj = vec![Vec::new(); num_nodes];
         let mut in_degree = vec![0; num_nodes];
 
-        for (u, v) in temp_edges {
-            adj[u].push(v);
-            in_degree[v] += 1;
+        for (u, edge, _) in &temp_edges {
+            adj[*u].push(*edge);
+            in_degree[edge.to] += 1;
         }
 
         // Kahn's Algorithm for Topological Sort
@@ -66,19 +191,38 @@ impl Solver {
         let mut topo_order = Vec::with_capacity(num_nodes);
         while let Some(u) = queue.pop_front() {
             topo_order.push(u);
-            for &v in &adj[u] {
-                in_degree[v] -= 1;
-                if in_degree[v] == 0 {
-                    queue.push_back(v);
+            for edge in &adj[u] {
+                in_degree[edge.to] -= 1;
+                if in_degree[edge.to] == 0 {
+                    queue.push_back(edge.to);
                 }
             }
         }
 
         // Check for cycles (though problem implies DAG)
         if topo_order.len() != num_nodes {
-            return Err(miette!(
-                "Graph contains a cycle; cannot process paths safely."
-            ));
+            let mut names = vec![""; num_nodes];
+            for (name, &u) in &name_to_id {
+                names[u] = name;
+            }
+            let cycle = find_cycle(&temp_edges, &in_degree);
+            let labels = cycle
+                .iter()
+                .zip(cycle.iter().cycle().skip(1))
+                .filter_map(|(&u, &v)| {
+                    let (_, _, span) = temp_edges
+                        .iter()
+                        .find(|(from, edge, _)| *from == u && edge.to == v)?;
+                    let label = format!("{} -> {}", names[u], names[v]);
+                    Some(LabeledSpan::new_with_span(Some(label), span.clone()))
+                })
+                .collect();
+            return Err(CycleError {
+                cycle: cycle.iter().map(|&u| names[u].to_string()).collect(),
+                source_code: NamedSource::new("input", input.to_string()),
+                labels,
+            }
+            .into());
         }
 
         Ok(Self {
@@ -88,79 +232,376 @@ impl Solver {
         })
     }
 
-    /// Counts paths from `start_node` to `end_node` using Dynamic Programming
-    /// over the pre-calculated topological order.
-    fn count_paths(&self, start: &str, end: &str) -> u128 {
-        let u_start = match self.name_to_id.get(start) {
-            Some(&id) => id,
-            None => return 0,
+    /// Resolves both endpoints of a query, `None` if either name is unknown.
+    fn endpoints(&self, start: &str, end: &str) -> Option<(usize, usize)> {
+        Some((*self.name_to_id.get(start)?, *self.name_to_id.get(end)?))
+    }
+
+    /// Restricts the graph to the vertices that lie on some path from `start`
+    /// to `end`: reachable from `start` and co-reachable to `end`.
+    ///
+    /// Queries between vertices of the pruned graph give the same answers as
+    /// on the full graph, over fewer nodes. Returns `None` when either name is
+    /// unknown or `end` is unreachable from `start`.
+    pub fn subgraph_between(&self, start: &str, end: &str) -> Option<Self> {
+        let (u_start, u_end) = self.endpoints(start, end)?;
+        let num_nodes = self.adj.len();
+
+        let mut forward = vec![false; num_nodes];
+        forward[u_start] = true;
+        for &u in &self.topo_order {
+            if forward[u] {
+                for edge in &self.adj[u] {
+                    forward[edge.to] = true;
+                }
+            }
+        }
+        if !forward[u_end] {
+            return None;
+        }
+
+        // Successors come later in topological order, so walking it backwards
+        // settles every successor before the node itself.
+        let mut backward = vec![false; num_nodes];
+        backward[u_end] = true;
+        for &u in self.topo_order.iter().rev() {
+            if self.adj[u].iter().any(|edge| backward[edge.to]) {
+                backward[u] = true;
+            }
+        }
+
+        // Renumber the kept vertices in topological order, so the new order is
+        // simply 0..len.
+        let kept = self
+            .topo_order
+            .iter()
+            .copied()
+            .filter(|&u| forward[u] && backward[u])
+            .collect::<Vec<_>>();
+        let mut new_id = vec![usize::MAX; num_nodes];
+        for (id, &u) in kept.iter().enumerate() {
+            new_id[u] = id;
+        }
+
+        let adj = kept
+            .iter()
+            .map(|&u| {
+                self.adj[u]
+                    .iter()
+                    .filter(|edge| new_id[edge.to] != usize::MAX)
+                    .map(|edge| Edge {
+                        to: new_id[edge.to],
+                        weight: edge.weight,
+                    })
+                    .collect()
+            })
+            .collect();
+        let name_to_id = self
+            .name_to_id
+            .iter()
+            .filter(|&(_, &u)| new_id[u] != usize::MAX)
+            .map(|(name, &u)| (name.clone(), new_id[u]))
+            .collect();
+
+        Some(Self {
+            adj,
+            name_to_id,
+            topo_order: (0..kept.len()).collect(),
+        })
+    }
+
+    /// Whether a device named `name` is in the graph.
+    pub fn contains(&self, name: &str) -> bool {
+        self.name_to_id.contains_key(name)
+    }
+
+    /// Number of devices in the graph.
+    pub fn len(&self) -> usize {
+        self.adj.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.adj.is_empty()
+    }
+
+    /// Renders the graph in Graphviz DOT format, labelling edges that weigh
+    /// more than one.
+    pub fn to_dot(&self) -> String {
+        let mut names = vec![""; self.adj.len()];
+        for (name, &u) in &self.name_to_id {
+            names[u] = name;
+        }
+
+        let mut dot = String::from("digraph devices {\n");
+        for &u in &self.topo_order {
+            for edge in &self.adj[u] {
+                dot.push_str(&format!("    \"{}\" -> \"{}\"", names[u], names[edge.to]));
+                if edge.weight != 1 {
+                    dot.push_str(&format!(" [label=\"{}\"]", edge.weight));
+                }
+                dot.push_str(";\n");
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Counts paths from `start` to `end`, or returns `None` when there are
+    /// more than a `u128` holds.
+    pub fn count_paths(&self, start: &str, end: &str) -> Option<u128> {
+        self.count_paths_through(start, end, &[])
+    }
+
+    /// Counts paths from `start` to `end`, however many there are.
+    pub fn count_paths_big(&self, start: &str, end: &str) -> BigUint {
+        self.count_paths_through_big(start, end, &[])
+    }
+
+    /// Counts paths from `start` to `end` that visit every device in
+    /// `required`, in any order, or returns `None` when there are more than
+    /// a `u128` holds.
+    pub fn count_paths_through(&self, start: &str, end: &str, required: &[&str]) -> Option<u128> {
+        self.paths_through(start, end, required)
+    }
+
+    /// Like [`Solver::count_paths_through`], counting with a [`BigUint`]
+    /// so that no number of paths is too many.
+    pub fn count_paths_through_big(&self, start: &str, end: &str, required: &[&str]) -> BigUint {
+        self.paths_through(start, end, required)
+            .expect("a BigUint sum never overflows")
+    }
+
+    /// The DP behind the path counts, over the pre-calculated topological
+    /// order, stopping at the first sum `T` cannot hold.
+    ///
+    /// The DP carries, per node, the number of paths reaching it for each
+    /// subset of waypoints seen so far, as a bitmask. Memory grows as
+    /// `2^required.len()` per node, which is fine for the handful of
+    /// waypoints a query names.
+    fn paths_through<T: PathCount>(&self, start: &str, end: &str, required: &[&str]) -> Option<T> {
+        let zero = T::from(0);
+        let Some((u_start, u_end)) = self.endpoints(start, end) else {
+            return Some(zero);
         };
-        let u_end = match self.name_to_id.get(end) {
-            Some(&id) => id,
-            None => return 0,
+        let mut waypoint = vec![0usize; self.adj.len()];
+        for (bit, name) in required.iter().enumerate() {
+            let Some(&u) = self.name_to_id.get(*name) else {
+                return Some(zero);
+            };
+            waypoint[u] |= 1 << bit;
+        }
+        let subsets = 1 << required.len();
+
+        // DP state: paths[i * subsets + mask] counts paths from `start` to
+        // node `i` that visited exactly the waypoints in `mask`.
+        let mut paths = vec![zero.clone(); self.adj.len() * subsets];
+        paths[u_start * subsets + waypoint[u_start]] = T::from(1);
+
+        // Processing nodes in topological order means all of a node's
+        // incoming paths are counted before it passes them on.
+        for &u in &self.topo_order {
+            for mask in 0..subsets {
+                let count = paths[u * subsets + mask].clone();
+                if count == zero {
+                    continue;
+                }
+                for edge in &self.adj[u] {
+                    if !paths[edge.to * subsets + (mask | waypoint[edge.to])].accumulate(&count) {
+                        return None;
+                    }
+                }
+            }
+        }
+
+        Some(paths.swap_remove(u_end * subsets + subsets - 1))
+    }
+
+    /// Finds the minimum total edge weight of any path from `start` to `end`,
+    /// together with the number of paths achieving it.
+    ///
+    /// Returns `None` when `end` is unreachable from `start`, or when there
+    /// are more lightest paths than a `u128` holds.
+    pub fn min_weight_paths(&self, start: &str, end: &str) -> Option<(u128, u128)> {
+        self.lightest_paths(start, end).flatten()
+    }
+
+    /// Like [`Solver::min_weight_paths`], counting with a [`BigUint`] so
+    /// that no number of paths is too many.
+    pub fn min_weight_paths_big(&self, start: &str, end: &str) -> Option<(u128, BigUint)> {
+        self.lightest_paths(start, end)
+            .expect("a BigUint sum never overflows")
+    }
+
+    /// The DP behind the lightest paths, or `None` at the first count `T`
+    /// cannot hold.
+    ///
+    /// A path has fewer edges than there are devices, and each edge weighs at
+    /// most `u64::MAX`, so its weight always fits a `u128`.
+    fn lightest_paths<T: PathCount>(&self, start: &str, end: &str) -> Option<Option<(u128, T)>> {
+        let Some((u_start, u_end)) = self.endpoints(start, end) else {
+            return Some(None);
         };
 
-        // DP state: count of paths from `start` to node `i`
-        let mut paths = vec![0u128; self.adj.len()];
-        paths[u_start] = 1;
+        // DP state: (lightest weight, number of lightest paths) from `start` to node `i`
+        let mut best: Vec<Option<(u128, T)>> = vec![None; self.adj.len()];
+        best[u_start] = Some((0, T::from(1)));
 
-        // Iterate through nodes in topological order.
-        // This ensures that when we process node u, all its incoming paths
-        // (from ancestors) have been counted.
         for &u in &self.topo_order {
-            // Optimization: If u is unreachable from start, skip
-            if paths[u] == 0 {
+            let Some((weight_u, count_u)) = best[u].clone() else {
                 continue;
+            };
+
+            for edge in &self.adj[u] {
+                let candidate = weight_u + edge.weight as u128;
+                match &mut best[edge.to] {
+                    Some((w, c)) if *w == candidate => {
+                        if !c.accumulate(&count_u) {
+                            return None;
+                        }
+                    }
+                    Some((w, _)) if *w < candidate => {}
+                    slot => *slot = Some((candidate, count_u.clone())),
+                }
             }
+        }
+
+        Some(best.swap_remove(u_end))
+    }
+
+    /// Sums the total edge weight of every path from `start` to `end`, or
+    /// returns `None` when the sum is more than a `u128` holds.
+    pub fn total_path_weight(&self, start: &str, end: &str) -> Option<u128> {
+        self.path_weight(start, end)
+    }
 
-            // If we've passed the end node in topological order, we technically could stop
-            // if we knew u_end was visited, but iterating to the end is cheap (O(V+E)).
+    /// Like [`Solver::total_path_weight`], summing with a [`BigUint`] so
+    /// that no total is too large.
+    pub fn total_path_weight_big(&self, start: &str, end: &str) -> BigUint {
+        self.path_weight(start, end)
+            .expect("a BigUint sum never overflows")
+    }
+
+    /// The DP behind the total path weights, stopping at the first sum `T`
+    /// cannot hold.
+    ///
+    /// Alongside the path counts we carry the summed weight of all paths
+    /// reaching each node: extending every path into `u` by an edge of weight
+    /// `w` adds `w` once per path, so `sum[v] += sum[u] + w * paths[u]`.
+    fn path_weight<T: PathCount>(&self, start: &str, end: &str) -> Option<T> {
+        let zero = T::from(0);
+        let Some((u_start, u_end)) = self.endpoints(start, end) else {
+            return Some(zero);
+        };
 
-            for &v in &self.adj[u] {
-                paths[v] += paths[u];
+        let mut paths = vec![zero.clone(); self.adj.len()];
+        let mut weights = vec![zero.clone(); self.adj.len()];
+        paths[u_start] = T::from(1);
+
+        for &u in &self.topo_order {
+            if paths[u] == zero {
+                continue;
+            }
+
+            let (paths_u, weights_u) = (paths[u].clone(), weights[u].clone());
+            for edge in &self.adj[u] {
+                let added = paths_u.scaled(edge.weight)?;
+                if !paths[edge.to].accumulate(&paths_u)
+                    || !weights[edge.to].accumulate(&weights_u)
+                    || !weights[edge.to].accumulate(&added)
+                {
+                    return None;
+                }
             }
         }
 
-        paths[u_end]
+        Some(weights.swap_remove(u_end))
     }
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> Result<String> {
-    let raw_graph = parser()
-        .parse(input)
-        .into_result()
-        .map_err(|e| miette!("Parse failed: {:?}", e))?;
-
-    let solver = Solver::new(raw_graph)?;
+/// Extracts one cycle, in edge order, from the nodes Kahn's algorithm could
+/// not sort, those left with a positive in-degree.
+///
+/// Each of them still has an unsorted predecessor, so walking predecessors
+/// from any of them must eventually revisit a node; the walk from there on,
+/// reversed, is a cycle.
+fn find_cycle(edges: &[(usize, Edge, Range<usize>)], in_degree: &[usize]) -> Vec<usize> {
+    let mut predecessor = vec![usize::MAX; in_degree.len()];
+    for (u, edge, _) in edges {
+        if in_degree[*u] > 0 && in_degree[edge.to] > 0 {
+            predecessor[edge.to] = *u;
+        }
+    }
 
-    // We need paths from `svr` to `out` passing through BOTH `dac` and `fft`.
-    // Since it's a DAG, the order must be either:
-    // 1. svr -> ... -> dac -> ... -> fft -> ... -> out
-    // 2. svr -> ... -> fft -> ... -> dac -> ... -> out
+    let Some(start) = in_degree.iter().position(|&degree| degree > 0) else {
+        return Vec::new();
+    };
+    let mut seen_at = vec![usize::MAX; in_degree.len()];
+    let mut walk = Vec::new();
+    let mut u = start;
+    while seen_at[u] == usize::MAX {
+        seen_at[u] = walk.len();
+        walk.push(u);
+        u = predecessor[u];
+    }
+    let mut cycle = walk.split_off(seen_at[u]);
+    cycle.reverse();
+    cycle
+}
 
-    // Case 1: svr -> dac -> fft -> out
-    let paths_dac_first = solver.count_paths("svr", "dac")
-        * solver.count_paths("dac", "fft")
-        * solver.count_paths("fft", "out");
+#[tracing::instrument]
+pub fn process(input: &str) -> Result<String> {
+    Ok(solve(&Solver::parse(input)?)?.to_string())
+}
 
-    // Case 2: svr -> fft -> dac -> out
-    let paths_fft_first = solver.count_paths("svr", "fft")
-        * solver.count_paths("fft", "dac")
-        * solver.count_paths("dac", "out");
+/// Counts the paths from `svr` to `out` through both `dac` and `fft`, in a
+/// `u128` unless there are too many for one.
+#[tracing::instrument(skip(solver))]
+pub fn solve(solver: &Solver) -> Result<Answer> {
+    // Only devices on some svr -> out path can contribute.
+    let Some(solver) = solver.subgraph_between("svr", "out") else {
+        explain!("out is unreachable from svr");
+        return Ok(Answer::U64(0));
+    };
+    explain!("{} devices lie on some svr -> out path", solver.len());
 
-    let total = paths_dac_first + paths_fft_first;
+    let required = ["dac", "fft"];
+    let total = match solver.count_paths_through("svr", "out", &required) {
+        Some(total) => Answer::from(total),
+        None => {
+            explain!("the paths overflow a u128, counting again with a BigUint");
+            let total = solver.count_paths_through_big("svr", "out", &required);
+            Answer::Text(total.to_string())
+        }
+    };
+    explain!("{total} paths visit both dac and fft");
 
-    Ok(total.to_string())
+    Ok(total)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn it_works() -> Result<()> {
-        let input = "svr: aaa bbb
+    /// `from`, then `layers` layers of four devices named after `prefix`,
+    /// each wired to all of the next, then `to`: `4^layers` paths.
+    fn layered(prefix: &str, from: &str, to: &str, layers: usize) -> String {
+        let layer = |i: usize| {
+            (0..4)
+                .map(|j| format!("{prefix}{i}x{j}"))
+                .collect::<Vec<_>>()
+        };
+        let mut lines = vec![format!("{from}: {}", layer(0).join(" "))];
+        for i in 0..layers {
+            let next = match i + 1 == layers {
+                true => to.to_string(),
+                false => layer(i + 1).join(" "),
+            };
+            lines.extend(layer(i).iter().map(|device| format!("{device}: {next}")));
+        }
+        lines.join("\n")
+    }
+
+    const EXAMPLE: &str = "svr: aaa bbb
 aaa: fft
 fft: ccc
 bbb: tty
@@ -173,7 +614,220 @@ dac: fff
 fff: ggg hhh
 ggg: out
 hhh: out";
-        assert_eq!("2", process(input)?);
+
+    #[test]
+    fn it_works() -> Result<()> {
+        assert_eq!("2", process(EXAMPLE)?);
+        Ok(())
+    }
+
+    #[test]
+    fn adjacency_snapshot() -> Result<()> {
+        let solver = Solver::parse(&format!("{EXAMPLE}\nsvr: fff*3"))?;
+        insta::assert_snapshot!(solver.to_dot());
+        Ok(())
+    }
+
+    #[test]
+    fn lenient_parses_leave_bad_lines_out() -> Result<()> {
+        let input = "you: aaa bbb\naaa: out\nbbb out\n";
+        let parsed = Solver::parse_with(input, ParseMode::Lenient)?;
+        assert_eq!(parsed.warnings.len(), 1);
+        let offset = parsed.warnings[0].offset();
+        assert_eq!(input[..offset].lines().count(), 3);
+        assert_eq!(parsed.value.count_paths("you", "out"), Some(1));
+        assert!(Solver::parse(input).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn counts_past_u128() -> Result<()> {
+        // 4^34 ways to dac, 4^34 more on to fft: 2^136 paths in all.
+        let input = [
+            layered("a", "svr", "dac", 34),
+            layered("b", "dac", "fft", 34),
+            "fft: out".to_string(),
+        ]
+        .join("\n");
+        let solver = Solver::parse(&input)?;
+        let expected = BigUint::from(2u8).pow(136);
+
+        assert_eq!(solver.count_paths("svr", "out"), None);
+        assert_eq!(solver.count_paths_big("svr", "out"), expected);
+        assert_eq!(
+            solver.count_paths_big("svr", "dac"),
+            BigUint::from(4u8).pow(34)
+        );
+        assert_eq!(process(&input)?, expected.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn paths_through_any_number_of_waypoints() -> Result<()> {
+        // Six paths in all: src [ccc] [aaa [bbb]] out, and src bbb out.
+        let solver = Solver::parse(
+            "src: aaa bbb ccc
+aaa: bbb out
+bbb: out
+ccc: aaa out",
+        )?;
+        assert_eq!(Some(6), solver.count_paths("src", "out"));
+        assert_eq!(Some(4), solver.count_paths_through("src", "out", &["aaa"]));
+        assert_eq!(
+            Some(2),
+            solver.count_paths_through("src", "out", &["aaa", "bbb"])
+        );
+        assert_eq!(
+            Some(1),
+            solver.count_paths_through("src", "out", &["ccc", "bbb"])
+        );
+        assert_eq!(
+            Some(1),
+            solver.count_paths_through("src", "out", &["src", "ccc", "bbb"])
+        );
+        assert_eq!(Some(0), solver.count_paths_through("src", "out", &["zzz"]));
+        Ok(())
+    }
+
+    #[test]
+    fn reports_the_cycle() {
+        let input = "src: aaa
+aaa: bbb
+bbb: ccc out
+ccc: aaa";
+        let error = Solver::parse(input).err().unwrap();
+        let cycle = error.downcast_ref::<CycleError>().unwrap();
+        assert_eq!(cycle.cycle.len(), 3);
+        let start = cycle.cycle.iter().position(|name| name == "aaa").unwrap();
+        let mut names = cycle.cycle.clone();
+        names.rotate_left(start);
+        assert_eq!(names, ["aaa", "bbb", "ccc"]);
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "Graph contains a cycle: {} -> {}",
+                cycle.cycle.join(" -> "),
+                cycle.cycle[0]
+            )
+        );
+
+        let labels = cycle
+            .labels
+            .iter()
+            .map(|label| &input[label.offset()..label.offset() + label.len()])
+            .collect::<Vec<_>>();
+        assert_eq!(labels.len(), 3);
+        assert!(labels
+            .iter()
+            .all(|name| ["aaa", "bbb", "ccc"].contains(name)));
+        // The label on `ccc: aaa` points at that `aaa`, not the one on line 2.
+        assert!(cycle
+            .labels
+            .iter()
+            .any(|label| label.offset() == input.rfind("aaa").unwrap()));
+    }
+
+    #[test]
+    fn unweighted_edges_weigh_one() -> Result<()> {
+        let solver = Solver::parse(
+            "you: bbb ccc
+bbb: out
+ccc: ddd
+ddd: out",
+        )?;
+        assert_eq!(Some((2, 1)), solver.min_weight_paths("you", "out"));
+        assert_eq!(Some(5), solver.total_path_weight("you", "out"));
+        Ok(())
+    }
+
+    #[test]
+    fn weighted_paths() -> Result<()> {
+        // you -> aaa -> out: 2 + 3 = 5
+        // you -> bbb -> out: 4 + 1 = 5
+        // you -> bbb -> aaa -> out: 4 + 1 + 3 = 8
+        // you -> out:        7
+        let solver = Solver::parse(
+            "you: aaa*2 bbb*4 out*7
+bbb: aaa out
+aaa: out*3",
+        )?;
+        assert_eq!(Some(4), solver.count_paths("you", "out"));
+        assert_eq!(Some((5, 2)), solver.min_weight_paths("you", "out"));
+        assert_eq!(Some(25), solver.total_path_weight("you", "out"));
+        assert_eq!(None, solver.min_weight_paths("out", "you"));
+        Ok(())
+    }
+
+    #[test]
+    fn lightest_paths_past_u128() -> Result<()> {
+        // 2^136 paths of 71 unit edges each, all of them the lightest.
+        let input = [
+            layered("a", "svr", "dac", 34),
+            layered("b", "dac", "fft", 34),
+            "fft: out".to_string(),
+        ]
+        .join("\n");
+        let solver = Solver::parse(&input)?;
+        assert_eq!(solver.min_weight_paths("svr", "out"), None);
+        assert_eq!(
+            solver.min_weight_paths_big("svr", "out"),
+            Some((71, BigUint::from(2u8).pow(136)))
+        );
+
+        // Three of the heaviest edges outweigh a u64.
+        let max = u64::MAX;
+        let solver = Solver::parse(&format!("you: aaa*{max}\naaa: bbb*{max}\nbbb: out*{max}"))?;
+        assert_eq!(
+            solver.min_weight_paths("you", "out"),
+            Some((3 * max as u128, 1))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn total_weight_past_u128() -> Result<()> {
+        let input = [
+            layered("a", "svr", "dac", 34),
+            layered("b", "dac", "fft", 34),
+            "fft: out".to_string(),
+        ]
+        .join("\n");
+        let solver = Solver::parse(&input)?;
+        assert_eq!(solver.total_path_weight("svr", "out"), None);
+        assert_eq!(
+            solver.total_path_weight_big("svr", "out"),
+            BigUint::from(71u8) * BigUint::from(2u8).pow(136)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn subgraph_keeps_only_vertices_between_the_endpoints() -> Result<()> {
+        let solver = Solver::parse(
+            "src: aaa bbb*2
+aaa: out
+bbb: out dead
+ccc: aaa
+out: tail",
+        )?;
+        let pruned = solver.subgraph_between("src", "out").unwrap();
+        assert_eq!(4, pruned.len());
+        assert_eq!(
+            solver.count_paths("src", "out"),
+            pruned.count_paths("src", "out")
+        );
+        assert_eq!(Some(0), pruned.count_paths("ccc", "out"));
+        assert_eq!(
+            "digraph devices {
+    \"src\" -> \"aaa\";
+    \"src\" -> \"bbb\" [label=\"2\"];
+    \"bbb\" -> \"out\";
+    \"aaa\" -> \"out\";
+}
+",
+            pruned.to_dot()
+        );
+        assert!(solver.subgraph_between("out", "src").is_none());
         Ok(())
     }
 }
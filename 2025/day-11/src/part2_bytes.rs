@@ -0,0 +1,98 @@
+//! [`part2`](crate::part2) with the devices scanned straight from the bytes
+//! instead of through chumsky, to compare the two in the benchmarks.
+
+use crate::part2::{GraphRaw, RawEdge, Solver};
+use aoc_common::bytes::{self, Cursor};
+use miette::*;
+
+/// Parses the device list, one `aaa: bbb*3 ccc` per line.
+pub(crate) fn parse(input: &str) -> Result<GraphRaw> {
+    let edges = bytes::lines(input.as_bytes())
+        .map(|(offset, line)| {
+            let mut cursor = Cursor::new(line);
+            device(&mut cursor, offset).map_err(|expected| {
+                Report::new(bytes::malformed(input, offset + cursor.pos(), expected))
+            })
+        })
+        .collect::<Result<_>>()?;
+    Ok(GraphRaw { edges })
+}
+
+/// Reads a device and its outputs up to the end of the line, with spans
+/// shifted by the line's `offset`, or says what was expected where it
+/// stopped.
+fn device(
+    cursor: &mut Cursor,
+    offset: usize,
+) -> std::result::Result<(String, Vec<RawEdge>), &'static str> {
+    let name = cursor.ident().ok_or("a device name")?;
+    cursor.skip_spaces();
+    cursor.expect(b':').ok_or("':'")?;
+    cursor.skip_spaces();
+
+    let mut outputs = Vec::new();
+    while !cursor.is_empty() {
+        if !outputs.is_empty() {
+            cursor.expect(b' ').ok_or("' '")?;
+        }
+        let start = offset + cursor.pos();
+        let to = cursor.ident().ok_or("an output name")?.to_string();
+        let span = start..offset + cursor.pos();
+        // Unweighted edges weigh 1.
+        let weight = if cursor.eat(b'*') {
+            cursor.uint().ok_or("a weight")?
+        } else {
+            1
+        };
+        outputs.push(RawEdge { to, weight, span });
+    }
+    Ok((name.to_string(), outputs))
+}
+
+#[tracing::instrument]
+pub fn process(input: &str) -> Result<String> {
+    let solver = Solver::new(parse(input)?, input)?;
+    Ok(crate::part2::solve(&solver)?.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn agrees_with_the_combinator_parser() -> Result<()> {
+        let input = "svr: aaa bbb
+aaa: fft
+fft: ccc
+bbb: tty
+tty: ccc
+ccc: ddd eee
+ddd: hub
+hub: fff
+eee: dac
+dac: fff
+fff: ggg hhh
+ggg: out
+hhh: out";
+        assert_eq!(process(input)?, crate::part2::process(input)?);
+        assert_eq!(process(input)?, "2");
+        Ok(())
+    }
+
+    #[test]
+    fn cycles_point_at_the_same_outputs() {
+        let input = "svr: aaa*2\naaa: svr out\n";
+        let bytes = process(input).unwrap_err();
+        let chumsky = crate::part2::process(input).unwrap_err();
+        let spans = |report: &Report| {
+            report
+                .labels()
+                .into_iter()
+                .flatten()
+                .map(|label| label.offset())
+                .collect::<Vec<_>>()
+        };
+        assert!(!spans(&bytes).is_empty());
+        assert_eq!(spans(&bytes), spans(&chumsky));
+    }
+}
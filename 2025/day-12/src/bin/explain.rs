@@ -0,0 +1,20 @@
+use aoc_runtime::Args;
+use miette::*;
+
+use aoc2025_day_12::part1;
+
+/// Prints why each unsolvable region of the input could not be filled.
+fn main() -> Result<()> {
+    aoc_runtime::init();
+    let args = Args::from_env();
+    let input = args.load(&aoc_runtime::day!()?)?;
+    for (index, report) in part1::explain(&input)?.iter().enumerate() {
+        if let Err(reason) = &report.outcome {
+            println!(
+                "Region {} ({}x{}): {}",
+                index, report.width, report.height, reason
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use aoc_runtime::Args;
+use miette::*;
+
+use aoc2025_day_12::part1;
+
+/// Prints, for each unsolvable region, the fewest presents that must be left
+/// out for the rest to fit.
+fn main() -> Result<()> {
+    aoc_runtime::init();
+    let args = Args::from_env();
+    let input = args.load(&aoc_runtime::day!()?)?;
+    for (index, relaxation) in part1::minimum_relaxations(&input)?.iter().enumerate() {
+        if relaxation.total() > 0 {
+            println!(
+                "Region {} ({}x{}): drop {} presents {:?}",
+                index,
+                relaxation.width,
+                relaxation.height,
+                relaxation.total(),
+                relaxation.dropped
+            );
+        }
+    }
+    Ok(())
+}
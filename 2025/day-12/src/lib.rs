@@ -1,2 +1,28 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+use part1::Puzzle;
+
 pub mod part1;
 pub mod part2;
+
+/// Both parts fit presents under the same trees.
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Parsed = Puzzle;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        part1::parse(input)
+    }
+
+    fn part1(puzzle: &Self::Parsed) -> Result<Answer> {
+        part1::solve(puzzle)
+    }
+
+    fn part2(puzzle: &Self::Parsed) -> Result<Answer> {
+        part2::solve(puzzle)
+    }
+}
+
+aoc_common::solution!(Day12);
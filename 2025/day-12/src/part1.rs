@@ -1,8 +1,15 @@
+use aoc_common::answer::Answer;
+use aoc_common::diagnostics;
+use aoc_common::grid::Grid;
+use aoc_common::par::prelude::*;
 use bitvec::prelude::*;
 use chumsky::prelude::*;
 use miette::*;
-use rayon::prelude::*;
 use std::collections::HashSet;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+mod dlx;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 struct Point {
@@ -10,20 +17,66 @@ struct Point {
     c: i8,
 }
 
-#[derive(Debug, Clone)]
+/// Shapes are at most this many cells across, so that every orientation
+/// of them stays within a [`Point`].
+const MAX_SHAPE_SIDE: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 struct Shape {
     id: usize,
     area: usize,
+    /// The cells as drawn in the input, normalized.
+    cells: Vec<Point>,
     variants: Vec<Vec<Point>>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 struct Region {
     width: usize,
     height: usize,
     reqs: Vec<usize>,
 }
 
+/// The parsed input: the present shapes, indexed by id, and the regions
+/// under the trees.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Puzzle {
+    shapes: Vec<Shape>,
+    regions: Vec<Region>,
+}
+
+/// Writes the puzzle back in the input format, shapes first.
+impl fmt::Display for Puzzle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (id, shape) in self.shapes.iter().enumerate() {
+            writeln!(f, "{id}:")?;
+            // Normalizing puts the first cell at the origin, so cells of
+            // later rows may sit left of it.
+            let rows = shape.cells.iter().map(|p| p.r).max().unwrap_or(0);
+            let (left, right) = shape
+                .cells
+                .iter()
+                .fold((0, 0), |(left, right), p| (p.c.min(left), p.c.max(right)));
+            for r in 0..=rows {
+                for c in left..=right {
+                    let cell = shape.cells.contains(&Point { r, c });
+                    f.write_str(if cell { "#" } else { "." })?;
+                }
+                writeln!(f)?;
+            }
+            writeln!(f)?;
+        }
+        for region in &self.regions {
+            write!(f, "{}x{}:", region.width, region.height)?;
+            for req in &region.reqs {
+                write!(f, " {req}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone)]
 enum LineSuffix {
     Shape(Vec<Point>),
@@ -36,18 +89,133 @@ enum InputItem {
     Region(Region),
 }
 
+/// Why a region cannot be filled with its required presents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Unsat {
+    /// The presents cover more cells than the region has.
+    AreaExceeded { required: usize, available: usize },
+    /// The region asks for a shape that was never defined.
+    UnknownShape { shape: usize },
+    /// The shape cannot be placed anywhere in the region, in any orientation.
+    NoPlacement { shape: usize },
+    /// The search ran out of options; `rendering` shows the deepest partial
+    /// fill it reached, with each cell labelled by the shape covering it.
+    Exhausted {
+        placed: usize,
+        total: usize,
+        rendering: String,
+    },
+}
+
+impl fmt::Display for Unsat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Unsat::AreaExceeded {
+                required,
+                available,
+            } => write!(
+                f,
+                "presents need {required} cells but the region only has {available}"
+            ),
+            Unsat::UnknownShape { shape } => write!(f, "shape {shape} is not defined"),
+            Unsat::NoPlacement { shape } => write!(f, "shape {shape} fits nowhere in the region"),
+            Unsat::Exhausted {
+                placed,
+                total,
+                rendering,
+            } => write!(
+                f,
+                "search exhausted after placing at most {placed} of {total} presents:\n{rendering}"
+            ),
+        }
+    }
+}
+
+/// The outcome of solving a single region.
+#[derive(Debug, Clone)]
+pub struct RegionReport {
+    pub width: usize,
+    pub height: usize,
+    pub outcome: std::result::Result<(), Unsat>,
+}
+
+/// The fewest presents that must be left out of a region for the rest to fit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Relaxation {
+    pub width: usize,
+    pub height: usize,
+    /// How many presents of each shape to drop, indexed like the region's
+    /// requirements. All zeros when the region already fits.
+    pub dropped: Vec<usize>,
+}
+
+impl Relaxation {
+    /// Total number of presents dropped.
+    pub fn total(&self) -> usize {
+        self.dropped.iter().sum()
+    }
+}
+
+/// How a region is searched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Engine {
+    /// Bitmask backtracking, one present at a time in order of shape area.
+    Backtracking,
+    /// Dancing Links over an exact cover with a column per shape and cell.
+    Dlx,
+}
+
+impl Engine {
+    /// Regions up to this many cells are searched with [`Engine::Dlx`].
+    const DLX_MAX_CELLS: usize = 256;
+
+    /// The engine suited to a `width` by `height` region: Dancing Links for
+    /// small, tight regions, where its column choice prunes best, and
+    /// backtracking for the large ones, whose matrices would not pay off.
+    pub fn auto(width: usize, height: usize) -> Self {
+        if width * height <= Self::DLX_MAX_CELLS {
+            Self::Dlx
+        } else {
+            Self::Backtracking
+        }
+    }
+}
+
+/// The deepest point the search reached, kept to explain a failed region.
+#[derive(Default)]
+struct Deepest {
+    /// `(shape_id, placement index)` of every present placed at that point.
+    placed: Vec<(usize, usize)>,
+}
+
+/// The state of one branch of the backtracking search.
+struct Branch<'a> {
+    /// `(shape_id, placement index)` of every present placed so far.
+    placed: Vec<(usize, usize)>,
+    deepest: Deepest,
+    /// Set by the first branch to fill the region.
+    solved: &'a AtomicBool,
+}
+
 struct Solver {
     /// Precomputed valid placement masks for each shape ID.
     /// masks[shape_id] = Vec<(anchor_index, BitVec)>
     placements: Vec<Vec<(usize, BitVec)>>,
     /// Tasks to solve: (shape_id, count_needed)
     tasks: Vec<(usize, usize)>,
+    /// Shape area of every task.
+    areas: Vec<usize>,
+    /// Cells still to cover from each task onwards, with one entry past the
+    /// last task.
+    suffix_areas: Vec<usize>,
     /// Total number of cells in the grid
     total_cells: usize,
+    width: usize,
 }
 
 impl Solver {
-    fn new(shapes: &[Shape], region: &Region) -> Option<Self> {
+    fn new(shapes: &[Shape], region: &Region) -> std::result::Result<Self, Unsat> {
+        let _phase = tracing::info_span!("build").entered();
         let w = region.width;
         let h = region.height;
         let total_cells = w * h;
@@ -60,7 +228,7 @@ impl Solver {
                 continue;
             }
             if id >= shapes.len() {
-                return None;
+                return Err(Unsat::UnknownShape { shape: id });
             }
 
             let shape = &shapes[id];
@@ -69,7 +237,10 @@ impl Solver {
         }
 
         if total_area > total_cells {
-            return None;
+            return Err(Unsat::AreaExceeded {
+                required: total_area,
+                available: total_cells,
+            });
         }
 
         // Sort tasks by shape area (Largest First)
@@ -116,21 +287,174 @@ impl Solver {
             shape_masks.sort_by_key(|(anchor, _)| *anchor);
 
             if shape_masks.is_empty() {
-                return None;
+                return Err(Unsat::NoPlacement { shape: id });
             }
             placements[id] = shape_masks;
         }
 
-        Some(Self {
+        let areas = tasks
+            .iter()
+            .map(|&(id, _)| shapes[id].area)
+            .collect::<Vec<_>>();
+        let mut suffix_areas = vec![0; tasks.len() + 1];
+        for t in (0..tasks.len()).rev() {
+            suffix_areas[t] = suffix_areas[t + 1] + tasks[t].1 * areas[t];
+        }
+
+        Ok(Self {
             placements,
             tasks,
+            areas,
+            suffix_areas,
             total_cells,
+            width: w,
         })
     }
 
-    fn solve(&self) -> bool {
-        let mut grid = BitVec::<usize, Lsb0>::repeat(false, self.total_cells);
-        self.backtrack(0, 0, 0, &mut grid)
+    fn solve(&self, engine: Engine) -> std::result::Result<(), Unsat> {
+        match engine {
+            Engine::Backtracking => self.solve_backtracking(),
+            Engine::Dlx => self.solve_dlx(),
+        }
+    }
+
+    /// Backtracks with the first present's placements split across rayon
+    /// tasks, so a single hard region still uses every thread. The first
+    /// branch to fill the region cancels the others through `solved`.
+    fn solve_backtracking(&self) -> std::result::Result<(), Unsat> {
+        let Some(&(shape_id, _)) = self.tasks.first() else {
+            return Ok(());
+        };
+        let solved = AtomicBool::new(false);
+        let (fits, deepest) = self.placements[shape_id]
+            .par_iter()
+            .enumerate()
+            .map(|(placement, (anchor, mask))| {
+                let mut grid = mask.clone();
+                let mut branch = Branch {
+                    placed: vec![(shape_id, placement)],
+                    deepest: Deepest::default(),
+                    solved: &solved,
+                };
+                let fits = self.backtrack(0, 1, *anchor, &mut grid, &mut branch);
+                if fits {
+                    solved.store(true, Ordering::Relaxed);
+                }
+                (fits, branch.deepest)
+            })
+            // Keeps the earliest of equally deep branches, as the sequential
+            // search would, so explanations do not depend on scheduling.
+            .reduce_with(|first, second| {
+                if first.0 || (!second.0 && first.1.placed.len() >= second.1.placed.len()) {
+                    first
+                } else {
+                    second
+                }
+            })
+            .unwrap_or_default();
+        if fits {
+            return Ok(());
+        }
+        Err(Unsat::Exhausted {
+            placed: deepest.placed.len(),
+            total: self.tasks.iter().map(|&(_, count)| count).sum(),
+            rendering: self.render(&deepest.placed),
+        })
+    }
+
+    /// Solves the region as an exact cover: every task's shape column needs
+    /// as many placements as it has presents, and every cell is a secondary
+    /// column that at most one of them may cover.
+    fn solve_dlx(&self) -> std::result::Result<(), Unsat> {
+        let needs = self
+            .tasks
+            .iter()
+            .map(|&(_, count)| count)
+            .collect::<Vec<_>>();
+        let mut matrix = dlx::Dlx::new(&needs, self.total_cells);
+        let mut rows = Vec::new();
+        for (task, &(shape_id, _)) in self.tasks.iter().enumerate() {
+            for (placement, (_, mask)) in self.placements[shape_id].iter().enumerate() {
+                let columns = std::iter::once(task)
+                    .chain(mask.iter_ones().map(|cell| self.tasks.len() + cell))
+                    .collect::<Vec<_>>();
+                matrix.add_row(&columns);
+                rows.push((shape_id, placement));
+            }
+        }
+        match matrix.solve() {
+            Ok(_) => Ok(()),
+            Err(deepest) => {
+                let placed = deepest.iter().map(|&row| rows[row]).collect::<Vec<_>>();
+                Err(Unsat::Exhausted {
+                    placed: placed.len(),
+                    total: needs.iter().sum(),
+                    rendering: self.render(&placed),
+                })
+            }
+        }
+    }
+
+    /// Draws the placements on the region, labelling cells by shape ID.
+    fn render(&self, placed: &[(usize, usize)]) -> String {
+        let mut cells = Grid::filled(self.width, self.total_cells / self.width, '.');
+        for &(shape_id, placement) in placed {
+            let label = char::from_digit(shape_id as u32 % 36, 36).unwrap_or('#');
+            for idx in self.placements[shape_id][placement].1.iter_ones() {
+                cells.cells_mut()[idx] = label;
+            }
+        }
+        cells
+            .rows()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Counts the empty cells in 4-connected areas at least as big as the
+    /// smallest shape, stopping early once `needed` is reached.
+    ///
+    /// Smaller areas are dead: no remaining present fits in them, so the
+    /// presents still to place must fit in what this returns.
+    fn usable_area(&self, grid: &BitSlice<usize, Lsb0>, needed: usize) -> usize {
+        // Tasks are sorted largest first.
+        let smallest = self.areas.last().copied().unwrap_or(0);
+        let height = self.total_cells / self.width;
+        let mut seen = grid.to_bitvec();
+        let mut stack = Vec::new();
+        let mut usable = 0;
+
+        for start in grid.iter_zeros() {
+            if seen[start] {
+                continue;
+            }
+            seen.set(start, true);
+            stack.push(start);
+            let mut area = 0;
+            while let Some(idx) = stack.pop() {
+                area += 1;
+                let (x, y) = (idx % self.width, idx / self.width);
+                let neighbors = [
+                    (x > 0).then(|| idx - 1),
+                    (x + 1 < self.width).then(|| idx + 1),
+                    (y > 0).then(|| idx - self.width),
+                    (y + 1 < height).then(|| idx + self.width),
+                ];
+                for next in neighbors.into_iter().flatten() {
+                    if !seen[next] {
+                        seen.set(next, true);
+                        stack.push(next);
+                    }
+                }
+            }
+            if area >= smallest {
+                usable += area;
+                if usable >= needed {
+                    break;
+                }
+            }
+        }
+        usable
     }
 
     fn backtrack(
@@ -139,7 +463,16 @@ impl Solver {
         count_placed: usize,
         min_anchor: usize,
         grid: &mut BitSlice<usize, Lsb0>,
+        branch: &mut Branch,
     ) -> bool {
+        // Another branch already filled the region.
+        if branch.solved.load(Ordering::Relaxed) {
+            return false;
+        }
+        if branch.placed.len() > branch.deepest.placed.len() {
+            branch.deepest.placed.clone_from(&branch.placed);
+        }
+
         // Base case: All tasks completed
         if task_idx >= self.tasks.len() {
             return true;
@@ -149,13 +482,13 @@ impl Solver {
 
         // If we finished placing the current shape type, move to the next one
         if count_placed >= total_needed {
-            return self.backtrack(task_idx + 1, 0, 0, grid);
+            return self.backtrack(task_idx + 1, 0, 0, grid, branch);
         }
 
         // Try to place the current shape
         let masks = &self.placements[shape_id];
 
-        for (anchor, mask) in masks {
+        for (placement, (anchor, mask)) in masks.iter().enumerate() {
             // Enforce canonical ordering: identical shapes must be placed in increasing anchor order
             if *anchor < min_anchor {
                 continue;
@@ -178,9 +511,16 @@ impl Solver {
                     }
                 }
 
-                // Recurse
-                if self.backtrack(task_idx, count_placed + 1, *anchor, grid) {
-                    return true;
+                // Recurse, unless the presents left can no longer fit in the
+                // empty areas big enough to hold one
+                let needed =
+                    self.suffix_areas[task_idx] - (count_placed + 1) * self.areas[task_idx];
+                if needed == 0 || self.usable_area(grid, needed) >= needed {
+                    branch.placed.push((shape_id, placement));
+                    if self.backtrack(task_idx, count_placed + 1, *anchor, grid, branch) {
+                        return true;
+                    }
+                    branch.placed.pop();
                 }
 
                 // Backtrack (Remove shape)
@@ -233,9 +573,11 @@ fn generate_variants(raw_points: &[Point]) -> Vec<Vec<Point>> {
     variants
 }
 
-fn parser<'a>() -> impl Parser<'a, &'a str, (Vec<Shape>, Vec<Region>), extra::Err<Rich<'a, char>>> {
+fn parser<'a>() -> impl Parser<'a, &'a str, Puzzle, extra::Err<Rich<'a, char>>> {
     let newline = text::newline();
-    let number = text::int(10).from_str::<usize>().unwrapped();
+    let number = text::int(10)
+        .from_str::<usize>()
+        .try_map(|n, span| n.map_err(|e| Rich::custom(span, e)));
 
     // Suffix 1: Shape Definition ":\n###"
     let shape_suffix = just(':')
@@ -246,7 +588,17 @@ fn parser<'a>() -> impl Parser<'a, &'a str, (Vec<Shape>, Vec<Region>), extra::Er
                 .at_least(1)
                 .collect::<String>()
                 .separated_by(newline)
-                .collect::<Vec<String>>(),
+                .collect::<Vec<String>>()
+                .try_map(|lines, span| {
+                    let widest = lines.iter().map(String::len).max().unwrap_or(0);
+                    if lines.len().max(widest) > MAX_SHAPE_SIDE {
+                        return Err(Rich::custom(
+                            span,
+                            format!("shapes are at most {MAX_SHAPE_SIDE} cells across"),
+                        ));
+                    }
+                    Ok(lines)
+                }),
         )
         .map(|lines| {
             let mut points = Vec::new();
@@ -279,6 +631,7 @@ fn parser<'a>() -> impl Parser<'a, &'a str, (Vec<Shape>, Vec<Region>), extra::Er
                 id: prefix,
                 area: points.len(),
                 variants: generate_variants(&points),
+                cells: points,
             }),
             LineSuffix::Region(height, reqs) => InputItem::Region(Region {
                 width: prefix,
@@ -288,66 +641,180 @@ fn parser<'a>() -> impl Parser<'a, &'a str, (Vec<Shape>, Vec<Region>), extra::Er
         });
 
     line_parser
+        .map_with(|item, e| (item, e.span()))
         .separated_by(newline.repeated().at_least(1))
         .allow_trailing()
-        .collect::<Vec<InputItem>>()
-        .map(|items| {
+        .collect::<Vec<(InputItem, SimpleSpan)>>()
+        .validate(|items, _, emitter| {
             let mut shapes = Vec::new();
             let mut regions = Vec::new();
-            for item in items {
+            for (item, span) in items {
                 match item {
-                    InputItem::Shape(s) => {
-                        let id = s.id;
-                        if shapes.len() <= id {
-                            shapes.resize(
-                                id + 1,
-                                Shape {
-                                    id: 0,
-                                    area: 0,
-                                    variants: vec![],
-                                },
-                            );
-                        }
-                        shapes[id] = s;
+                    // Regions refer to shapes by position, so the ids must
+                    // count up from zero without gaps.
+                    InputItem::Shape(s) if s.id != shapes.len() => emitter.emit(Rich::custom(
+                        span,
+                        format!("expected shape {}, found shape {}", shapes.len(), s.id),
+                    )),
+                    InputItem::Shape(s) => shapes.push(s),
+                    // An empty region would leave rows of no width.
+                    InputItem::Region(r) if r.width == 0 || r.height == 0 => {
+                        emitter.emit(Rich::custom(
+                            span,
+                            format!(
+                                "expected a region at least 1x1, found {}x{}",
+                                r.width, r.height
+                            ),
+                        ))
                     }
                     InputItem::Region(r) => regions.push(r),
                 }
             }
-            (shapes, regions)
+            Puzzle { shapes, regions }
         })
 }
 
+/// Parses the present shapes and the regions under the trees.
+pub fn parse(input: &str) -> Result<Puzzle> {
+    Ok(diagnostics::parse(parser(), "input", input)?)
+}
+
+/// Solves every region, explaining the ones that cannot be filled.
 #[tracing::instrument]
-pub fn process(input: &str) -> Result<String> {
-    let (shapes, regions) = parser()
-        .parse(input)
-        .into_result()
-        .map_err(|e| miette!("Parse failed: {:?}", e))?;
+pub fn explain(input: &str) -> Result<Vec<RegionReport>> {
+    explain_with(input, Engine::auto)
+}
 
-    let success_count = regions
+/// Like [`explain`], but searching each region with the engine `select`
+/// picks from its width and height.
+#[tracing::instrument(skip(select))]
+pub fn explain_with(
+    input: &str,
+    select: impl Fn(usize, usize) -> Engine + Sync,
+) -> Result<Vec<RegionReport>> {
+    Ok(reports(&parse(input)?, select))
+}
+
+/// Like [`explain_with`], on an already parsed puzzle.
+pub fn reports(
+    puzzle: &Puzzle,
+    select: impl Fn(usize, usize) -> Engine + Sync,
+) -> Vec<RegionReport> {
+    let Puzzle { shapes, regions } = puzzle;
+
+    regions
         .par_iter()
-        .map(|region| match Solver::new(&shapes, region) {
-            Some(solver) => {
-                if solver.solve() {
-                    1
-                } else {
-                    0
-                }
-            }
-            None => 0,
+        .map(|region| RegionReport {
+            width: region.width,
+            height: region.height,
+            outcome: Solver::new(shapes, region)
+                .and_then(|solver| solver.solve(select(region.width, region.height))),
         })
-        .sum::<usize>();
+        .collect()
+}
+
+/// Whether `region` can be filled with its required presents.
+fn fits(shapes: &[Shape], region: &Region) -> bool {
+    Solver::new(shapes, region)
+        .and_then(|solver| solver.solve(Engine::auto(region.width, region.height)))
+        .is_ok()
+}
+
+/// Finds the fewest presents to drop from `region` so that the rest fit.
+///
+/// Dropping presents never makes a region harder to fill, so the relaxed
+/// requirement vectors are tried in order of how many presents they drop and
+/// the first one that fits is minimal. Vectors that still exceed the region's
+/// area are skipped without running the solver.
+fn relax(shapes: &[Shape], region: &Region) -> Relaxation {
+    let reqs = &region.reqs;
+    let area = |id: usize| shapes.get(id).map_or(0, |shape| shape.area);
+    let available = region.width * region.height;
+
+    let mut dropped = vec![0; reqs.len()];
+    let total = reqs.iter().sum::<usize>();
+    for k in 0..=total {
+        let found = drop_presents(reqs, &mut dropped, 0, k, &mut |dropped| {
+            let required = (0..reqs.len())
+                .map(|id| (reqs[id] - dropped[id]) * area(id))
+                .sum::<usize>();
+            if required > available {
+                return false;
+            }
+            let relaxed = Region {
+                width: region.width,
+                height: region.height,
+                reqs: (0..reqs.len()).map(|id| reqs[id] - dropped[id]).collect(),
+            };
+            fits(shapes, &relaxed)
+        });
+        if found {
+            break;
+        }
+    }
+
+    Relaxation {
+        width: region.width,
+        height: region.height,
+        dropped,
+    }
+}
+
+/// Calls `accept` on every way of dropping exactly `k` presents from shape
+/// `id` onwards, stopping at the first it accepts with `dropped` left set.
+fn drop_presents(
+    reqs: &[usize],
+    dropped: &mut [usize],
+    id: usize,
+    k: usize,
+    accept: &mut impl FnMut(&[usize]) -> bool,
+) -> bool {
+    if id == reqs.len() {
+        return k == 0 && accept(dropped);
+    }
+    for n in 0..=k.min(reqs[id]) {
+        dropped[id] = n;
+        if drop_presents(reqs, dropped, id + 1, k - n, accept) {
+            return true;
+        }
+    }
+    dropped[id] = 0;
+    false
+}
+
+/// Computes, for every region, the fewest presents to leave out so that the
+/// rest fit.
+#[tracing::instrument]
+pub fn minimum_relaxations(input: &str) -> Result<Vec<Relaxation>> {
+    let Puzzle { shapes, regions } = parse(input)?;
 
-    Ok(success_count.to_string())
+    Ok(regions
+        .par_iter()
+        .map(|region| relax(&shapes, region))
+        .collect())
+}
+
+#[tracing::instrument]
+pub fn process(input: &str) -> Result<String> {
+    Ok(solve(&parse(input)?)?.to_string())
+}
+
+/// Counts the regions that fit all of their presents.
+#[tracing::instrument(skip(puzzle))]
+pub fn solve(puzzle: &Puzzle) -> Result<Answer> {
+    let success_count = reports(puzzle, Engine::auto)
+        .iter()
+        .filter(|report| report.outcome.is_ok())
+        .count();
+
+    Ok(success_count.into())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn it_works() -> Result<()> {
-        let input = "0:
+    const EXAMPLE: &str = "0:
 ###
 ##.
 ##.
@@ -380,7 +847,161 @@ mod tests {
 4x4: 0 0 0 0 2 0
 12x5: 1 0 1 0 2 2
 12x5: 1 0 1 0 3 2";
-        assert_eq!("2", process(input)?);
+
+    #[test]
+    fn it_works() -> Result<()> {
+        assert_eq!("2", process(EXAMPLE)?);
+        Ok(())
+    }
+
+    /// Draws cells as `#` on a `.` background, one row per line.
+    fn draw(cells: &[Point]) -> String {
+        let (rows, cols) = cells.iter().fold((0..=0, 0..=0), |(rows, cols), p| {
+            (
+                *rows.start().min(&p.r)..=*rows.end().max(&p.r),
+                *cols.start().min(&p.c)..=*cols.end().max(&p.c),
+            )
+        });
+        rows.map(|r| {
+            cols.clone()
+                .map(|c| {
+                    if cells.contains(&Point { r, c }) {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+    }
+
+    #[test]
+    fn shape_variants_snapshot() -> Result<()> {
+        let Puzzle { shapes, regions } = parse(EXAMPLE)?;
+        let mut out = String::new();
+        for shape in &shapes {
+            out += &format!("shape {} (area {}):\n", shape.id, shape.area);
+            for variant in &shape.variants {
+                out += &format!("{}\n\n", draw(variant));
+            }
+        }
+        for region in &regions {
+            out += &format!("{region:?}\n");
+        }
+        insta::assert_snapshot!(out);
+        Ok(())
+    }
+
+    #[test]
+    fn usable_area_skips_dead_cells() -> Result<()> {
+        // A plus in the corner of a 4x4 region walls off the corner cell.
+        let input = format!("{PLUS}4x4: 2");
+        let Puzzle { shapes, regions } = parse(&input)?;
+        let solver = Solver::new(&shapes, &regions[0]).unwrap();
+        let grid = solver.placements[0][0].1.clone();
+        assert_eq!(solver.usable_area(&grid, 16), 10);
+        assert_eq!(solver.usable_area(&grid, 5), 10);
+        Ok(())
+    }
+
+    #[test]
+    fn engines_agree() -> Result<()> {
+        let input = format!("{PLUS}5x3: 2\n6x3: 2\n3x3: 1\n6x6: 4\n5x5: 3");
+        let outcomes = |engine| -> Result<Vec<bool>> {
+            Ok(explain_with(&input, |_, _| engine)?
+                .iter()
+                .map(|report| report.outcome.is_ok())
+                .collect())
+        };
+        assert_eq!(outcomes(Engine::Dlx)?, [false, true, true, true, false]);
+        assert_eq!(outcomes(Engine::Backtracking)?, outcomes(Engine::Dlx)?);
+        Ok(())
+    }
+
+    const PLUS: &str = "0:
+.#.
+###
+.#.
+
+";
+
+    #[test]
+    fn explains_area_and_shape_failures() -> Result<()> {
+        let input = format!("{PLUS}2x2: 1\n7x1: 1\n3x3: 0 1");
+        let outcomes = explain(&input)?
+            .into_iter()
+            .map(|report| report.outcome)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            outcomes,
+            vec![
+                Err(Unsat::AreaExceeded {
+                    required: 5,
+                    available: 4
+                }),
+                Err(Unsat::NoPlacement { shape: 0 }),
+                Err(Unsat::UnknownShape { shape: 1 }),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn explains_exhausted_search_with_deepest_fill() -> Result<()> {
+        let input = format!("{PLUS}5x3: 2");
+        let report = explain(&input)?.remove(0);
+        assert_eq!(
+            report.outcome,
+            Err(Unsat::Exhausted {
+                placed: 1,
+                total: 2,
+                rendering: ".0...\n000..\n.0...".to_string(),
+            })
+        );
         Ok(())
     }
+
+    #[test]
+    fn relaxes_unsolvable_regions() -> Result<()> {
+        let input = format!("{PLUS}2x2: 1\n3x3: 1\n5x3: 2\n3x3: 1 2\n6x3: 3");
+        let dropped = minimum_relaxations(&input)?
+            .into_iter()
+            .map(|relaxation| relaxation.dropped)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            dropped,
+            vec![vec![1], vec![0], vec![1], vec![0, 2], vec![1]]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn puzzles_round_trip_through_display() -> Result<()> {
+        let input = format!("{PLUS}1:\n..#\n.##\n\n12x5: 1 0\n4x4:");
+        let puzzle = parse(&input)?;
+        assert_eq!(
+            puzzle.to_string(),
+            format!("{PLUS}1:\n.#\n##\n\n12x5: 1 0\n4x4:\n")
+        );
+        assert_eq!(parse(&puzzle.to_string())?, puzzle);
+        Ok(())
+    }
+
+    #[test]
+    fn regions_must_not_be_empty() {
+        for region in ["0x5: 1", "5x0: 1"] {
+            let report = parse(&format!("0:\n#\n\n{region}")).unwrap_err();
+            let error = report.downcast_ref::<diagnostics::ParseError>().unwrap();
+            assert_eq!(error.labels()[0].offset(), 6);
+        }
+    }
+
+    #[test]
+    fn shape_ids_must_count_up() {
+        let report = parse("1:\n#\n").unwrap_err();
+        let error = report.downcast_ref::<diagnostics::ParseError>().unwrap();
+        assert_eq!(error.labels()[0].offset(), 0);
+    }
 }
@@ -0,0 +1,268 @@
+//! Dancing Links for exact cover with multiplicities and secondary columns.
+//!
+//! Every primary column must be covered exactly `need` times, and every
+//! secondary column at most once. For the presents, each shape is a primary
+//! column needing as many rows as there are presents of it, and each cell a
+//! secondary one, as cells may stay empty.
+
+/// The four-way linked matrix, Knuth style: node 0 is the root, nodes
+/// `1..=columns` the column headers and the rest one node per row entry.
+pub struct Dlx {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    /// Column header of every node.
+    column: Vec<usize>,
+    /// Row of every entry node; unused for headers.
+    row: Vec<usize>,
+    /// Live rows in every column, indexed by header.
+    len: Vec<usize>,
+    /// Rows still to pick from every column, indexed by header.
+    need: Vec<usize>,
+    rows: usize,
+}
+
+impl Dlx {
+    /// Creates a matrix with a primary column for every entry of `needs`,
+    /// followed by `secondary` columns.
+    pub fn new(needs: &[usize], secondary: usize) -> Self {
+        let columns = needs.len() + secondary;
+        let headers = columns + 1;
+        let mut dlx = Self {
+            left: (0..headers).map(|i| i.wrapping_sub(1)).collect(),
+            right: (0..headers).map(|i| i + 1).collect(),
+            up: (0..headers).collect(),
+            down: (0..headers).collect(),
+            column: (0..headers).collect(),
+            row: vec![usize::MAX; headers],
+            len: vec![0; headers],
+            need: std::iter::once(0)
+                .chain(needs.iter().copied())
+                .chain(std::iter::repeat_n(0, secondary))
+                .collect(),
+            rows: 0,
+        };
+        // Only primary headers join the root's list, so only they are ever
+        // chosen to branch on; secondary headers link to themselves.
+        let last = needs.len();
+        dlx.left[0] = last;
+        dlx.right[last] = 0;
+        for header in last + 1..headers {
+            dlx.left[header] = header;
+            dlx.right[header] = header;
+        }
+        dlx
+    }
+
+    /// Adds a row covering `columns`, numbered from zero across the primary
+    /// then the secondary columns, and returns its index.
+    ///
+    /// A row may cover several primary columns only if all but one of them
+    /// need a single row.
+    pub fn add_row(&mut self, columns: &[usize]) -> usize {
+        let row = self.rows;
+        self.rows += 1;
+        let first = self.left.len();
+        for (i, &column) in columns.iter().enumerate() {
+            let header = column + 1;
+            let node = first + i;
+            self.left.push(if i == 0 {
+                first + columns.len() - 1
+            } else {
+                node - 1
+            });
+            self.right.push(if i + 1 == columns.len() {
+                first
+            } else {
+                node + 1
+            });
+            self.up.push(self.up[header]);
+            self.down.push(header);
+            self.down[self.up[header]] = node;
+            self.up[header] = node;
+            self.column.push(header);
+            self.row.push(row);
+            self.len[header] += 1;
+        }
+        row
+    }
+
+    /// Finds rows meeting every column's need, as `Ok(rows)`, or returns the
+    /// deepest partial choice the search reached as `Err(rows)`.
+    pub fn solve(&mut self) -> Result<Vec<usize>, Vec<usize>> {
+        let mut chosen = Vec::new();
+        let mut deepest = Vec::new();
+        if self.search(&mut chosen, &mut deepest) {
+            Ok(chosen)
+        } else {
+            Err(deepest)
+        }
+    }
+
+    fn search(&mut self, chosen: &mut Vec<usize>, deepest: &mut Vec<usize>) -> bool {
+        if chosen.len() > deepest.len() {
+            deepest.clone_from(chosen);
+        }
+        if self.right[0] == 0 {
+            return true;
+        }
+
+        // Branch on the column with the fewest spare rows. A column with
+        // fewer rows than it needs is a dead end.
+        let mut column = self.right[0];
+        let mut header = column;
+        while header != 0 {
+            if self.len[header] < self.need[header] {
+                return false;
+            }
+            if self.len[header] - self.need[header] < self.len[column] - self.need[column] {
+                column = header;
+            }
+            header = self.right[header];
+        }
+
+        // Picking the column's rows in order, and dropping each row once its
+        // branch is done, enumerates every multiset of rows only once.
+        let mut dropped = Vec::new();
+        let mut node = self.down[column];
+        let mut found = false;
+        while node != column && self.len[column] >= self.need[column] {
+            let next = self.down[node];
+            self.hide_row(node);
+            dropped.push(node);
+
+            self.need[column] -= 1;
+            self.for_row(node, |dlx, entry| dlx.cover(dlx.column[entry]));
+            if self.need[column] == 0 {
+                self.cover(column);
+            }
+            chosen.push(self.row[node]);
+
+            found = self.search(chosen, deepest);
+
+            if !found {
+                chosen.pop();
+            }
+            if self.need[column] == 0 {
+                self.uncover(column);
+            }
+            self.for_row_rev(node, |dlx, entry| dlx.uncover(dlx.column[entry]));
+            self.need[column] += 1;
+
+            if found {
+                break;
+            }
+            node = next;
+        }
+        for &node in dropped.iter().rev() {
+            self.unhide_row(node);
+        }
+        found
+    }
+
+    /// Calls `f` on every other entry of `node`'s row, left to right.
+    fn for_row(&mut self, node: usize, mut f: impl FnMut(&mut Self, usize)) {
+        let mut entry = self.right[node];
+        while entry != node {
+            f(self, entry);
+            entry = self.right[entry];
+        }
+    }
+
+    /// Calls `f` on every other entry of `node`'s row, right to left.
+    fn for_row_rev(&mut self, node: usize, mut f: impl FnMut(&mut Self, usize)) {
+        let mut entry = self.left[node];
+        while entry != node {
+            f(self, entry);
+            entry = self.left[entry];
+        }
+    }
+
+    fn unlink_vertically(&mut self, node: usize) {
+        self.down[self.up[node]] = self.down[node];
+        self.up[self.down[node]] = self.up[node];
+        self.len[self.column[node]] -= 1;
+    }
+
+    fn relink_vertically(&mut self, node: usize) {
+        self.down[self.up[node]] = node;
+        self.up[self.down[node]] = node;
+        self.len[self.column[node]] += 1;
+    }
+
+    /// Takes `node`'s whole row out of every column.
+    fn hide_row(&mut self, node: usize) {
+        self.unlink_vertically(node);
+        self.for_row(node, Self::unlink_vertically);
+    }
+
+    fn unhide_row(&mut self, node: usize) {
+        self.for_row_rev(node, Self::relink_vertically);
+        self.relink_vertically(node);
+    }
+
+    /// Removes `header` from the root's list and every row in it from the
+    /// other columns.
+    fn cover(&mut self, header: usize) {
+        self.right[self.left[header]] = self.right[header];
+        self.left[self.right[header]] = self.left[header];
+        let mut node = self.down[header];
+        while node != header {
+            self.for_row(node, Self::unlink_vertically);
+            node = self.down[node];
+        }
+    }
+
+    fn uncover(&mut self, header: usize) {
+        let mut node = self.up[header];
+        while node != header {
+            self.for_row_rev(node, Self::relink_vertically);
+            node = self.up[node];
+        }
+        self.right[self.left[header]] = header;
+        self.left[self.right[header]] = header;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn covers_primary_columns_exactly() {
+        // Knuth's example from "Dancing Links".
+        let mut dlx = Dlx::new(&[1; 7], 0);
+        for columns in [
+            &[2, 4, 5][..],
+            &[0, 3, 6],
+            &[1, 2, 5],
+            &[0, 3],
+            &[1, 6],
+            &[3, 4, 6],
+        ] {
+            dlx.add_row(columns);
+        }
+        let mut rows = dlx.solve().unwrap();
+        rows.sort_unstable();
+        assert_eq!(rows, [0, 3, 4]);
+    }
+
+    #[test]
+    fn honours_needs_and_secondary_columns() {
+        // Two of column 0 over three cells that each take at most one.
+        let mut dlx = Dlx::new(&[2], 3);
+        dlx.add_row(&[0, 1, 2]);
+        dlx.add_row(&[0, 2, 3]);
+        dlx.add_row(&[0, 3]);
+        let mut rows = dlx.solve().unwrap();
+        rows.sort_unstable();
+        assert_eq!(rows, [0, 2]);
+
+        let mut dlx = Dlx::new(&[3], 3);
+        dlx.add_row(&[0, 1, 2]);
+        dlx.add_row(&[0, 2, 3]);
+        dlx.add_row(&[0, 3]);
+        assert!(dlx.solve().is_err());
+    }
+}
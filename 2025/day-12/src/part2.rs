@@ -1,10 +1,18 @@
+use aoc_common::answer::Answer;
 use miette::*;
 
+use crate::part1::Puzzle;
+
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
     Ok(String::from(""))
 }
 
+/// The last day has no second puzzle to solve.
+pub fn solve(_puzzle: &Puzzle) -> Result<Answer> {
+    Ok(Answer::Text(String::new()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -4,12 +4,47 @@ fn main() {
     divan::main();
 }
 
-#[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!("../input1.txt",))).unwrap();
-}
+/// Range widths swept by the scaling benchmarks, from 10^4 up to 10^9 IDs.
+const WIDTHS: &[u64] = &[
+    10_000,
+    100_000,
+    1_000_000,
+    10_000_000,
+    100_000_000,
+    1_000_000_000,
+];
+
+/// First ID of every swept range. Starting at a 10-digit boundary keeps the
+/// digit count (and so the number of pattern lengths tried) realistic.
+const RANGE_START: u64 = 1_000_000_000;
+
+#[divan::bench_group(sample_count = 10, sample_size = 1)]
+mod scaling {
+    use super::*;
+
+    #[divan::bench(args = WIDTHS)]
+    fn is_invalid_id(width: u64) -> usize {
+        (RANGE_START..RANGE_START + width)
+            .filter(|&id| part2::is_invalid_id(divan::black_box(id)))
+            .count()
+    }
+
+    #[divan::bench(args = WIDTHS)]
+    fn is_invalid_id_optimized(width: u64) -> usize {
+        (RANGE_START..RANGE_START + width)
+            .filter(|&id| part2::is_invalid_id_optimized(divan::black_box(id)))
+            .count()
+    }
+
+    #[divan::bench(args = WIDTHS)]
+    fn process_part1(bencher: divan::Bencher, width: u64) {
+        let input = format!("{}-{}", RANGE_START, RANGE_START + width - 1);
+        bencher.bench(|| part1::process(divan::black_box(&input)).unwrap());
+    }
 
-#[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!("../input2.txt",))).unwrap();
+    #[divan::bench(args = WIDTHS)]
+    fn process_part2(bencher: divan::Bencher, width: u64) {
+        let input = format!("{}-{}", RANGE_START, RANGE_START + width - 1);
+        bencher.bench(|| part2::process(divan::black_box(&input)).unwrap());
+    }
 }
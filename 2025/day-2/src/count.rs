@@ -0,0 +1,104 @@
+//! Sums invalid IDs without visiting them.
+//!
+//! An ID of `len` digits made of a `p`-digit block repeated `len / p` times
+//! is the block times the mask `10^(len - p) + ... + 10^p + 1`, so the sum of
+//! all of them up to a bound is the mask times an arithmetic series over the
+//! blocks. A number like `111111` repeats blocks of 1, 2 and 3 digits; to
+//! count it once, each sum is split by the shortest block the IDs repeat.
+
+use aoc_common::ranges::RangeSet;
+
+/// Sum of the IDs in `0..=n` made of a block of digits repeated `k` times
+/// for some `k` that `repeats` accepts (`k == 2` for part 1, any `k` for
+/// part 2).
+pub fn sum_up_to(n: u64, repeats: impl Fn(u32) -> bool) -> u128 {
+    let n = n as u128;
+    let digits = n.checked_ilog10().map_or(1, |log| log + 1);
+    let mut total = 0;
+    for len in 2..=digits {
+        // Sum of the `len`-digit IDs up to `n` whose shortest block has
+        // `p` digits, indexed by `p`.
+        let mut primitive = vec![0u128; len as usize];
+        for p in (1..len).filter(|p| len.is_multiple_of(*p)) {
+            let shorter = (1..p)
+                .filter(|q| p.is_multiple_of(*q))
+                .map(|q| primitive[q as usize])
+                .sum::<u128>();
+            primitive[p as usize] = periodic_sum(n, len, p) - shorter;
+
+            // Every multiple of `p` that divides `len` is also a block
+            // length of these IDs.
+            let counted = (p..len)
+                .step_by(p as usize)
+                .any(|block| len.is_multiple_of(block) && repeats(len / block));
+            if counted {
+                total += primitive[p as usize];
+            }
+        }
+    }
+    total
+}
+
+/// Sum of the `len`-digit IDs up to `n` that repeat a `p`-digit block,
+/// including the ones that also repeat a shorter block.
+fn periodic_sum(n: u128, len: u32, p: u32) -> u128 {
+    let mask = (10u128.pow(len) - 1) / (10u128.pow(p) - 1);
+    let lowest = 10u128.pow(p - 1);
+    let highest = (10u128.pow(p) - 1).min(n / mask);
+    if highest < lowest {
+        return 0;
+    }
+    mask * (lowest + highest) * (highest - lowest + 1) / 2
+}
+
+/// Sum of the invalid IDs in any of `ranges`, counting the IDs of
+/// overlapping ranges once.
+pub fn sum_in_ranges(ranges: &[(u64, u64)], repeats: impl Fn(u32) -> bool) -> u128 {
+    let set = ranges
+        .iter()
+        .map(|&(start, end)| start..=end)
+        .collect::<RangeSet>();
+    set.ranges()
+        .iter()
+        .map(|range| {
+            let below = match *range.start() {
+                0 => 0,
+                start => sum_up_to(start - 1, &repeats),
+            };
+            sum_up_to(*range.end(), &repeats) - below
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn matches_enumeration() {
+        let (mut doubled, mut repeated) = (0u128, 0u128);
+        for n in 0..=1_200_000u64 {
+            doubled += u128::from(part1::is_invalid_id(n)) * n as u128;
+            repeated += u128::from(part2::is_invalid_id(n)) * n as u128;
+            if n % 997 == 0 || [9, 10, 11, 99, 100, 1010, 999_999].contains(&n) {
+                assert_eq!(sum_up_to(n, |k| k == 2), doubled, "part 1 up to {n}");
+                assert_eq!(sum_up_to(n, |k| k >= 2), repeated, "part 2 up to {n}");
+            }
+        }
+    }
+
+    #[test]
+    fn counts_overlapping_ranges_once() {
+        let ranges = [(95, 115), (100, 120), (5, 1), (998, 1012), (1000, 1011)];
+        assert_eq!(sum_in_ranges(&ranges, |k| k >= 2), 99 + 111 + 999 + 1010);
+    }
+
+    #[test]
+    fn handles_the_largest_ids() {
+        // 18446744073709551615 has 20 digits; 1844674407 repeated twice is
+        // the largest doubled ID below it.
+        let last = sum_in_ranges(&[(18_446_744_071_844_674_407, u64::MAX)], |k| k == 2);
+        assert_eq!(last, 18_446_744_071_844_674_407);
+    }
+}
@@ -1,2 +1,32 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+
+pub mod count;
 pub mod part1;
 pub mod part2;
+
+/// Both parts sum the invalid IDs in the same ranges.
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Parsed = Vec<(u64, u64)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        part1::parse(input)
+    }
+
+    fn part1(ranges: &Self::Parsed) -> Result<Answer> {
+        part1::solve(ranges)
+    }
+
+    fn part2(ranges: &Self::Parsed) -> Result<Answer> {
+        part2::solve(ranges)
+    }
+
+    fn items(ranges: &Self::Parsed) -> Option<usize> {
+        Some(ranges.len())
+    }
+}
+
+aoc_common::solution!(Day2);
@@ -1,14 +1,19 @@
+use std::ops::RangeInclusive;
+
+use aoc_common::answer::Answer;
+use aoc_common::diagnostics::{self, ParseMode, Recovered};
 use chumsky::prelude::*;
-use itertools::Itertools;
 use miette::*;
 
+use crate::count;
+
 /// Checks if an ID consists of a digit sequence repeated twice (e.g., 123123, 55).
-fn is_invalid_id(n: u64) -> bool {
+pub fn is_invalid_id(n: u64) -> bool {
     let s = n.to_string();
     let len = s.len();
 
     // An ID must have even length to be two identical halves
-    if len % 2 != 0 {
+    if !len.is_multiple_of(2) {
         return false;
     }
 
@@ -17,36 +22,49 @@ fn is_invalid_id(n: u64) -> bool {
     left == right
 }
 
+/// Yields the invalid IDs in `range` in ascending order, checking each ID only
+/// as it is pulled.
+pub fn invalid_ids_in(range: RangeInclusive<u64>) -> impl Iterator<Item = u64> {
+    range.filter(|&id| is_invalid_id(id))
+}
+
 /// Parses a list of ranges "min-max" separated by commas.
 fn parser<'a>() -> impl Parser<'a, &'a str, Vec<(u64, u64)>, extra::Err<Rich<'a, char>>> {
-    let range = text::int(10)
-        .from_str::<u64>()
-        .unwrapped()
+    let range = diagnostics::number::<u64>()
         .then_ignore(just('-'))
-        .then(text::int(10).from_str::<u64>().unwrapped())
+        .then(diagnostics::number::<u64>())
         .padded(); // Handles surrounding whitespace (including newlines)
 
-    range.separated_by(just(',')).allow_trailing().collect()
+    diagnostics::skip_malformed(range, ",")
+        .separated_by(just(','))
+        .allow_trailing()
+        .collect::<Vec<_>>()
+        .map(|ranges| ranges.into_iter().flatten().collect())
+}
+
+/// Parses the comma separated `min-max` ranges.
+pub fn parse(input: &str) -> Result<Vec<(u64, u64)>> {
+    Ok(parse_with(input, ParseMode::Strict)?.value)
+}
+
+/// Like [`parse`], but [`ParseMode::Lenient`] skips malformed ranges and
+/// returns them as warnings.
+pub fn parse_with(input: &str, mode: ParseMode) -> Result<Recovered<Vec<(u64, u64)>>> {
+    Ok(diagnostics::parse_with(parser(), "input", input, mode)?)
 }
 
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let ranges = parser()
-        .parse(input)
-        .into_result()
-        .map_err(|e| miette!("Parse failed: {:?}", e))?;
-
-    let sum: u64 = ranges
-        .into_iter()
-        // Flatten the ranges into a single iterator of IDs
-        .flat_map(|(start, end)| start..=end)
-        // Check the pattern condition
-        .filter(|&id| is_invalid_id(id))
-        // Ensure we don't double count if the input ranges happen to overlap
-        .unique()
-        .sum();
-
-    Ok(sum.to_string())
+    Ok(solve(&parse(input)?)?.to_string())
+}
+
+/// Sums the IDs made of some digits repeated exactly twice.
+#[tracing::instrument(skip(ranges))]
+pub fn solve(ranges: &[(u64, u64)]) -> Result<Answer> {
+    // Overlapping ranges are merged, so every ID is counted once.
+    let sum = count::sum_in_ranges(ranges, |repeats| repeats == 2);
+
+    Ok(sum.into())
 }
 
 #[cfg(test)]
@@ -65,6 +83,12 @@ mod tests {
         assert!(!is_invalid_id(12123)); // Odd length
     }
 
+    #[test]
+    fn invalid_ids_are_yielded_in_order() {
+        assert_eq!(invalid_ids_in(95..=115).collect::<Vec<_>>(), vec![99]);
+        assert_eq!(invalid_ids_in(12..=21).next(), None);
+    }
+
     #[test]
     fn it_works() -> Result<()> {
         let input = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,
@@ -1,16 +1,20 @@
+use std::ops::RangeInclusive;
+
+use aoc_common::answer::Answer;
+use aoc_common::diagnostics::{self, ParseMode, Recovered};
 use chumsky::prelude::*;
-use itertools::Itertools;
 use miette::*;
 
+use crate::count;
+
 /// Checks if an ID consists of a digit sequence repeated at least twice.
 /// # Examples:
-/// ```no_run
+/// ```text
 /// 11 (1 repeated 2 times) -> Valid
 /// 123123 (123 repeated 2 times) -> Valid
 /// 121212 (12 repeated 3 times) -> Valid
 /// ```
-#[allow(dead_code)]
-fn is_invalid_id(n: u64) -> bool {
+pub fn is_invalid_id(n: u64) -> bool {
     let s = n.to_string();
     let len = s.len();
     let bytes = s.as_bytes();
@@ -19,7 +23,7 @@ fn is_invalid_id(n: u64) -> bool {
     // from 1 up to half the length of the string.
     for pattern_len in 1..=len / 2 {
         // The total length must be divisible by the pattern length to be a perfect repetition
-        if len % pattern_len == 0 {
+        if len.is_multiple_of(pattern_len) {
             let pattern = &bytes[0..pattern_len];
 
             // Check if every chunk of size pattern_len matches the pattern
@@ -36,7 +40,7 @@ fn is_invalid_id(n: u64) -> bool {
 
 /// Checks if an ID consists of a digit sequence repeated at least twice.
 /// # Examples:
-/// ```no_run
+/// ```text
 /// 11 (1 repeated 2 times) -> Valid
 /// 123123 (123 repeated 2 times) -> Valid
 /// 121212 (12 repeated 3 times) -> Valid
@@ -46,12 +50,12 @@ fn is_invalid_id(n: u64) -> bool {
 /// This optimized version avoids string manipulation.
 /// Hyperfine bechmark results
 ///
-/// ```no_run
+/// ```text
 /// Time (mean ± σ):     155.9 ms ±   2.9 ms    [User: 144.0 ms, System: 29.4 ms]
 /// Range (min … max):   152.1 ms … 162.1 ms    18 runs
 /// ```
 /// vs the [original][is_invalid_id] implementation:
-/// ```no_run
+/// ```text
 /// Time (mean ± σ):     214.0 ms ±   2.4 ms    [User: 211.8 ms, System: 26.1 ms]
 /// Range (min … max):   211.8 ms … 220.4 ms    14 runs
 /// ```
@@ -68,7 +72,7 @@ pub fn is_invalid_id_optimized(n: u64) -> bool {
     // We only need to check lengths up to half the total digits
     for pattern_len in 1..=(num_digits / 2) {
         // A pattern can only be valid if it divides the total length evenly
-        if num_digits % pattern_len != 0 {
+        if !num_digits.is_multiple_of(pattern_len) {
             continue;
         }
 
@@ -89,7 +93,7 @@ pub fn is_invalid_id_optimized(n: u64) -> bool {
 
         // 4. Verification
         // If n is perfectly divisible by the mask, it is a repetition.
-        if n % mask == 0 {
+        if n.is_multiple_of(mask) {
             return true;
         }
     }
@@ -97,36 +101,44 @@ pub fn is_invalid_id_optimized(n: u64) -> bool {
     false
 }
 
+/// Yields the invalid IDs in `range` in ascending order, checking each ID only
+/// as it is pulled.
+pub fn invalid_ids_in(range: RangeInclusive<u64>) -> impl Iterator<Item = u64> {
+    range.filter(|&id| is_invalid_id(id))
+}
+
 /// Parses a list of ranges "min-max" separated by commas.
 fn parser<'a>() -> impl Parser<'a, &'a str, Vec<(u64, u64)>, extra::Err<Rich<'a, char>>> {
-    let range = text::int(10)
-        .from_str::<u64>()
-        .unwrapped()
+    let range = diagnostics::number::<u64>()
         .then_ignore(just('-'))
-        .then(text::int(10).from_str::<u64>().unwrapped())
+        .then(diagnostics::number::<u64>())
         .padded(); // Handles whitespace around tokens
 
-    range.separated_by(just(',')).allow_trailing().collect()
+    diagnostics::skip_malformed(range, ",")
+        .separated_by(just(','))
+        .allow_trailing()
+        .collect::<Vec<_>>()
+        .map(|ranges| ranges.into_iter().flatten().collect())
+}
+
+/// Parses the ranges; [`ParseMode::Lenient`] skips malformed ones and returns
+/// them as warnings.
+pub fn parse_with(input: &str, mode: ParseMode) -> Result<Recovered<Vec<(u64, u64)>>> {
+    Ok(diagnostics::parse_with(parser(), "input", input, mode)?)
 }
 
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let ranges = parser()
-        .parse(input)
-        .into_result()
-        .map_err(|e| miette!("Parse failed: {:?}", e))?;
-
-    let sum: u64 = ranges
-        .into_iter()
-        // Flatten ranges into a single stream of IDs
-        .flat_map(|(start, end)| start..=end)
-        // Check the repeating pattern condition
-        .filter(|&id| is_invalid_id(id))
-        // Ensure unique IDs if ranges overlap
-        .unique()
-        .sum();
-
-    Ok(sum.to_string())
+    Ok(solve(&parse_with(input, ParseMode::Strict)?.value)?.to_string())
+}
+
+/// Sums the IDs made of some digits repeated at least twice.
+#[tracing::instrument(skip(ranges))]
+pub fn solve(ranges: &[(u64, u64)]) -> Result<Answer> {
+    // Overlapping ranges are merged, so every ID is counted once.
+    let sum = count::sum_in_ranges(ranges, |repeats| repeats >= 2);
+
+    Ok(sum.into())
 }
 
 #[cfg(test)]
@@ -174,6 +186,12 @@ mod tests {
         assert!(!is_invalid_id_optimized(12123));
     }
 
+    #[test]
+    fn invalid_ids_are_yielded_in_order() {
+        assert_eq!(invalid_ids_in(95..=115).collect::<Vec<_>>(), vec![99, 111]);
+        assert_eq!(invalid_ids_in(12..=21).next(), None);
+    }
+
     #[test]
     fn it_works() -> Result<()> {
         let input = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,
@@ -182,4 +200,14 @@ mod tests {
         assert_eq!("4174379265", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn lenient_parses_skip_bad_ranges() -> Result<()> {
+        let input = "11-22,95-,998-1012\n";
+        let parsed = parse_with(input, ParseMode::Lenient)?;
+        assert_eq!(parsed.value, [(11, 22), (998, 1012)]);
+        assert_eq!(parsed.warnings.len(), 1);
+        assert!(process(input).is_err());
+        Ok(())
+    }
 }
@@ -1,2 +1,31 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+
 pub mod part1;
 pub mod part2;
+
+/// Both parts pick batteries from the same banks.
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Parsed = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        part1::parse(input)
+    }
+
+    fn part1(banks: &Self::Parsed) -> Result<Answer> {
+        part1::solve(banks)
+    }
+
+    fn part2(banks: &Self::Parsed) -> Result<Answer> {
+        part2::solve(banks)
+    }
+
+    fn items(banks: &Self::Parsed) -> Option<usize> {
+        Some(banks.len())
+    }
+}
+
+aoc_common::solution!(Day3);
@@ -1,3 +1,5 @@
+use aoc_common::answer::Answer;
+use aoc_common::diagnostics;
 use chumsky::prelude::*;
 use miette::*;
 
@@ -40,16 +42,23 @@ fn parser<'a>() -> impl Parser<'a, &'a str, Vec<&'a str>, extra::Err<Rich<'a, ch
         .collect()
 }
 
+/// Parses one bank of battery joltages per line.
+pub fn parse(input: &str) -> Result<Vec<String>> {
+    let banks = diagnostics::parse(parser(), "input", input)?;
+    Ok(banks.into_iter().map(str::to_owned).collect())
+}
+
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let banks = parser()
-        .parse(input)
-        .into_result()
-        .map_err(|e| miette!("Parse failed: {:?}", e))?;
+    Ok(solve(&parse(input)?)?.to_string())
+}
 
-    let total_joltage: u32 = banks.into_iter().map(max_joltage).sum();
+/// Sums the largest joltage of every bank with two batteries turned on.
+#[tracing::instrument(skip(banks))]
+pub fn solve(banks: &[String]) -> Result<Answer> {
+    let total_joltage: u32 = banks.iter().map(|bank| max_joltage(bank)).sum();
 
-    Ok(total_joltage.to_string())
+    Ok(total_joltage.into())
 }
 
 #[cfg(test)]
@@ -1,71 +1,163 @@
-use chumsky::prelude::*;
+use aoc_common::answer::Answer;
+use aoc_common::par::prelude::*;
 use miette::*;
 
-/// Finds the largest integer that can be formed by keeping exactly `k` digits
-/// from the input string `s` while preserving their relative order.
-fn find_max_subsequence(s: &str, k: usize) -> u64 {
-    let digits = s.as_bytes();
+use crate::part1::parse;
+
+/// Which end of the ordering [`subsequence`] picks from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Extremum {
+    Max,
+    Min,
+}
+
+/// The digits kept by [`subsequence`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selection {
+    /// The number the kept digits spell.
+    pub value: u64,
+    /// Where the kept digits are in the bank, in ascending order.
+    pub indices: Vec<usize>,
+}
+
+/// Finds the largest or smallest integer that can be formed by keeping
+/// exactly `k` digits of `bank` while preserving their relative order, or
+/// `None` if the bank has fewer than `k` digits.
+///
+/// Ties go to the earliest digits.
+pub fn subsequence(bank: &str, k: usize, extremum: Extremum) -> Option<Selection> {
+    let digits = bank.as_bytes();
     let n = digits.len();
 
     if n < k {
-        return 0;
+        return None;
     }
 
+    // A kept digit is dropped for a later one that beats it, as long as
+    // enough digits remain to still keep `k`.
+    let beats = |next: u8, kept: u8| match extremum {
+        Extremum::Max => next > kept,
+        Extremum::Min => next < kept,
+    };
     let to_remove = n - k;
-    let mut stack: Vec<u8> = Vec::with_capacity(k);
+    let mut stack: Vec<usize> = Vec::with_capacity(n);
     let mut removed_count = 0;
 
-    for &digit in digits {
-        while removed_count < to_remove && !stack.is_empty() && *stack.last().unwrap() < digit {
+    for (i, &digit) in digits.iter().enumerate() {
+        while removed_count < to_remove
+            && stack.last().is_some_and(|&top| beats(digit, digits[top]))
+        {
             stack.pop();
             removed_count += 1;
         }
-        stack.push(digit);
+        stack.push(i);
     }
 
     stack.truncate(k);
 
     // '0' in ASCII is 48. So we subtract b'0' to get the integer value 0-9.
-    stack.into_iter().fold(0u64, |acc, digit_byte| {
-        acc * 10 + (digit_byte - b'0') as u64
+    let value = stack
+        .iter()
+        .fold(0u64, |acc, &i| acc * 10 + (digits[i] - b'0') as u64);
+    Some(Selection {
+        value,
+        indices: stack,
     })
 }
 
-fn parser<'a>() -> impl Parser<'a, &'a str, Vec<&'a str>, extra::Err<Rich<'a, char>>> {
-    text::digits(10)
-        .to_slice()
-        .separated_by(text::newline())
-        .allow_trailing()
-        .collect()
-}
+/// Batteries the puzzle turns on in every bank.
+const BATTERIES: usize = 12;
 
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let banks = parser()
-        .parse(input)
-        .into_result()
-        .map_err(|e| miette!("Parse failed: {:?}", e))?;
+    process_with_k(input, BATTERIES)
+}
 
-    let k = 12;
+/// Like [`process`], but turning on `k` batteries per bank.
+#[tracing::instrument]
+pub fn process_with_k(input: &str, k: usize) -> Result<String> {
+    Ok(solve_with_k(&parse(input)?, k)?.to_string())
+}
+
+/// Sums the largest joltage of every bank with 12 batteries turned on.
+#[tracing::instrument(skip(banks))]
+pub fn solve(banks: &[String]) -> Result<Answer> {
+    solve_with_k(banks, BATTERIES)
+}
+
+/// Sums the largest joltage of every bank with `k` batteries turned on;
+/// banks with fewer than `k` batteries add nothing.
+#[tracing::instrument(skip(banks))]
+pub fn solve_with_k(banks: &[String], k: usize) -> Result<Answer> {
+    // u64::MAX has 20 digits, so any 19 fit.
+    ensure!(k <= 19, "Joltages of {} digits do not fit in a u64", k);
 
-    let total_joltage: u64 = banks
-        .into_iter()
-        .map(|bank| find_max_subsequence(bank, k))
+    // Each bank is independent, so the greedy selection parallelizes trivially.
+    // A single 19-digit joltage fits a u64, but a handful of them summed
+    // does not.
+    let total_joltage: u128 = banks
+        .par_iter()
+        .map(|bank| subsequence(bank, k, Extremum::Max).map_or(0, |s| s.value as u128))
         .sum();
 
-    Ok(total_joltage.to_string())
+    Ok(total_joltage.into())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn max(bank: &str, k: usize) -> u64 {
+        subsequence(bank, k, Extremum::Max).unwrap().value
+    }
+
     #[test]
     fn test_find_max_subsequence() {
-        assert_eq!(find_max_subsequence("987654321111111", 12), 987654321111);
-        assert_eq!(find_max_subsequence("811111111111119", 12), 811111111119);
-        assert_eq!(find_max_subsequence("234234234234278", 12), 434234234278);
-        assert_eq!(find_max_subsequence("818181911112111", 12), 888911112111);
+        assert_eq!(max("987654321111111", 12), 987654321111);
+        assert_eq!(max("811111111111119", 12), 811111111119);
+        assert_eq!(max("234234234234278", 12), 434234234278);
+        assert_eq!(max("818181911112111", 12), 888911112111);
+    }
+
+    #[test]
+    fn min_subsequence() {
+        let selection = subsequence("818181911112111", 5, Extremum::Min).unwrap();
+        assert_eq!(selection.value, 11111);
+        assert_eq!(selection.indices, [1, 3, 5, 7, 8]);
+        assert_eq!(subsequence("4302", 2, Extremum::Min).unwrap().value, 2);
+        assert_eq!(subsequence("4302", 4, Extremum::Min).unwrap().value, 4302);
+        assert_eq!(subsequence("43", 3, Extremum::Min), None);
+    }
+
+    #[test]
+    fn indices_spell_the_value() {
+        for extremum in [Extremum::Max, Extremum::Min] {
+            for bank in ["987654321111111", "234234234234278", "9081726354"] {
+                for k in 0..=bank.len() {
+                    let Selection { value, indices } = subsequence(bank, k, extremum).unwrap();
+                    assert_eq!(indices.len(), k);
+                    assert!(indices.windows(2).all(|pair| pair[0] < pair[1]));
+                    let spelled = indices.iter().map(|&i| &bank[i..=i]).collect::<String>();
+                    assert_eq!(spelled.parse::<u64>().unwrap_or(0), value);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn any_k() -> Result<()> {
+        let input = "987654321111111\n811111111111119\n234234234234278\n818181911112111";
+        assert_eq!(process_with_k(input, 2)?, crate::part1::process(input)?);
+        assert_eq!(process_with_k(input, 12)?, process(input)?);
+        assert!(process_with_k(input, 20).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn long_joltages_sum_past_u64() -> Result<()> {
+        let input = ["9999999999999999999"; 4].join("\n");
+        assert_eq!(process_with_k(&input, 19)?, "39999999999999999996");
+        Ok(())
     }
 
     #[test]
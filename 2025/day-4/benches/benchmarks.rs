@@ -1,15 +1,33 @@
 use aoc2025_day_4::*;
 
 fn main() {
-    divan::main();
+    // Without a stored input there is nothing to benchmark.
+    if aoc_runtime::bench_input!().is_some() {
+        divan::main();
+    }
+}
+
+/// The stored input, which `main` has checked is there.
+fn input() -> &'static str {
+    aoc_runtime::bench_input!().unwrap()
 }
 
 #[divan::bench]
 fn part1() {
-    part1::process(divan::black_box(include_str!("../input1.txt",))).unwrap();
+    part1::process(divan::black_box(input())).unwrap();
+}
+
+#[divan::bench]
+fn part1_bits() {
+    part1_bits::process(divan::black_box(input())).unwrap();
 }
 
 #[divan::bench]
 fn part2() {
-    part2::process(divan::black_box(include_str!("../input2.txt",))).unwrap();
+    part2::process(divan::black_box(input())).unwrap();
+}
+
+#[divan::bench]
+fn part2_bits() {
+    part2_bits::process(divan::black_box(input())).unwrap();
 }
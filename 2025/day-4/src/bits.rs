@@ -0,0 +1,160 @@
+//! The diagram as bitboards, for counting the neighbors of 64 cells at once.
+//!
+//! Each row is a run of `u64` words with bit `x % 64` of word `x / 64` set
+//! for a roll at column `x`. Shifting a row one bit left or right lines every
+//! cell up with its west or east neighbor, so the eight neighbors of a whole
+//! word are eight words taken from the row above, the row itself and the row
+//! below; adding them bitwise gives every cell's count in a few operations.
+
+use aoc_common::grid::Grid;
+use miette::*;
+
+use crate::diagram::Diagram;
+
+/// Bits in a word.
+const WORD: usize = u64::BITS as usize;
+
+/// The paper rolls of a diagram, one bit per cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    /// Words per row.
+    stride: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// Packs `grid`, where `true` is a paper roll.
+    pub fn new(grid: &Grid<bool>) -> Self {
+        let stride = grid.width().div_ceil(WORD);
+        let mut words = vec![0; stride * grid.height()];
+        for (y, row) in grid.rows().enumerate() {
+            for (x, _) in row.iter().enumerate().filter(|(_, &roll)| roll) {
+                words[y * stride + x / WORD] |= 1 << (x % WORD);
+            }
+        }
+        Self {
+            width: grid.width(),
+            height: grid.height(),
+            stride,
+            words,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The words of row `y`.
+    pub fn row(&self, y: usize) -> &[u64] {
+        &self.words[y * self.stride..(y + 1) * self.stride]
+    }
+
+    /// Number of rolls.
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// The rolls in word `w` of row `y` with fewer than 4 paper neighbors.
+    pub fn removable(&self, y: usize, w: usize) -> u64 {
+        let above = y.checked_sub(1).map(|y| self.row(y));
+        let below = (y + 1 < self.height).then(|| self.row(y + 1));
+        let row = self.row(y);
+
+        let mut count = Saturating::default();
+        for neighbors in [above, below].into_iter().flatten() {
+            count.add(west(neighbors, w));
+            count.add(neighbors[w]);
+            count.add(east(neighbors, w));
+        }
+        count.add(west(row, w));
+        count.add(east(row, w));
+        row[w] & !count.four_or_more
+    }
+
+    /// Removes the rolls set in `mask` from word `w` of row `y`.
+    pub fn remove(&mut self, y: usize, w: usize, mask: u64) {
+        self.words[y * self.stride + w] &= !mask;
+    }
+}
+
+/// Bit `x` set when column `x - 1` of `row` is a roll.
+fn west(row: &[u64], w: usize) -> u64 {
+    let carry = match w {
+        0 => 0,
+        _ => row[w - 1] >> (WORD - 1),
+    };
+    (row[w] << 1) | carry
+}
+
+/// Bit `x` set when column `x + 1` of `row` is a roll.
+///
+/// Bits past the last column are never set, so nothing comes in from the
+/// right of the row.
+fn east(row: &[u64], w: usize) -> u64 {
+    let carry = row.get(w + 1).map_or(0, |next| next << (WORD - 1));
+    (row[w] >> 1) | carry
+}
+
+/// 64 counters that add one-bit inputs and remember whether they reached 4.
+///
+/// `ones` and `twos` count modulo 4; the carry out of `twos` only happens on
+/// the way from 3 to 4, so it latches `four_or_more`.
+#[derive(Debug, Default)]
+struct Saturating {
+    ones: u64,
+    twos: u64,
+    four_or_more: u64,
+}
+
+impl Saturating {
+    fn add(&mut self, input: u64) {
+        let carry = self.ones & input;
+        self.ones ^= input;
+        self.four_or_more |= self.twos & carry;
+        self.twos ^= carry;
+    }
+}
+
+/// Parses the diagram straight into a [`BitGrid`].
+pub fn parse(input: &str) -> Result<BitGrid> {
+    let Diagram(grid) = input.parse()?;
+    Ok(BitGrid::new(&grid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_across_word_boundaries() -> Result<()> {
+        // A 3x4 block of rolls straddling the first word boundary.
+        let line = |cols: std::ops::RangeInclusive<usize>| {
+            (0..130)
+                .map(|x| if cols.contains(&x) { '@' } else { '.' })
+                .collect::<String>()
+        };
+        let input = [line(62..=65), line(62..=65), line(62..=65), line(0..=0)].join("\n");
+        let bits = parse(&input)?;
+        assert_eq!(bits.count(), 13);
+
+        // Corners have three neighbors; every other roll of the block has
+        // at least five. The lone roll on the last row has none.
+        let removable = (0..bits.height())
+            .flat_map(|y| (0..2).map(move |w| (y, w)))
+            .map(|(y, w)| bits.removable(y, w))
+            .collect::<Vec<_>>();
+        assert_eq!(removable[0], 1 << 62);
+        assert_eq!(removable[1], 1 << 1);
+        assert_eq!(removable[2] | removable[3], 0);
+        assert_eq!(removable[4], 1 << 62);
+        assert_eq!(removable[5], 1 << 1);
+        assert_eq!(removable[6], 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use aoc_common::diagnostics::ParseError;
+use aoc_common::grid::Grid;
+use miette::*;
+use std::fmt;
+use std::str::FromStr;
+
+/// The map of the paper rolls, `@` for a roll and `.` for empty floor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagram(pub Grid<bool>);
+
+impl FromStr for Diagram {
+    type Err = Report;
+
+    /// Reads one row per line, failing on anything but `@` and `.`.
+    fn from_str(input: &str) -> Result<Self> {
+        let _phase = tracing::info_span!("parse").entered();
+        let stray = input
+            .char_indices()
+            .find(|&(_, c)| !matches!(c, '@' | '.' | '\n' | '\r'));
+        if let Some((offset, c)) = stray {
+            let label = LabeledSpan::new_with_span(
+                Some(format!("expected '@' or '.', found {c:?}")),
+                offset..offset + c.len_utf8(),
+            );
+            return Err(ParseError::from_labels("input", input, vec![label]).into());
+        }
+        Ok(Diagram(Grid::from_chars(input, |c| c == '@')?))
+    }
+}
+
+impl fmt::Display for Diagram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0.render(|&roll| if roll { '@' } else { '.' }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_display() -> Result<()> {
+        let diagram = "..@@.\n@.@.@\n".parse::<Diagram>()?;
+        assert_eq!(diagram.to_string(), "..@@.\n@.@.@\n");
+        assert_eq!(diagram.to_string().parse::<Diagram>()?, diagram);
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_stray_characters() {
+        let report = "..@@.\n@x@.@\n".parse::<Diagram>().unwrap_err();
+        let error = report.downcast_ref::<ParseError>().unwrap();
+        let label = &error.labels()[0];
+        assert_eq!((label.offset(), label.len()), (7, 1));
+    }
+}
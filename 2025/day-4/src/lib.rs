@@ -1,2 +1,34 @@
+use aoc_common::answer::Answer;
+use aoc_common::grid::Grid;
+use aoc_common::solution::Solution;
+use diagram::Diagram;
+use miette::Result;
+
+pub mod bits;
+pub mod diagram;
 pub mod part1;
+pub mod part1_bits;
 pub mod part2;
+pub mod part2_bits;
+
+/// Both parts work on the same map of paper rolls.
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Parsed = Grid<bool>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        let Diagram(grid) = input.parse()?;
+        Ok(grid)
+    }
+
+    fn part1(grid: &Self::Parsed) -> Result<Answer> {
+        part1::solve(grid)
+    }
+
+    fn part2(grid: &Self::Parsed) -> Result<Answer> {
+        part2::solve(grid)
+    }
+}
+
+aoc_common::solution!(Day4);
@@ -1,102 +1,41 @@
-use chumsky::prelude::*;
+use crate::diagram::Diagram;
+use aoc_common::answer::Answer;
+use aoc_common::grid::Grid;
 use miette::*;
 
-struct Grid {
-    width: usize,
-    height: usize,
-    // true = '@' (paper), false = '.' (empty)
-    cells: Vec<bool>,
-}
-
-impl Grid {
-    /// Returns the value at (x, y), or false if out of bounds.
-    fn get(&self, x: isize, y: isize) -> bool {
-        if x < 0 || y < 0 {
-            return false;
-        }
-        let x = x as usize;
-        let y = y as usize;
-        // Bounds check ensures we don't access indices beyond the grid logic,
-        // but we must ensure cells.len() == width * height for this to be safe.
-        if x >= self.width || y >= self.height {
-            return false;
-        }
-        self.cells[y * self.width + x]
-    }
-
-    /// Counts how many neighbors (including diagonals) contain paper.
-    fn count_neighbors(&self, x: usize, y: usize) -> usize {
-        let x = x as isize;
-        let y = y as isize;
-        let offsets = [
-            (-1, -1),
-            (0, -1),
-            (1, -1),
-            (-1, 0),
-            (1, 0),
-            (-1, 1),
-            (0, 1),
-            (1, 1),
-        ];
-
-        offsets
-            .iter()
-            .filter(|&&(dx, dy)| self.get(x + dx, y + dy))
-            .count()
-    }
-}
-
-/// Parses the grid of characters into a Grid struct.
-fn parser<'a>() -> impl Parser<'a, &'a str, Grid, extra::Err<Rich<'a, char>>> {
-    let cell = just('@').to(true).or(just('.').to(false));
-
-    cell.repeated()
-        .collect::<Vec<_>>()
-        .separated_by(text::newline())
-        .allow_trailing()
-        .collect::<Vec<_>>()
-        .map(|rows| {
-            // Filter out empty rows to prevent "ragged" grids caused by trailing newlines
-            let rows: Vec<_> = rows.into_iter().filter(|r| !r.is_empty()).collect();
-
-            let height = rows.len();
-            let width = rows.first().map(|r| r.len()).unwrap_or(0);
-            let cells = rows.into_iter().flatten().collect();
-
-            Grid {
-                width,
-                height,
-                cells,
-            }
-        })
+/// Counts how many neighbors (including diagonals) contain paper.
+fn count_neighbors(grid: &Grid<bool>, x: usize, y: usize) -> usize {
+    grid.neighbors8(x, y).filter(|&cell| grid[cell]).count()
 }
 
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let grid = parser()
-        .parse(input)
-        .into_result()
-        .map_err(|e| miette!("Parse failed: {:?}", e))?;
+    // true = '@' (paper), false = '.' (empty)
+    let Diagram(grid) = input.parse()?;
+    Ok(solve(&grid)?.to_string())
+}
 
+/// Counts the rolls a forklift can reach, those with fewer than four
+/// neighboring rolls.
+#[tracing::instrument(skip(grid))]
+pub fn solve(grid: &Grid<bool>) -> Result<Answer> {
     let mut accessible_count = 0;
 
-    for y in 0..grid.height {
-        for x in 0..grid.width {
+    for y in 0..grid.height() {
+        for x in 0..grid.width() {
             // First check if there is actually a roll here.
-            // Direct access is safe here because we loop strictly within bounds
-            // and we ensured the grid is built correctly.
-            if !grid.cells[y * grid.width + x] {
+            if !grid[(x, y)] {
                 continue;
             }
 
             // Check neighbor condition
-            if grid.count_neighbors(x, y) < 4 {
+            if count_neighbors(grid, x, y) < 4 {
                 accessible_count += 1;
             }
         }
     }
 
-    Ok(accessible_count.to_string())
+    Ok(accessible_count.into())
 }
 
 #[cfg(test)]
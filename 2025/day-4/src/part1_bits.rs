@@ -0,0 +1,39 @@
+//! Part 1 on a [`BitGrid`], 64 cells at a time.
+
+use miette::*;
+
+use crate::bits::{self, BitGrid};
+
+/// Counts the rolls with fewer than 4 paper neighbors.
+pub fn accessible(grid: &BitGrid) -> usize {
+    (0..grid.height())
+        .flat_map(|y| (0..grid.row(y).len()).map(move |w| (y, w)))
+        .map(|(y, w)| grid.removable(y, w).count_ones() as usize)
+        .sum()
+}
+
+#[tracing::instrument]
+pub fn process(input: &str) -> Result<String> {
+    Ok(accessible(&bits::parse(input)?).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() -> Result<()> {
+        let input = "..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.";
+        assert_eq!("13", process(input)?);
+        Ok(())
+    }
+}
@@ -1,122 +1,164 @@
-use chumsky::prelude::*;
+use crate::diagram::Diagram;
+use aoc_common::answer::Answer;
+use aoc_common::grid::{FrameSink, Grid};
 use miette::*;
 
-struct Grid {
-    width: usize,
-    height: usize,
-    // true = '@' (paper), false = '.' (empty)
-    cells: Vec<bool>,
+/// Rolls with fewer paper neighbors than this can be removed.
+const THRESHOLD: u8 = 4;
+
+/// Counts how many neighbors (including diagonals) contain paper.
+fn count_neighbors(grid: &Grid<bool>, x: usize, y: usize) -> usize {
+    grid.neighbors8(x, y).filter(|&cell| grid[cell]).count()
 }
 
-impl Grid {
-    /// Returns the value at (x, y), or false if out of bounds.
-    fn get(&self, x: isize, y: isize) -> bool {
-        if x < 0 || y < 0 {
-            return false;
-        }
-        let x = x as usize;
-        let y = y as usize;
-        if x >= self.width || y >= self.height {
-            return false;
-        }
-        self.cells[y * self.width + x]
-    }
+/// The paper neighbors of every cell, counted once up front and then kept
+/// current as rolls are removed.
+fn neighbor_counts(grid: &Grid<bool>) -> Grid<u8> {
+    Grid::from_fn(grid.width(), grid.height(), |x, y| {
+        count_neighbors(grid, x, y) as u8
+    })
+}
 
-    /// Counts how many neighbors (including diagonals) contain paper.
-    fn count_neighbors(&self, x: usize, y: usize) -> usize {
-        let x = x as isize;
-        let y = y as isize;
-        let offsets = [
-            (-1, -1),
-            (0, -1),
-            (1, -1),
-            (-1, 0),
-            (1, 0),
-            (-1, 1),
-            (0, 1),
-            (1, 1),
-        ];
-
-        // We manually count to avoid overhead of iterator chains in the hot loop
-        let mut count = 0;
-        for (dx, dy) in offsets {
-            if self.get(x + dx, y + dy) {
-                count += 1;
-            }
+/// The rolls that can be removed right away, in row-major order.
+fn removable(grid: &Grid<bool>, counts: &Grid<u8>) -> Vec<(usize, usize)> {
+    (0..grid.height())
+        .flat_map(|y| (0..grid.width()).map(move |x| (x, y)))
+        .filter(|&cell| grid[cell] && counts[cell] < THRESHOLD)
+        .collect()
+}
+
+/// Removes the roll at `cell` and hands `exposed` every roll that it leaves
+/// with too few neighbors for the first time.
+fn remove(
+    grid: &mut Grid<bool>,
+    counts: &mut Grid<u8>,
+    (x, y): (usize, usize),
+    mut exposed: impl FnMut((usize, usize)),
+) {
+    grid[(x, y)] = false;
+    for neighbor in grid.neighbors8(x, y) {
+        counts[neighbor] -= 1;
+        if grid[neighbor] && counts[neighbor] == THRESHOLD - 1 {
+            exposed(neighbor);
         }
-        count
     }
 }
 
-/// Parses the grid of characters into a Grid struct.
-fn parser<'a>() -> impl Parser<'a, &'a str, Grid, extra::Err<Rich<'a, char>>> {
-    let cell = just('@').to(true).or(just('.').to(false));
-
-    cell.repeated()
-        .collect::<Vec<_>>()
-        .separated_by(text::newline())
-        .allow_trailing()
-        .collect::<Vec<_>>()
-        .map(|rows| {
-            // Filter out empty rows (handles trailing newline at EOF)
-            let rows: Vec<_> = rows.into_iter().filter(|r| !r.is_empty()).collect();
-
-            let height = rows.len();
-            let width = rows.first().map(|r| r.len()).unwrap_or(0);
-            let cells = rows.into_iter().flatten().collect();
-
-            Grid {
-                width,
-                height,
-                cells,
-            }
-        })
+/// Removes rolls one at a time until every roll left has at least 4 paper
+/// neighbors, and returns how many were removed.
+///
+/// Removing a roll only ever takes neighbors away, so the rolls left do not
+/// depend on the order; this is the same total as running [`Removals`] to
+/// the end. Every roll is queued at most once, when it first has too few
+/// neighbors, so the peel is linear in the cells.
+pub fn peel(mut grid: Grid<bool>) -> usize {
+    let mut counts = neighbor_counts(&grid);
+    let mut queue = removable(&grid, &counts);
+    let mut removed = 0;
+    while let Some(cell) = queue.pop() {
+        remove(&mut grid, &mut counts, cell, |exposed| queue.push(exposed));
+        removed += 1;
+    }
+    removed
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> Result<String> {
-    let mut grid = parser()
-        .parse(input)
-        .into_result()
-        .map_err(|e| miette!("Parse failed: {:?}", e))?;
+/// One round of removals: every roll with fewer than 4 neighbors at the
+/// start of the round goes at once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generation {
+    /// The `(x, y)` of every roll removed this round, in row-major order.
+    pub removed: Vec<(usize, usize)>,
+    pub removed_count: usize,
+}
 
-    let mut total_removed = 0;
+/// The removal simulation, one [`Generation`] per round until a round
+/// removes nothing.
+///
+/// Only the neighbors of the rolls removed in a round can become removable
+/// in the next, so rounds look at those instead of rescanning the grid.
+#[derive(Debug, Clone)]
+pub struct Removals {
+    grid: Grid<bool>,
+    counts: Grid<u8>,
+    /// The rolls the next round removes, in row-major order.
+    next: Vec<(usize, usize)>,
+}
 
-    loop {
-        let mut indices_to_remove = Vec::new();
+impl Removals {
+    /// Starts the simulation on `grid`, where `true` is a paper roll.
+    pub fn new(grid: Grid<bool>) -> Self {
+        let counts = neighbor_counts(&grid);
+        let next = removable(&grid, &counts);
+        Self { grid, counts, next }
+    }
 
-        for y in 0..grid.height {
-            for x in 0..grid.width {
-                let idx = y * grid.width + x;
+    /// The grid as of the last round.
+    pub fn grid(&self) -> &Grid<bool> {
+        &self.grid
+    }
+}
 
-                // Only check cells that currently have paper
-                if !grid.cells[idx] {
-                    continue;
-                }
+impl Iterator for Removals {
+    type Item = Generation;
 
-                // Check condition: fewer than 4 adjacent paper rolls
-                if grid.count_neighbors(x, y) < 4 {
-                    indices_to_remove.push(idx);
-                }
-            }
+    fn next(&mut self) -> Option<Generation> {
+        if self.next.is_empty() {
+            return None;
         }
-
-        if indices_to_remove.is_empty() {
-            break;
+        // The whole round was decided from the counts at its start; a roll
+        // exposed by this round's removals waits for the next one.
+        let removed = std::mem::take(&mut self.next);
+        for &cell in &removed {
+            remove(&mut self.grid, &mut self.counts, cell, |exposed| {
+                self.next.push(exposed)
+            });
         }
-        total_removed += indices_to_remove.len();
+        self.next.sort_unstable_by_key(|&(x, y)| (y, x));
+        Some(Generation {
+            removed_count: removed.len(),
+            removed,
+        })
+    }
+}
 
-        for idx in indices_to_remove {
-            grid.cells[idx] = false;
-        }
+/// Runs the removal simulation to completion, recording the initial grid and
+/// the grid after every removal round into `sink`.
+///
+/// Returns the total number of removed rolls.
+#[tracing::instrument(skip(sink))]
+pub fn process_with_frames(input: &str, sink: &mut impl FrameSink<bool>) -> Result<usize> {
+    // true = '@' (paper), false = '.' (empty)
+    let Diagram(grid) = input.parse()?;
+    let mut removals = Removals::new(grid);
+
+    let mut total_removed = 0;
+    removals.grid().record_frame(sink);
+    while let Some(generation) = removals.next() {
+        total_removed += generation.removed_count;
+        removals.grid().record_frame(sink);
     }
 
-    Ok(total_removed.to_string())
+    Ok(total_removed)
+}
+
+#[tracing::instrument]
+pub fn process(input: &str) -> Result<String> {
+    let Diagram(grid) = input.parse()?;
+    Ok(solve(&grid)?.to_string())
+}
+
+/// Counts the rolls removed before none is left within reach.
+#[tracing::instrument(skip(grid))]
+pub fn solve(grid: &Grid<bool>) -> Result<Answer> {
+    Ok(peel(grid.clone()).into())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_common::grid::Recording;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
 
     #[test]
     fn it_works() -> Result<()> {
@@ -133,4 +175,73 @@ mod tests {
         assert_eq!("43", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn records_a_frame_per_round() -> Result<()> {
+        let input = "@@@
+@@@
+@@@";
+        let mut recording = Recording::new();
+        // The rounds peel the corners, then the edges, then the center.
+        assert_eq!(9, process_with_frames(input, &mut recording)?);
+
+        let frames = recording.replay().collect::<Vec<_>>();
+        let render = |grid: &Grid<bool>| grid.render(|&p| if p { '@' } else { '.' });
+        assert_eq!(frames.len(), 4);
+        assert_eq!(render(&frames[1]), ".@.\n@@@\n.@.\n");
+        assert_eq!(render(&frames[2]), "...\n.@.\n...\n");
+        assert!(frames[3].cells().iter().all(|&p| !p));
+        Ok(())
+    }
+
+    #[test]
+    fn yields_a_generation_per_round() -> Result<()> {
+        let input = "@@@
+@@@
+@@@";
+        let grid = Grid::from_chars(input, |c| c == '@')?;
+        let counts = Removals::new(grid.clone())
+            .map(|generation| generation.removed_count)
+            .collect::<Vec<_>>();
+        assert_eq!(counts, [4, 4, 1]);
+
+        let first = Removals::new(grid).next().unwrap();
+        assert_eq!(first.removed, [(0, 0), (2, 0), (0, 2), (2, 2)]);
+        Ok(())
+    }
+
+    /// The original simulation: rescan the whole grid every round.
+    fn rescan(mut grid: Grid<bool>) -> Vec<Vec<(usize, usize)>> {
+        let mut rounds = Vec::new();
+        loop {
+            let removed = (0..grid.height())
+                .flat_map(|y| (0..grid.width()).map(move |x| (x, y)))
+                .filter(|&(x, y)| grid[(x, y)] && count_neighbors(&grid, x, y) < 4)
+                .collect::<Vec<_>>();
+            if removed.is_empty() {
+                return rounds;
+            }
+            for &cell in &removed {
+                grid[cell] = false;
+            }
+            rounds.push(removed);
+        }
+    }
+
+    #[test]
+    fn matches_rescanning_the_grid() {
+        let mut rng = StdRng::seed_from_u64(4);
+        for _ in 0..200 {
+            let (width, height) = (rng.random_range(1..16), rng.random_range(1..16));
+            let density = rng.random_range(0.3..0.95);
+            let grid = Grid::from_fn(width, height, |_, _| rng.random_bool(density));
+
+            let expected = rescan(grid.clone());
+            let rounds = Removals::new(grid.clone())
+                .map(|generation| generation.removed)
+                .collect::<Vec<_>>();
+            assert_eq!(rounds, expected);
+            assert_eq!(peel(grid), expected.iter().map(Vec::len).sum::<usize>());
+        }
+    }
 }
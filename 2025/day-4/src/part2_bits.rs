@@ -0,0 +1,76 @@
+//! Part 2 on a [`BitGrid`], 64 cells at a time.
+
+use miette::*;
+
+use crate::bits::{self, BitGrid};
+
+/// Removes rolls until every roll left has at least 4 paper neighbors, and
+/// returns how many were removed.
+///
+/// Rows are swept top to bottom, removing what is removable as soon as it is
+/// found, so a sweep already sees the removals of the rows above it. The
+/// total does not depend on the order (see [`peel`](crate::part2::peel)).
+/// Only rows next to a removal can change, so later sweeps skip the rest.
+pub fn peel(mut grid: BitGrid) -> usize {
+    let before = grid.count();
+    let mut dirty = vec![true; grid.height()];
+    while dirty.iter().any(|&d| d) {
+        for y in 0..grid.height() {
+            if !std::mem::take(&mut dirty[y]) {
+                continue;
+            }
+            for w in 0..grid.row(y).len() {
+                let removable = grid.removable(y, w);
+                if removable != 0 {
+                    grid.remove(y, w, removable);
+                    dirty[y.saturating_sub(1)..(y + 2).min(grid.height())].fill(true);
+                }
+            }
+        }
+    }
+    before - grid.count()
+}
+
+#[tracing::instrument]
+pub fn process(input: &str) -> Result<String> {
+    Ok(peel(bits::parse(input)?).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::grid::Grid;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn it_works() -> Result<()> {
+        let input = "..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.";
+        assert_eq!("43", process(input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn matches_the_cell_by_cell_peel() {
+        let mut rng = StdRng::seed_from_u64(62);
+        for _ in 0..100 {
+            let (width, height) = (rng.random_range(1..150), rng.random_range(1..20));
+            let density = rng.random_range(0.4..0.95);
+            let grid = Grid::from_fn(width, height, |_, _| rng.random_bool(density));
+            assert_eq!(
+                peel(BitGrid::new(&grid)),
+                crate::part2::peel(grid.clone()),
+                "{width}x{height}"
+            );
+        }
+    }
+}
@@ -1,2 +1,32 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+use part1::Database;
+
 pub mod part1;
 pub mod part2;
+
+/// Both parts read the same database of fresh ranges.
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Parsed = Database;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        part1::parse(input)
+    }
+
+    fn part1(database: &Self::Parsed) -> Result<Answer> {
+        part1::solve(database)
+    }
+
+    fn part2(database: &Self::Parsed) -> Result<Answer> {
+        part2::solve(&database.ranges)
+    }
+
+    fn items(database: &Self::Parsed) -> Option<usize> {
+        Some(database.ranges.len())
+    }
+}
+
+aoc_common::solution!(Day5);
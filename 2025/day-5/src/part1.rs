@@ -1,3 +1,6 @@
+use aoc_common::answer::Answer;
+use aoc_common::diagnostics;
+use aoc_common::ranges::RangeSet;
 use chumsky::prelude::*;
 use miette::*;
 use std::ops::RangeInclusive;
@@ -7,20 +10,16 @@ fn parser<'a>(
     // Robust newline parser handling CRLF (\r\n) or LF (\n)
     let newline = just('\r').or_not().ignore_then(just('\n'));
 
-    let range = text::int(10)
-        .from_str()
-        .unwrapped()
+    let range = diagnostics::number()
         .then_ignore(just('-'))
-        .then(text::int(10).from_str().unwrapped())
+        .then(diagnostics::number())
         .map(|(start, end)| start..=end);
 
     // Block 1: Ranges
     let ranges = range.separated_by(newline).allow_trailing().collect();
 
     // Block 2: IDs
-    let ids = text::int(10)
-        .from_str()
-        .unwrapped()
+    let ids = diagnostics::number()
         .separated_by(newline)
         .allow_trailing()
         .collect();
@@ -32,26 +31,41 @@ fn parser<'a>(
         .padded()
 }
 
+/// The fresh ID ranges and the available IDs to check against them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Database {
+    pub ranges: Vec<RangeInclusive<u64>>,
+    pub ids: Vec<u64>,
+}
+
+/// Parses the ranges, a blank line, then one available ID per line.
+pub fn parse(input: &str) -> Result<Database> {
+    let (ranges, ids) = diagnostics::parse(parser(), "input", input)?;
+    Ok(Database { ranges, ids })
+}
+
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let (ranges, ids) = parser()
-        .parse(input)
-        .into_result()
-        .map_err(|e| miette!("Parse failed: {:?}", e))?;
-
-    // Count how many IDs fall into at least one fresh range
-    let fresh_count = ids
-        .into_iter()
-        .filter(|id| ranges.iter().any(|r| r.contains(id)))
-        .count();
-
-    Ok(fresh_count.to_string())
+    Ok(solve(&parse(input)?)?.to_string())
+}
+
+/// Counts the available IDs that fall into at least one fresh range.
+#[tracing::instrument(skip(database))]
+pub fn solve(database: &Database) -> Result<Answer> {
+    // The merged ranges answer each lookup with a binary search.
+    let fresh = RangeSet::new(&database.ranges);
+    let fresh_count = fresh.count_contained(database.ids.iter().copied());
+
+    Ok(fresh_count.into())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
     #[test]
     fn it_works() -> Result<()> {
         let input = "3-5
@@ -68,4 +82,27 @@ mod tests {
         assert_eq!("3", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn matches_checking_every_range() {
+        let mut rng = StdRng::seed_from_u64(5);
+        for _ in 0..500 {
+            let ranges = (0..rng.random_range(0..=8))
+                .map(|_| {
+                    let start = rng.random_range(0..100);
+                    start..=rng.random_range(start..110)
+                })
+                .collect::<Vec<RangeInclusive<u64>>>();
+            let ids = (0..20)
+                .map(|_| rng.random_range(0..120))
+                .collect::<Vec<u64>>();
+
+            let naive = ids
+                .iter()
+                .filter(|id| ranges.iter().any(|r| r.contains(id)))
+                .count();
+            let fresh = RangeSet::new(&ranges);
+            assert_eq!(fresh.count_contained(ids), naive, "{ranges:?}");
+        }
+    }
 }
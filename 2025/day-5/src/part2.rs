@@ -1,3 +1,7 @@
+use aoc_common::answer::Answer;
+use aoc_common::diagnostics;
+use aoc_common::explain;
+use aoc_common::ranges::RangeSet;
 use chumsky::prelude::*;
 use miette::*;
 use std::ops::RangeInclusive;
@@ -6,11 +10,9 @@ fn parser<'a>() -> impl Parser<'a, &'a str, Vec<RangeInclusive<u64>>, extra::Err
     // Robust newline parser handling CRLF (\r\n) or LF (\n)
     let newline = just('\r').or_not().ignore_then(just('\n'));
 
-    let range = text::int(10)
-        .from_str()
-        .unwrapped()
+    let range = diagnostics::number()
         .then_ignore(just('-'))
-        .then(text::int(10).from_str().unwrapped())
+        .then(diagnostics::number())
         .map(|(start, end)| start..=end);
 
     // Block 1: Ranges
@@ -20,9 +22,7 @@ fn parser<'a>() -> impl Parser<'a, &'a str, Vec<RangeInclusive<u64>>, extra::Err
         .collect();
 
     // Block 2: IDs (we interpret and discard these to consume the full input properly)
-    let ids = text::int(10)
-        .from_str::<u64>()
-        .unwrapped()
+    let ids = diagnostics::number::<u64>()
         .separated_by(newline)
         .allow_trailing()
         .collect::<Vec<_>>();
@@ -30,54 +30,33 @@ fn parser<'a>() -> impl Parser<'a, &'a str, Vec<RangeInclusive<u64>>, extra::Err
     ranges.then_ignore(newline).then_ignore(ids).padded()
 }
 
+/// Counts the IDs covered by at least one range, merging overlapping and
+/// adjacent ranges in a single scan.
+pub fn count_fresh(ranges: &[RangeInclusive<u64>]) -> u64 {
+    let total_fresh_count = RangeSet::new(ranges).len();
+    explain!("{total_fresh_count} fresh IDs");
+    total_fresh_count
+}
+
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let mut ranges = parser()
-        .parse(input)
-        .into_result()
-        .map_err(|e| miette!("Parse failed: {:?}", e))?;
-
-    // Sort ranges by start position to enable linear merge scan
-    ranges.sort_by_key(|r| *r.start());
-
-    let mut total_fresh_count: u64 = 0;
-
-    // Iterate through sorted ranges and merge them
-    if let Some(first) = ranges.first() {
-        let mut current_start = *first.start();
-        let mut current_end = *first.end();
-
-        for r in ranges.iter().skip(1) {
-            let next_start = *r.start();
-            let next_end = *r.end();
-
-            // Check if ranges overlap or are adjacent (contiguous integers)
-            // e.g., 3-5 and 6-8 should merge into 3-8.
-            if next_start <= current_end + 1 {
-                // Merge: extend the current end if the next one goes further
-                if next_end > current_end {
-                    current_end = next_end;
-                }
-            } else {
-                // Gap detected: The current merged range is complete
-                total_fresh_count += current_end - current_start + 1;
-
-                // Start tracking the new range
-                current_start = next_start;
-                current_end = next_end;
-            }
-        }
-        // Don't forget to add the last range
-        total_fresh_count += current_end - current_start + 1;
-    }
+    let ranges = diagnostics::parse(parser(), "input", input)?;
+    Ok(solve(&ranges)?.to_string())
+}
 
-    Ok(total_fresh_count.to_string())
+/// Counts the IDs covered by at least one fresh range.
+#[tracing::instrument(skip(ranges))]
+pub fn solve(ranges: &[RangeInclusive<u64>]) -> Result<Answer> {
+    Ok(count_fresh(ranges).into())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
     #[test]
     fn it_works() -> Result<()> {
         let input = "3-5
@@ -94,4 +73,35 @@ mod tests {
         assert_eq!("14", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn adjacent_ranges_merge() {
+        assert_eq!(count_fresh(&[3..=5, 6..=8]), 6);
+        assert_eq!(count_fresh(&[6..=8, 3..=5, 10..=10]), 7);
+        assert_eq!(count_fresh(&[0..=0, 1..=1]), 2);
+        assert_eq!(count_fresh(&[]), 0);
+    }
+
+    #[test]
+    fn random_ranges_match_bitmap_oracle() {
+        const SPAN: u64 = 64;
+
+        let mut rng = StdRng::seed_from_u64(5);
+        for _ in 0..2000 {
+            let ranges = (0..rng.random_range(1..=8))
+                .map(|_| {
+                    let start = rng.random_range(0..SPAN);
+                    start..=rng.random_range(start..SPAN)
+                })
+                .collect::<Vec<_>>();
+
+            let mut fresh = [false; SPAN as usize];
+            for id in ranges.iter().cloned().flatten() {
+                fresh[id as usize] = true;
+            }
+            let expected = fresh.iter().filter(|&&f| f).count() as u64;
+
+            assert_eq!(count_fresh(&ranges), expected, "{ranges:?}");
+        }
+    }
 }
@@ -1,2 +1,31 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+use worksheet::Worksheet;
+
+pub mod ops;
 pub mod part1;
 pub mod part2;
+pub mod worksheet;
+
+/// Both parts split the same worksheet into problems, then read their
+/// numbers in different directions.
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Parsed = Worksheet;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Ok(Worksheet::new(input))
+    }
+
+    fn part1(sheet: &Self::Parsed) -> Result<Answer> {
+        part1::solve(sheet)
+    }
+
+    fn part2(sheet: &Self::Parsed) -> Result<Answer> {
+        part2::solve(sheet)
+    }
+}
+
+aoc_common::solution!(Day6);
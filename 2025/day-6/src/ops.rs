@@ -0,0 +1,145 @@
+//! The operators a worksheet's operator rows can name.
+//!
+//! Each operator is a unit struct behind the [`Op`] trait and listed in
+//! [`OPS`], so a new one only needs an entry there to be parsed and applied
+//! by both parts.
+
+use std::fmt::Debug;
+
+/// A binary operator that folds a problem's numbers left to right.
+pub trait Op: Debug + Sync {
+    /// The token naming the operator on an operator row.
+    fn symbol(&self) -> &'static str;
+
+    /// `acc` combined with the next number, or `None` when the result is not
+    /// an `i64` (overflow, division by zero).
+    fn apply(&self, acc: i64, next: i64) -> Option<i64>;
+}
+
+#[derive(Debug)]
+pub struct Add;
+
+#[derive(Debug)]
+pub struct Sub;
+
+#[derive(Debug)]
+pub struct Mul;
+
+/// Integer division, rounding toward zero.
+#[derive(Debug)]
+pub struct Div;
+
+#[derive(Debug)]
+pub struct Min;
+
+#[derive(Debug)]
+pub struct Max;
+
+impl Op for Add {
+    fn symbol(&self) -> &'static str {
+        "+"
+    }
+
+    fn apply(&self, acc: i64, next: i64) -> Option<i64> {
+        acc.checked_add(next)
+    }
+}
+
+impl Op for Sub {
+    fn symbol(&self) -> &'static str {
+        "-"
+    }
+
+    fn apply(&self, acc: i64, next: i64) -> Option<i64> {
+        acc.checked_sub(next)
+    }
+}
+
+impl Op for Mul {
+    fn symbol(&self) -> &'static str {
+        "*"
+    }
+
+    fn apply(&self, acc: i64, next: i64) -> Option<i64> {
+        acc.checked_mul(next)
+    }
+}
+
+impl Op for Div {
+    fn symbol(&self) -> &'static str {
+        "/"
+    }
+
+    fn apply(&self, acc: i64, next: i64) -> Option<i64> {
+        acc.checked_div(next)
+    }
+}
+
+impl Op for Min {
+    fn symbol(&self) -> &'static str {
+        "min"
+    }
+
+    fn apply(&self, acc: i64, next: i64) -> Option<i64> {
+        Some(acc.min(next))
+    }
+}
+
+impl Op for Max {
+    fn symbol(&self) -> &'static str {
+        "max"
+    }
+
+    fn apply(&self, acc: i64, next: i64) -> Option<i64> {
+        Some(acc.max(next))
+    }
+}
+
+/// Every operator an operator row may use.
+pub static OPS: &[&dyn Op] = &[&Add, &Sub, &Mul, &Div, &Min, &Max];
+
+/// The operator named `symbol`.
+pub fn lookup(symbol: &str) -> Option<&'static dyn Op> {
+    OPS.iter().copied().find(|op| op.symbol() == symbol)
+}
+
+/// Folds `numbers` left to right, combining the accumulator with the `i`th
+/// number after the first using the `i`th of `ops`. Once `ops` runs out the
+/// last one carries on, so a single operator applies throughout; without
+/// any the numbers are summed.
+///
+/// An empty problem is worth 0.
+pub fn reduce(numbers: &[i64], ops: &[&dyn Op]) -> Option<i64> {
+    let Some((&first, rest)) = numbers.split_first() else {
+        return Some(0);
+    };
+    rest.iter().enumerate().try_fold(first, |acc, (i, &next)| {
+        let op = ops.get(i).or(ops.last()).copied().unwrap_or(&Add);
+        op.apply(acc, next)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn looks_up_every_operator() {
+        for op in OPS {
+            assert_eq!(lookup(op.symbol()).map(Op::symbol), Some(op.symbol()));
+        }
+        assert!(lookup("%").is_none());
+    }
+
+    #[test]
+    fn applies_operators_in_sequence() {
+        assert_eq!(reduce(&[2, 3, 4], &[&Mul]), Some(24));
+        assert_eq!(reduce(&[2, 3, 4], &[&Add, &Mul]), Some(20));
+        assert_eq!(reduce(&[20, 3, 4], &[&Sub, &Div]), Some(4));
+        assert_eq!(reduce(&[5, -3, 9], &[&Min]), Some(-3));
+        assert_eq!(reduce(&[5, -3, 9], &[&Max]), Some(9));
+        assert_eq!(reduce(&[5, 6], &[]), Some(11));
+        assert_eq!(reduce(&[], &[&Mul]), Some(0));
+        assert_eq!(reduce(&[1, 0], &[&Div]), None);
+    }
+}
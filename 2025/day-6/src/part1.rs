@@ -1,125 +1,74 @@
+use aoc_common::answer::Answer;
 use chumsky::prelude::*;
-use itertools::Itertools;
 use miette::*;
 
-#[derive(Debug, Clone, Copy)]
-enum Op {
-    Add,
-    Mul,
-}
-
-#[derive(Debug)]
-struct Problem {
-    numbers: Vec<u64>,
-    op: Op,
-}
-
-impl Problem {
-    fn solve(&self) -> u64 {
-        match self.op {
-            Op::Add => self.numbers.iter().sum(),
-            Op::Mul => self.numbers.iter().product(),
-        }
-    }
-}
+use crate::worksheet::{self, Worksheet};
 
-#[derive(Clone, Debug)]
-enum Token {
-    Num(u64),
-    Op(Op),
+/// Parser for a number within a problem block.
+fn number_parser<'a>() -> impl Parser<'a, &'a str, i64, extra::Err<Rich<'a, char>>> {
+    // A leading '-' makes the number negative.
+    just('-')
+        .or_not()
+        .then(text::int(10))
+        .to_slice()
+        .try_map(|digits: &str, span| digits.parse().map_err(|e| Rich::custom(span, e)))
 }
 
-/// Parser for a single line content within a problem block.
-fn line_content_parser<'a>() -> impl Parser<'a, &'a str, Token, extra::Err<Rich<'a, char>>> {
-    choice((
-        text::int(10)
-            .from_str()
-            .unwrapped()
-            .map(Token::Num),
-        just('+').to(Token::Op(Op::Add)),
-        just('*').to(Token::Op(Op::Mul)),
-    ))
-}
-
-/// Extracts a problem from a vertical slice of the grid defined by [start_col, end_col).
-fn extract_problem(lines: &[&str], start_col: usize, end_col: usize) -> Option<Problem> {
+/// Extracts a problem's numbers from a vertical slice of the grid defined by [start_col, end_col).
+fn extract_numbers(lines: &[String], start_col: usize, end_col: usize) -> Vec<i64> {
     let mut numbers = Vec::new();
-    let mut op = None;
 
-    let parser = line_content_parser();
+    let parser = number_parser();
 
     for line in lines {
         // Handle ragged lines (lines shorter than the current column block)
         if start_col >= line.len() {
             continue;
         }
-        
+
         // Extract the substring for this column block
         let slice_end = std::cmp::min(end_col, line.len());
-        let chunk = &line[start_col..slice_end];
+        let Some(chunk) = line.get(start_col..slice_end) else {
+            continue;
+        };
         let trimmed = chunk.trim();
 
         if trimmed.is_empty() {
             continue;
         }
 
-        // Parse the trimmed chunk
-        match parser.parse(trimmed).into_result() {
-            Ok(Token::Num(n)) => numbers.push(n),
-            Ok(Token::Op(o)) => op = Some(o),
-            Err(_) => {
-                // Ignore parsing errors for empty/noise lines if any, 
-                // though problem guarantees clean input.
-            }
+        // Parse the trimmed chunk, ignoring noise; the problem guarantees
+        // clean input.
+        if let Ok(n) = parser.parse(trimmed).into_result() {
+            numbers.push(n);
         }
     }
 
-    // A valid problem must have an operator.
-    op.map(|op| Problem { numbers, op })
+    numbers
 }
 
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let lines: Vec<&str> = input.lines().collect();
-    if lines.is_empty() {
-        return Ok("0".to_string());
-    }
-
-    let width = lines.iter().map(|l| l.len()).max().unwrap_or(0);
-    let height = lines.len();
-
-    // Identify which columns are purely whitespace separators
-    let is_col_empty = |x: usize| {
-        (0..height).all(|y| {
-            let line = lines[y];
-            if x >= line.len() {
-                true // Out of bounds is treated as empty/space
-            } else {
-                line.as_bytes()[x] == b' '
-            }
-        })
-    };
+    Ok(solve(&Worksheet::new(input))?.to_string())
+}
 
+/// Sums the answers of the problems, reading each one's numbers row by row.
+#[tracing::instrument(skip(sheet))]
+pub fn solve(sheet: &Worksheet) -> Result<Answer> {
+    let build = tracing::info_span!("build").entered();
     let mut problems = Vec::new();
-
-    // Group contiguous columns by whether they are empty or not
-    // chunk_by is equivalent to group_by in itertools logic
-    for (is_empty, cols) in (0..width).chunk_by(|&x| is_col_empty(x)).into_iter() {
-        if !is_empty {
-            // This is a block of content columns
-            let cols_vec: Vec<usize> = cols.collect();
-            let start = *cols_vec.first().unwrap();
-            let end = *cols_vec.last().unwrap() + 1;
-
-            if let Some(prob) = extract_problem(&lines, start, end) {
-                problems.push(prob);
-            }
-        }
+    for block in sheet.blocks() {
+        let numbers = extract_numbers(&sheet.numbers, block.start, block.end);
+        problems.push((numbers, sheet.ops(block)?));
     }
+    drop(build);
 
-    let total: u64 = problems.iter().map(|p| p.solve()).sum();
+    let mut total = 0i64;
+    for (numbers, ops) in &problems {
+        total += worksheet::solve(numbers, ops)?;
+    }
 
-    Ok(total.to_string())
+    Ok(total.into())
 }
 
 #[cfg(test)]
@@ -135,4 +84,32 @@ mod tests {
         assert_eq!("4277556", process(input)?);
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn negative_numbers() -> Result<()> {
+        let input = "-12  3
+  4 -5
+ -2  7
+*   + ";
+        assert_eq!("101", process(input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn extended_operators() -> Result<()> {
+        // 20 - 3 - 4, min(5, -2, 8), (7 + 5) / 4 and max(1, 9, 3).
+        let input = " 20   5 7 1
+  3  -2 5 9
+  4   8 4 3
+  - min + max
+        /";
+        assert_eq!("23", process(input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_unknown_operators() {
+        assert!(process("1 2\n3 4\n+ %").is_err());
+        assert!(process("1\n0\n/").is_err());
+    }
+}
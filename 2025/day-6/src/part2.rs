@@ -1,14 +1,87 @@
+use aoc_common::answer::Answer;
+use aoc_common::par::prelude::*;
 use miette::Result;
-use rayon::prelude::*;
 
+use crate::worksheet::{self, Worksheet};
+
+/// The operators [`process_rescan`] understands.
 #[derive(Clone, Copy, Debug)]
 enum Op {
     Add,
     Mul,
 }
 
+/// Per-column state accumulated while streaming over the worksheet.
+#[derive(Clone, Copy, Debug, Default)]
+struct Column {
+    /// Digits read top to bottom, most significant first.
+    value: u64,
+    has_digits: bool,
+    /// Whether a '-' sits above the column's digits.
+    negative: bool,
+}
+
+impl Column {
+    fn signed(&self) -> i64 {
+        if self.negative {
+            -(self.value as i64)
+        } else {
+            self.value as i64
+        }
+    }
+}
+
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
+    Ok(solve(&Worksheet::new(input))?.to_string())
+}
+
+/// Solves the worksheet in a single pass over the number rows.
+///
+/// Each line is fed into per-column accumulators, so digit accumulation
+/// happens while reading, and only the per-column totals are revisited
+/// once the [`Worksheet`] has found the problems and their operators.
+#[tracing::instrument(skip(sheet))]
+pub fn solve(sheet: &Worksheet) -> Result<Answer> {
+    let build = tracing::info_span!("build").entered();
+    let width = sheet.numbers.iter().map(|line| line.len()).max();
+    let mut columns = vec![Column::default(); width.unwrap_or(0)];
+
+    for line in &sheet.numbers {
+        for (column, &byte) in columns.iter_mut().zip(line.as_bytes()) {
+            match byte {
+                b'0'..=b'9' => {
+                    column.value = column.value * 10 + (byte - b'0') as u64;
+                    column.has_digits = true;
+                }
+                b'-' if !column.has_digits => column.negative = true,
+                _ => {}
+            }
+        }
+    }
+    drop(build);
+
+    let mut grand_total = 0i64;
+    for block in sheet.blocks() {
+        let numbers = columns
+            .get(block.start.min(columns.len())..block.end.min(columns.len()))
+            .unwrap_or_default()
+            .iter()
+            .filter(|column| column.has_digits)
+            .map(Column::signed)
+            .collect::<Vec<_>>();
+        grand_total += worksheet::solve(&numbers, &sheet.ops(block)?)?;
+    }
+
+    Ok(grand_total.into())
+}
+
+/// The original implementation: classifies separator columns first, then
+/// re-scans every column of each block in parallel.
+///
+/// Kept as the reference [`process`] is checked and benchmarked against.
+#[tracing::instrument]
+pub fn process_rescan(input: &str) -> Result<String> {
     let lines: Vec<&[u8]> = input.lines().map(|l| l.as_bytes()).collect();
     if lines.is_empty() {
         return Ok("0".to_string());
@@ -47,7 +120,7 @@ pub fn process(input: &str) -> Result<String> {
     }
 
     // Solve Blocks in Parallel
-    let grand_total: u64 = blocks
+    let grand_total: i64 = blocks
         .into_par_iter()
         .map(|range| {
             let mut numbers = Vec::with_capacity(range.len());
@@ -55,8 +128,9 @@ pub fn process(input: &str) -> Result<String> {
 
             // Iterate over each column in the identified block
             for x in range {
-                let mut num = 0u64;
+                let mut num = 0i64;
                 let mut has_digits = false;
+                let mut negative = false;
 
                 // Vertical Scan: Top-to-Bottom (Most Significant Digit to Least)
                 for line in &lines {
@@ -66,8 +140,10 @@ pub fn process(input: &str) -> Result<String> {
                     let b = line[x];
 
                     if b.is_ascii_digit() {
-                        num = num * 10 + (b - b'0') as u64;
+                        num = num * 10 + (b - b'0') as i64;
                         has_digits = true;
+                    } else if b == b'-' && !has_digits {
+                        negative = true;
                     } else if b == b'+' {
                         op = Op::Add;
                     } else if b == b'*' {
@@ -76,14 +152,14 @@ pub fn process(input: &str) -> Result<String> {
                 }
 
                 if has_digits {
-                    numbers.push(num);
+                    numbers.push(if negative { -num } else { num });
                 }
             }
 
             // Reduce based on the operator found in the block
             match op {
-                Op::Add => numbers.iter().sum::<u64>(),
-                Op::Mul => numbers.iter().product::<u64>(),
+                Op::Add => numbers.iter().sum::<i64>(),
+                Op::Mul => numbers.iter().product::<i64>(),
             }
         })
         .sum();
@@ -95,6 +171,8 @@ pub fn process(input: &str) -> Result<String> {
 mod tests {
     use super::*;
 
+    use rstest::rstest;
+
     #[test]
     fn it_works() -> Result<()> {
         let input = "123 328  51 64 
@@ -104,4 +182,53 @@ mod tests {
         assert_eq!("3263827", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn negative_columns() -> Result<()> {
+        // Columns read -14, 2 | -35: (-14 * 2) + (-35).
+        let input = "-  -
+12 3
+4  5
+*  +";
+        assert_eq!("-63", process(input)?);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  ")]
+    #[case("1 2\r\n3 4\r\n+ *\r\n")]
+    #[case("12  7\n3  81\n*   +")]
+    #[case("")]
+    #[case("-  -\n12 3\n4  5\n*  +")]
+    fn single_pass_matches_rescan(#[case] input: &str) -> Result<()> {
+        assert_eq!(process_rescan(input)?, process(input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn single_pass_matches_rescan_on_a_large_sheet() -> Result<()> {
+        let mut rows = vec![String::new(); 5];
+        for problem in 0..500u64 {
+            for (row, line) in rows.iter_mut().take(4).enumerate() {
+                let value = (problem * 7919 + row as u64 * 104_729) % 9999 + 1;
+                line.push_str(&format!("{:<5}", value));
+            }
+            rows[4].push_str(if problem % 3 == 0 { "*    " } else { "+    " });
+        }
+        let input = rows.join("\n");
+        assert_eq!(process_rescan(&input)?, process(&input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn extended_operators() -> Result<()> {
+        // Columns read 12, 3 | 4, 56 | 7, 8, 9: 12 / 3, max(4, 56) and
+        // (7 - 8) * 9.
+        let input = "1  45  789
+23  6
+/  max -
+       *";
+        assert_eq!("51", process(input)?);
+        Ok(())
+    }
 }
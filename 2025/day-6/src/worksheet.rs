@@ -0,0 +1,111 @@
+//! Splits a worksheet into its problems, for both parts to read numbers from
+//! in their own direction.
+//!
+//! Operator rows are the rows below the last digit. A problem is a run of
+//! columns with a non-space on some row, numbers and operators alike, so an
+//! operator word wider than its numbers still stays in one problem.
+
+use std::ops::Range;
+
+use miette::*;
+
+use crate::ops::{self, Op};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Worksheet {
+    /// The rows above the operators, top to bottom.
+    pub numbers: Vec<String>,
+    /// The rows naming each problem's operators, top to bottom.
+    pub operators: Vec<String>,
+}
+
+impl Worksheet {
+    pub fn new(input: &str) -> Self {
+        let mut numbers = input.lines().map(str::to_owned).collect::<Vec<_>>();
+        let last_digits = numbers
+            .iter()
+            .rposition(|line| line.bytes().any(|b| b.is_ascii_digit()));
+        let operators = numbers.split_off(last_digits.map_or(0, |y| y + 1));
+        Self { numbers, operators }
+    }
+
+    fn rows(&self) -> impl Iterator<Item = &String> {
+        self.numbers.iter().chain(&self.operators)
+    }
+
+    /// The column ranges of the problems, left to right.
+    pub fn blocks(&self) -> Vec<Range<usize>> {
+        let width = self.rows().map(|line| line.len()).max().unwrap_or(0);
+        let mut occupied = vec![false; width];
+        for line in self.rows() {
+            for (x, _) in line.bytes().enumerate().filter(|&(_, b)| b != b' ') {
+                occupied[x] = true;
+            }
+        }
+
+        let mut blocks = Vec::new();
+        let mut start = None;
+        for (x, &occupied) in occupied.iter().enumerate() {
+            match (occupied, start) {
+                (true, None) => start = Some(x),
+                (false, Some(s)) => {
+                    blocks.push(s..x);
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        blocks.extend(start.map(|s| s..width));
+        blocks
+    }
+
+    /// The operators under `block`, top to bottom, skipping rows blank there.
+    pub fn ops(&self, block: Range<usize>) -> Result<Vec<&'static dyn Op>> {
+        self.operators
+            .iter()
+            .filter_map(|line| {
+                let end = block.end.min(line.len());
+                let token = line.get(block.start.min(end)..end)?.trim();
+                (!token.is_empty()).then_some(token)
+            })
+            .map(|token| ops::lookup(token).ok_or_else(|| unknown(token)))
+            .collect()
+    }
+}
+
+fn unknown(token: &str) -> Report {
+    let known = ops::OPS
+        .iter()
+        .map(|op| op.symbol())
+        .collect::<Vec<_>>()
+        .join(", ");
+    miette!("Unknown operator {token:?}; expected one of {known}")
+}
+
+/// Solves one problem, failing when it leaves the range of an `i64`.
+pub fn solve(numbers: &[i64], ops: &[&dyn Op]) -> Result<i64> {
+    ops::reduce(numbers, ops)
+        .ok_or_else(|| miette!("The problem {numbers:?} overflows or divides by zero"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_off_operator_rows() -> Result<()> {
+        let sheet = Worksheet::new("-1  20\n 3  4\nmin  +\n*    -\n");
+        assert_eq!(sheet.numbers, ["-1  20", " 3  4"]);
+        assert_eq!(sheet.operators, ["min  +", "*    -"]);
+        assert_eq!(sheet.blocks(), [0..3, 4..6]);
+
+        let symbols = |block| -> Result<Vec<_>> {
+            Ok(sheet.ops(block)?.iter().map(|op| op.symbol()).collect())
+        };
+        assert_eq!(symbols(0..3)?, ["min", "*"]);
+        assert_eq!(symbols(4..6)?, ["+", "-"]);
+
+        assert!(Worksheet::new("1\n%").ops(0..1).is_err());
+        Ok(())
+    }
+}
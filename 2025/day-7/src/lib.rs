@@ -1,2 +1,29 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+use tiles::Manifold;
+
 pub mod part1;
 pub mod part2;
+pub mod tiles;
+
+/// Both parts send beams through the same manifold.
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Parsed = Manifold;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Manifold::parse(input)
+    }
+
+    fn part1(manifold: &Self::Parsed) -> Result<Answer> {
+        part1::solve(manifold)
+    }
+
+    fn part2(manifold: &Self::Parsed) -> Result<Answer> {
+        part2::solve(manifold)
+    }
+}
+
+aoc_common::solution!(Day7);
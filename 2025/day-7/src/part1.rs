@@ -1,61 +1,55 @@
+use aoc_common::answer::Answer;
+use aoc_common::grid::{FrameSink, Grid};
 use miette::*;
 
+use crate::tiles::{Manifold, Next, Tile};
+
+/// What a cell shows in a recorded frame of the simulation.
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
-enum Tile {
+pub enum Cell {
     Empty,
-    Splitter,
+    /// Any other tile, by its glyph.
+    Tile(char),
+    /// An empty cell a beam has passed through.
+    Beam,
 }
 
-struct Grid {
-    width: usize,
-    height: usize,
-    tiles: Vec<Tile>,
-    start: (usize, usize),
+impl From<&dyn Tile> for Cell {
+    fn from(tile: &dyn Tile) -> Self {
+        match tile.glyph() {
+            '.' => Cell::Empty,
+            glyph => Cell::Tile(glyph),
+        }
+    }
 }
 
-impl Grid {
-    fn from_str(input: &str) -> Result<Self> {
-        let mut tiles = Vec::new();
-        let mut start = None;
-        let mut width = 0;
-        let mut height = 0;
-
-        for (y, line) in input.lines().enumerate() {
-            width = line.len();
-            height += 1;
-            for (x, c) in line.chars().enumerate() {
-                match c {
-                    'S' => {
-                        start = Some((x, y));
-                        tiles.push(Tile::Empty); // S behaves like empty space for physics
-                    }
-                    '^' => tiles.push(Tile::Splitter),
-                    // Treat anything else ('.') as empty
-                    _ => tiles.push(Tile::Empty),
-                }
-            }
-        }
+/// Runs the beam simulation, recording the manifold once before the beam
+/// enters and again after every row it sweeps, with visited cells marked as
+/// [`Cell::Beam`].
+///
+/// Returns the number of splits.
+#[tracing::instrument(skip(sink))]
+pub fn process_with_frames(input: &str, sink: &mut impl FrameSink<Cell>) -> Result<usize> {
+    Ok(simulate(&Manifold::parse(input)?, sink))
+}
 
-        let start = start.ok_or(miette!("No start position 'S' found in grid"))?;
+/// Like [`process_with_frames`], on an already parsed manifold.
+pub fn simulate(manifold: &Manifold, sink: &mut impl FrameSink<Cell>) -> usize {
+    let grid = &manifold.tiles;
+    let (sx, sy) = manifold.start;
 
-        Ok(Grid {
-            width,
-            height,
-            tiles,
-            start,
-        })
+    // The overlay is only built when someone is watching.
+    let mut overlay = sink
+        .is_recording()
+        .then(|| Grid::from_fn(grid.width(), grid.height(), |x, y| Cell::from(grid[(x, y)])));
+    if let Some(overlay) = &overlay {
+        overlay.record_frame(sink);
     }
-}
-
-#[tracing::instrument]
-pub fn process(input: &str) -> Result<String> {
-    let grid = Grid::from_str(input)?;
-    let (sx, sy) = grid.start;
 
     // We only need to track which columns have a beam in the current row.
     // Using a boolean vector implicitly handles beam merging.
-    let mut current_beams = vec![false; grid.width];
-    let mut next_beams = vec![false; grid.width];
+    let mut current_beams = vec![false; grid.width()];
+    let mut next_beams = vec![false; grid.width()];
 
     // Initialize the beam at S
     current_beams[sx] = true;
@@ -63,37 +57,28 @@ pub fn process(input: &str) -> Result<String> {
     let mut total_splits = 0;
 
     // Simulate row by row, starting from the source row
-    for y in sy..grid.height {
+    for y in sy..grid.height() {
         // Clear the next row buffer
         next_beams.fill(false);
 
         let mut active_beams_count = 0;
 
-        for x in 0..grid.width {
+        for x in 0..grid.width() {
             if current_beams[x] {
                 active_beams_count += 1;
-                let idx = y * grid.width + x;
 
-                match grid.tiles[idx] {
-                    Tile::Empty => {
-                        // Beam continues straight down
-                        // It will exist at column x in row y+1
-                        next_beams[x] = true;
-                    }
-                    Tile::Splitter => {
-                        // Beam hits splitter
-                        total_splits += 1;
-
-                        // Beam stops, new beams emitted left (x-1) and right (x+1)
-                        // These new beams will exist in row y+1
-                        if x > 0 {
-                            next_beams[x - 1] = true;
-                        }
-                        if x + 1 < grid.width {
-                            next_beams[x + 1] = true;
-                        }
-                    }
+                // Splitters stop the beam and emit new ones left and right,
+                // other tiles let it through or deflect it; the new beams
+                // exist in row y+1
+                let tile = grid[(x, y)];
+                if tile.splits() {
+                    total_splits += 1;
                 }
+                tile.propagate(x, &manifold.row(y), &mut |next| {
+                    if let Next::Column(nx) = next {
+                        next_beams[nx] = true;
+                    }
+                });
             }
         }
 
@@ -101,17 +86,160 @@ pub fn process(input: &str) -> Result<String> {
             break;
         }
 
+        if let Some(overlay) = &mut overlay {
+            for (x, _) in current_beams.iter().enumerate().filter(|(_, &beam)| beam) {
+                if overlay[(x, y)] == Cell::Empty {
+                    overlay[(x, y)] = Cell::Beam;
+                }
+            }
+            overlay.record_frame(sink);
+        }
+
         // Advance to the next row
         // We swap the buffers so `next_beams` becomes `current_beams` for the next iteration
         std::mem::swap(&mut current_beams, &mut next_beams);
     }
 
-    Ok(total_splits.to_string())
+    total_splits
+}
+
+/// How hard a splitter is hit over the whole simulation.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Heat {
+    /// Beams arriving at the splitter before they merge: one from straight
+    /// above and one from each neighbouring splitter in the row above.
+    pub strikes: u32,
+    /// Distinct source-to-splitter paths folded into those beams.
+    pub timelines: u128,
+}
+
+/// Simulates the beams while counting, for every splitter, how many beams
+/// strike it and how many merged timelines they carry.
+///
+/// The returned overlay has a [`Heat`] on every splitter cell, including the
+/// ones no beam reaches, and `None` everywhere else. Fails when a splitter
+/// carries more timelines than a `u128` holds.
+#[tracing::instrument]
+pub fn heat_map(input: &str) -> Result<Grid<Option<Heat>>> {
+    let manifold = Manifold::parse(input)?;
+    let grid = &manifold.tiles;
+    let (sx, sy) = manifold.start;
+
+    let mut heat = Grid::from_fn(grid.width(), grid.height(), |x, y| {
+        grid[(x, y)].splits().then(Heat::default)
+    });
+
+    // Per column of the current row: beams arriving, and the timelines they carry.
+    let mut arrivals = vec![0u32; grid.width()];
+    let mut timelines = vec![0u128; grid.width()];
+    let mut next_arrivals = vec![0u32; grid.width()];
+    let mut next_timelines = vec![0u128; grid.width()];
+    arrivals[sx] = 1;
+    timelines[sx] = 1;
+
+    for y in sy..grid.height() {
+        next_arrivals.fill(0);
+        next_timelines.fill(0);
+
+        for x in (0..grid.width()).filter(|&x| arrivals[x] > 0) {
+            if let Some(cell) = &mut heat[(x, y)] {
+                cell.strikes += arrivals[x];
+                cell.timelines = cell
+                    .timelines
+                    .checked_add(timelines[x])
+                    .ok_or_else(|| miette!("Splitter at ({x}, {y}) carries too many timelines"))?;
+            }
+            let mut overflow = false;
+            grid[(x, y)].propagate(x, &manifold.row(y), &mut |next| {
+                if let Next::Column(nx) = next {
+                    next_arrivals[nx] += 1;
+                    let sum = next_timelines[nx].checked_add(timelines[x]);
+                    overflow |= sum.is_none();
+                    next_timelines[nx] = sum.unwrap_or(u128::MAX);
+                }
+            });
+            if overflow {
+                bail!("Beams below ({x}, {y}) carry too many timelines");
+            }
+        }
+
+        std::mem::swap(&mut arrivals, &mut next_arrivals);
+        std::mem::swap(&mut timelines, &mut next_timelines);
+    }
+
+    Ok(heat)
+}
+
+#[tracing::instrument]
+pub fn process(input: &str) -> Result<String> {
+    Ok(solve(&Manifold::parse(input)?)?.to_string())
+}
+
+/// Counts how many times the beam is split on its way down.
+#[tracing::instrument(skip(manifold))]
+pub fn solve(manifold: &Manifold) -> Result<Answer> {
+    Ok(simulate(manifold, &mut ()).into())
+}
+
+/// Animates the beam simulation in the terminal at `fps` frames per second,
+/// one frame per row swept, with the beams entering the newest row
+/// highlighted.
+///
+/// Returns the number of splits, like [`process`].
+#[cfg(feature = "viz")]
+pub fn visualize(input: &str, fps: u32) -> Result<String> {
+    use crossterm::style::{PrintStyledContent, Stylize};
+    use crossterm::terminal::{Clear, ClearType};
+    use crossterm::{cursor, execute, queue};
+    use std::io::Write;
+
+    let delay = std::time::Duration::from_secs_f64(1.0 / f64::from(fps.max(1)));
+    let mut stdout = std::io::stdout();
+    execute!(stdout, cursor::Hide, Clear(ClearType::All)).into_diagnostic()?;
+
+    // A cell is part of the newest row when it turned into a beam since the
+    // previous frame; every row is swept once, so nothing else changes.
+    let mut previous: Option<Grid<Cell>> = None;
+    let mut draw = |grid: &Grid<Cell>| -> std::io::Result<()> {
+        queue!(stdout, cursor::MoveTo(0, 0))?;
+        for y in 0..grid.height() {
+            for x in 0..grid.width() {
+                let entered = previous.as_ref().is_some_and(|p| p[(x, y)] != Cell::Beam);
+                let glyph = match grid[(x, y)] {
+                    Cell::Empty => '.'.dark_grey(),
+                    Cell::Tile(glyph) => glyph.yellow(),
+                    Cell::Beam if entered => '|'.cyan().bold(),
+                    Cell::Beam => '|'.blue(),
+                };
+                queue!(stdout, PrintStyledContent(glyph))?;
+            }
+            queue!(stdout, cursor::MoveToNextLine(1))?;
+        }
+        stdout.flush()?;
+        previous = Some(grid.clone());
+        std::thread::sleep(delay);
+        Ok(())
+    };
+
+    // Sinks cannot fail, so the first terminal error stops the drawing and
+    // is reported once the simulation is done.
+    let mut failed = None;
+    let splits = process_with_frames(input, &mut |grid: &Grid<Cell>| {
+        if failed.is_none() {
+            failed = draw(grid).err();
+        }
+    });
+    execute!(std::io::stdout(), cursor::Show).into_diagnostic()?;
+    if let Some(error) = failed {
+        return Err(error).into_diagnostic();
+    }
+    Ok(splits?.to_string())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_common::grid::Recording;
 
     #[test]
     fn it_works() -> Result<()> {
@@ -134,4 +262,87 @@ mod tests {
         assert_eq!("21", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn heat_map_agrees_with_split_count() -> Result<()> {
+        let input = ".......S.......
+...............
+.......^.......
+...............
+......^.^......
+...............
+.....^.^.^.....
+...............
+....^.^...^....
+...............
+...^.^...^.^...
+...............
+..^...^.....^..
+...............
+.^.^.^.^.^...^.
+...............";
+        let heat = heat_map(input)?;
+        let struck = heat.cells().iter().flatten().filter(|h| h.strikes > 0);
+        assert_eq!(struck.count(), 21);
+        Ok(())
+    }
+
+    #[test]
+    fn heat_map_counts_merging_beams() -> Result<()> {
+        let input = "..S..
+.....
+..^..
+.....
+.^.^.
+..^..";
+        let heat = heat_map(input)?;
+        let cell = |x, y| heat[(x, y)].map(|h: Heat| (h.strikes, h.timelines));
+        assert_eq!(cell(2, 2), Some((1, 1)));
+        assert_eq!(cell(1, 4), Some((1, 1)));
+        assert_eq!(cell(2, 5), Some((2, 2)));
+        assert_eq!(cell(0, 0), None);
+        Ok(())
+    }
+
+    #[test]
+    fn heat_map_rejects_overflowing_timelines() {
+        // The middle splitters of 140 doubling levels carry more than 2^128
+        // timelines each.
+        assert!(heat_map(&crate::part2::tests::doubling(10)).is_ok());
+        assert!(heat_map(&crate::part2::tests::doubling(140)).is_err());
+    }
+
+    #[test]
+    fn records_a_frame_per_row() -> Result<()> {
+        let input = "..S..
+.....
+..^..
+.....";
+        let mut recording = Recording::new();
+        assert_eq!(1, process_with_frames(input, &mut recording)?);
+
+        let glyph = |cell: &Cell| match cell {
+            Cell::Empty => '.',
+            Cell::Tile(glyph) => *glyph,
+            Cell::Beam => '|',
+        };
+        let last = recording.replay().last().unwrap();
+        assert_eq!(recording.len(), 5);
+        assert_eq!(last.render(glyph), "..|..\n..|..\n..^..\n.|.|.\n");
+        Ok(())
+    }
+
+    #[test]
+    fn mirrors_and_absorbers() -> Result<()> {
+        // The mirror sends the beam right onto a splitter; the absorber
+        // swallows its left half and the last splitter sends the right half
+        // out through the side.
+        let input = "..S..
+..\\..
+...^.
+..#..
+....^";
+        assert_eq!("2", process(input)?);
+        Ok(())
+    }
 }
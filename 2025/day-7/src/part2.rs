@@ -1,105 +1,98 @@
+use aoc_common::answer::Answer;
 use miette::*;
-
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
-enum Tile {
-    Empty,
-    Splitter,
+use num_bigint::BigUint;
+
+use crate::tiles::{Manifold, Next};
+
+/// A number of timelines. Counts only ever grow by adding, so that is all
+/// the simulation needs from them.
+trait Count: Clone {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn is_zero(&self) -> bool;
+    /// Adds `other` in place, or returns `None` if the sum does not fit.
+    fn add(&mut self, other: &Self) -> Option<()>;
 }
 
-struct Grid {
-    width: usize,
-    height: usize,
-    tiles: Vec<Tile>,
-    start: (usize, usize),
+impl Count for u128 {
+    fn zero() -> Self {
+        0
+    }
+
+    fn one() -> Self {
+        1
+    }
+
+    fn is_zero(&self) -> bool {
+        *self == 0
+    }
+
+    fn add(&mut self, other: &Self) -> Option<()> {
+        *self = self.checked_add(*other)?;
+        Some(())
+    }
 }
 
-impl Grid {
-    fn from_str(input: &str) -> Result<Self> {
-        let mut tiles = Vec::new();
-        let mut start = None;
-        let mut width = 0;
-        let mut height = 0;
-
-        for (y, line) in input.lines().enumerate() {
-            width = line.len();
-            height += 1;
-            for (x, c) in line.chars().enumerate() {
-                match c {
-                    'S' => {
-                        start = Some((x, y));
-                        tiles.push(Tile::Empty);
-                    }
-                    '^' => tiles.push(Tile::Splitter),
-                    _ => tiles.push(Tile::Empty),
-                }
-            }
-        }
+impl Count for BigUint {
+    fn zero() -> Self {
+        BigUint::ZERO
+    }
+
+    fn one() -> Self {
+        BigUint::from(1u8)
+    }
 
-        let start = start.ok_or(miette!("No start position 'S' found in grid"))?;
+    fn is_zero(&self) -> bool {
+        *self == BigUint::ZERO
+    }
 
-        Ok(Grid {
-            width,
-            height,
-            tiles,
-            start,
-        })
+    fn add(&mut self, other: &Self) -> Option<()> {
+        *self += other;
+        Some(())
     }
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> Result<String> {
-    let grid = Grid::from_str(input)?;
-    let (sx, sy) = grid.start;
+/// Counts the timelines leaving the manifold, or `None` if they overflow `C`.
+fn count_timelines<C: Count>(manifold: &Manifold) -> Option<C> {
+    let grid = &manifold.tiles;
+    let (sx, sy) = manifold.start;
 
     // We track the number of distinct timelines (paths) reaching each column.
-    // u128 is used because splitters cause exponential growth (2^N).
-    let mut current_counts: Vec<u128> = vec![0; grid.width];
-    let mut next_counts: Vec<u128> = vec![0; grid.width];
+    let mut current_counts = vec![C::zero(); grid.width()];
+    let mut next_counts = vec![C::zero(); grid.width()];
 
     // Initialize: 1 particle timeline starts at S
-    current_counts[sx] = 1;
+    current_counts[sx] = C::one();
 
     // Accumulator for timelines that exit the grid boundaries (sides or bottom)
-    let mut finished_timelines: u128 = 0;
+    let mut finished_timelines = C::zero();
 
-    for y in sy..grid.height {
+    for y in sy..grid.height() {
         // Clear next row buffer
-        next_counts.fill(0);
+        next_counts.fill(C::zero());
 
         let mut active = false;
 
-        for x in 0..grid.width {
-            let count = current_counts[x];
-            if count == 0 {
+        for x in 0..grid.width() {
+            let count = &current_counts[x];
+            if count.is_zero() {
                 continue;
             }
             active = true;
 
-            let idx = y * grid.width + x;
-            match grid.tiles[idx] {
-                Tile::Empty => {
-                    // Beam passes straight through to the next row
-                    next_counts[x] += count;
-                }
-                Tile::Splitter => {
-                    // Beam splits: 1 path becomes 2 distinct paths (Left and Right)
-
-                    // Left Branch
-                    if x > 0 {
-                        next_counts[x - 1] += count;
-                    } else {
-                        // Exited grid to the left
-                        finished_timelines += count;
-                    }
-
-                    // Right Branch
-                    if x + 1 < grid.width {
-                        next_counts[x + 1] += count;
-                    } else {
-                        // Exited grid to the right
-                        finished_timelines += count;
-                    }
-                }
+            // Each beam leaving the tile carries every timeline that reached
+            // it; a splitter turns 1 path into 2 distinct ones (left and right)
+            let mut overflow = false;
+            grid[(x, y)].propagate(x, &manifold.row(y), &mut |next| {
+                let target = match next {
+                    Next::Column(nx) => &mut next_counts[nx],
+                    // Exited the grid through a side
+                    Next::Exit => &mut finished_timelines,
+                };
+                overflow |= target.add(count).is_none();
+            });
+            if overflow {
+                return None;
             }
         }
 
@@ -112,13 +105,35 @@ pub fn process(input: &str) -> Result<String> {
     }
 
     // Add all timelines that successfully reached the bottom of the grid
-    finished_timelines += current_counts.iter().sum::<u128>();
+    for count in &current_counts {
+        finished_timelines.add(count)?;
+    }
+
+    Some(finished_timelines)
+}
 
-    Ok(finished_timelines.to_string())
+#[tracing::instrument]
+pub fn process(input: &str) -> Result<String> {
+    Ok(solve(&Manifold::parse(input)?)?.to_string())
+}
+
+/// Counts the timelines a single particle ends up in.
+#[tracing::instrument(skip(manifold))]
+pub fn solve(manifold: &Manifold) -> Result<Answer> {
+    // Splitters cause exponential growth (2^N), which u128 covers for any
+    // reasonable grid; deeper ones are recounted with big integers.
+    match count_timelines::<u128>(manifold) {
+        Some(timelines) => Ok(timelines.into()),
+        None => {
+            let timelines =
+                count_timelines::<BigUint>(manifold).expect("big integers never overflow");
+            Ok(timelines.to_string().into())
+        }
+    }
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
 
     #[test]
@@ -142,4 +157,46 @@ mod tests {
         assert_eq!("40", process(input)?);
         Ok(())
     }
+
+    /// A manifold where every beam hits a splitter on every level, doubling
+    /// the timelines `levels` times without any leaving through the sides.
+    pub(crate) fn doubling(levels: usize) -> String {
+        let width = 2 * levels + 3;
+        let start = levels + 1;
+        let row = |glyph: &dyn Fn(usize) -> char| (0..width).map(glyph).collect::<String>();
+        std::iter::once(row(&|x| if x == start { 'S' } else { '.' }))
+            .chain((0..levels).map(|level| {
+                row(&|x| {
+                    if (x + level + start).is_multiple_of(2) {
+                        '^'
+                    } else {
+                        '.'
+                    }
+                })
+            }))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn counts_past_u128() -> Result<()> {
+        assert_eq!("1024", process(&doubling(10))?);
+        let expected = BigUint::from(1u8) << 130u32;
+        assert_eq!(expected.to_string(), process(&doubling(130))?);
+        Ok(())
+    }
+
+    #[test]
+    fn mirrors_and_absorbers() -> Result<()> {
+        // The mirror sends the beam right onto a splitter; the absorber
+        // swallows its left half and the last splitter sends the right half
+        // out through the side.
+        let input = "..S..
+..\\..
+...^.
+..#..
+....^";
+        assert_eq!("2", process(input)?);
+        Ok(())
+    }
 }
@@ -0,0 +1,193 @@
+//! The manifold's tiles as rules for where a beam goes next.
+//!
+//! Every beam moves down one row per step; a tile only decides which
+//! columns of the next row the beams crossing it land in. Both parts sweep
+//! the rows the same way and ask each tile through [`Tile::propagate`], so a
+//! new kind of tile is a struct implementing [`Tile`] plus an entry in
+//! [`TILES`].
+
+use std::fmt::Debug;
+
+use aoc_common::grid::Grid;
+use miette::*;
+
+/// The row a beam is crossing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RowCtx {
+    pub y: usize,
+    pub width: usize,
+}
+
+impl RowCtx {
+    /// Column `x` of the next row, or [`Next::Exit`] past either side.
+    pub fn column(&self, x: Option<usize>) -> Next {
+        match x {
+            Some(x) if x < self.width => Next::Column(x),
+            _ => Next::Exit,
+        }
+    }
+}
+
+/// Where a beam leaving a tile goes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Next {
+    /// This column of the next row.
+    Column(usize),
+    /// Out through a side of the manifold.
+    Exit,
+}
+
+/// A kind of tile.
+pub trait Tile: Debug + Sync {
+    /// The character drawing the tile in a diagram.
+    fn glyph(&self) -> char;
+
+    /// Whether a beam reaching the tile counts as split in part 1.
+    fn splits(&self) -> bool {
+        false
+    }
+
+    /// Sends the beams arriving at column `x` of `row` on, calling `emit`
+    /// once for every beam leaving with where it goes. Whatever the sweep
+    /// counts per beam (a flag, strikes, timelines) follows each of them; a
+    /// tile that emits nothing absorbs it.
+    fn propagate(&self, x: usize, row: &RowCtx, emit: &mut dyn FnMut(Next));
+}
+
+/// Lets the beam through.
+#[derive(Debug)]
+pub struct Empty;
+
+/// `^`: stops the beam and sends one down each side.
+#[derive(Debug)]
+pub struct Splitter;
+
+/// `#`: stops the beam for good.
+#[derive(Debug)]
+pub struct Absorber;
+
+/// `/` and `\`: deflect the beam one column left or right.
+#[derive(Debug)]
+pub struct Mirror {
+    glyph: char,
+    left: bool,
+}
+
+impl Tile for Empty {
+    fn glyph(&self) -> char {
+        '.'
+    }
+
+    fn propagate(&self, x: usize, row: &RowCtx, emit: &mut dyn FnMut(Next)) {
+        emit(row.column(Some(x)));
+    }
+}
+
+impl Tile for Splitter {
+    fn glyph(&self) -> char {
+        '^'
+    }
+
+    fn splits(&self) -> bool {
+        true
+    }
+
+    fn propagate(&self, x: usize, row: &RowCtx, emit: &mut dyn FnMut(Next)) {
+        emit(row.column(x.checked_sub(1)));
+        emit(row.column(Some(x + 1)));
+    }
+}
+
+impl Tile for Absorber {
+    fn glyph(&self) -> char {
+        '#'
+    }
+
+    fn propagate(&self, _: usize, _: &RowCtx, _: &mut dyn FnMut(Next)) {}
+}
+
+impl Tile for Mirror {
+    fn glyph(&self) -> char {
+        self.glyph
+    }
+
+    fn propagate(&self, x: usize, row: &RowCtx, emit: &mut dyn FnMut(Next)) {
+        match self.left {
+            true => emit(row.column(x.checked_sub(1))),
+            false => emit(row.column(Some(x + 1))),
+        }
+    }
+}
+
+/// Every tile a diagram can draw. Anything else, the source `S` included,
+/// is [`Empty`].
+pub static TILES: &[&dyn Tile] = &[
+    &Empty,
+    &Splitter,
+    &Absorber,
+    &Mirror {
+        glyph: '/',
+        left: true,
+    },
+    &Mirror {
+        glyph: '\\',
+        left: false,
+    },
+];
+
+/// The tile drawn as `glyph`.
+pub fn tile(glyph: char) -> &'static dyn Tile {
+    TILES
+        .iter()
+        .copied()
+        .find(|tile| tile.glyph() == glyph)
+        .unwrap_or(&Empty)
+}
+
+pub struct Manifold {
+    pub tiles: Grid<&'static dyn Tile>,
+    pub start: (usize, usize),
+}
+
+impl Manifold {
+    pub fn parse(input: &str) -> Result<Self> {
+        let _phase = tracing::info_span!("parse").entered();
+        let chars = Grid::from_chars(input, |c| c)?;
+        let start = chars
+            .position(|&c| c == 'S')
+            .ok_or(miette!("No start position 'S' found in grid"))?;
+        let tiles = chars.map(|&c| tile(c));
+
+        Ok(Manifold { tiles, start })
+    }
+
+    /// The context of row `y`.
+    pub fn row(&self, y: usize) -> RowCtx {
+        RowCtx {
+            y,
+            width: self.tiles.width(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn targets(glyph: char, x: usize) -> Vec<Next> {
+        let mut targets = Vec::new();
+        let row = RowCtx { y: 0, width: 3 };
+        tile(glyph).propagate(x, &row, &mut |next| targets.push(next));
+        targets
+    }
+
+    #[test]
+    fn routes_beams() {
+        assert_eq!(targets('.', 1), [Next::Column(1)]);
+        assert_eq!(targets('S', 1), [Next::Column(1)]);
+        assert_eq!(targets('^', 0), [Next::Exit, Next::Column(1)]);
+        assert_eq!(targets('#', 1), []);
+        assert_eq!(targets('/', 1), [Next::Column(0)]);
+        assert_eq!(targets('\\', 2), [Next::Exit]);
+    }
+}
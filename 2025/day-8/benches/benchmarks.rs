@@ -1,15 +1,41 @@
 use aoc2025_day_8::*;
+use aoc_common::solution::Solution;
 
 fn main() {
-    divan::main();
+    // Without a stored input there is nothing to benchmark.
+    if aoc_runtime::bench_input!().is_some() {
+        divan::main();
+    }
+}
+
+/// The stored input, which `main` has checked is there.
+fn input() -> &'static str {
+    aoc_runtime::bench_input!().unwrap()
+}
+
+#[divan::bench]
+fn part1_bytes() {
+    part1_bytes::process(divan::black_box(input())).unwrap();
+}
+
+#[divan::bench]
+fn part2_bytes() {
+    part2_bytes::process(divan::black_box(input())).unwrap();
+}
+
+#[divan::bench]
+fn parse() {
+    Day8::parse(divan::black_box(input())).unwrap();
 }
 
 #[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!("../input1.txt",))).unwrap();
+fn solve_part1(bencher: divan::Bencher) {
+    let points = Day8::parse(input()).unwrap();
+    bencher.bench(|| Day8::part1(divan::black_box(&points)).unwrap());
 }
 
 #[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!("../input2.txt",))).unwrap();
+fn solve_part2(bencher: divan::Bencher) {
+    let points = Day8::parse(input()).unwrap();
+    bencher.bench(|| Day8::part2(divan::black_box(&points)).unwrap());
 }
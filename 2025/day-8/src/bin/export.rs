@@ -0,0 +1,31 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+
+use miette::*;
+
+use aoc2025_day_8::export::{self, Format};
+use aoc2025_day_8::part1;
+
+/// Writes the part 1 circuits to the `.ply` or `.obj` file given as the only argument.
+fn main() -> Result<()> {
+    aoc_runtime::init();
+    let path = std::env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .ok_or_else(|| miette!("Usage: export <output.ply|output.obj>"))?;
+    let format = path
+        .extension()
+        .and_then(|ext| Format::from_extension(&ext.to_string_lossy()))
+        .ok_or_else(|| miette!("Unsupported output format: {}", path.display()))?;
+
+    let input = aoc_runtime::day!()?.load_input()?;
+    let points = part1::parse(&input)?;
+    let labels = part1::component_labels(&points, 1000);
+
+    let file = File::create(&path).into_diagnostic()?;
+    export::write_clusters(&mut BufWriter::new(file), &points, &labels, format)
+        .into_diagnostic()?;
+    println!("Wrote {} points to {}", points.len(), path.display());
+    Ok(())
+}
@@ -0,0 +1,128 @@
+//! Exports the circuits as coloured point clouds for inspection in a 3D
+//! viewer (MeshLab, Blender, ...), one colour per circuit.
+
+use std::io::{self, Write};
+
+use glam::DVec3;
+
+/// Point cloud formats understood by [`write_clusters`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// ASCII PLY with per-vertex `red`/`green`/`blue` properties.
+    Ply,
+    /// Wavefront OBJ using the widespread `v x y z r g b` vertex colour extension.
+    Obj,
+}
+
+impl Format {
+    /// Picks the format from a file extension (`ply` or `obj`).
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "ply" => Some(Self::Ply),
+            "obj" => Some(Self::Obj),
+            _ => None,
+        }
+    }
+}
+
+/// Writes every point coloured by its circuit label.
+///
+/// `labels[i]` is the circuit of `points[i]`, as produced by
+/// [`component_labels`](crate::part1::component_labels).
+pub fn write_clusters<W: Write>(
+    writer: &mut W,
+    points: &[DVec3],
+    labels: &[usize],
+    format: Format,
+) -> io::Result<()> {
+    assert_eq!(points.len(), labels.len(), "every point needs a label");
+
+    if format == Format::Ply {
+        writeln!(writer, "ply")?;
+        writeln!(writer, "format ascii 1.0")?;
+        writeln!(writer, "element vertex {}", points.len())?;
+        for axis in ["x", "y", "z"] {
+            writeln!(writer, "property float {axis}")?;
+        }
+        for channel in ["red", "green", "blue"] {
+            writeln!(writer, "property uchar {channel}")?;
+        }
+        writeln!(writer, "end_header")?;
+    }
+
+    for (point, &label) in points.iter().zip(labels) {
+        let [r, g, b] = color(label);
+        match format {
+            Format::Ply => writeln!(writer, "{} {} {} {r} {g} {b}", point.x, point.y, point.z)?,
+            Format::Obj => writeln!(
+                writer,
+                "v {} {} {} {:.4} {:.4} {:.4}",
+                point.x,
+                point.y,
+                point.z,
+                r as f64 / 255.0,
+                g as f64 / 255.0,
+                b as f64 / 255.0
+            )?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Spreads labels around the hue wheel by the golden angle, so that circuits
+/// with neighbouring labels still get clearly distinct colours.
+fn color(label: usize) -> [u8; 3] {
+    const GOLDEN_ANGLE: f64 = 137.507_764;
+    let hue = (label as f64 * GOLDEN_ANGLE) % 360.0;
+
+    // HSV -> RGB with full saturation and value
+    let sector = hue / 60.0;
+    let x = 1.0 - (sector % 2.0 - 1.0).abs();
+    let (r, g, b) = match sector as u32 {
+        0 => (1.0, x, 0.0),
+        1 => (x, 1.0, 0.0),
+        2 => (0.0, 1.0, x),
+        3 => (0.0, x, 1.0),
+        4 => (x, 0.0, 1.0),
+        _ => (1.0, 0.0, x),
+    };
+
+    [r, g, b].map(|c: f64| (c * 255.0).round() as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ply_header_and_vertices() -> io::Result<()> {
+        let points = [DVec3::new(1.0, 2.0, 3.0), DVec3::new(4.0, 5.0, 6.0)];
+        let mut out = Vec::new();
+        write_clusters(&mut out, &points, &[0, 1], Format::Ply)?;
+
+        let text = String::from_utf8(out).unwrap();
+        let lines = text.lines().collect::<Vec<_>>();
+        assert_eq!(lines[2], "element vertex 2");
+        assert_eq!(lines[9], "end_header");
+        assert_eq!(lines[10], "1 2 3 255 0 0");
+        assert_eq!(lines.len(), 12);
+        Ok(())
+    }
+
+    #[test]
+    fn obj_vertices_share_circuit_color() -> io::Result<()> {
+        let points = [DVec3::ZERO, DVec3::ONE, DVec3::X];
+        let mut out = Vec::new();
+        write_clusters(&mut out, &points, &[3, 3, 4], Format::Obj)?;
+
+        let text = String::from_utf8(out).unwrap();
+        let colors = text
+            .lines()
+            .map(|line| line.split(' ').skip(4).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(colors[0], colors[1]);
+        assert_ne!(colors[1], colors[2]);
+        Ok(())
+    }
+}
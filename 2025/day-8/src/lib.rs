@@ -1,2 +1,35 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use glam::DVec3;
+use miette::Result;
+
+pub mod export;
 pub mod part1;
+pub mod part1_bytes;
 pub mod part2;
+pub mod part2_bytes;
+
+/// Both parts work on the same junction box positions.
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Parsed = Vec<DVec3>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        part1::parse(input)
+    }
+
+    fn part1(points: &Self::Parsed) -> Result<Answer> {
+        part1::solve(points)
+    }
+
+    fn part2(points: &Self::Parsed) -> Result<Answer> {
+        part2::solve(points)
+    }
+
+    fn items(points: &Self::Parsed) -> Option<usize> {
+        Some(points.len())
+    }
+}
+
+aoc_common::solution!(Day8);
@@ -1,122 +1,144 @@
+use aoc_common::answer::Answer;
+use aoc_common::diagnostics::{self, ParseMode, Recovered};
+use aoc_common::dsu::Dsu;
+use aoc_spatial::ClosestPairs;
 use chumsky::prelude::*;
 use glam::DVec3;
-use itertools::Itertools;
 use miette::*;
-
-/// A standard Disjoint Set Union (DSU) with path compression and union by size.
-struct Dsu {
-    parent: Vec<usize>,
-    sizes: Vec<usize>,
-}
-
-impl Dsu {
-    fn new(n: usize) -> Self {
-        Self {
-            parent: (0..n).collect(),
-            sizes: vec![1; n],
-        }
-    }
-
-    fn find(&mut self, i: usize) -> usize {
-        if self.parent[i] == i {
-            i
-        } else {
-            let root = self.find(self.parent[i]);
-            self.parent[i] = root;
-            root
-        }
-    }
-
-    fn union(&mut self, i: usize, j: usize) {
-        let root_i = self.find(i);
-        let root_j = self.find(j);
-
-        if root_i != root_j {
-            if self.sizes[root_i] < self.sizes[root_j] {
-                self.parent[root_i] = root_j;
-                self.sizes[root_j] += self.sizes[root_i];
-            } else {
-                self.parent[root_j] = root_i;
-                self.sizes[root_i] += self.sizes[root_j];
-            }
-        }
-    }
-
-    fn get_component_sizes(&mut self) -> Vec<usize> {
-        let n = self.parent.len();
-        let mut components = Vec::new();
-        for i in 0..n {
-            if self.parent[i] == i {
-                components.push(self.sizes[i]);
-            }
-        }
-        components
-    }
-}
+use sorting::select::top_k;
 
 fn parser<'a>() -> impl Parser<'a, &'a str, Vec<DVec3>, extra::Err<Rich<'a, char>>> {
-    let coord = text::int(10).from_str::<f64>().unwrapped();
+    let coord = diagnostics::number::<f64>();
 
     let point = coord
+        .clone()
         .then_ignore(just(','))
-        .then(coord)
+        .then(coord.clone())
         .then_ignore(just(','))
         .then(coord)
         .map(|((x, y), z)| DVec3::new(x, y, z));
 
-    point
+    diagnostics::skip_malformed(point, "\r\n")
         .separated_by(text::newline())
         .allow_trailing()
-        .collect()
+        .collect::<Vec<_>>()
+        .map(|points| points.into_iter().flatten().collect())
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> Result<String> {
-    let points = parser()
-        .parse(input)
-        .into_result()
-        .map_err(|e| miette!("Parse failed: {:?}", e))?;
+/// Parses the junction box positions, one `x,y,z` per line.
+pub fn parse(input: &str) -> Result<Vec<DVec3>> {
+    Ok(parse_with(input, ParseMode::Strict)?.value)
+}
 
-    if points.is_empty() {
-        return Ok("0".to_string());
+/// Like [`parse`], but [`ParseMode::Lenient`] skips malformed lines and
+/// returns them as warnings.
+pub fn parse_with(input: &str, mode: ParseMode) -> Result<Recovered<Vec<DVec3>>> {
+    Ok(diagnostics::parse_with(parser(), "input", input, mode)?)
+}
+
+/// Joins the `limit` closest pairs of points and returns the resulting circuits.
+///
+/// Pairs are streamed closest first from a k-d tree, so only the `limit`
+/// edges that are used are ever generated.
+fn connect_closest(points: &[DVec3], limit: usize) -> Dsu {
+    let mut dsu = Dsu::new(points.len());
+    let pairs = tracing::info_span!("build").in_scope(|| ClosestPairs::new(points));
+
+    for (u, v, _) in pairs.take(limit) {
+        dsu.union(u, v);
+    }
+
+    dsu
+}
+
+/// The circuits left after joining the closest pairs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Circuits {
+    /// The circuit of every point, by index.
+    ///
+    /// Labels are dense (`0..circuit_count`) and numbered in order of each
+    /// circuit's first point, so they can be used directly as palette
+    /// indices.
+    pub labels: Vec<usize>,
+    /// The points of every circuit, by label, in ascending order.
+    pub members: Vec<Vec<usize>>,
+}
+
+impl Circuits {
+    /// The number of points in every circuit, by label.
+    pub fn sizes(&self) -> Vec<usize> {
+        self.members.iter().map(Vec::len).collect()
     }
+}
 
-    // Generate all pairs and calculate squared Euclidean distance
-    let mut edges = (0..points.len())
-        .tuple_combinations()
-        .map(|(i, j)| {
-            let dist_sq = points[i].distance_squared(points[j]);
-            (i, j, dist_sq)
+/// Finds the circuit of every point after joining the `connections` closest
+/// pairs.
+pub fn circuits(points: &[DVec3], connections: usize) -> Circuits {
+    let mut dsu = connect_closest(points, connections);
+    let mut label_of_root = vec![usize::MAX; points.len()];
+    let mut members: Vec<Vec<usize>> = Vec::new();
+
+    let labels = (0..points.len())
+        .map(|i| {
+            let root = dsu.find(i);
+            if label_of_root[root] == usize::MAX {
+                label_of_root[root] = members.len();
+                members.push(Vec::new());
+            }
+            members[label_of_root[root]].push(i);
+            label_of_root[root]
         })
-        .collect::<Vec<_>>();
+        .collect();
 
-    // Sort edges by distance (ascending).
-    // f64 doesn't implement Ord, so we use partial_cmp.
-    // Since inputs are integers, we won't have NaNs, so unwrap is safe.
-    edges.sort_unstable_by(|(_, _, dist_a), (_, _, dist_b)| dist_a.partial_cmp(dist_b).unwrap());
+    Circuits { labels, members }
+}
 
-    let mut dsu = Dsu::new(points.len());
+/// Labels every point with the circuit it ends up in after joining the
+/// `connections` closest pairs, as in [`Circuits::labels`].
+pub fn component_labels(points: &[DVec3], connections: usize) -> Vec<usize> {
+    circuits(points, connections).labels
+}
 
-    // Connect the 1000 closest pairs
-    let limit = 1000.min(edges.len());
+/// Pairs joined by the puzzle before the circuits are measured.
+const CONNECTIONS: usize = 1000;
 
-    for &(u, v, _) in edges.iter().take(limit) {
-        dsu.union(u, v);
-    }
+#[tracing::instrument]
+pub fn process(input: &str) -> Result<String> {
+    process_with_connections(input, CONNECTIONS)
+}
 
-    let mut sizes = dsu.get_component_sizes();
+/// Like [`process`], but joining the `connections` closest pairs, as the
+/// example does with 10.
+#[tracing::instrument]
+pub fn process_with_connections(input: &str, connections: usize) -> Result<String> {
+    Ok(solve_with_connections(&parse(input)?, connections)?.to_string())
+}
 
-    // Get top 3 largest circuits
-    sizes.sort_unstable_by(|a, b| b.cmp(a));
+/// Multiplies the sizes of the three largest circuits after joining the 1000
+/// closest pairs.
+#[tracing::instrument(skip(points))]
+pub fn solve(points: &[DVec3]) -> Result<Answer> {
+    solve_with_connections(points, CONNECTIONS)
+}
 
-    let result: usize = sizes.iter().take(3).product();
+/// Multiplies the sizes of the three largest circuits after joining the
+/// `connections` closest pairs.
+#[tracing::instrument(skip(points))]
+pub fn solve_with_connections(points: &[DVec3], connections: usize) -> Result<Answer> {
+    if points.is_empty() {
+        return Ok(Answer::U64(0));
+    }
+
+    let sizes = circuits(points, connections).sizes();
+    let result: usize = top_k(sizes, 3).iter().product();
 
-    Ok(result.to_string())
+    Ok(result.into())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use itertools::Itertools;
 
     #[test]
     fn it_works() -> Result<()> {
@@ -141,27 +163,78 @@ mod tests {
 984,92,344
 425,690,689";
 
-        // To test strictly against the logic "10 shortest connections" from the example text:
-        let points = parser().parse(input).unwrap();
-        let mut edges = (0..points.len())
-            .tuple_combinations()
-            .map(|(i, j)| (i, j, points[i].distance_squared(points[j])))
-            .collect::<Vec<_>>();
+        assert_eq!("40", process_with_connections(input, 10)?);
+        Ok(())
+    }
 
-        edges.sort_by(|a, b| a.2.partial_cmp(&b.2).unwrap());
+    #[test]
+    fn labels_group_circuits() -> Result<()> {
+        let points = parse(
+            "162,817,812
+57,618,57
+906,360,560
+592,479,940
+352,342,300
+466,668,158
+542,29,236
+431,825,988
+739,650,466
+52,470,668
+216,146,977
+819,987,18
+117,168,530
+805,96,715
+346,949,466
+970,615,88
+941,993,340
+862,61,35
+984,92,344
+425,690,689",
+        )?;
 
-        let mut dsu = Dsu::new(points.len());
-        // Use 10 instead of 1000 for the unit test example check
-        for &(u, v, _) in edges.iter().take(10) {
-            dsu.union(u, v);
-        }
+        let labels = component_labels(&points, 10);
+        assert_eq!(labels[0], 0);
+
+        let mut sizes = labels.iter().counts().into_values().collect::<Vec<_>>();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        assert_eq!(sizes.len(), 11);
+        assert_eq!(&sizes[..3], &[5, 4, 2]);
+        Ok(())
+    }
 
-        let mut sizes = dsu.get_component_sizes();
-        sizes.sort_by(|a, b| b.cmp(a));
-        let ans: usize = sizes.iter().take(3).product();
+    #[test]
+    fn circuits_list_their_members() -> Result<()> {
+        let points = parse("0,0,0\n9,0,0\n1,0,0\n10,0,0\n50,0,0")?;
+        let circuits = circuits(&points, 2);
+        assert_eq!(circuits.labels, [0, 1, 0, 1, 2]);
+        assert_eq!(circuits.members, [vec![0, 2], vec![1, 3], vec![4]]);
+        assert_eq!(circuits.sizes(), [2, 2, 1]);
+        for (label, members) in circuits.members.iter().enumerate() {
+            assert!(members.iter().all(|&i| circuits.labels[i] == label));
+        }
+        Ok(())
+    }
 
-        assert_eq!(ans, 40);
+    #[test]
+    fn limit_beyond_pair_count_joins_everything() -> Result<()> {
+        let points = parse("0,0,0\n1,0,0\n5,0,0")?;
+        assert_eq!(component_labels(&points, 0), vec![0, 1, 2]);
+        assert_eq!(component_labels(&points, 1), vec![0, 0, 1]);
+        assert_eq!(component_labels(&points, 10), vec![0, 0, 0]);
+        Ok(())
+    }
 
+    #[test]
+    fn lenient_parses_skip_bad_lines() -> Result<()> {
+        let input = "1,2,3\n4,5\n6,7,8\n";
+        let parsed = parse_with(input, ParseMode::Lenient)?;
+        assert_eq!(
+            parsed.value,
+            [DVec3::new(1.0, 2.0, 3.0), DVec3::new(6.0, 7.0, 8.0)]
+        );
+        assert_eq!(parsed.warnings.len(), 1);
+        assert_eq!(parsed.warnings[0].offset(), 9);
+        assert!(parse(input).is_err());
         Ok(())
     }
 }
@@ -0,0 +1,58 @@
+//! [`part1`](crate::part1) with the points scanned straight from the bytes
+//! instead of through chumsky, to compare the two in the benchmarks.
+
+use aoc_common::bytes::{self, Cursor};
+use glam::DVec3;
+use miette::*;
+
+/// Parses the junction box positions, one `x,y,z` per line.
+pub fn parse(input: &str) -> Result<Vec<DVec3>> {
+    bytes::lines(input.as_bytes())
+        .map(|(offset, line)| {
+            let mut cursor = Cursor::new(line);
+            point(&mut cursor).map_err(|expected| {
+                Report::new(bytes::malformed(input, offset + cursor.pos(), expected))
+            })
+        })
+        .collect()
+}
+
+/// Reads `x,y,z` up to the end of the line, or says what was expected where
+/// it stopped.
+fn point(cursor: &mut Cursor) -> std::result::Result<DVec3, &'static str> {
+    let x = cursor.uint().ok_or("a coordinate")?;
+    cursor.expect(b',').ok_or("','")?;
+    let y = cursor.uint().ok_or("a coordinate")?;
+    cursor.expect(b',').ok_or("','")?;
+    let z = cursor.uint().ok_or("a coordinate")?;
+    if !cursor.is_empty() {
+        return Err("the end of the line");
+    }
+    Ok(DVec3::new(x as f64, y as f64, z as f64))
+}
+
+#[tracing::instrument]
+pub fn process(input: &str) -> Result<String> {
+    Ok(crate::part1::solve(&parse(input)?)?.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::diagnostics::ParseError;
+
+    #[test]
+    fn agrees_with_the_combinator_parser() -> Result<()> {
+        let input = "162,817,812\n57,618,57\r\n906,360,560\n";
+        assert_eq!(parse(input)?, crate::part1::parse(input)?);
+        assert!(parse("1,2\n").is_err());
+        assert!(parse("1,2,3,\n").is_err());
+        // Neither parser skips blank lines.
+        let blank = "1,2,3\n\n4,5,6\n";
+        assert!(crate::part1::parse(blank).is_err());
+        let error = parse(blank).unwrap_err();
+        let error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!(error.labels()[0].offset(), 6);
+        Ok(())
+    }
+}
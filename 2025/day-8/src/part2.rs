@@ -1,107 +1,53 @@
-use chumsky::prelude::*;
+use aoc_common::answer::Answer;
+use aoc_common::dsu::Dsu;
+use aoc_spatial::ClosestPairs;
 use glam::DVec3;
-use itertools::Itertools;
 use miette::*;
 
-/// Disjoint Set Union (DSU) tracking the number of active components.
-struct Dsu {
-    parent: Vec<usize>,
-    /// Tracks how many disjoint sets currently exist.
-    num_components: usize,
-}
+use crate::part1::parse;
 
-impl Dsu {
-    fn new(n: usize) -> Self {
-        Self {
-            parent: (0..n).collect(),
-            num_components: n,
-        }
-    }
-
-    fn find(&mut self, i: usize) -> usize {
-        if self.parent[i] == i {
-            i
-        } else {
-            let root = self.find(self.parent[i]);
-            self.parent[i] = root;
-            root
-        }
-    }
-
-    /// Unifies sets. Returns `true` if a merge actually occurred (sets were disjoint).
-    fn union(&mut self, i: usize, j: usize) -> bool {
-        let root_i = self.find(i);
-        let root_j = self.find(j);
+/// The pair whose connection leaves a single circuit, as `(u, v)` with
+/// `u < v`.
+///
+/// Runs Kruskal lazily: pairs are streamed closest first from the k-d tree
+/// and joined until one component remains, so the long pairs past the last
+/// merge are never generated. Equally distant pairs come out ordered by
+/// `(u, v)`, which makes the answer unique even when lengths tie.
+fn last_connection(points: &[DVec3]) -> Option<(usize, usize)> {
+    let mut dsu = Dsu::new(points.len());
+    let mut components = points.len();
+    let pairs = tracing::info_span!("build").in_scope(|| ClosestPairs::new(points));
 
-        if root_i != root_j {
-            // Arbitrary linking since we don't need rank/size optimizations for just this logic,
-            // but path compression in find() handles the complexity.
-            self.parent[root_i] = root_j;
-            self.num_components -= 1;
-            true
-        } else {
-            false
+    for (u, v, _) in pairs {
+        if dsu.union(u, v) {
+            components -= 1;
+            if components == 1 {
+                return Some((u, v));
+            }
         }
     }
-}
-
-fn parser<'a>() -> impl Parser<'a, &'a str, Vec<DVec3>, extra::Err<Rich<'a, char>>> {
-    let coord = text::int(10).from_str::<f64>().unwrapped();
-
-    let point = coord
-        .then_ignore(just(','))
-        .then(coord)
-        .then_ignore(just(','))
-        .then(coord)
-        .map(|((x, y), z)| DVec3::new(x, y, z));
 
-    point
-        .separated_by(text::newline())
-        .allow_trailing()
-        .collect()
+    None
 }
 
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let points = parser()
-        .parse(input)
-        .into_result()
-        .map_err(|e| miette!("Parse failed: {:?}", e))?;
+    Ok(solve(&parse(input)?)?.to_string())
+}
 
+/// Multiplies the X coordinates of the pair whose connection joins every
+/// junction box into a single circuit.
+#[tracing::instrument(skip(points))]
+pub fn solve(points: &[DVec3]) -> Result<Answer> {
     if points.len() < 2 {
-        return Ok("0".to_string());
-    }
-
-    // Generate all edges: (u_index, v_index, distance_squared)
-    let mut edges = (0..points.len())
-        .tuple_combinations()
-        .map(|(i, j)| {
-            let dist_sq = points[i].distance_squared(points[j]);
-            (i, j, dist_sq)
-        })
-        .collect::<Vec<_>>();
-
-    // Sort edges ascending by distance
-    edges.sort_unstable_by(|(_, _, dist_a), (_, _, dist_b)| dist_a.partial_cmp(dist_b).unwrap());
-
-    let mut dsu = Dsu::new(points.len());
-
-    // Iterate through edges until the graph is fully connected
-    for (u, v, _) in edges {
-        // Try to merge the two components
-        if dsu.union(u, v) {
-            // If this merge reduced the component count to 1,
-            // the graph is now fully connected.
-            if dsu.num_components == 1 {
-                let x1 = points[u].x as i64;
-                let x2 = points[v].x as i64;
-                let result = x1 * x2;
-                return Ok(result.to_string());
-            }
-        }
+        return Ok(Answer::U64(0));
     }
 
-    Err(miette!("Graph could not be fully connected"))
+    let (u, v) =
+        last_connection(points).ok_or_else(|| miette!("Graph could not be fully connected"))?;
+    let x1 = points[u].x as i64;
+    let x2 = points[v].x as i64;
+    Ok((x1 * x2).into())
 }
 
 #[cfg(test)]
@@ -134,4 +80,48 @@ mod tests {
         assert_eq!("25272", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn last_connection_is_the_longest_tree_edge() -> Result<()> {
+        assert_eq!("30", process("0,0,0\n1,0,0\n3,0,0\n10,0,0")?);
+        // Both edges tie; the later pair in index order joins last.
+        assert_eq!("6", process("1,0,0\n2,0,0\n3,0,0")?);
+        Ok(())
+    }
+
+    /// Kruskal over every pair, sorted up front.
+    fn last_connection_by_sorting(points: &[DVec3]) -> (usize, usize) {
+        let mut pairs = (0..points.len())
+            .flat_map(|u| (u + 1..points.len()).map(move |v| (u, v)))
+            .collect::<Vec<_>>();
+        pairs.sort_by(|&(a, b), &(c, d)| {
+            let left = points[a].distance_squared(points[b]);
+            let right = points[c].distance_squared(points[d]);
+            left.total_cmp(&right).then((a, b).cmp(&(c, d)))
+        });
+
+        let mut dsu = Dsu::new(points.len());
+        let mut components = points.len();
+        for (u, v) in pairs {
+            if dsu.union(u, v) {
+                components -= 1;
+                if components == 1 {
+                    return (u, v);
+                }
+            }
+        }
+        unreachable!("every pair was joined")
+    }
+
+    #[test]
+    fn streaming_matches_sorting_every_pair() {
+        // A small lattice, so many pairs tie on length.
+        let points = (0..60)
+            .map(|i| DVec3::new((i * 7 % 11) as f64, (i * 3 % 5) as f64, (i % 4) as f64))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            Some(last_connection_by_sorting(&points)),
+            last_connection(&points)
+        );
+    }
 }
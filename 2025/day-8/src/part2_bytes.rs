@@ -0,0 +1,22 @@
+//! [`part2`](crate::part2) with the points scanned straight from the bytes
+//! instead of through chumsky, to compare the two in the benchmarks.
+
+use crate::part1_bytes::parse;
+use miette::*;
+
+#[tracing::instrument]
+pub fn process(input: &str) -> Result<String> {
+    Ok(crate::part2::solve(&parse(input)?)?.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn agrees_with_the_combinator_parser() -> Result<()> {
+        let input = "162,817,812\n57,618,57\n906,360,560\n592,479,940\n";
+        assert_eq!(process(input)?, crate::part2::process(input)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,11 @@
+use aoc2025_day_9::{part1, part2};
+use aoc_bench::perf;
+
+fn main() {
+    // Without a stored input there is nothing to benchmark.
+    let Some(input) = aoc_runtime::bench_input!() else {
+        return;
+    };
+    perf::bench("part1", 10, || part1::process(input).unwrap());
+    perf::bench("part2", 10, || part2::process(input).unwrap());
+}
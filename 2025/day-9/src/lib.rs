@@ -1,2 +1,33 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+
 pub mod part1;
+pub mod part1_bytes;
 pub mod part2;
+pub mod part2_bytes;
+
+/// Both parts span rectangles between the same red tiles.
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Parsed = Vec<(i64, i64)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        part1::parse(input)
+    }
+
+    fn part1(tiles: &Self::Parsed) -> Result<Answer> {
+        part1::solve(tiles)
+    }
+
+    fn part2(tiles: &Self::Parsed) -> Result<Answer> {
+        part2::solve(tiles)
+    }
+
+    fn items(tiles: &Self::Parsed) -> Option<usize> {
+        Some(tiles.len())
+    }
+}
+
+aoc_common::solution!(Day9);
@@ -1,29 +1,109 @@
+use aoc_common::answer::Answer;
+use aoc_common::diagnostics;
 use chumsky::prelude::*;
 use itertools::Itertools;
 use miette::*;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> Result<String> {
-    let coord = text::int::<&str, extra::Err<Rich<char>>>(10)
-        .from_str::<i64>()
-        .unwrapped();
-
-    let parser = coord
+/// Parses the red tiles, one `x,y` per line.
+pub fn parse(input: &str) -> Result<Vec<(i64, i64)>> {
+    let parser = diagnostics::number::<i64>()
         .then_ignore(just(','))
-        .then(coord)
+        .then(diagnostics::number::<i64>())
         .separated_by(text::newline())
         .allow_trailing()
         .collect::<Vec<(i64, i64)>>();
 
-    let points = parser
-        .parse(input)
-        .into_result()
-        .map_err(|e| miette!("Parse failed: {:?}", e))?;
+    Ok(diagnostics::parse(parser, "input", input)?)
+}
+
+#[tracing::instrument]
+pub fn process(input: &str) -> Result<String> {
+    Ok(solve(&parse(input)?)?.to_string())
+}
+
+/// The area of the largest rectangle with red tiles at two opposite corners.
+#[tracing::instrument(skip(points))]
+pub fn solve(points: &[(i64, i64)]) -> Result<Answer> {
+    Ok(largest_area(points).into())
+}
+
+/// The largest rectangle with red tiles at two opposite corners.
+///
+/// A rectangle from a lower-left to an upper-right corner only grows when
+/// either corner moves further out, so the largest one joins a point of the
+/// lower-left staircase (the points with no other below and left of them) to
+/// one of the upper-right staircase; flipping `y` covers the other diagonal.
+/// Walking along the staircases, each lower corner's best partner never
+/// moves back, so a divide and conquer finds them all in O(n log n).
+pub fn largest_area(points: &[(i64, i64)]) -> u64 {
+    if points.len() < 2 {
+        return 0;
+    }
+    let flipped = points.iter().map(|&(x, y)| (x, -y)).collect::<Vec<_>>();
+    diagonal_area(points).max(diagonal_area(&flipped))
+}
+
+/// The largest rectangle from a lower-left to an upper-right tile.
+fn diagonal_area(points: &[(i64, i64)]) -> u64 {
+    let mut sorted = points.to_vec();
+    sorted.sort_unstable();
 
-    // Iterate over all unique pairs of points to find the max area.
+    // Both staircases run with x ascending and y descending.
+    let mut lower: Vec<(i64, i64)> = Vec::new();
+    for &(x, y) in &sorted {
+        if lower.last().is_none_or(|&(_, low)| y < low) {
+            lower.push((x, y));
+        }
+    }
+    let mut upper: Vec<(i64, i64)> = Vec::new();
+    for &(x, y) in sorted.iter().rev() {
+        if upper.last().is_none_or(|&(_, high)| y > high) {
+            upper.push((x, y));
+        }
+    }
+    upper.reverse();
+
+    best_pair(&lower, &upper, 0..lower.len(), 0, upper.len() - 1) as u64
+}
+
+/// Area of the rectangle between `low` and `high`, negative when `high` is
+/// left of or below `low`. No staircase corner is both, as it would have
+/// been dominated.
+fn area(low: (i64, i64), high: (i64, i64)) -> i128 {
+    let w = i128::from(high.0) - i128::from(low.0) + 1;
+    let h = i128::from(high.1) - i128::from(low.1) + 1;
+    w * h
+}
+
+/// The largest area between the lower corners `rows` and the upper corners
+/// `first..=last`, knowing the best partners lie in that window.
+fn best_pair(
+    lower: &[(i64, i64)],
+    upper: &[(i64, i64)],
+    rows: std::ops::Range<usize>,
+    first: usize,
+    last: usize,
+) -> i128 {
+    if rows.is_empty() {
+        return 0;
+    }
+    let mid = rows.start + rows.len() / 2;
+    let (partner, best) = (first..=last)
+        .map(|j| (j, area(lower[mid], upper[j])))
+        .max_by_key(|&(j, area)| (area, std::cmp::Reverse(j)))
+        .expect("the window is never empty");
+
+    let left = best_pair(lower, upper, rows.start..mid, first, partner);
+    let right = best_pair(lower, upper, mid + 1..rows.end, partner, last);
+    best.max(left).max(right)
+}
+
+/// [`largest_area`] by trying every pair of tiles, as the reference it is
+/// checked against.
+pub fn largest_area_pairs(points: &[(i64, i64)]) -> u64 {
     // Area of inclusive rectangle defined by opposite corners (x1,y1) and (x2,y2)
     // is (|x1 - x2| + 1) * (|y1 - y2| + 1).
-    let max_area = points
+    points
         .iter()
         .tuple_combinations()
         .map(|(p1, p2)| {
@@ -32,9 +112,7 @@ pub fn process(input: &str) -> Result<String> {
             w * h
         })
         .max()
-        .unwrap_or(0);
-
-    Ok(max_area.to_string())
+        .unwrap_or(0)
 }
 
 #[cfg(test)]
@@ -54,4 +132,28 @@ mod tests {
         assert_eq!("50", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn staircases_match_every_pair() {
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(570);
+        for round in 0..2000 {
+            // Narrow ranges force ties and duplicates.
+            let span = [3, 10, 1000][round % 3];
+            let points = (0..rng.random_range(0..40))
+                .map(|_| (rng.random_range(0..span), rng.random_range(0..span)))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                largest_area(&points),
+                largest_area_pairs(&points),
+                "{points:?}"
+            );
+        }
+
+        // Every point on one staircase.
+        let diagonal = (0..500).map(|i| (i, 500 - i)).collect::<Vec<_>>();
+        assert_eq!(largest_area(&diagonal), largest_area_pairs(&diagonal));
+    }
 }
@@ -0,0 +1,57 @@
+//! [`part1`](crate::part1) with the tiles scanned straight from the bytes
+//! instead of through chumsky, to compare the two in the benchmarks.
+
+use aoc_common::bytes::{self, Cursor};
+use miette::*;
+
+/// Parses the red tiles, one `x,y` per line.
+pub fn parse(input: &str) -> Result<Vec<(i64, i64)>> {
+    bytes::lines(input.as_bytes())
+        .map(|(offset, line)| {
+            let mut cursor = Cursor::new(line);
+            tile(&mut cursor).map_err(|expected| {
+                Report::new(bytes::malformed(input, offset + cursor.pos(), expected))
+            })
+        })
+        .collect()
+}
+
+/// Reads `x,y` up to the end of the line, or says what was expected where
+/// it stopped.
+fn tile(cursor: &mut Cursor) -> std::result::Result<(i64, i64), &'static str> {
+    let coord = |cursor: &mut Cursor| {
+        cursor
+            .uint()
+            .and_then(|n| i64::try_from(n).ok())
+            .ok_or("a coordinate")
+    };
+    let x = coord(cursor)?;
+    cursor.expect(b',').ok_or("','")?;
+    let y = coord(cursor)?;
+    if !cursor.is_empty() {
+        return Err("the end of the line");
+    }
+    Ok((x, y))
+}
+
+#[tracing::instrument]
+pub fn process(input: &str) -> Result<String> {
+    Ok(crate::part1::largest_area(&parse(input)?).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn agrees_with_the_combinator_parser() -> Result<()> {
+        let input = "7,1\n11,1\n11,7\n9,7\n9,5\n2,5\n2,3\n7,3\n";
+        assert_eq!(process(input)?, crate::part1::process(input)?);
+        assert_eq!(process(input)?, "50");
+        assert!(parse("7,1\n11\n").is_err());
+        // Neither parser skips blank lines.
+        assert!(crate::part1::parse("7,1\n\n11,1\n").is_err());
+        assert!(parse("7,1\n\n11,1\n").is_err());
+        Ok(())
+    }
+}
@@ -1,44 +1,21 @@
 #![allow(dead_code)]
 
+use aoc_common::answer::Answer;
+use aoc_common::diagnostics;
+use aoc_common::grid::Grid;
+use aoc_common::par::prelude::*;
 use bitvec::prelude::*;
 use chumsky::prelude::*;
 use glam::I64Vec2;
 use miette::*;
-use rayon::prelude::*;
 use std::ops::Range;
 
-type Point = I64Vec2;
+pub type Point = I64Vec2;
 
-/// A dense 2D grid wrapper for flattened vectors.
-#[derive(Debug, Clone)]
-struct Grid2D<T> {
-    width: usize,
-    height: usize,
-    data: Vec<T>,
-}
-
-impl<T: Clone + Default> Grid2D<T> {
-    fn new(width: usize, height: usize) -> Self {
-        Self {
-            width,
-            height,
-            data: vec![T::default(); width * height],
-        }
-    }
-
-    #[inline(always)]
-    fn get(&self, x: usize, y: usize) -> Option<&T> {
-        if x >= self.width || y >= self.height {
-            None
-        } else {
-            // Safety: Bounds checked above
-            Some(&self.data[y * self.width + x])
-        }
-    }
-}
-
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 struct AxisMap {
+    /// The coordinates the map was built from, sorted and deduplicated.
+    coords: Vec<i64>,
     starts: Vec<i64>,
     lengths: Vec<i64>,
 }
@@ -46,6 +23,9 @@ struct AxisMap {
 impl AxisMap {
     fn new(coords: impl Iterator<Item = i64>) -> Self {
         let mut unique: Vec<i64> = coords.collect();
+        unique.sort_unstable();
+        unique.dedup();
+        let coords = unique.clone();
 
         // Add padding to guarantee an outer boundary for flood fill
         if let (Some(&min), Some(&max)) = (unique.iter().min(), unique.iter().max()) {
@@ -71,14 +51,34 @@ impl AxisMap {
             }
         }
 
-        Self { starts, lengths }
+        Self {
+            coords,
+            starts,
+            lengths,
+        }
     }
 
+    /// The map with `val` added to its coordinates.
+    fn with(&self, val: i64) -> Self {
+        match self.coords.binary_search(&val) {
+            Ok(_) => self.clone(),
+            Err(_) => Self::new(self.coords.iter().copied().chain([val])),
+        }
+    }
+
+    /// Index of the segment that starts at `val`, which must be one of the
+    /// coordinates the map was built from.
     #[inline]
-    fn index_of(&self, val: i64) -> usize {
+    fn index_of(&self, val: i64) -> Result<usize> {
         self.starts
             .binary_search(&val)
-            .expect("Coordinate not found in map")
+            .map_err(|_| miette!("Coordinate {val} is not in the compressed axis"))
+    }
+
+    /// Index of the segment covering `val`, for query points that were not in
+    /// the original set. Values beyond either end map to the outermost segment.
+    fn segment_of(&self, val: i64) -> usize {
+        self.starts.partition_point(|&start| start <= val).max(1) - 1
     }
 
     fn size(&self) -> usize {
@@ -90,69 +90,199 @@ impl AxisMap {
 // Geometry Engine
 // -----------------------------------------------------------------------------
 
-struct GeometryEngine {
-    prefix_area: Grid2D<u64>,
+/// The tiles inside the loop, compressed to the segments between red tile
+/// coordinates, with prefix sums to measure any rectangle of them in O(1).
+///
+/// Besides building it at once from a loop, vertices can be
+/// [appended](Self::append) one by one: the axes are refined only where the
+/// new vertex adds a coordinate and only the window around the changed edges
+/// is flooded again, so an editor can keep one engine as the polygon grows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeometryEngine {
+    points: Vec<Point>,
+    x_map: AxisMap,
+    y_map: AxisMap,
+    /// How many edges cover each compressed cell.
+    walls: Vec<u32>,
+    exterior: BitVec<usize, Lsb0>,
+    prefix_area: Grid<u64>,
 }
 
+/// A window of compressed cells, as column and row ranges.
+type Window = (Range<usize>, Range<usize>);
+
 impl GeometryEngine {
-    fn build(points: &[Point]) -> Self {
+    /// Builds the engine for the loop through `points`, which closes from
+    /// the last point back to the first.
+    pub fn build(points: &[Point]) -> Result<Self> {
         let x_map = AxisMap::new(points.iter().map(|p| p.x));
         let y_map = AxisMap::new(points.iter().map(|p| p.y));
         let width = x_map.size();
         let height = y_map.size();
 
-        let boundaries = Self::mark_boundaries(points, &x_map, &y_map, width, height);
-        let visited = Self::scanline_flood_fill(&boundaries, width, height);
-        let prefix_area = Self::compute_prefix_sums(&visited, &x_map, &y_map, width, height);
-
-        Self { prefix_area }
+        let mut engine = Self {
+            points: points.to_vec(),
+            walls: vec![0; width * height],
+            exterior: bitvec![usize, Lsb0; 0; width * height],
+            prefix_area: Grid::new(width + 1, height + 1, vec![0; (width + 1) * (height + 1)]),
+            x_map,
+            y_map,
+        };
+        if points.is_empty() {
+            return Ok(engine);
+        }
+        for i in 0..points.len() {
+            engine.mark_edge(points[i], points[(i + 1) % points.len()], 1)?;
+        }
+        // Start at (0,0) - guaranteed outside due to padding
+        engine.flood(&(0..width, 0..height), [(0, 0)]);
+        engine.compute_prefix_sums(0);
+        Ok(engine)
     }
 
-    fn mark_boundaries(
-        points: &[Point],
-        x_map: &AxisMap,
-        y_map: &AxisMap,
-        width: usize,
-        height: usize,
-    ) -> BitVec<u64, Lsb0> {
-        let mut grid = bitvec![u64, Lsb0; 0; width * height];
+    /// The loop's vertices, in order.
+    pub fn points(&self) -> &[Point] {
+        &self.points
+    }
 
-        let mapped_points = points
+    /// Inserts `vertex` between the last vertex and the first, updating the
+    /// engine to match [`build`](Self::build) on the longer loop.
+    ///
+    /// Only the cells around the three edges that change are flooded again.
+    /// That is exact as long as the loop is simple before and after, since a
+    /// cell outside their bounding box can look for the outside along a ray
+    /// that never meets them.
+    pub fn append(&mut self, vertex: Point) -> Result<()> {
+        let (Some(&first), Some(&last)) = (self.points.first(), self.points.last()) else {
+            *self = Self::build(&[vertex])?;
+            return Ok(());
+        };
+        let split = self.refine(vertex);
+
+        self.mark_edge(last, first, -1)?;
+        self.mark_edge(last, vertex, 1)?;
+        self.mark_edge(vertex, first, 1)?;
+        self.points.push(vertex);
+
+        let corners = [first, last, vertex]
             .iter()
-            .map(|p| (x_map.index_of(p.x), y_map.index_of(p.y)))
+            .map(|p| Ok((self.x_map.index_of(p.x)?, self.y_map.index_of(p.y)?)))
+            .collect::<Result<Vec<_>>>()?;
+        let xs = corners.iter().map(|c| c.0);
+        let ys = corners.iter().map(|c| c.1);
+        let window = (
+            xs.clone().min().unwrap()..xs.max().unwrap() + 1,
+            ys.clone().min().unwrap()..ys.max().unwrap() + 1,
+        );
+        self.reflood(&window);
+        // Splitting cells moves every prefix sum after them.
+        self.compute_prefix_sums(if split { 0 } else { window.1.start });
+        Ok(())
+    }
+
+    /// Adds `vertex`'s coordinates to the axes, splitting the compressed
+    /// cells they fall in. Each new cell starts as a copy of the cell it was
+    /// cut from, which is exact: a gap between coordinates is crossed by
+    /// edges from side to side or not at all, and past the padding there is
+    /// only outside.
+    ///
+    /// Returns whether any cell was split.
+    fn refine(&mut self, vertex: Point) -> bool {
+        let x_map = self.x_map.with(vertex.x);
+        let y_map = self.y_map.with(vertex.y);
+        if x_map == self.x_map && y_map == self.y_map {
+            return false;
+        }
+        let columns = (0..x_map.size())
+            .map(|x| self.x_map.segment_of(x_map.starts[x]))
+            .collect::<Vec<_>>();
+        let rows = (0..y_map.size())
+            .map(|y| self.y_map.segment_of(y_map.starts[y]))
             .collect::<Vec<_>>();
 
-        for i in 0..mapped_points.len() {
-            let (x1, y1) = mapped_points[i];
-            let (x2, y2) = mapped_points[(i + 1) % mapped_points.len()];
-
-            // Calculate ranges
-            let x_start = x1.min(x2);
-            let x_end = x1.max(x2);
-            let y_start = y1.min(y2);
-            let y_end = y1.max(y2);
-
-            for y in y_start..=y_end {
-                let row_offset = y * width;
-                let start = row_offset + x_start;
-                // slice range is exclusive at the end, so +1
-                let end = row_offset + x_end + 1;
-                grid[start..end].fill(true);
+        let old_width = self.x_map.size();
+        let cells = rows
+            .iter()
+            .flat_map(|&y| columns.iter().map(move |&x| y * old_width + x));
+        let (walls, exterior) = cells
+            .map(|old| (self.walls[old], self.exterior[old]))
+            .unzip();
+
+        self.walls = walls;
+        self.exterior = exterior;
+        self.x_map = x_map;
+        self.y_map = y_map;
+        true
+    }
+
+    /// Adds `delta` to the wall count of every cell the edge from `a` to `b`
+    /// covers.
+    fn mark_edge(&mut self, a: Point, b: Point, delta: i32) -> Result<()> {
+        let width = self.x_map.size();
+        let (x1, y1) = (self.x_map.index_of(a.x)?, self.y_map.index_of(a.y)?);
+        let (x2, y2) = (self.x_map.index_of(b.x)?, self.y_map.index_of(b.y)?);
+
+        // Calculate ranges
+        let x_start = x1.min(x2);
+        let x_end = x1.max(x2);
+        let y_start = y1.min(y2);
+        let y_end = y1.max(y2);
+
+        for y in y_start..=y_end {
+            let row_offset = y * width;
+            // slice range is exclusive at the end, so +1
+            for wall in &mut self.walls[row_offset + x_start..row_offset + x_end + 1] {
+                *wall = wall.wrapping_add_signed(delta);
             }
         }
-        grid
+        Ok(())
     }
 
-    fn scanline_flood_fill(
-        boundaries: &BitSlice<u64, Lsb0>,
-        width: usize,
-        height: usize,
-    ) -> BitVec<u64, Lsb0> {
-        let mut visited = bitvec![u64, Lsb0; 0; width * height];
-        let mut stack = Vec::with_capacity(height * 4);
+    /// Floods `window` again from the outside cells bordering it.
+    fn reflood(&mut self, window: &Window) {
+        let width = self.x_map.size();
+        let height = self.y_map.size();
+        let (xs, ys) = window;
+        for y in ys.clone() {
+            self.exterior[y * width + xs.start..y * width + xs.end].fill(false);
+        }
 
-        // Start at (0,0) - guaranteed outside due to padding
-        stack.push((0usize, 0usize));
+        let mut seeds = Vec::new();
+        for y in ys.clone() {
+            for x in xs.clone() {
+                let neighbors = [
+                    (x.checked_sub(1), Some(y)),
+                    (Some(x + 1).filter(|&x| x < width), Some(y)),
+                    (Some(x), y.checked_sub(1)),
+                    (Some(x), Some(y + 1).filter(|&y| y < height)),
+                ];
+                let outside = neighbors.into_iter().any(|neighbor| match neighbor {
+                    (Some(nx), Some(ny)) => {
+                        !(xs.contains(&nx) && ys.contains(&ny)) && self.exterior[ny * width + nx]
+                    }
+                    _ => true,
+                });
+                if outside {
+                    seeds.push((x, y));
+                }
+            }
+        }
+        self.flood(window, seeds);
+    }
+
+    /// Marks as exterior every open cell of `window` reachable from `seeds`
+    /// without leaving it.
+    fn flood(&mut self, window: &Window, seeds: impl IntoIterator<Item = (usize, usize)>) {
+        let width = self.x_map.size();
+        let (xs, ys) = window;
+        let boundaries = &self.walls;
+        let visited = &mut self.exterior;
+        let mut stack = Vec::with_capacity(ys.len() * 4);
+        stack.extend(
+            seeds
+                .into_iter()
+                .filter(|&(x, y)| boundaries[y * width + x] == 0),
+        );
 
         while let Some((x, y)) = stack.pop() {
             let row_offset = y * width;
@@ -164,11 +294,11 @@ impl GeometryEngine {
 
             // Scan Left
             let mut lx = x;
-            while lx > 0 {
+            while lx > xs.start {
                 let prev = lx - 1;
                 let p_idx = row_offset + prev;
                 // Check boundary logic without recursion
-                if !boundaries[p_idx] && !visited[p_idx] {
+                if boundaries[p_idx] == 0 && !visited[p_idx] {
                     lx = prev;
                 } else {
                     break;
@@ -177,10 +307,10 @@ impl GeometryEngine {
 
             // Scan Right
             let mut rx = x;
-            while rx < width - 1 {
+            while rx < xs.end - 1 {
                 let next = rx + 1;
                 let n_idx = row_offset + next;
-                if !boundaries[n_idx] && !visited[n_idx] {
+                if boundaries[n_idx] == 0 && !visited[n_idx] {
                     rx = next;
                 } else {
                     break;
@@ -193,14 +323,14 @@ impl GeometryEngine {
             // Scan Rows Above and Below to seed new spans
             let scan_row = |stack: &mut Vec<(usize, usize)>, ny: usize| {
                 let n_row_offset = ny * width;
+                let open =
+                    |i: usize| boundaries[n_row_offset + i] == 0 && !visited[n_row_offset + i];
                 let mut i = lx;
                 while i <= rx {
-                    let idx = n_row_offset + i;
-                    if !boundaries[idx] && !visited[idx] {
+                    if open(i) {
                         stack.push((i, ny));
                         // Skip the contiguous segment we just pushed to avoid duplicate seeds
-                        while i <= rx && !boundaries[n_row_offset + i] && !visited[n_row_offset + i]
-                        {
+                        while i <= rx && open(i) {
                             i += 1;
                         }
                     }
@@ -208,46 +338,41 @@ impl GeometryEngine {
                 }
             };
 
-            if y > 0 {
+            if y > ys.start {
                 scan_row(&mut stack, y - 1);
             }
-            if y < height - 1 {
+            if y < ys.end - 1 {
                 scan_row(&mut stack, y + 1);
             }
         }
-
-        visited
     }
 
-    fn compute_prefix_sums(
-        visited_exterior: &BitSlice<u64, Lsb0>,
-        x_map: &AxisMap,
-        y_map: &AxisMap,
-        width: usize,
-        height: usize,
-    ) -> Grid2D<u64> {
+    /// Recomputes the prefix sums from compressed row `from` down; the rows
+    /// above only sum cells above them.
+    fn compute_prefix_sums(&mut self, from: usize) {
+        let width = self.x_map.size();
+        let height = self.y_map.size();
         let pw = width + 1;
         let ph = height + 1;
-        let mut data = vec![0u64; pw * ph];
+        if (self.prefix_area.width(), self.prefix_area.height()) != (pw, ph) {
+            self.prefix_area = Grid::new(pw, ph, vec![0; pw * ph]);
+        }
+        let data = self.prefix_area.cells_mut();
 
         // Flatten lengths to avoid double indirection/bounds checking in hot loop
-        let x_lengths: Vec<u64> = x_map.lengths.iter().map(|&x| x as u64).collect();
-
-        let mut visited_iter = visited_exterior.iter();
+        let x_lengths: Vec<u64> = self.x_map.lengths.iter().map(|&x| x as u64).collect();
 
-        for y in 0..height {
-            // Safety: loop is bounded by height
-            let y_len = unsafe { *y_map.lengths.get_unchecked(y) } as u64;
+        for y in from..height {
+            let y_len = self.y_map.lengths[y] as u64;
 
             // Pre-calculate row pointers
             let row_prev_start = y * pw;
             let row_curr_start = (y + 1) * pw;
+            let exterior = &self.exterior[y * width..(y + 1) * width];
 
             let mut row_acc = 0;
 
-            for x in 0..width {
-                // Safety: iterator is exactly width * height long
-                let is_exterior = visited_iter.next().unwrap();
+            for (x, is_exterior) in exterior.iter().by_vals().enumerate() {
                 let is_interior_mask = (!is_exterior) as u64;
 
                 // Safety: x bounded by width
@@ -262,20 +387,20 @@ impl GeometryEngine {
                 }
             }
         }
+    }
 
-        Grid2D {
-            width: pw,
-            height: ph,
-            data,
-        }
+    /// Tiles inside the loop, edges included, in the rectangle with red tiles
+    /// `a` and `b` at opposite corners.
+    pub fn area_inside(&self, a: Point, b: Point) -> Result<u64> {
+        let (x1, x2) = (self.x_map.index_of(a.x)?, self.x_map.index_of(b.x)?);
+        let (y1, y2) = (self.y_map.index_of(a.y)?, self.y_map.index_of(b.y)?);
+        Ok(self.query_area(x1.min(x2)..x1.max(x2), y1.min(y2)..y1.max(y2)))
     }
 
     /// Queries valid area.
     /// Uses unchecked access because indices are derived from AxisMap which guarantees validity.
     #[inline(always)]
     fn query_area(&self, x_range: Range<usize>, y_range: Range<usize>) -> u64 {
-        let pw = self.prefix_area.width;
-
         // Map compressed map indices to 1-based prefix array indices
         // Range from main loop is (min..max inclusive).
         let idx_x_high = x_range.end + 1;
@@ -283,26 +408,13 @@ impl GeometryEngine {
         let idx_y_high = y_range.end + 1;
         let idx_y_low = y_range.start;
 
-        let row_high_offset = idx_y_high * pw;
-        let row_low_offset = idx_y_low * pw;
-
+        // SAFETY: the ranges come from the axis maps, whose sizes are one less
+        // than the prefix grid's dimensions.
         unsafe {
-            let a = *self
-                .prefix_area
-                .data
-                .get_unchecked(row_high_offset + idx_x_high);
-            let b = *self
-                .prefix_area
-                .data
-                .get_unchecked(row_high_offset + idx_x_low);
-            let c = *self
-                .prefix_area
-                .data
-                .get_unchecked(row_low_offset + idx_x_high);
-            let d = *self
-                .prefix_area
-                .data
-                .get_unchecked(row_low_offset + idx_x_low);
+            let a = *self.prefix_area.get_unchecked(idx_x_high, idx_y_high);
+            let b = *self.prefix_area.get_unchecked(idx_x_low, idx_y_high);
+            let c = *self.prefix_area.get_unchecked(idx_x_high, idx_y_low);
+            let d = *self.prefix_area.get_unchecked(idx_x_low, idx_y_low);
 
             // (A + D) - B - C
             (a + d).wrapping_sub(b).wrapping_sub(c)
@@ -311,45 +423,78 @@ impl GeometryEngine {
 }
 
 fn parser<'a>() -> impl Parser<'a, &'a str, Vec<Point>, extra::Err<Rich<'a, char>>> {
-    let coord = text::int(10).from_str::<i64>().unwrapped();
-    coord
+    diagnostics::number::<i64>()
         .then_ignore(just(','))
-        .then(coord)
+        .then(diagnostics::number::<i64>())
         .map(|(x, y)| Point::new(x, y))
         .separated_by(text::newline())
         .allow_trailing()
         .collect()
 }
 
+/// The largest rectangle that fits inside the loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RectReport {
+    /// The two red tiles at opposite corners, in input order.
+    pub corners: [Point; 2],
+    /// Tiles covered, edges included.
+    pub area: u64,
+}
+
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    let points = parser()
-        .parse(input)
-        .into_result()
-        .map_err(|e| miette!("Parse failed: {:?}", e))?;
+    let points = parse(input)?;
+    let area = largest_rectangle(&points)?.map_or(0, |report| report.area);
+    Ok(area.to_string())
+}
+
+/// The area of the largest rectangle inside the loop, with red tiles at two
+/// opposite corners.
+#[tracing::instrument(skip(tiles))]
+pub fn solve(tiles: &[(i64, i64)]) -> Result<Answer> {
+    let points = tiles
+        .iter()
+        .map(|&(x, y)| Point::new(x, y))
+        .collect::<Vec<_>>();
+    let area = largest_rectangle(&points)?.map_or(0, |report| report.area);
+    Ok(area.into())
+}
+
+/// Like [`process`], but also reports which red tiles span the rectangle.
+#[tracing::instrument]
+pub fn solve_detailed(input: &str) -> Result<RectReport> {
+    largest_rectangle(&parse(input)?)?
+        .ok_or_else(|| miette!("At least two red tiles are needed to span a rectangle"))
+}
+
+fn parse(input: &str) -> Result<Vec<Point>> {
+    Ok(diagnostics::parse(parser(), "input", input)?)
+}
 
+/// Finds the largest rectangle with red tiles at opposite corners that lies
+/// entirely inside the loop, or `None` with fewer than two tiles.
+pub(crate) fn largest_rectangle(points: &[Point]) -> Result<Option<RectReport>> {
     if points.len() < 2 {
-        return Ok("0".to_string());
+        return Ok(None);
     }
 
-    let engine = GeometryEngine::build(&points);
-
-    // Pre-calculate indices
-    let x_map = AxisMap::new(points.iter().map(|p| p.x));
-    let y_map = AxisMap::new(points.iter().map(|p| p.y));
+    let build = tracing::info_span!("build").entered();
+    let engine = GeometryEngine::build(points)?;
+    let (x_map, y_map) = (&engine.x_map, &engine.y_map);
 
     // Combine Point and Map Indices into one struct to improve cache locality
     // and reduce lookups in the parallel loop.
     let indexed_points: Vec<(Point, (usize, usize))> = points
         .iter()
-        .map(|&p| (p, (x_map.index_of(p.x), y_map.index_of(p.y))))
-        .collect();
+        .map(|&p| Ok((p, (x_map.index_of(p.x)?, y_map.index_of(p.y)?))))
+        .collect::<Result<_>>()?;
+    drop(build);
 
-    let max_valid_area = indexed_points
+    let largest = indexed_points
         .par_iter()
         .enumerate()
-        .map(|(i, (p1, (x1, y1)))| {
-            let mut local_max = 0;
+        .filter_map(|(i, (p1, (x1, y1)))| {
+            let mut local_best: Option<RectReport> = None;
 
             for (p2, (x2, y2)) in indexed_points.iter().skip(i + 1) {
                 // Computes min/max for X and Y simultaneously
@@ -361,6 +506,10 @@ pub fn process(input: &str) -> Result<String> {
                 let dims = (max_p - min_p).abs() + 1;
                 let geometric_area = (dims.x as u64) * (dims.y as u64);
 
+                if local_best.is_some_and(|best| best.area >= geometric_area) {
+                    continue;
+                }
+
                 // Integer comparisons are very fast
                 let idx_x1 = *x1.min(x2);
                 let idx_x2 = *x1.max(x2);
@@ -370,15 +519,17 @@ pub fn process(input: &str) -> Result<String> {
                 let valid_area = engine.query_area(idx_x1..idx_x2, idx_y1..idx_y2);
 
                 if valid_area == geometric_area {
-                    local_max = local_max.max(valid_area);
+                    local_best = Some(RectReport {
+                        corners: [*p1, *p2],
+                        area: valid_area,
+                    });
                 }
             }
-            local_max
+            local_best
         })
-        .max()
-        .unwrap_or(0);
+        .max_by_key(|report| report.area);
 
-    Ok(max_valid_area.to_string())
+    Ok(largest)
 }
 
 #[cfg(test)]
@@ -398,4 +549,62 @@ mod tests {
         assert_eq!("24", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn reports_the_corners() -> Result<()> {
+        let report = solve_detailed("7,1\n11,1\n11,7\n9,7\n9,5\n2,5\n2,3\n7,3")?;
+        assert_eq!(report.area, 24);
+        assert_eq!(report.corners, [Point::new(9, 5), Point::new(2, 3)]);
+        assert!(solve_detailed("7,1").is_err());
+        assert_eq!(process("7,1")?, "0");
+        Ok(())
+    }
+
+    #[test]
+    fn appending_matches_rebuilding() -> Result<()> {
+        let points = parse("7,1\n11,1\n11,7\n9,7\n9,5\n2,5\n2,3\n7,3")?;
+        let mut engine = GeometryEngine::build(&points[..1])?;
+        for n in 2..=points.len() {
+            engine.append(points[n - 1])?;
+            assert_eq!(engine, GeometryEngine::build(&points[..n])?, "{n} vertices");
+        }
+        assert_eq!(engine.area_inside(points[4], points[6])?, 24);
+        Ok(())
+    }
+
+    #[test]
+    fn appending_random_loops_matches_rebuilding() -> Result<()> {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let mut rng = StdRng::seed_from_u64(9);
+        for _ in 0..300 {
+            let points = aoc_gen::day9::polygon(&mut rng, 6, 8)
+                .into_iter()
+                .map(|(x, y)| Point::new(x, y))
+                .collect::<Vec<_>>();
+            let mut engine = GeometryEngine::build(&points[..2])?;
+            for n in 3..=points.len() {
+                engine.append(points[n - 1])?;
+                let rebuilt = GeometryEngine::build(&points[..n])?;
+                assert_eq!(engine, rebuilt, "{:?}", &points[..n]);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn axis_lookups() {
+        // Segments: 1, 2, 3..=4, 5, 6..=9, 10, 11
+        let axis = AxisMap::new([2, 5, 10].into_iter());
+        assert_eq!(axis.index_of(5).unwrap(), 3);
+        let err = axis.index_of(7).unwrap_err();
+        assert!(err.to_string().contains("Coordinate 7"), "{err}");
+
+        assert_eq!(axis.segment_of(5), 3);
+        assert_eq!(axis.segment_of(7), 4);
+        assert_eq!(axis.segment_of(9), 4);
+        assert_eq!(axis.segment_of(-100), 0);
+        assert_eq!(axis.segment_of(100), 6);
+    }
 }
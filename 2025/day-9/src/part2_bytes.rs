@@ -0,0 +1,28 @@
+//! [`part2`](crate::part2) with the tiles scanned straight from the bytes
+//! instead of through chumsky, to compare the two in the benchmarks.
+
+use crate::part2::{largest_rectangle, Point};
+use miette::*;
+
+#[tracing::instrument]
+pub fn process(input: &str) -> Result<String> {
+    let points = crate::part1_bytes::parse(input)?
+        .into_iter()
+        .map(|(x, y)| Point::new(x, y))
+        .collect::<Vec<_>>();
+    let area = largest_rectangle(&points)?.map_or(0, |report| report.area);
+    Ok(area.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn agrees_with_the_combinator_parser() -> Result<()> {
+        let input = "7,1\n11,1\n11,7\n9,7\n9,5\n2,5\n2,3\n7,3\n";
+        assert_eq!(process(input)?, crate::part2::process(input)?);
+        assert_eq!(process(input)?, "24");
+        Ok(())
+    }
+}
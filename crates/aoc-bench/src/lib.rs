@@ -0,0 +1,7 @@
+//! Measurement helpers shared by the daily benchmarks.
+//!
+//! Wall-clock timing lives in `divan` and instruction-exact profiles in
+//! `gungraun`; [`perf`] sits in between, reading the CPU's own counters at
+//! native speed.
+
+pub mod perf;
@@ -0,0 +1,256 @@
+//! Hardware performance counters through Linux `perf_event_open`.
+//!
+//! Counts cycles, retired instructions and cache misses for user-space code
+//! only, so it works at the default `perf_event_paranoid` level of 2. On other
+//! platforms, or where the kernel exposes no PMU (many VMs and containers),
+//! [`Counters::open`] returns an error instead.
+
+use std::fmt;
+
+use miette::*;
+
+/// Per-run averages of the counters, as returned by [`measure`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Counters {
+    pub cycles: f64,
+    pub instructions: f64,
+    pub cache_misses: f64,
+}
+
+impl Counters {
+    /// Instructions retired per cycle.
+    pub fn ipc(&self) -> f64 {
+        self.instructions / self.cycles
+    }
+
+    /// Opens the counters for the calling thread, stopped and zeroed.
+    #[cfg(target_os = "linux")]
+    pub fn open() -> Result<Group> {
+        Ok(Group {
+            counters: [
+                sys::Counter::open(sys::HW_CPU_CYCLES)?,
+                sys::Counter::open(sys::HW_INSTRUCTIONS)?,
+                sys::Counter::open(sys::HW_CACHE_MISSES)?,
+            ],
+        })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn open() -> Result<Group> {
+        bail!("hardware counters are only supported on Linux")
+    }
+}
+
+impl fmt::Display for Counters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>14.0} cycles {:>14.0} instructions ({:.2} IPC) {:>10.0} cache misses",
+            self.cycles,
+            self.instructions,
+            self.ipc(),
+            self.cache_misses
+        )
+    }
+}
+
+/// The open counters of the calling thread.
+pub struct Group {
+    #[cfg(target_os = "linux")]
+    counters: [sys::Counter; 3],
+}
+
+impl Group {
+    /// Runs `f` with the counters enabled and returns how far each advanced.
+    #[cfg(target_os = "linux")]
+    pub fn count<T>(&self, f: impl FnOnce() -> T) -> Result<(T, [u64; 3])> {
+        for counter in &self.counters {
+            counter.reset()?;
+        }
+        for counter in &self.counters {
+            counter.enable()?;
+        }
+        let out = std::hint::black_box(f());
+        for counter in &self.counters {
+            counter.disable()?;
+        }
+        let mut values = [0; 3];
+        for (value, counter) in values.iter_mut().zip(&self.counters) {
+            *value = counter.read()?;
+        }
+        Ok((out, values))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn count<T>(&self, _f: impl FnOnce() -> T) -> Result<(T, [u64; 3])> {
+        bail!("hardware counters are only supported on Linux")
+    }
+}
+
+/// Runs `f` once to warm up, then `runs` more times under the counters, and
+/// returns the per-run averages.
+pub fn measure<T>(runs: u32, mut f: impl FnMut() -> T) -> Result<Counters> {
+    let group = Counters::open()?;
+    std::hint::black_box(f());
+
+    let mut totals = [0u64; 3];
+    for _ in 0..runs {
+        let (_, values) = group.count(&mut f)?;
+        for (total, value) in totals.iter_mut().zip(values) {
+            *total += value;
+        }
+    }
+
+    let runs = runs.max(1) as f64;
+    Ok(Counters {
+        cycles: totals[0] as f64 / runs,
+        instructions: totals[1] as f64 / runs,
+        cache_misses: totals[2] as f64 / runs,
+    })
+}
+
+/// Measures `f` and prints one line for it, in the style of a bench harness.
+/// A missing PMU is reported on that line rather than aborting the run.
+pub fn bench<T>(name: &str, runs: u32, f: impl FnMut() -> T) {
+    match measure(runs, f) {
+        Ok(counters) => println!("{name:<24} {counters}"),
+        Err(err) => println!("{name:<24} unavailable: {err}"),
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod sys {
+    use std::io;
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+    use miette::*;
+
+    const TYPE_HARDWARE: u32 = 0;
+    pub const HW_CPU_CYCLES: u64 = 0;
+    pub const HW_INSTRUCTIONS: u64 = 1;
+    pub const HW_CACHE_MISSES: u64 = 3;
+
+    const FLAG_DISABLED: u64 = 1 << 0;
+    const FLAG_EXCLUDE_KERNEL: u64 = 1 << 5;
+    const FLAG_EXCLUDE_HV: u64 = 1 << 6;
+
+    const IOC_ENABLE: libc::c_ulong = 0x2400;
+    const IOC_DISABLE: libc::c_ulong = 0x2401;
+    const IOC_RESET: libc::c_ulong = 0x2403;
+
+    /// The first published layout of `struct perf_event_attr`
+    /// (`PERF_ATTR_SIZE_VER1`); the kernel accepts older sizes and zero-fills
+    /// the rest.
+    #[repr(C)]
+    #[derive(Default)]
+    struct Attr {
+        kind: u32,
+        size: u32,
+        config: u64,
+        sample_period: u64,
+        sample_type: u64,
+        read_format: u64,
+        flags: u64,
+        wakeup_events: u32,
+        bp_type: u32,
+        config1: u64,
+        config2: u64,
+    }
+
+    /// One hardware counter, following the calling thread on any CPU.
+    pub struct Counter(OwnedFd);
+
+    impl Counter {
+        pub fn open(config: u64) -> Result<Self> {
+            let attr = Attr {
+                kind: TYPE_HARDWARE,
+                size: std::mem::size_of::<Attr>() as u32,
+                config,
+                flags: FLAG_DISABLED | FLAG_EXCLUDE_KERNEL | FLAG_EXCLUDE_HV,
+                ..Default::default()
+            };
+            // SAFETY: `attr` is a valid, fully initialised perf_event_attr
+            // prefix whose `size` matches its layout.
+            let fd = unsafe {
+                libc::syscall(
+                    libc::SYS_perf_event_open,
+                    &attr as *const Attr,
+                    0 as libc::pid_t,
+                    -1 as libc::c_int,
+                    -1 as libc::c_int,
+                    0 as libc::c_ulong,
+                )
+            };
+            if fd < 0 {
+                let err = io::Error::last_os_error();
+                bail!("perf_event_open failed ({err}); is a PMU available?");
+            }
+            // SAFETY: the syscall returned a new descriptor that nothing else owns.
+            Ok(Self(unsafe { OwnedFd::from_raw_fd(fd as libc::c_int) }))
+        }
+
+        fn ioctl(&self, request: libc::c_ulong) -> Result<()> {
+            // SAFETY: the descriptor is an open perf event and these requests
+            // take no argument.
+            if unsafe { libc::ioctl(self.0.as_raw_fd(), request as _, 0) } < 0 {
+                return Err(io::Error::last_os_error()).into_diagnostic();
+            }
+            Ok(())
+        }
+
+        pub fn reset(&self) -> Result<()> {
+            self.ioctl(IOC_RESET)
+        }
+
+        pub fn enable(&self) -> Result<()> {
+            self.ioctl(IOC_ENABLE)
+        }
+
+        pub fn disable(&self) -> Result<()> {
+            self.ioctl(IOC_DISABLE)
+        }
+
+        pub fn read(&self) -> Result<u64> {
+            let mut value = 0u64;
+            // SAFETY: `value` is valid for writes of 8 bytes, which is what a
+            // counter without read_format flags returns.
+            let read = unsafe {
+                libc::read(
+                    self.0.as_raw_fd(),
+                    &mut value as *mut u64 as *mut libc::c_void,
+                    std::mem::size_of::<u64>(),
+                )
+            };
+            if read != std::mem::size_of::<u64>() as isize {
+                return Err(io::Error::last_os_error()).into_diagnostic();
+            }
+            Ok(value)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn attr_matches_the_first_kernel_layout() {
+            assert_eq!(std::mem::size_of::<Attr>(), 72);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_work_when_a_pmu_is_available() {
+        // Containers and VMs often have no PMU; there is nothing to check then.
+        let Ok(counters) = measure(3, || (0..10_000u64).map(std::hint::black_box).sum::<u64>())
+        else {
+            return;
+        };
+        assert!(counters.instructions > 10_000.0, "{counters}");
+        assert!(counters.cycles > 0.0, "{counters}");
+    }
+}
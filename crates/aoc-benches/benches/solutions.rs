@@ -0,0 +1,30 @@
+use std::sync::LazyLock;
+
+use aoc_benches::{benches, Bench};
+use aoc_common::solution::Part;
+
+fn main() {
+    divan::main();
+}
+
+static BENCHES: LazyLock<Vec<Bench>> = LazyLock::new(benches);
+
+/// Parses the input and solves the part on every iteration, like the
+/// binaries do.
+fn bench(bencher: divan::Bencher, day: &Bench, part: Part) {
+    if !day.solves(part) {
+        eprintln!("skipping {day} part {part}: it does not solve");
+        return;
+    }
+    bencher.bench(|| day.entry.run(divan::black_box(&day.input), part).unwrap());
+}
+
+#[divan::bench(args = BENCHES.iter(), max_time = 5)]
+fn part1(bencher: divan::Bencher, day: &Bench) {
+    bench(bencher, day, Part::One);
+}
+
+#[divan::bench(args = BENCHES.iter(), max_time = 5)]
+fn part2(bencher: divan::Bencher, day: &Bench) {
+    bench(bencher, day, Part::Two);
+}
@@ -0,0 +1,56 @@
+//! The days benchmarked by the shared `solutions` harness.
+//!
+//! Every solution in [`aoc_registry`] is timed the same way from its stored
+//! input, so no day needs a bench file of its own just for its two parts.
+//! Days keep their own benches only for what is specific to them, such as
+//! comparing alternative implementations.
+
+use std::fmt;
+
+use aoc_common::solution::{Entry, Part};
+use aoc_runtime::Day;
+
+/// A registered day together with its stored input.
+pub struct Bench {
+    pub entry: &'static Entry,
+    pub input: String,
+}
+
+impl Bench {
+    /// Whether `part` solves at all, so unfinished parts can be skipped
+    /// rather than failing the whole run.
+    pub fn solves(&self, part: Part) -> bool {
+        self.entry.run(&self.input, part).is_ok()
+    }
+}
+
+/// Labelled `<year>-<day>` with the day zero-padded, so a filter such as
+/// `2025-01` picks a single day.
+impl fmt::Display for Bench {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{:02}", self.entry.year, self.entry.day)
+    }
+}
+
+/// Every registered day whose input is in the store, warning about the
+/// ones that are not.
+pub fn benches() -> Vec<Bench> {
+    aoc_registry::solutions()
+        .iter()
+        .copied()
+        .filter_map(|entry| {
+            let day = Day {
+                year: entry.year,
+                day: entry.day,
+                crate_dir: "",
+            };
+            match day.load_input() {
+                Ok(input) => Some(Bench { entry, input }),
+                Err(error) => {
+                    eprintln!("skipping {}: {error:?}", entry.package);
+                    None
+                }
+            }
+        })
+        .collect()
+}
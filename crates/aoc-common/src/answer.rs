@@ -0,0 +1,169 @@
+//! Typed puzzle answers.
+
+use std::fmt;
+
+/// A puzzle answer, kept as a number whenever the puzzle asks for one.
+///
+/// Answers compare by their printed form, so `U64(5)`, `I64(5)` and
+/// `Text("5")` are all equal: what matters is what would be submitted.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    U64(u64),
+    I64(i64),
+    U128(u128),
+    Text(String),
+}
+
+impl Answer {
+    /// Reads an answer back from its printed form, preferring the narrowest
+    /// numeric variant.
+    pub fn parse(s: &str) -> Self {
+        if let Ok(n) = s.parse() {
+            Answer::U64(n)
+        } else if let Ok(n) = s.parse() {
+            Answer::I64(n)
+        } else if let Ok(n) = s.parse() {
+            Answer::U128(n)
+        } else {
+            Answer::Text(s.to_string())
+        }
+    }
+
+    /// Whether the answer is a number.
+    pub fn is_numeric(&self) -> bool {
+        !matches!(self, Answer::Text(_))
+    }
+
+    /// The answer as a JSON value: numbers unquoted, text as a string.
+    ///
+    /// Values beyond `u64` are quoted, since most JSON readers stop at
+    /// 64-bit integers.
+    pub fn to_json(&self) -> String {
+        match self {
+            Answer::U64(n) => n.to_string(),
+            Answer::I64(n) => n.to_string(),
+            Answer::U128(n) => match u64::try_from(*n) {
+                Ok(n) => n.to_string(),
+                Err(_) => format!("\"{n}\""),
+            },
+            Answer::Text(s) => format!("\"{}\"", escape_json(s)),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::U64(n) => write!(f, "{n}"),
+            Answer::I64(n) => write!(f, "{n}"),
+            Answer::U128(n) => write!(f, "{n}"),
+            Answer::Text(s) => f.write_str(s),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::U64(a), Answer::U64(b)) => a == b,
+            (Answer::I64(a), Answer::I64(b)) => a == b,
+            (Answer::U128(a), Answer::U128(b)) => a == b,
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            _ => self.to_string() == other.to_string(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+macro_rules! from_number {
+    ($($t:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::$variant(n.into())
+                }
+            }
+        )*
+    };
+}
+
+from_number!(u8 => U64, u16 => U64, u32 => U64, u64 => U64, i32 => I64, i64 => I64, u128 => U128);
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::U64(n as u64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&String> for Answer {
+    fn from(s: &String) -> Self {
+        Answer::Text(s.clone())
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+impl From<&Answer> for Answer {
+    fn from(answer: &Answer) -> Self {
+        answer.clone()
+    }
+}
+
+/// Escapes `value` for use inside a JSON string literal.
+pub fn escape_json(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_by_value() {
+        assert_eq!(Answer::from(5u64), Answer::from(5i64));
+        assert_eq!(Answer::from(5u64), Answer::from("5"));
+        assert_ne!(Answer::from(5u64), Answer::from(-5i64));
+        assert_eq!(Answer::parse("-5"), Answer::I64(-5));
+        assert!(matches!(Answer::parse("12"), Answer::U64(12)));
+        assert!(matches!(Answer::parse("1,2"), Answer::Text(_)));
+    }
+
+    #[test]
+    fn json_is_typed() {
+        assert_eq!(Answer::from(42u64).to_json(), "42");
+        assert_eq!(Answer::from(-3i64).to_json(), "-3");
+        assert_eq!(
+            Answer::from(u128::MAX).to_json(),
+            format!("\"{}\"", u128::MAX)
+        );
+        assert_eq!(Answer::from("a\"b\n").to_json(), "\"a\\\"b\\n\"");
+    }
+
+    #[test]
+    fn escapes_json_strings() {
+        assert_eq!(escape_json("a\"b\\c\n"), "a\\\"b\\\\c\\n");
+    }
+}
@@ -0,0 +1,144 @@
+//! Hand-rolled byte scanning for parsers on hot paths.
+//!
+//! Combinators pay for their generality on every character; inputs made of a
+//! handful of fixed line shapes parse faster with [`lines`] and a [`Cursor`]
+//! per line. Errors still point into the input through [`malformed`], as the
+//! same [`ParseError`] the combinator parsers report.
+
+use miette::LabeledSpan;
+
+use crate::diagnostics::ParseError;
+
+/// Every line of `input` with the offset it starts at, without its line
+/// ending.
+///
+/// Blank lines are kept, so that the line parsers reject them like the
+/// combinator parsers do; only the empty remainder after a final newline is
+/// left out.
+pub fn lines(input: &[u8]) -> impl Iterator<Item = (usize, &[u8])> {
+    let mut start = 0;
+    memchr::memchr_iter(b'\n', input)
+        .map(|end| (end, true))
+        .chain(std::iter::once((input.len(), false)))
+        .filter_map(move |(end, terminated)| {
+            let line = &input[start..end];
+            let offset = start;
+            start = end + 1;
+            (terminated || !line.is_empty())
+                .then(|| (offset, line.strip_suffix(b"\r").unwrap_or(line)))
+        })
+}
+
+/// Reads one line from left to right.
+#[derive(Debug, Clone)]
+pub struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    /// How far into the line the cursor is.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos == self.bytes.len()
+    }
+
+    pub fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    /// Consumes `byte` if it is next.
+    pub fn eat(&mut self, byte: u8) -> bool {
+        let found = self.peek() == Some(byte);
+        self.pos += usize::from(found);
+        found
+    }
+
+    /// Consumes `byte`, or returns `None` if something else is next.
+    pub fn expect(&mut self, byte: u8) -> Option<()> {
+        self.eat(byte).then_some(())
+    }
+
+    /// Consumes any run of spaces.
+    pub fn skip_spaces(&mut self) {
+        while self.eat(b' ') {}
+    }
+
+    /// Reads a decimal number, or returns `None` if there are no digits or it
+    /// does not fit in a `u64`.
+    pub fn uint(&mut self) -> Option<u64> {
+        let start = self.pos;
+        let mut value = 0u64;
+        while let Some(digit @ b'0'..=b'9') = self.peek() {
+            value = value
+                .checked_mul(10)?
+                .checked_add(u64::from(digit - b'0'))?;
+            self.pos += 1;
+        }
+        (self.pos > start).then_some(value)
+    }
+
+    /// Reads an ASCII identifier: a letter or `_`, then letters, digits or
+    /// `_`.
+    pub fn ident(&mut self) -> Option<&'a str> {
+        let start = self.pos;
+        if !matches!(self.peek(), Some(b) if b.is_ascii_alphabetic() || b == b'_') {
+            return None;
+        }
+        while matches!(self.peek(), Some(b) if b.is_ascii_alphanumeric() || b == b'_') {
+            self.pos += 1;
+        }
+        // Only ASCII was consumed, so the slice is valid UTF-8.
+        std::str::from_utf8(&self.bytes[start..self.pos]).ok()
+    }
+}
+
+/// A parse error on the character at `offset` in `input`, or at its end.
+pub fn malformed(input: &str, offset: usize, expected: &str) -> ParseError {
+    let len = input[offset..].chars().next().map_or(0, char::len_utf8);
+    let label =
+        LabeledSpan::new_with_span(Some(format!("expected {expected}")), offset..offset + len);
+    ParseError::from_labels("input", input, vec![label])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_keep_blanks_and_drop_carriage_returns() {
+        let found = lines(b"ab\r\n\ncd\n").collect::<Vec<_>>();
+        assert_eq!(found, [(0, &b"ab"[..]), (4, &b""[..]), (5, &b"cd"[..])]);
+        assert_eq!(lines(b"").count(), 0);
+    }
+
+    #[test]
+    fn malformed_labels_the_offending_character() {
+        let error = malformed("1,2\n3;4", 5, "','");
+        let label = &error.labels()[0];
+        assert_eq!((label.offset(), label.len()), (5, 1));
+        assert_eq!(label.label(), Some("expected ','"));
+        let error = malformed("1,2", 3, "','");
+        assert_eq!(error.labels()[0].offset(), 3);
+    }
+
+    #[test]
+    fn cursor_reads_tokens() {
+        let mut cursor = Cursor::new(b"abc_1: 42 x");
+        assert_eq!(cursor.ident(), Some("abc_1"));
+        assert_eq!(cursor.expect(b':'), Some(()));
+        cursor.skip_spaces();
+        assert_eq!(cursor.uint(), Some(42));
+        assert_eq!(cursor.uint(), None);
+        assert_eq!(cursor.pos(), 9);
+        assert!(!cursor.is_empty());
+        assert_eq!(Cursor::new(b"99999999999999999999").uint(), None);
+    }
+}
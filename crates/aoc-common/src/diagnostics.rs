@@ -0,0 +1,212 @@
+//! Conversion of chumsky parse errors into miette diagnostics, and lenient
+//! parsing that skips malformed items instead of failing on them.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chumsky::error::Rich;
+use chumsky::extra;
+use chumsky::prelude::{none_of, via_parser};
+use chumsky::{text, Parser};
+use miette::{Diagnostic, LabeledSpan, NamedSource};
+
+/// How to treat malformed items in the input.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Any malformed item fails the whole parse.
+    #[default]
+    Strict,
+    /// Malformed items are skipped and reported as warnings, as long as the
+    /// parser can recover from them.
+    Lenient,
+}
+
+/// The output of a parse, with a warning for every malformed item skipped.
+///
+/// Strict parses never skip anything, so their `warnings` are empty.
+#[derive(Debug)]
+pub struct Recovered<T> {
+    pub value: T,
+    /// The skipped spans of the input, labelled with what was wrong there.
+    pub warnings: Vec<LabeledSpan>,
+}
+
+/// A failed parse, labelled against the input it was reading.
+///
+/// Every error chumsky reported becomes a label on the offending span, so the
+/// graphical report handler can underline the exact characters in context.
+#[derive(Debug, Diagnostic)]
+#[diagnostic(code(aoc::parse))]
+pub struct ParseError {
+    #[source_code]
+    source_code: NamedSource<String>,
+    #[label(collection)]
+    labels: Vec<LabeledSpan>,
+}
+
+impl ParseError {
+    /// Builds the diagnostic for `errors` raised while parsing `input`.
+    pub fn new(name: impl AsRef<str>, input: &str, errors: Vec<Rich<'_, char>>) -> Self {
+        Self {
+            source_code: NamedSource::new(name, input.to_string()),
+            labels: labels(errors),
+        }
+    }
+
+    /// Builds the diagnostic for errors already labelled against `input`, as
+    /// parsers that do not go through chumsky report them.
+    pub fn from_labels(name: impl AsRef<str>, input: &str, labels: Vec<LabeledSpan>) -> Self {
+        Self {
+            source_code: NamedSource::new(name, input.to_string()),
+            labels,
+        }
+    }
+
+    /// The labels attached to the input, one per reported error.
+    pub fn labels(&self) -> &[LabeledSpan] {
+        &self.labels
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Failed to parse {}", self.source_code.name())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Runs `parser` over `input`, reporting failures as a [`ParseError`] named
+/// after `name`.
+///
+/// Runs in a `parse` [phase](crate::solution::PHASES) span.
+pub fn parse<'a, T>(
+    parser: impl Parser<'a, &'a str, T, extra::Err<Rich<'a, char>>>,
+    name: impl AsRef<str>,
+    input: &'a str,
+) -> Result<T, ParseError> {
+    let _phase = tracing::info_span!("parse").entered();
+    parser
+        .parse(input)
+        .into_result()
+        .map_err(|errors| ParseError::new(name, input, errors))
+}
+
+/// Runs `parser` over `input`, skipping the items it recovers from when
+/// `mode` is [`ParseMode::Lenient`].
+///
+/// The parser reports recoverable errors the same way in both modes, so it
+/// only needs writing once; see [`skip_malformed`].
+pub fn parse_with<'a, T>(
+    parser: impl Parser<'a, &'a str, T, extra::Err<Rich<'a, char>>>,
+    name: impl AsRef<str>,
+    input: &'a str,
+    mode: ParseMode,
+) -> Result<Recovered<T>, ParseError> {
+    let _phase = tracing::info_span!("parse").entered();
+    match (parser.parse(input).into_output_errors(), mode) {
+        ((Some(value), errors), ParseMode::Lenient) => Ok(Recovered {
+            value,
+            warnings: labels(errors),
+        }),
+        ((Some(value), errors), ParseMode::Strict) if errors.is_empty() => Ok(Recovered {
+            value,
+            warnings: Vec::new(),
+        }),
+        ((_, errors), _) => Err(ParseError::new(name, input, errors)),
+    }
+}
+
+/// Wraps `item` so that, when it fails, the error is recorded and the input
+/// is skipped up to the next of the `delimiters`, yielding `None`.
+///
+/// At least one character must be skipped, so an empty item, such as the one
+/// after a trailing separator, still fails as before.
+pub fn skip_malformed<'a, T: Clone + 'a>(
+    item: impl Parser<'a, &'a str, T, extra::Err<Rich<'a, char>>> + Clone,
+    delimiters: &'a str,
+) -> impl Parser<'a, &'a str, Option<T>, extra::Err<Rich<'a, char>>> + Clone {
+    item.map(Some).recover_with(via_parser(
+        none_of(delimiters).repeated().at_least(1).to(None),
+    ))
+}
+
+/// A decimal integer read as `T`.
+///
+/// Unlike `text::int(10).from_str().unwrapped()`, a number that does not fit
+/// in `T` fails with an error on its span instead of panicking.
+pub fn number<'a, T>() -> impl Parser<'a, &'a str, T, extra::Err<Rich<'a, char>>> + Clone
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    text::int(10)
+        .try_map(|digits: &str, span| digits.parse().map_err(|error| Rich::custom(span, error)))
+}
+
+fn labels(errors: Vec<Rich<'_, char>>) -> Vec<LabeledSpan> {
+    errors
+        .into_iter()
+        .map(|error| {
+            let span = error.span().into_range();
+            LabeledSpan::new_with_span(Some(error.to_string()), span)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chumsky::prelude::*;
+
+    fn numbers<'a>() -> impl Parser<'a, &'a str, Vec<u32>, extra::Err<Rich<'a, char>>> {
+        number()
+            .separated_by(just(','))
+            .collect()
+            .then_ignore(end())
+    }
+
+    #[test]
+    fn labels_the_offending_span() {
+        let error = parse(numbers(), "input", "1,2,x").unwrap_err();
+        let label = &error.labels()[0];
+        assert_eq!(label.offset(), 4);
+        assert!(label.label().is_some());
+        assert_eq!(error.to_string(), "Failed to parse input");
+    }
+
+    #[test]
+    fn labels_numbers_that_overflow() {
+        let error = parse(numbers(), "input", "1,4294967296,3").unwrap_err();
+        let label = &error.labels()[0];
+        assert_eq!((label.offset(), label.len()), (2, 10));
+        assert!(label.label().unwrap().contains("too large"));
+    }
+
+    #[test]
+    fn passes_successful_parses_through() {
+        assert_eq!(parse(numbers(), "input", "1,2,3").unwrap(), vec![1, 2, 3]);
+    }
+
+    fn lenient_numbers<'a>() -> impl Parser<'a, &'a str, Vec<u32>, extra::Err<Rich<'a, char>>> {
+        skip_malformed(number(), ",")
+            .separated_by(just(','))
+            .collect::<Vec<_>>()
+            .map(|numbers| numbers.into_iter().flatten().collect())
+            .then_ignore(end())
+    }
+
+    #[test]
+    fn lenient_parses_skip_malformed_items() {
+        let parsed = parse_with(lenient_numbers(), "input", "1,x2,3", ParseMode::Lenient).unwrap();
+        assert_eq!(parsed.value, vec![1, 3]);
+        assert_eq!(parsed.warnings.len(), 1);
+        assert_eq!(parsed.warnings[0].offset(), 2);
+        assert!(parse_with(lenient_numbers(), "input", "1,,3", ParseMode::Lenient).is_err());
+
+        let error =
+            parse_with(lenient_numbers(), "input", "1,x2,3", ParseMode::Strict).unwrap_err();
+        assert_eq!(error.labels()[0].offset(), 2);
+    }
+}
@@ -0,0 +1,92 @@
+//! Disjoint sets over `0..n`.
+
+/// A disjoint set union with path compression and union by rank.
+///
+/// `find` walks the parent chain in a loop rather than recursing, so even a
+/// chain as long as the set cannot overflow the stack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dsu {
+    parent: Vec<usize>,
+    /// An upper bound on the height of every root's tree.
+    rank: Vec<u8>,
+}
+
+impl Dsu {
+    /// `n` singleton sets.
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            rank: vec![0; n],
+        }
+    }
+
+    /// Number of elements.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// The representative of the set holding `i`.
+    pub fn find(&mut self, i: usize) -> usize {
+        let mut root = i;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        // Second pass: point the whole chain straight at the root.
+        let mut i = i;
+        while self.parent[i] != root {
+            i = std::mem::replace(&mut self.parent[i], root);
+        }
+        root
+    }
+
+    /// Joins the sets holding `i` and `j`, returning whether they were apart.
+    pub fn union(&mut self, i: usize, j: usize) -> bool {
+        let (root_i, root_j) = (self.find(i), self.find(j));
+        if root_i == root_j {
+            return false;
+        }
+        let (low, high) = match self.rank[root_i] < self.rank[root_j] {
+            true => (root_i, root_j),
+            false => (root_j, root_i),
+        };
+        self.parent[low] = high;
+        if self.rank[low] == self.rank[high] {
+            self.rank[high] += 1;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn joins_sets() {
+        let mut dsu = Dsu::new(5);
+        assert!(dsu.union(0, 1));
+        assert!(dsu.union(3, 4));
+        assert!(!dsu.union(1, 0));
+        assert!(dsu.union(1, 4));
+        assert_eq!(dsu.find(0), dsu.find(3));
+        assert_ne!(dsu.find(0), dsu.find(2));
+        assert_eq!(dsu.len(), 5);
+    }
+
+    #[test]
+    fn survives_a_chain_as_long_as_the_set() {
+        // Union by rank never builds this chain itself, so build it by hand:
+        // a recursive find would need a frame per element.
+        let n = 1_000_000usize;
+        let mut dsu = Dsu {
+            parent: (0..n).map(|i| i.saturating_sub(1)).collect(),
+            rank: vec![0; n],
+        };
+        assert_eq!(dsu.find(n - 1), 0);
+        assert!(dsu.parent.iter().all(|&p| p == 0));
+    }
+}
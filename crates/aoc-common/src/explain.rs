@@ -0,0 +1,33 @@
+//! Narrated walkthroughs of how a solver reaches its answer.
+//!
+//! Solvers describe their key steps with [`explain!`](crate::explain!), which
+//! emits `DEBUG` events under [`TARGET`]. They cost nothing unless the runner
+//! is started with `AOC_EXPLAIN` set, in which case it prints them as a
+//! numbered walkthrough instead of the usual logs.
+
+#[doc(hidden)]
+pub use tracing;
+
+/// Tracing target carrying the walkthrough.
+pub const TARGET: &str = "aoc::explain";
+
+/// Records one step of the walkthrough.
+///
+/// Takes the same fields and format arguments as [`tracing::debug!`].
+#[macro_export]
+macro_rules! explain {
+    ($($arg:tt)+) => {
+        $crate::explain::tracing::debug!(target: $crate::explain::TARGET, $($arg)+)
+    };
+}
+
+/// Opens a span whose fields label every step recorded inside it, so steps
+/// from parallel work can still be told apart.
+///
+/// Takes the same arguments as [`tracing::debug_span!`].
+#[macro_export]
+macro_rules! explain_span {
+    ($($arg:tt)+) => {
+        $crate::explain::tracing::debug_span!(target: $crate::explain::TARGET, $($arg)+)
+    };
+}
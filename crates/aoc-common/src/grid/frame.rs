@@ -0,0 +1,161 @@
+//! Frame capture for animating grid simulations.
+//!
+//! Simulations call [`Grid::record_frame`] whenever they want a snapshot to
+//! appear in the output; what happens to it is up to the [`FrameSink`]. A
+//! closure can render frames live, [`Recording`] stores them compactly as one
+//! keyframe followed by per-frame diffs, and `()` discards them so that the
+//! recording hooks cost nothing in the plain `process` path.
+
+use super::Grid;
+
+/// Receives grid snapshots from a running simulation.
+pub trait FrameSink<T> {
+    fn push(&mut self, grid: &Grid<T>);
+
+    /// Whether frames are consumed at all. Simulations that need extra work
+    /// to build a frame can skip it when this is `false`.
+    fn is_recording(&self) -> bool {
+        true
+    }
+}
+
+/// Discards every frame.
+impl<T> FrameSink<T> for () {
+    #[inline]
+    fn push(&mut self, _grid: &Grid<T>) {}
+
+    #[inline]
+    fn is_recording(&self) -> bool {
+        false
+    }
+}
+
+impl<T, F: FnMut(&Grid<T>)> FrameSink<T> for F {
+    fn push(&mut self, grid: &Grid<T>) {
+        self(grid)
+    }
+}
+
+/// One stored frame of a [`Recording`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Frame<T> {
+    /// A full snapshot, stored for the first frame and whenever the
+    /// dimensions change.
+    Key(Grid<T>),
+    /// The cells that changed since the previous frame, as
+    /// `(flat index, new value)` pairs.
+    Delta(Vec<(usize, T)>),
+}
+
+/// An in-memory [`FrameSink`] that stores frames as diffs.
+#[derive(Debug, Clone)]
+pub struct Recording<T> {
+    frames: Vec<Frame<T>>,
+    last: Option<Grid<T>>,
+}
+
+impl<T> Default for Recording<T> {
+    fn default() -> Self {
+        Self {
+            frames: Vec::new(),
+            last: None,
+        }
+    }
+}
+
+impl<T: Clone + PartialEq> Recording<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The recorded frames, in capture order.
+    pub fn frames(&self) -> &[Frame<T>] {
+        &self.frames
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Rebuilds the full grid of every recorded frame.
+    pub fn replay(&self) -> impl Iterator<Item = Grid<T>> + '_ {
+        self.frames.iter().scan(None::<Grid<T>>, |current, frame| {
+            match frame {
+                Frame::Key(grid) => *current = Some(grid.clone()),
+                Frame::Delta(changes) => {
+                    let grid = current.as_mut().expect("recordings start with a keyframe");
+                    for (idx, value) in changes {
+                        grid.cells[*idx] = value.clone();
+                    }
+                }
+            }
+            current.clone()
+        })
+    }
+}
+
+impl<T: Clone + PartialEq> FrameSink<T> for Recording<T> {
+    fn push(&mut self, grid: &Grid<T>) {
+        let frame = match &self.last {
+            Some(last) if last.width == grid.width && last.height == grid.height => Frame::Delta(
+                last.cells
+                    .iter()
+                    .zip(&grid.cells)
+                    .enumerate()
+                    .filter(|(_, (old, new))| old != new)
+                    .map(|(idx, (_, new))| (idx, new.clone()))
+                    .collect(),
+            ),
+            _ => Frame::Key(grid.clone()),
+        };
+        self.frames.push(frame);
+        self.last = Some(grid.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_keyframe_then_deltas() {
+        let mut grid = Grid::filled(3, 2, 0u8);
+        let mut recording = Recording::new();
+
+        grid.record_frame(&mut recording);
+        grid[(1, 0)] = 7;
+        grid.record_frame(&mut recording);
+        grid.record_frame(&mut recording);
+
+        assert!(matches!(recording.frames()[0], Frame::Key(_)));
+        assert_eq!(recording.frames()[1], Frame::Delta(vec![(1, 7)]));
+        assert_eq!(recording.frames()[2], Frame::Delta(vec![]));
+
+        let replayed = recording.replay().collect::<Vec<_>>();
+        assert_eq!(replayed.len(), 3);
+        assert_eq!(replayed[0], Grid::filled(3, 2, 0));
+        assert_eq!(replayed[2], grid);
+    }
+
+    #[test]
+    fn resizing_starts_a_new_keyframe() {
+        let mut recording = Recording::new();
+        Grid::filled(2, 2, false).record_frame(&mut recording);
+        Grid::filled(3, 1, true).record_frame(&mut recording);
+
+        assert!(matches!(recording.frames()[1], Frame::Key(_)));
+        assert_eq!(recording.replay().last(), Some(Grid::filled(3, 1, true)));
+    }
+
+    #[test]
+    fn closures_are_sinks() {
+        let mut rendered = Vec::new();
+        let grid = Grid::new(2, 1, vec!['a', 'b']);
+        grid.record_frame(&mut |g: &Grid<char>| rendered.push(g.render(|&c| c)));
+        assert_eq!(rendered, vec!["ab\n".to_string()]);
+    }
+}
@@ -0,0 +1,366 @@
+//! 2D grids: a dense, row-major [`Grid`] and a run-length encoded [`RleGrid`]
+//! for large, sparse inputs.
+//!
+//! [`Grid`] also carries the helpers the days keep needing: parsing from
+//! characters, 4- and 8-connected neighbors, and row and column iteration.
+
+mod frame;
+mod rle;
+
+pub use frame::{Frame, FrameSink, Recording};
+pub use rle::RleGrid;
+
+use std::ops::{Index, IndexMut};
+
+use miette::{bail, Result};
+
+/// Offsets of the 4-connected neighbors: up, left, right, down.
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// Offsets of the 8-connected neighbors, in reading order.
+const ADJACENT: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// Cell access shared by the grid representations, so an algorithm written
+/// against it can switch storage without changes.
+pub trait GridAccess<T>: Index<(usize, usize), Output = T> + IndexMut<(usize, usize)> {
+    fn width(&self) -> usize;
+
+    fn height(&self) -> usize;
+
+    /// Returns the cell at `(x, y)`, or `None` when out of bounds.
+    fn get(&self, x: usize, y: usize) -> Option<&T>;
+
+    /// Returns the cell at `(x, y)` mutably, or `None` when out of bounds.
+    fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T>;
+}
+
+/// A dense 2D grid stored as a single row-major vector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Wraps row-major `cells` as a `width` x `height` grid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cells.len() != width * height`.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "a {width}x{height} grid needs {} cells",
+            width * height
+        );
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Builds a grid by evaluating `f(x, y)` for every cell.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+        Self::new(width, height, cells)
+    }
+
+    /// Parses one row per line of `input`, turning every character into a
+    /// cell with `cell`.
+    ///
+    /// Blank lines are skipped, so a trailing newline is harmless. Fails when
+    /// the rows are not all the same width.
+    pub fn from_chars(input: &str, mut cell: impl FnMut(char) -> T) -> Result<Self> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::with_capacity(input.len());
+        for line in input.lines().filter(|line| !line.is_empty()) {
+            let before = cells.len();
+            cells.extend(line.chars().map(&mut cell));
+            let row = cells.len() - before;
+            match width {
+                None => width = Some(row),
+                Some(width) if width != row => bail!(
+                    "Row {} has {} cells, but the rows above have {}",
+                    height,
+                    row,
+                    width
+                ),
+                Some(_) => {}
+            }
+            height += 1;
+        }
+        Ok(Self::new(width.unwrap_or(0), height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The row-major backing storage.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// Mutable access to the row-major backing storage.
+    pub fn cells_mut(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    /// Flat index of `(x, y)` into [`cells`](Self::cells).
+    #[inline]
+    pub fn index_of(&self, x: usize, y: usize) -> usize {
+        y * self.width + x
+    }
+
+    /// Returns the cell at `(x, y)`, or `None` when out of bounds.
+    #[inline]
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width && y < self.height {
+            Some(&self.cells[self.index_of(x, y)])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the cell at `(x, y)` mutably, or `None` when out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            let idx = self.index_of(x, y);
+            Some(&mut self.cells[idx])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the cell at `(x, y)` without bounds checking.
+    ///
+    /// # Safety
+    ///
+    /// `x` must be below [`width`](Self::width) and `y` below
+    /// [`height`](Self::height).
+    #[inline]
+    pub unsafe fn get_unchecked(&self, x: usize, y: usize) -> &T {
+        debug_assert!(x < self.width && y < self.height);
+        // SAFETY: the caller keeps `(x, y)` in bounds, so the index is below
+        // `width * height == cells.len()`.
+        unsafe { self.cells.get_unchecked(self.index_of(x, y)) }
+    }
+
+    /// Returns the cell at `(x, y)` mutably without bounds checking.
+    ///
+    /// # Safety
+    ///
+    /// `x` must be below [`width`](Self::width) and `y` below
+    /// [`height`](Self::height).
+    #[inline]
+    pub unsafe fn get_unchecked_mut(&mut self, x: usize, y: usize) -> &mut T {
+        debug_assert!(x < self.width && y < self.height);
+        let idx = self.index_of(x, y);
+        // SAFETY: as in `get_unchecked`.
+        unsafe { self.cells.get_unchecked_mut(idx) }
+    }
+
+    /// The in-bounds neighbors of `(x, y)` sharing an edge with it.
+    pub fn neighbors4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offset_by(x, y, &ORTHOGONAL)
+    }
+
+    /// The in-bounds neighbors of `(x, y)` sharing an edge or a corner with it.
+    pub fn neighbors8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offset_by(x, y, &ADJACENT)
+    }
+
+    fn offset_by<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let nx = x.checked_add_signed(dx).filter(|&nx| nx < self.width)?;
+            let ny = y.checked_add_signed(dy).filter(|&ny| ny < self.height)?;
+            Some((nx, ny))
+        })
+    }
+
+    /// Row `y`, left to right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `y` is out of bounds.
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {y} out of bounds");
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Every row, top to bottom.
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &[T]> + '_ {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// Column `x`, top to bottom.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is out of bounds.
+    pub fn column(&self, x: usize) -> impl ExactSizeIterator<Item = &T> + '_ {
+        assert!(x < self.width, "column {x} out of bounds");
+        (0..self.height).map(move |y| &self.cells[y * self.width + x])
+    }
+
+    /// Every column, left to right.
+    pub fn columns(&self) -> impl ExactSizeIterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Coordinates of the first cell, in reading order, matching `pred`.
+    pub fn position(&self, pred: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        let idx = self.cells.iter().position(pred)?;
+        Some((idx % self.width, idx / self.width))
+    }
+
+    /// A grid of the same size holding `f` of every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    /// Hands the current state of the grid to `sink` as one animation frame.
+    pub fn record_frame<S: FrameSink<T> + ?Sized>(&self, sink: &mut S) {
+        sink.push(self);
+    }
+
+    /// Renders the grid as text, one line per row.
+    pub fn render(&self, mut glyph: impl FnMut(&T) -> char) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for row in self.cells.chunks(self.width.max(1)) {
+            out.extend(row.iter().map(&mut glyph));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid with every cell set to `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Self::new(width, height, vec![value; width * height])
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) out of bounds"
+        );
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    #[inline]
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) out of bounds"
+        );
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+impl<T> GridAccess<T> for Grid<T> {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.get(x, y)
+    }
+
+    fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.get_mut(x, y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indexing_is_row_major() {
+        let grid = Grid::from_fn(3, 2, |x, y| x + 10 * y);
+        assert_eq!(grid.cells(), &[0, 1, 2, 10, 11, 12]);
+        assert_eq!(grid[(2, 1)], 12);
+        assert_eq!(grid.get(1, 1), Some(&11));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, 2), None);
+    }
+
+    #[test]
+    fn parses_rows_of_chars() -> Result<()> {
+        let grid = Grid::from_chars("@.@\n.@.\n", |c| c == '@')?;
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.row(1), &[false, true, false]);
+        assert_eq!(grid.position(|&paper| paper), Some((0, 0)));
+        assert!(Grid::from_chars("@.@\n.@\n", |c| c).is_err());
+        assert_eq!(Grid::from_chars("", |c| c)?.height(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn neighbors_stay_in_bounds() {
+        let grid = Grid::filled(3, 3, 0);
+        assert_eq!(grid.neighbors4(1, 1).count(), 4);
+        assert_eq!(grid.neighbors8(1, 1).count(), 8);
+        assert_eq!(grid.neighbors4(0, 0).collect::<Vec<_>>(), [(1, 0), (0, 1)]);
+        assert_eq!(
+            grid.neighbors8(2, 2).collect::<Vec<_>>(),
+            [(1, 1), (2, 1), (1, 2)]
+        );
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = Grid::from_fn(3, 2, |x, y| x + 10 * y);
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [&[0, 1, 2], &[10, 11, 12]]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), [1, 11]);
+        assert_eq!(grid.columns().len(), 3);
+        assert_eq!(grid.map(|&n| n * 2)[(2, 1)], 24);
+        // SAFETY: (2, 1) is inside the 3x2 grid.
+        assert_eq!(unsafe { *grid.get_unchecked(2, 1) }, 12);
+    }
+
+    #[test]
+    fn render_rows() {
+        let grid = Grid::new(2, 2, vec![true, false, false, true]);
+        assert_eq!(grid.render(|&b| if b { '#' } else { '.' }), "#.\n.#\n");
+    }
+}
@@ -0,0 +1,278 @@
+//! A run-length encoded grid for large, mostly uniform inputs.
+
+use std::ops::{Index, IndexMut};
+
+use super::{Grid, GridAccess};
+
+/// A 2D grid that stores each row as runs of equal cells.
+///
+/// Memory grows with the number of runs rather than the number of cells, so a
+/// mostly empty manifold costs a few entries per row. Reads are a binary
+/// search within the row. [`set`](Self::set) keeps runs merged, while
+/// [`get_mut`](Self::get_mut) and `IndexMut` split off a single-cell run that
+/// stays separate until [`compact`](Self::compact).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RleGrid<T> {
+    width: usize,
+    height: usize,
+    /// Per row, `(first column, value)` pairs sorted by column. A run extends
+    /// up to the next run's first column, or the end of the row.
+    rows: Vec<Vec<(usize, T)>>,
+}
+
+impl<T: Clone + PartialEq> RleGrid<T> {
+    /// Encodes row-major `cells` as a `width` x `height` grid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cells.len() != width * height`.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "a {width}x{height} grid needs {} cells",
+            width * height
+        );
+        let mut cells = cells.into_iter();
+        Self::from_fn(width, height, |_, _| cells.next().unwrap())
+    }
+
+    /// Builds a grid by evaluating `f(x, y)` for every cell.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let rows = (0..height)
+            .map(|y| {
+                let mut runs: Vec<(usize, T)> = Vec::new();
+                for x in 0..width {
+                    let value = f(x, y);
+                    if runs.last().is_none_or(|(_, last)| *last != value) {
+                        runs.push((x, value));
+                    }
+                }
+                runs
+            })
+            .collect();
+        Self {
+            width,
+            height,
+            rows,
+        }
+    }
+
+    /// Creates a grid with every cell set to `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        let row = if width > 0 {
+            vec![(0, value)]
+        } else {
+            Vec::new()
+        };
+        Self {
+            width,
+            height,
+            rows: vec![row; height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Total number of runs across all rows.
+    pub fn run_count(&self) -> usize {
+        self.rows.iter().map(Vec::len).sum()
+    }
+
+    /// Position within row `y` of the run covering column `x`.
+    #[inline]
+    fn run_of(&self, x: usize, y: usize) -> usize {
+        self.rows[y].partition_point(|&(start, _)| start <= x) - 1
+    }
+
+    /// Returns the cell at `(x, y)`, or `None` when out of bounds.
+    #[inline]
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width && y < self.height {
+            Some(&self.rows[y][self.run_of(x, y)].1)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the cell at `(x, y)` mutably, or `None` when out of bounds.
+    ///
+    /// The cell is split into a run of its own first, so writes through the
+    /// reference only affect that cell.
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            let i = self.isolate(x, y);
+            Some(&mut self.rows[y][i].1)
+        } else {
+            None
+        }
+    }
+
+    /// Sets the cell at `(x, y)`, merging it with equal neighbouring runs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `(x, y)` is out of bounds.
+    pub fn set(&mut self, x: usize, y: usize, value: T) {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) out of bounds"
+        );
+        if self[(x, y)] == value {
+            return;
+        }
+        let i = self.isolate(x, y);
+        let row = &mut self.rows[y];
+        row[i].1 = value;
+        if i + 1 < row.len() && row[i + 1].1 == row[i].1 {
+            row.remove(i + 1);
+        }
+        if i > 0 && row[i - 1].1 == row[i].1 {
+            row.remove(i);
+        }
+    }
+
+    /// Merges adjacent runs holding equal values, undoing the splits left
+    /// behind by [`get_mut`](Self::get_mut).
+    pub fn compact(&mut self) {
+        for row in &mut self.rows {
+            row.dedup_by(|(_, next), (_, prev)| next == prev);
+        }
+    }
+
+    /// Splits the run covering `(x, y)` so that the cell has a run of its own,
+    /// returning that run's position within the row.
+    fn isolate(&mut self, x: usize, y: usize) -> usize {
+        let mut i = self.run_of(x, y);
+        let row = &mut self.rows[y];
+        let end = row.get(i + 1).map_or(self.width, |&(start, _)| start);
+        if row[i].0 < x {
+            let value = row[i].1.clone();
+            row.insert(i + 1, (x, value));
+            i += 1;
+        }
+        if x + 1 < end {
+            let value = row[i].1.clone();
+            row.insert(i + 1, (x + 1, value));
+        }
+        i
+    }
+
+    /// Renders the grid as text, one line per row.
+    pub fn render(&self, mut glyph: impl FnMut(&T) -> char) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for row in &self.rows {
+            for (i, (start, value)) in row.iter().enumerate() {
+                let end = row.get(i + 1).map_or(self.width, |&(next, _)| next);
+                let c = glyph(value);
+                out.extend(std::iter::repeat_n(c, end - start));
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Expands the runs into a dense [`Grid`].
+    pub fn to_dense(&self) -> Grid<T> {
+        Grid::from_fn(self.width, self.height, |x, y| self[(x, y)].clone())
+    }
+}
+
+impl<T: Clone + PartialEq> From<&Grid<T>> for RleGrid<T> {
+    fn from(grid: &Grid<T>) -> Self {
+        Self::from_fn(grid.width(), grid.height(), |x, y| grid[(x, y)].clone())
+    }
+}
+
+impl<T: Clone + PartialEq> Index<(usize, usize)> for RleGrid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y)
+            .unwrap_or_else(|| panic!("({x}, {y}) out of bounds"))
+    }
+}
+
+impl<T: Clone + PartialEq> IndexMut<(usize, usize)> for RleGrid<T> {
+    #[inline]
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        self.get_mut(x, y)
+            .unwrap_or_else(|| panic!("({x}, {y}) out of bounds"))
+    }
+}
+
+impl<T: Clone + PartialEq> GridAccess<T> for RleGrid<T> {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.get(x, y)
+    }
+
+    fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.get_mut(x, y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_dense_grid() {
+        let dense = Grid::from_fn(7, 3, |x, y| (x + y) / 3);
+        let rle = RleGrid::from(&dense);
+        assert_eq!(rle.to_dense(), dense);
+        assert_eq!(rle.run_count(), 9);
+        assert_eq!(rle[(6, 2)], 2);
+        assert_eq!(rle.get(7, 0), None);
+        assert_eq!(
+            rle.render(|&v| char::from(b'0' + v as u8)),
+            dense.render(|&v| char::from(b'0' + v as u8))
+        );
+    }
+
+    #[test]
+    fn writes_split_and_merge_runs() {
+        let mut grid = RleGrid::filled(5, 1, '.');
+        grid.set(2, 0, '^');
+        assert_eq!(grid.render(|&c| c), "..^..\n");
+        assert_eq!(grid.run_count(), 3);
+
+        grid.set(2, 0, '.');
+        assert_eq!(grid.run_count(), 1);
+
+        grid[(0, 0)] = '|';
+        grid[(1, 0)] = '.';
+        assert_eq!(grid.render(|&c| c), "|....\n");
+        assert_eq!(grid.run_count(), 3);
+        grid.compact();
+        assert_eq!(grid.run_count(), 2);
+    }
+
+    #[test]
+    fn algorithms_run_on_either_storage() {
+        fn count<G: GridAccess<char>>(grid: &G) -> usize {
+            (0..grid.height())
+                .flat_map(|y| (0..grid.width()).map(move |x| (x, y)))
+                .filter(|&(x, y)| grid.get(x, y) == Some(&'#'))
+                .count()
+        }
+
+        let dense = Grid::new(3, 2, "#..##.".chars().collect());
+        assert_eq!(count(&dense), 3);
+        assert_eq!(count(&RleGrid::from(&dense)), 3);
+    }
+}
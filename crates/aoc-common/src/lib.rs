@@ -0,0 +1,15 @@
+//! Building blocks shared between the daily solutions.
+
+pub mod answer;
+pub mod bytes;
+pub mod diagnostics;
+pub mod dsu;
+pub mod explain;
+pub mod grid;
+pub mod par;
+pub mod ranges;
+pub mod solution;
+
+// Used by `solution!` to register each day.
+#[doc(hidden)]
+pub use inventory;
@@ -0,0 +1,90 @@
+//! Rayon's parallel iterators, or plain iterators standing in for them.
+//!
+//! Days import [`prelude`] instead of `rayon::prelude`. With the `parallel`
+//! feature it is rayon's own; without it the same methods run one item after
+//! another on the calling thread, so the days also build for targets without
+//! threads such as `wasm32-unknown-unknown`. Only the parts of rayon the days
+//! use are mirrored.
+
+#[cfg(feature = "parallel")]
+pub use rayon::{current_num_threads, prelude};
+
+/// How many items the days should hand out at once; always one without
+/// threads.
+#[cfg(not(feature = "parallel"))]
+pub fn current_num_threads() -> usize {
+    1
+}
+
+#[cfg(not(feature = "parallel"))]
+pub mod prelude {
+    //! Sequential stand-ins for rayon's iterator traits.
+
+    /// `into_par_iter` is `into_iter`.
+    pub trait IntoParallelIterator: IntoIterator + Sized {
+        fn into_par_iter(self) -> Self::IntoIter {
+            self.into_iter()
+        }
+    }
+
+    impl<I: IntoIterator> IntoParallelIterator for I {}
+
+    /// `par_iter` is `iter`.
+    pub trait IntoParallelRefIterator<'data> {
+        type Iter: Iterator;
+
+        fn par_iter(&'data self) -> Self::Iter;
+    }
+
+    impl<'data, C: ?Sized + 'data> IntoParallelRefIterator<'data> for C
+    where
+        &'data C: IntoIterator,
+    {
+        type Iter = <&'data C as IntoIterator>::IntoIter;
+
+        fn par_iter(&'data self) -> Self::Iter {
+            self.into_iter()
+        }
+    }
+
+    /// The rayon adaptors whose names differ from [`Iterator`]'s.
+    pub trait ParallelIterator: Iterator + Sized {
+        fn flat_map_iter<U, F>(self, f: F) -> std::iter::FlatMap<Self, U, F>
+        where
+            U: IntoIterator,
+            F: FnMut(Self::Item) -> U,
+        {
+            self.flat_map(f)
+        }
+
+        fn reduce_with<F>(self, f: F) -> Option<Self::Item>
+        where
+            F: FnMut(Self::Item, Self::Item) -> Self::Item,
+        {
+            self.reduce(f)
+        }
+    }
+
+    impl<I: Iterator> ParallelIterator for I {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::prelude::*;
+
+    #[test]
+    fn mirrors_the_rayon_methods_the_days_use() {
+        let numbers = vec![1u64, 2, 3, 4];
+        assert_eq!(numbers.par_iter().map(|n| n * 2).sum::<u64>(), 20);
+        assert_eq!(
+            numbers
+                .clone()
+                .into_par_iter()
+                .flat_map_iter(|n| [n; 2])
+                .collect::<Vec<_>>(),
+            [1, 1, 2, 2, 3, 3, 4, 4]
+        );
+        assert_eq!(numbers.par_iter().copied().reduce_with(u64::max), Some(4));
+        assert!(super::current_num_threads() >= 1);
+    }
+}
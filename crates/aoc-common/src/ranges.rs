@@ -0,0 +1,294 @@
+//! Sets of integers kept as the ranges covering them.
+
+use std::fmt::Display;
+use std::ops::{Add, RangeInclusive, Sub};
+
+use sorting::kmerge_by;
+
+use crate::explain;
+
+/// An integer type a [`RangeSet`] can hold.
+pub trait Endpoint: Copy + Ord + Display + Add<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// `self + 1`, or `self` at the type's maximum.
+    fn saturating_succ(self) -> Self;
+}
+
+macro_rules! endpoint {
+    ($($t:ty),*) => {
+        $(impl Endpoint for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn saturating_succ(self) -> Self {
+                self.saturating_add(1)
+            }
+        })*
+    };
+}
+
+endpoint!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// A set of integers, stored as sorted ranges that neither overlap nor
+/// touch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeSet<T = u64> {
+    ranges: Vec<RangeInclusive<T>>,
+}
+
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self {
+        Self { ranges: Vec::new() }
+    }
+}
+
+impl<T: Endpoint> RangeSet<T> {
+    /// The union of `ranges`, merging overlapping and adjacent ones in a
+    /// single scan. Empty ranges are ignored.
+    pub fn new(ranges: &[RangeInclusive<T>]) -> Self {
+        // Merge the runs already ascending by start instead of sorting them all,
+        // so mostly ordered lists cost little more than the scan.
+        let runs = ranges.chunk_by(|a, b| a.start() <= b.start());
+        let mut ranges = kmerge_by(runs, |a, b| a.start() < b.start()).filter(|r| !r.is_empty());
+
+        let mut merged = Vec::new();
+        if let Some(first) = ranges.next() {
+            let mut current_start = *first.start();
+            let mut current_end = *first.end();
+
+            for r in ranges {
+                let next_start = *r.start();
+                let next_end = *r.end();
+
+                // Check if ranges overlap or are adjacent (contiguous integers)
+                // e.g., 3-5 and 6-8 should merge into 3-8.
+                if next_start <= current_end.saturating_succ() {
+                    // Merge: extend the current end if the next one goes further
+                    if next_end > current_end {
+                        current_end = next_end;
+                    }
+                    explain!("merge {next_start}-{next_end} into {current_start}-{current_end}");
+                } else {
+                    // Gap detected: The current merged range is complete
+                    merged.push(current_start..=current_end);
+                    explain!(
+                        "close {current_start}-{current_end} before the gap {}-{}",
+                        current_end + T::ONE,
+                        next_start - T::ONE
+                    );
+
+                    // Start tracking the new range
+                    current_start = next_start;
+                    current_end = next_end;
+                }
+            }
+            // Don't forget to add the last range
+            merged.push(current_start..=current_end);
+            explain!("close {current_start}-{current_end}, the last range");
+        }
+
+        Self { ranges: merged }
+    }
+
+    /// Adds `range`, merging it with the ranges it overlaps or touches.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let (start, end) = range.into_inner();
+        // The ranges in `first..last` overlap or touch the new one.
+        let first = self
+            .ranges
+            .partition_point(|r| r.end().saturating_succ() < start);
+        let last = self
+            .ranges
+            .partition_point(|r| *r.start() <= end.saturating_succ());
+        let merged = match &self.ranges[first..last] {
+            [] => start..=end,
+            touched => {
+                let lowest = *touched[0].start();
+                let highest = *touched[touched.len() - 1].end();
+                start.min(lowest)..=end.max(highest)
+            }
+        };
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    /// Adds every integer of `other`.
+    pub fn merge(&mut self, other: &Self) {
+        for range in other {
+            self.insert(range);
+        }
+    }
+
+    /// The disjoint ranges, in ascending order.
+    pub fn ranges(&self) -> &[RangeInclusive<T>] {
+        &self.ranges
+    }
+
+    /// Iterates over the disjoint ranges, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.into_iter()
+    }
+
+    /// Number of integers in the set.
+    ///
+    /// Overflows when the set covers every value of `T`.
+    pub fn len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::ZERO, |len, r| len + (*r.end() - *r.start() + T::ONE))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Whether `value` is in the set.
+    pub fn contains(&self, value: T) -> bool {
+        let after = self.ranges.partition_point(|r| *r.start() <= value);
+        after > 0 && value <= *self.ranges[after - 1].end()
+    }
+
+    /// How many of `values` are in the set, each found by a binary search on
+    /// the range starts.
+    pub fn count_contained(&self, values: impl IntoIterator<Item = T>) -> usize {
+        values
+            .into_iter()
+            .filter(|&value| self.contains(value))
+            .count()
+    }
+}
+
+impl<T: Endpoint> FromIterator<RangeInclusive<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        Self::new(&iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<T: Endpoint> Extend<RangeInclusive<T>> for RangeSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl<'a, T: Endpoint> IntoIterator for &'a RangeSet<T> {
+    type Item = RangeInclusive<T>;
+    type IntoIter = std::iter::Cloned<std::slice::Iter<'a, RangeInclusive<T>>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.iter().cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_overlapping_and_adjacent_ranges() {
+        #[allow(clippy::reversed_empty_ranges)]
+        let set = RangeSet::new(&[10..=12, 3..=5, 6..=8, 20..=25, 5..=1, 21..=22]);
+        assert_eq!(set.ranges(), [3..=8, 10..=12, 20..=25]);
+        assert_eq!(set.len(), 15);
+        assert!(set.contains(3) && set.contains(12) && set.contains(25));
+        assert!(!set.contains(2) && !set.contains(9) && !set.contains(26));
+        assert_eq!(set.count_contained([0, 3, 8, 9, 12, 12, 30]), 4);
+        assert!(RangeSet::<u64>::new(&[]).is_empty());
+    }
+
+    #[test]
+    fn reaches_the_largest_values() {
+        let set = [u64::MAX - 1..=u64::MAX, 0..=0, u64::MAX..=u64::MAX]
+            .into_iter()
+            .collect::<RangeSet>();
+        assert_eq!(set.ranges(), [0..=0, u64::MAX - 1..=u64::MAX]);
+        assert!(set.contains(u64::MAX));
+    }
+
+    #[test]
+    fn keeps_single_points() {
+        let set = RangeSet::new(&[7..=7, 5..=5, 9..=9]);
+        assert_eq!(set.ranges(), [5..=5, 7..=7, 9..=9]);
+        assert_eq!(set.len(), 3);
+        assert!(!set.contains(6) && !set.contains(8));
+
+        let set = RangeSet::new(&[7..=7, 6..=6, 8..=8]);
+        assert_eq!(set.ranges(), [6..=8]);
+    }
+
+    #[test]
+    fn inserts_into_place() {
+        let mut set = RangeSet::new(&[0..=2, 10..=12, 20..=22]);
+        set.insert(5..=6);
+        assert_eq!(set.ranges(), [0..=2, 5..=6, 10..=12, 20..=22]);
+
+        // Touches 0-2 on the left and 5-6 on the right.
+        set.insert(3..=4);
+        assert_eq!(set.ranges(), [0..=6, 10..=12, 20..=22]);
+
+        // Falls entirely inside an existing range.
+        set.insert(11..=11);
+        assert_eq!(set.ranges(), [0..=6, 10..=12, 20..=22]);
+
+        // Swallows several ranges whole.
+        set.insert(8..=30);
+        assert_eq!(set.ranges(), [0..=6, 8..=30]);
+
+        #[allow(clippy::reversed_empty_ranges)]
+        set.insert(50..=40);
+        assert_eq!(set.len(), 30);
+    }
+
+    #[test]
+    fn merges_sets() {
+        let mut set = RangeSet::new(&[1..=3, 10..=10]);
+        set.merge(&RangeSet::new(&[4..=9, 20..=21]));
+        assert_eq!(set.iter().collect::<Vec<_>>(), [1..=10, 20..=21]);
+
+        set.extend([-5..=0]);
+        assert_eq!(set.ranges(), [-5..=10, 20..=21]);
+        assert_eq!(set.len(), 18);
+    }
+
+    #[test]
+    fn inserting_matches_building_at_once() {
+        // A small xorshift keeps the test free of a rand dependency.
+        let mut state = 0x9e37_79b9_7f4a_7c15u64;
+        let mut next = |bound: u64| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state % bound
+        };
+        for _ in 0..500 {
+            let ranges = (0..next(10))
+                .map(|_| {
+                    let start = next(40);
+                    start..=start + next(6)
+                })
+                .collect::<Vec<_>>();
+            let mut set = RangeSet::default();
+            for range in &ranges {
+                set.insert(range.clone());
+            }
+            assert_eq!(set, RangeSet::new(&ranges), "{ranges:?}");
+            for value in 0..50 {
+                let inside = ranges.iter().any(|r| r.contains(&value));
+                assert_eq!(set.contains(value), inside, "{value} in {ranges:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn inserts_at_the_largest_values() {
+        let mut set = RangeSet::new(&[0..=0]);
+        set.insert(u8::MAX..=u8::MAX);
+        set.insert(u8::MAX - 2..=u8::MAX - 1);
+        assert_eq!(set.ranges(), [0..=0, u8::MAX - 2..=u8::MAX]);
+    }
+}
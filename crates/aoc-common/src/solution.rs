@@ -0,0 +1,255 @@
+//! The interface a day exposes to shared tooling.
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use miette::Result;
+
+use crate::answer::Answer;
+
+/// Which half of a puzzle to solve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// Names of the `tracing` spans the runner times as phases of a solution.
+///
+/// [`Solution::run`] wraps the whole parse and solve stages in `parse` and
+/// `solve` spans; days open their own, with
+/// `tracing::info_span!("build")` and the like, around work such as building
+/// a graph or an index before searching it, or around parsing that happens
+/// inside a part. A phase nested in another is only counted once, in the
+/// innermost one.
+pub const PHASES: [&str; 3] = ["parse", "build", "solve"];
+
+/// A day's solution, split into a parse stage shared by both parts and a
+/// solve stage per part, so each stage can be measured on its own.
+pub trait Solution {
+    /// What the input parses into; both parts solve from the same value.
+    type Parsed;
+
+    fn parse(input: &str) -> Result<Self::Parsed>;
+
+    fn part1(parsed: &Self::Parsed) -> Result<Answer>;
+
+    fn part2(parsed: &Self::Parsed) -> Result<Answer>;
+
+    /// How many items the input parsed into, for days whose parsed form has a
+    /// natural count.
+    fn items(_parsed: &Self::Parsed) -> Option<usize> {
+        None
+    }
+
+    /// Solves `part` from an already parsed input.
+    fn solve(parsed: &Self::Parsed, part: Part) -> Result<Answer> {
+        match part {
+            Part::One => Self::part1(parsed),
+            Part::Two => Self::part2(parsed),
+        }
+    }
+
+    /// Parses `input` and solves `part`.
+    fn run(input: &str, part: Part) -> Result<Answer> {
+        let parsed = tracing::info_span!("parse").in_scope(|| Self::parse(input))?;
+        tracing::info_span!("solve").in_scope(|| Self::solve(&parsed, part))
+    }
+
+    /// Like [`Solution::run`], recording how long each stage took.
+    fn run_timed(input: &str, part: Part) -> Result<Timed> {
+        let start = Instant::now();
+        let parsed = tracing::info_span!("parse").in_scope(|| Self::parse(input))?;
+        let parse = start.elapsed();
+
+        let start = Instant::now();
+        let answer = tracing::info_span!("solve").in_scope(|| Self::solve(&parsed, part))?;
+        let solve = start.elapsed();
+
+        Ok(Timed {
+            answer,
+            parse,
+            solve,
+            items: Self::items(&parsed),
+        })
+    }
+}
+
+/// An answer along with the time spent in each stage producing it.
+#[derive(Debug, Clone)]
+pub struct Timed {
+    pub answer: Answer,
+    pub parse: Duration,
+    pub solve: Duration,
+    /// See [`Solution::items`].
+    pub items: Option<usize>,
+}
+
+/// A registered [`Solution`] with its type erased, so every day can sit in
+/// one list.
+///
+/// Days declare theirs with [`solution!`](crate::solution!), which also
+/// submits it to the set that [`entries`] walks.
+#[derive(Debug, Clone, Copy)]
+pub struct Entry {
+    /// Package of the day, e.g. `aoc2025-day-8`.
+    pub package: &'static str,
+    pub year: u16,
+    pub day: u8,
+    run: fn(&str, Part) -> Result<Answer>,
+    run_timed: fn(&str, Part) -> Result<Timed>,
+}
+
+impl Entry {
+    /// The entry for `S`, with the year and day read from `package`.
+    ///
+    /// # Panics
+    ///
+    /// Panics, at compile time when used in a constant, if `package` is not
+    /// named `aoc<year>-day-<day>`.
+    pub const fn new<S: Solution>(package: &'static str) -> Self {
+        let (year, day) = package_day(package);
+        Self {
+            package,
+            year,
+            day,
+            run: S::run,
+            run_timed: S::run_timed,
+        }
+    }
+
+    /// Parses `input` and solves `part`.
+    pub fn run(&self, input: &str, part: Part) -> Result<Answer> {
+        (self.run)(input, part)
+    }
+
+    /// Like [`Entry::run`], recording how long each stage took.
+    pub fn run_timed(&self, input: &str, part: Part) -> Result<Timed> {
+        (self.run_timed)(input, part)
+    }
+}
+
+inventory::collect!(Entry);
+
+/// Every [`Entry`] linked into the binary, in no particular order.
+///
+/// A day only shows up once its crate is linked, which is what
+/// `aoc-registry` is for.
+pub fn entries() -> impl Iterator<Item = &'static Entry> {
+    inventory::iter::<Entry>.into_iter()
+}
+
+/// Reads the year and day out of a package name such as `aoc2025-day-7`.
+const fn package_day(package: &str) -> (u16, u8) {
+    const fn digit(byte: u8) -> u8 {
+        assert!(
+            byte.is_ascii_digit(),
+            "package is not named aoc<year>-day-<day>"
+        );
+        byte - b'0'
+    }
+
+    let bytes = package.as_bytes();
+    assert!(
+        bytes.len() > 12
+            && bytes[0] == b'a'
+            && bytes[1] == b'o'
+            && bytes[2] == b'c'
+            && bytes[7] == b'-'
+            && bytes[8] == b'd'
+            && bytes[9] == b'a'
+            && bytes[10] == b'y'
+            && bytes[11] == b'-',
+        "package is not named aoc<year>-day-<day>"
+    );
+
+    let mut year = 0;
+    let mut i = 3;
+    while i < 7 {
+        year = year * 10 + digit(bytes[i]) as u16;
+        i += 1;
+    }
+    let mut day = 0;
+    let mut i = 12;
+    while i < bytes.len() {
+        day = day * 10 + digit(bytes[i]);
+        i += 1;
+    }
+    (year, day)
+}
+
+/// Declares `SOLUTION`, the calling day's [`Entry`] for the given
+/// [`Solution`] type, and submits it to [`entries`].
+#[macro_export]
+macro_rules! solution {
+    ($solution:ty) => {
+        /// This day's entry in the registry of solutions.
+        pub const SOLUTION: $crate::solution::Entry =
+            $crate::solution::Entry::new::<$solution>(env!("CARGO_PKG_NAME"));
+
+        $crate::inventory::submit! { SOLUTION }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Parsed = Vec<u32>;
+
+        fn parse(input: &str) -> Result<Self::Parsed> {
+            input
+                .split_whitespace()
+                .map(|n| n.parse().map_err(|e| miette::miette!("{e}")))
+                .collect()
+        }
+
+        fn part1(parsed: &Self::Parsed) -> Result<Answer> {
+            Ok(parsed.iter().sum::<u32>().into())
+        }
+
+        fn part2(parsed: &Self::Parsed) -> Result<Answer> {
+            Ok(parsed.iter().product::<u32>().into())
+        }
+
+        fn items(parsed: &Self::Parsed) -> Option<usize> {
+            Some(parsed.len())
+        }
+    }
+
+    #[test]
+    fn stages_compose() -> Result<()> {
+        assert_eq!(Sum::run("2 3 4", Part::One)?, Answer::U64(9));
+        let timed = Sum::run_timed("2 3 4", Part::Two)?;
+        assert_eq!(
+            (timed.answer.to_string(), timed.items),
+            ("24".into(), Some(3))
+        );
+        assert!(Sum::run("2 x", Part::One).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn entries_erase_the_solution() -> Result<()> {
+        const ENTRY: Entry = Entry::new::<Sum>("aoc2025-day-12");
+        assert_eq!((ENTRY.year, ENTRY.day), (2025, 12));
+        assert_eq!(ENTRY.run("2 3 4", Part::Two)?, Answer::U64(24));
+        Ok(())
+    }
+}
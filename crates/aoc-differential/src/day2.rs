@@ -0,0 +1,60 @@
+use std::collections::HashSet;
+
+use aoc_solutions::y2025::day2::{part1, part2};
+
+/// Reference for part 1: the ID is made of two identical halves.
+pub fn is_doubled(n: u64) -> bool {
+    let s = n.to_string();
+    let (left, right) = s.split_at(s.len() / 2);
+    s.len().is_multiple_of(2) && left == right
+}
+
+/// Reference for part 2: a string is a repetition of a shorter block exactly
+/// when it occurs inside its own doubling with the first and last characters
+/// removed.
+pub fn is_repeated(n: u64) -> bool {
+    let s = n.to_string();
+    let doubled = s.repeat(2);
+    doubled[1..doubled.len() - 1].contains(&s)
+}
+
+/// Checks both part 2 predicates (string based and mask math) and both
+/// `process` functions against the references on one random set of ranges.
+pub fn check(seed: u64) {
+    let mut rng = aoc_gen::rng(seed);
+    let ranges = aoc_gen::day2::ranges(&mut rng, 4, 12, 2_000);
+    let input = aoc_gen::day2::render(&ranges);
+
+    let ids: HashSet<u64> = ranges
+        .iter()
+        .flat_map(|&(start, end)| start..=end)
+        .collect();
+
+    for &id in &ids {
+        let expected = is_repeated(id);
+        assert_eq!(
+            part2::is_invalid_id(id),
+            expected,
+            "is_invalid_id({id}) disagrees with the reference [seed {seed}]"
+        );
+        assert_eq!(
+            part2::is_invalid_id_optimized(id),
+            expected,
+            "is_invalid_id_optimized({id}) disagrees with the reference [seed {seed}]"
+        );
+    }
+
+    let expected1: u64 = ids.iter().filter(|&&id| is_doubled(id)).sum();
+    let expected2: u64 = ids.iter().filter(|&&id| is_repeated(id)).sum();
+
+    assert_eq!(
+        part1::process(&input).unwrap(),
+        expected1.to_string(),
+        "part1 on {input:?} [seed {seed}]"
+    );
+    assert_eq!(
+        part2::process(&input).unwrap(),
+        expected2.to_string(),
+        "part2 on {input:?} [seed {seed}]"
+    );
+}
@@ -0,0 +1,56 @@
+use aoc_solutions::y2025::day9::part2;
+use itertools::Itertools;
+
+/// Returns whether the tile at `(x, y)` is red or green, i.e. on the outline
+/// or strictly inside it.
+///
+/// Tile centers sit on integer coordinates like the vertices, so a point that
+/// is not on any edge can be classified by casting a ray towards `+x` and
+/// counting the vertical edges it crosses (half-open in `y` so that vertices
+/// are not counted twice).
+fn is_colored(polygon: &[(i64, i64)], (x, y): (i64, i64)) -> bool {
+    let edges = polygon.iter().copied().circular_tuple_windows::<(_, _)>();
+
+    let mut crossings = 0;
+    for ((x1, y1), (x2, y2)) in edges {
+        let on_edge =
+            (x1.min(x2)..=x1.max(x2)).contains(&x) && (y1.min(y2)..=y1.max(y2)).contains(&y);
+        if on_edge {
+            return true;
+        }
+        if x1 == x2 && x1 > x && (y1.min(y2)..y1.max(y2)).contains(&y) {
+            crossings += 1;
+        }
+    }
+    crossings % 2 == 1
+}
+
+/// Brute-force part 2: tries every pair of red tiles and checks every tile of
+/// the rectangle they span.
+pub fn max_area(polygon: &[(i64, i64)]) -> u64 {
+    polygon
+        .iter()
+        .tuple_combinations()
+        .filter(|(a, b)| {
+            (a.0.min(b.0)..=a.0.max(b.0))
+                .cartesian_product(a.1.min(b.1)..=a.1.max(b.1))
+                .all(|tile| is_colored(polygon, tile))
+        })
+        .map(|(a, b)| (a.0.abs_diff(b.0) + 1) * (a.1.abs_diff(b.1) + 1))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Checks the compressed-grid `GeometryEngine` against the naive rectangle
+/// scan on one random rectilinear polygon.
+pub fn check(seed: u64) {
+    let mut rng = aoc_gen::rng(seed);
+    let polygon = aoc_gen::day9::polygon(&mut rng, 6, 5);
+    let input = aoc_gen::day9::render(&polygon);
+
+    assert_eq!(
+        part2::process(&input).unwrap(),
+        max_area(&polygon).to_string(),
+        "part2 on {input:?} [seed {seed}]"
+    );
+}
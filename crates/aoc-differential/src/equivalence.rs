@@ -0,0 +1,47 @@
+//! Property tests asserting that two implementations of the same thing agree.
+//!
+//! Several days keep a straightforward implementation next to the optimized
+//! one (or a chumsky parser next to a hand-rolled byte scanner). Each such pair
+//! is registered with [`equivalent!`](crate::equivalent) against a proptest
+//! strategy from [`strategy`](crate::strategy), so that disagreements come
+//! back shrunk to a minimal input instead of a seed.
+
+/// Registers proptest cases asserting that two expressions agree on every
+/// generated input.
+///
+/// Each entry names the test, binds a pattern to a strategy like
+/// [`proptest!`](proptest::proptest) does, and gives the two expressions to
+/// compare, separated by a comma:
+///
+/// ```ignore
+/// aoc_differential::equivalent! {
+///     day2_is_invalid_id(id in 1..=u64::MAX / 10):
+///         part2::is_invalid_id(id), part2::is_invalid_id_optimized(id);
+/// }
+/// ```
+///
+/// An optional `#![proptest_config(...)]` at the top applies to every entry.
+#[macro_export]
+macro_rules! equivalent {
+    (
+        #![proptest_config($config:expr)]
+        $($(#[$meta:meta])* $name:ident($arg:pat in $strategy:expr): $left:expr, $right:expr;)+
+    ) => {
+        $crate::__proptest::proptest! {
+            #![proptest_config($config)]
+            $(
+                $(#[$meta])*
+                #[test]
+                fn $name($arg in $strategy) {
+                    $crate::__proptest::prop_assert_eq!($left, $right);
+                }
+            )+
+        }
+    };
+    ($($(#[$meta:meta])* $name:ident($arg:pat in $strategy:expr): $left:expr, $right:expr;)+) => {
+        $crate::equivalent! {
+            #![proptest_config($crate::__proptest::test_runner::Config::default())]
+            $($(#[$meta])* $name($arg in $strategy): $left, $right;)+
+        }
+    };
+}
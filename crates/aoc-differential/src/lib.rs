@@ -0,0 +1,25 @@
+//! Differential checks of the optimized solvers against brute-force references.
+//!
+//! Every `check(seed)` generates a small random input through [`aoc_gen`], solves
+//! it with both the real solver and a deliberately naive reference, and panics
+//! with the seed and the input if they disagree. The integration tests sweep a
+//! fixed range of seeds; a fuzz target can drive the same checks by turning its
+//! input bytes into a seed with [`seed_from_bytes`].
+//!
+//! Where a day keeps two implementations of the same thing, [`equivalent!`]
+//! registers property tests asserting that they agree; see [`equivalence`].
+
+pub mod day2;
+pub mod day9;
+pub mod equivalence;
+pub mod strategy;
+
+#[doc(hidden)]
+pub use proptest as __proptest;
+
+/// Folds arbitrary fuzzer bytes into a generator seed.
+pub fn seed_from_bytes(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
@@ -0,0 +1,115 @@
+//! Proptest strategies producing well-formed puzzle inputs.
+//!
+//! Unlike the [`aoc_gen`] generators these shrink: a failing worksheet loses
+//! blocks and digits until only the part the implementations disagree on is
+//! left.
+
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+/// Renders one value per line, with or without the trailing newline.
+fn lines<T>(values: Vec<T>, trailing: bool, render: impl Fn(T) -> String) -> String {
+    let mut out = values
+        .into_iter()
+        .map(render)
+        .collect::<Vec<_>>()
+        .join("\n");
+    if trailing {
+        out.push('\n');
+    }
+    out
+}
+
+/// 2024 day 1: 1..64 pairs of location ids, one pair per line.
+///
+/// The ids are small so that both lists repeat values, which is what the
+/// similarity score counts.
+pub fn location_lists() -> impl Strategy<Value = String> {
+    (vec((1u32..100, 1u32..100), 1..64), any::<bool>())
+        .prop_map(|(pairs, trailing)| lines(pairs, trailing, |(a, b)| format!("{a}   {b}")))
+}
+
+/// Day 6: a worksheet of 1..8 problems over `2..=4` rows of numbers.
+///
+/// Each problem's numbers are aligned left or right within the problem's
+/// width, with its operator under the first column, as in the puzzle input.
+pub fn worksheet() -> impl Strategy<Value = String> {
+    (2usize..=4).prop_flat_map(|rows| {
+        let problem = (
+            vec(1u64..10_000, rows),
+            any::<bool>(),
+            prop_oneof![Just('+'), Just('*')],
+        );
+        vec(problem, 1..8).prop_map(move |problems| {
+            let mut grid = vec![String::new(); rows + 1];
+            for (numbers, left, op) in problems {
+                let width = numbers.iter().map(|n| n.to_string().len()).max().unwrap();
+                for (line, n) in grid.iter_mut().zip(&numbers) {
+                    if !line.is_empty() {
+                        line.push(' ');
+                    }
+                    match left {
+                        true => line.push_str(&format!("{n:<width$}")),
+                        false => line.push_str(&format!("{n:>width$}")),
+                    }
+                }
+                let ops = &mut grid[rows];
+                if !ops.is_empty() {
+                    ops.push(' ');
+                }
+                ops.push_str(&format!("{op:<width$}"));
+            }
+            grid.join("\n")
+        })
+    })
+}
+
+/// Day 8: 1..32 junction boxes, one `x,y,z` per line.
+pub fn junction_boxes() -> impl Strategy<Value = String> {
+    (
+        vec((0u32..100_000, 0u32..100_000, 0u32..100_000), 1..32),
+        any::<bool>(),
+    )
+        .prop_map(|(points, trailing)| lines(points, trailing, |(x, y, z)| format!("{x},{y},{z}")))
+}
+
+/// Day 9: 2..32 red tiles, one `x,y` per line.
+pub fn red_tiles() -> impl Strategy<Value = String> {
+    (vec((0u32..100_000, 0u32..100_000), 2..32), any::<bool>())
+        .prop_map(|(tiles, trailing)| lines(tiles, trailing, |(x, y)| format!("{x},{y}")))
+}
+
+/// Day 10: 1..4 machines with up to 5 counters and 5 buttons each.
+///
+/// The joltage targets are produced by pressing every button a random number
+/// of times, so every machine is solvable.
+pub fn machines() -> impl Strategy<Value = String> {
+    let machine = (1usize..=5).prop_flat_map(|counters| {
+        let button = proptest::sample::subsequence((0..counters).collect::<Vec<_>>(), 1..=counters);
+        (vec(any::<bool>(), counters), vec((button, 0u32..8), 1..=5)).prop_map(
+            move |(lights, buttons)| {
+                let mut targets = vec![0; counters];
+                for (wires, presses) in &buttons {
+                    for &wire in wires {
+                        targets[wire] += presses;
+                    }
+                }
+                let lights: String = lights
+                    .iter()
+                    .map(|&on| if on { '#' } else { '.' })
+                    .collect();
+                let buttons = buttons
+                    .iter()
+                    .map(|(wires, _)| format!("({})", itertools::join(wires, ",")))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                format!(
+                    "[{lights}] {buttons} {{{}}}",
+                    itertools::join(&targets, ",")
+                )
+            },
+        )
+    });
+    (vec(machine, 1..4), any::<bool>())
+        .prop_map(|(machines, trailing)| lines(machines, trailing, |m| m))
+}
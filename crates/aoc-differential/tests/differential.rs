@@ -0,0 +1,17 @@
+use std::ops::Range;
+
+const SEEDS: Range<u64> = 0..200;
+
+#[test]
+fn day2_matches_reference() {
+    for seed in SEEDS {
+        aoc_differential::day2::check(seed);
+    }
+}
+
+#[test]
+fn day9_matches_reference() {
+    for seed in SEEDS {
+        aoc_differential::day9::check(seed);
+    }
+}
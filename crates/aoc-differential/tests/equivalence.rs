@@ -0,0 +1,70 @@
+use aoc_differential::{equivalent, strategy};
+use proptest::prelude::*;
+
+mod y2024_day1 {
+    use super::*;
+    use aoc_solutions::y2024::day1::part2::{self, Strategy};
+
+    equivalent! {
+        similarity(input in strategy::location_lists()):
+            part2::process_with(&input, Strategy::HashMap).unwrap(),
+            part2::process_with(&input, Strategy::Sorted).unwrap();
+    }
+}
+
+mod day2 {
+    use super::*;
+    use aoc_solutions::y2025::day2::part2;
+
+    equivalent! {
+        is_invalid_id(id in 1..u64::MAX / 10):
+            part2::is_invalid_id(id), part2::is_invalid_id_optimized(id);
+        is_invalid_id_small(id in 1..10_000_000u64):
+            part2::is_invalid_id(id), part2::is_invalid_id_optimized(id);
+    }
+}
+
+mod day6 {
+    use super::*;
+    use aoc_solutions::y2025::day6::part2;
+
+    equivalent! {
+        process(input in strategy::worksheet()):
+            part2::process(&input).unwrap(), part2::process_rescan(&input).unwrap();
+    }
+}
+
+mod day8 {
+    use super::*;
+    use aoc_solutions::y2025::day8::{part1, part1_bytes};
+
+    equivalent! {
+        parse(input in strategy::junction_boxes()):
+            part1::parse(&input).unwrap(), part1_bytes::parse(&input).unwrap();
+    }
+}
+
+mod day9 {
+    use super::*;
+    use aoc_solutions::y2025::day9::{part1, part1_bytes};
+
+    equivalent! {
+        process(input in strategy::red_tiles()):
+            part1::process(&input).unwrap(), part1_bytes::process(&input).unwrap();
+        largest_area(points in prop::collection::vec((0i64..100, 0i64..100), 0..64)):
+            part1::largest_area(&points), part1::largest_area_pairs(&points);
+    }
+}
+
+mod day10 {
+    use super::*;
+    use aoc_solutions::y2025::day10::part2;
+
+    equivalent! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+        process(input in strategy::machines()):
+            part2::process(&input).unwrap(), part2::process_exact(&input).unwrap();
+        process_lattice(input in strategy::machines()):
+            part2::process(&input).unwrap(), part2::process_lattice(&input).unwrap();
+    }
+}
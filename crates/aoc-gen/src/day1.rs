@@ -0,0 +1,21 @@
+use rand::Rng;
+
+/// Generates `count` dial rotations, each turning `1..=max_distance` clicks
+/// left or right.
+pub fn rotations(rng: &mut impl Rng, count: usize, max_distance: u32) -> Vec<(char, u32)> {
+    (0..count)
+        .map(|_| {
+            let direction = if rng.random_bool(0.5) { 'L' } else { 'R' };
+            (direction, rng.random_range(1..=max_distance))
+        })
+        .collect()
+}
+
+/// Renders rotations in the puzzle format (one `L68` per line).
+pub fn render(rotations: &[(char, u32)]) -> String {
+    rotations
+        .iter()
+        .map(|(direction, distance)| format!("{direction}{distance}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
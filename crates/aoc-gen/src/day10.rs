@@ -0,0 +1,89 @@
+use rand::seq::index;
+use rand::Rng;
+
+/// A machine: its indicator lights, the counters each button is wired to and
+/// the joltage targets.
+#[derive(Debug, Clone)]
+pub struct Machine {
+    pub lights: Vec<bool>,
+    pub buttons: Vec<Vec<usize>>,
+    pub targets: Vec<u32>,
+}
+
+/// Generates `count` machines with `1..=max_counters` counters and
+/// `1..=max_buttons` buttons each.
+///
+/// Both parts stay solvable: the lights are the toggles of a random subset
+/// of the buttons, and the targets come from pressing every button
+/// `0..=max_presses` times.
+pub fn machines(
+    rng: &mut impl Rng,
+    count: usize,
+    max_counters: usize,
+    max_buttons: usize,
+    max_presses: u32,
+) -> Vec<Machine> {
+    (0..count)
+        .map(|_| {
+            let counters = rng.random_range(1..=max_counters);
+            let buttons: Vec<Vec<usize>> = (0..rng.random_range(1..=max_buttons))
+                .map(|_| {
+                    let wires = rng.random_range(1..=counters);
+                    let mut wires = index::sample(rng, counters, wires).into_vec();
+                    wires.sort_unstable();
+                    wires
+                })
+                .collect();
+
+            let mut lights = vec![false; counters];
+            let mut targets = vec![0; counters];
+            for wires in &buttons {
+                let toggled = rng.random_bool(0.5);
+                let presses = rng.random_range(0..=max_presses);
+                for &wire in wires {
+                    lights[wire] ^= toggled;
+                    targets[wire] += presses;
+                }
+            }
+            Machine {
+                lights,
+                buttons,
+                targets,
+            }
+        })
+        .collect()
+}
+
+/// Renders the machines in the puzzle format (`[.##.] (3) (1,3) {3,5,4,7}`).
+pub fn render(machines: &[Machine]) -> String {
+    machines
+        .iter()
+        .map(|machine| {
+            let lights: String = machine
+                .lights
+                .iter()
+                .map(|&on| if on { '#' } else { '.' })
+                .collect();
+            let buttons: Vec<String> = machine
+                .buttons
+                .iter()
+                .map(|wires| format!("({})", list(wires)))
+                .collect();
+            format!(
+                "[{lights}] {} {{{}}}",
+                buttons.join(" "),
+                list(&machine.targets)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Joins values with commas.
+fn list<T: ToString>(values: &[T]) -> String {
+    values
+        .iter()
+        .map(T::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
@@ -0,0 +1,91 @@
+use std::collections::HashSet;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Devices the puzzle refers to by name; generated graphs always contain them.
+const NAMED: [&str; 5] = ["svr", "you", "fft", "dac", "out"];
+
+/// Generates a random device graph of `nodes` devices (at least the five
+/// named ones), each with `1..=max_outputs` outputs.
+///
+/// Devices are laid out in a random topological order with `svr` and `you`
+/// near the start, `fft` and `dac` somewhere in the middle and `out` last.
+/// Every output points further along that order, so the graph is acyclic,
+/// and every device but `out` has an output, so every path ends at `out`.
+/// Some path from `svr` is guaranteed to visit `fft` and then `dac`. The
+/// number of paths grows polynomially with `nodes`, which keeps the counts
+/// within `u128`.
+pub fn graph(rng: &mut impl Rng, nodes: usize, max_outputs: usize) -> Vec<(String, Vec<String>)> {
+    let nodes = nodes.max(NAMED.len());
+    let mut names = vec![String::new(); nodes];
+    names[0] = NAMED[0].to_string();
+    names[1.min(nodes - 2)] = NAMED[1].to_string();
+    let middle = nodes / 2;
+    names[rng.random_range(2..middle.max(3))] = NAMED[2].to_string();
+    names[rng.random_range(middle.max(3)..nodes - 1)] = NAMED[3].to_string();
+    names[nodes - 1] = NAMED[4].to_string();
+
+    // Three letters like the puzzle's, more once those would run short.
+    let letters = (3..).find(|&n| 26usize.pow(n) >= 2 * nodes).unwrap();
+    let mut taken: HashSet<String> = NAMED.iter().map(|name| name.to_string()).collect();
+    for name in names.iter_mut().filter(|name| name.is_empty()) {
+        *name = loop {
+            let candidate: String = (0..letters).map(|_| rng.random_range('a'..='z')).collect();
+            if taken.insert(candidate.clone()) {
+                break candidate;
+            }
+        };
+    }
+
+    let mut outputs: Vec<Vec<usize>> = (0..nodes - 1)
+        .map(|u| {
+            let count = rng.random_range(1..=max_outputs.min(nodes - 1 - u));
+            let mut targets: Vec<usize> = Vec::with_capacity(count);
+            while targets.len() < count {
+                let v = rng.random_range(u + 1..nodes);
+                if !targets.contains(&v) {
+                    targets.push(v);
+                }
+            }
+            targets
+        })
+        .collect();
+
+    // Every path ends at `out`, but one through `fft` and `dac` has to be
+    // laid down: a random chain from `svr` to `fft` and from there to `dac`.
+    let waypoints: Vec<usize> = [NAMED[0], NAMED[2], NAMED[3]]
+        .iter()
+        .map(|&name| names.iter().position(|n| n == name).unwrap())
+        .collect();
+    for pair in waypoints.windows(2) {
+        let (mut u, end) = (pair[0], pair[1]);
+        while u < end {
+            let v = rng.random_range(u + 1..=end);
+            if !outputs[u].contains(&v) {
+                outputs[u].push(v);
+            }
+            u = v;
+        }
+    }
+
+    let mut lines: Vec<(String, Vec<String>)> = outputs
+        .into_iter()
+        .enumerate()
+        .map(|(u, targets)| {
+            let targets = targets.into_iter().map(|v| names[v].clone()).collect();
+            (names[u].clone(), targets)
+        })
+        .collect();
+    lines.shuffle(rng);
+    lines
+}
+
+/// Renders the graph in the puzzle format (`aaa: bbb ccc`).
+pub fn render(graph: &[(String, Vec<String>)]) -> String {
+    graph
+        .iter()
+        .map(|(device, outputs)| format!("{device}: {}", outputs.join(" ")))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,104 @@
+use rand::seq::index;
+use rand::Rng;
+
+/// A region under a tree and how many presents of each shape must fit in it.
+#[derive(Debug, Clone)]
+pub struct Region {
+    pub width: usize,
+    pub height: usize,
+    pub counts: Vec<usize>,
+}
+
+/// Generates `count` present shapes, each `5..=7` cells of a 3x3 box.
+///
+/// The cells are drawn anywhere in the box, so shapes need not be
+/// connected; the packing does not care.
+pub fn shapes(rng: &mut impl Rng, count: usize) -> Vec<[[bool; 3]; 3]> {
+    (0..count)
+        .map(|_| {
+            let mut shape = [[false; 3]; 3];
+            let cells = rng.random_range(5..=7);
+            for cell in index::sample(rng, 9, cells) {
+                shape[cell / 3][cell % 3] = true;
+            }
+            shape
+        })
+        .collect()
+}
+
+/// Generates `count` regions between `min_side` and `max_side` cells across,
+/// each asking for presents of the given shapes.
+///
+/// As in the puzzle, about half the regions are roomy: their presents fill
+/// about `fill` (at most 1) of the 3x3 boxes that fit side by side in the
+/// region, so they fit without interlocking. The rest ask for more cells
+/// than the region has. Regions in between are left out: deciding those is
+/// a hard search, not a stress test.
+pub fn regions(
+    rng: &mut impl Rng,
+    shapes: &[[[bool; 3]; 3]],
+    count: usize,
+    (min_side, max_side): (usize, usize),
+    fill: f64,
+) -> Vec<Region> {
+    let areas: Vec<usize> = shapes
+        .iter()
+        .map(|shape| shape.iter().flatten().filter(|&&cell| cell).count())
+        .collect();
+    (0..count)
+        .map(|_| {
+            let width = rng.random_range(min_side..=max_side);
+            let height = rng.random_range(min_side..=max_side);
+            let area = width * height;
+            let mut counts = vec![0; shapes.len()];
+            if shapes.is_empty() {
+                return Region {
+                    width,
+                    height,
+                    counts,
+                };
+            }
+            if rng.random_bool(0.5) {
+                let boxes = (width / 3 * (height / 3)) as f64;
+                for _ in 0..(boxes * fill.min(1.0) * rng.random_range(0.9..=1.0)) as usize {
+                    counts[rng.random_range(0..shapes.len())] += 1;
+                }
+            } else {
+                let budget = area + rng.random_range(1..=area / 10 + 1);
+                let mut used = 0;
+                while used < budget {
+                    let shape = rng.random_range(0..shapes.len());
+                    used += areas[shape];
+                    counts[shape] += 1;
+                }
+            }
+            Region {
+                width,
+                height,
+                counts,
+            }
+        })
+        .collect()
+}
+
+/// Renders the shapes and regions in the puzzle format: each shape under its
+/// index, then one `WxH: counts...` line per region.
+pub fn render(shapes: &[[[bool; 3]; 3]], regions: &[Region]) -> String {
+    let mut out = String::new();
+    for (id, shape) in shapes.iter().enumerate() {
+        out.push_str(&format!("{id}:\n"));
+        for row in shape {
+            out.extend(row.iter().map(|&cell| if cell { '#' } else { '.' }));
+            out.push('\n');
+        }
+        out.push('\n');
+    }
+    let lines: Vec<String> = regions
+        .iter()
+        .map(|region| {
+            let counts: Vec<String> = region.counts.iter().map(usize::to_string).collect();
+            format!("{}x{}: {}", region.width, region.height, counts.join(" "))
+        })
+        .collect();
+    out + &lines.join("\n")
+}
@@ -0,0 +1,44 @@
+use rand::Rng;
+
+/// Generates `count` inclusive ID ranges, each at most `max_width` IDs wide.
+///
+/// Half of the ranges are anchored around a repeated-pattern ID (e.g. `123123`) so
+/// that the interesting IDs show up often even in narrow ranges; the other half
+/// start anywhere in `1..10^max_digits`.
+pub fn ranges(
+    rng: &mut impl Rng,
+    count: usize,
+    max_digits: u32,
+    max_width: u64,
+) -> Vec<(u64, u64)> {
+    (0..count)
+        .map(|_| {
+            let width = rng.random_range(0..=max_width);
+            let start = if rng.random_bool(0.5) {
+                let center = repeated_id(rng, max_digits);
+                center.saturating_sub(rng.random_range(0..=width)).max(1)
+            } else {
+                let digits = rng.random_range(1..=max_digits);
+                rng.random_range(10u64.pow(digits - 1)..10u64.pow(digits))
+            };
+            (start, start + width)
+        })
+        .collect()
+}
+
+/// Picks a random ID made of a digit pattern repeated at least twice.
+fn repeated_id(rng: &mut impl Rng, max_digits: u32) -> u64 {
+    let pattern_len = rng.random_range(1..=(max_digits / 2).max(1));
+    let repeats = rng.random_range(2..=(max_digits / pattern_len).max(2));
+    let pattern = rng.random_range(10u64.pow(pattern_len - 1)..10u64.pow(pattern_len));
+    (0..repeats).fold(0, |acc, _| acc * 10u64.pow(pattern_len) + pattern)
+}
+
+/// Renders ranges in the puzzle format (`11-22,95-115,...`).
+pub fn render(ranges: &[(u64, u64)]) -> String {
+    ranges
+        .iter()
+        .map(|(start, end)| format!("{start}-{end}"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
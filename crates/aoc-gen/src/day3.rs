@@ -0,0 +1,21 @@
+use rand::Rng;
+
+/// Generates `count` banks of `len` batteries, each rated `1..=9`.
+pub fn banks(rng: &mut impl Rng, count: usize, len: usize) -> Vec<Vec<u8>> {
+    (0..count)
+        .map(|_| (0..len).map(|_| rng.random_range(1..=9)).collect())
+        .collect()
+}
+
+/// Renders banks in the puzzle format (one line of digits per bank).
+pub fn render(banks: &[Vec<u8>]) -> String {
+    banks
+        .iter()
+        .map(|bank| {
+            bank.iter()
+                .map(|&d| char::from(b'0' + d))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,21 @@
+use rand::Rng;
+
+/// Generates a `width` by `height` grid where each cell holds a roll of paper
+/// with probability `density`.
+pub fn grid(rng: &mut impl Rng, width: usize, height: usize, density: f64) -> Vec<Vec<bool>> {
+    (0..height)
+        .map(|_| (0..width).map(|_| rng.random_bool(density)).collect())
+        .collect()
+}
+
+/// Renders the grid in the puzzle format (`@` for a roll, `.` otherwise).
+pub fn render(grid: &[Vec<bool>]) -> String {
+    grid.iter()
+        .map(|row| {
+            row.iter()
+                .map(|&roll| if roll { '@' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,50 @@
+use rand::Rng;
+
+/// The ingredient database: fresh ID ranges and the available IDs.
+#[derive(Debug, Clone)]
+pub struct Database {
+    pub ranges: Vec<(u64, u64)>,
+    pub ids: Vec<u64>,
+}
+
+/// Generates `ranges` fresh ranges at most `max_width` wide and `ids`
+/// available IDs, all below `max_id`.
+///
+/// Ranges are placed independently, so they overlap and nest about as often
+/// as their widths allow, which is what the merging has to cope with.
+pub fn database(
+    rng: &mut impl Rng,
+    ranges: usize,
+    ids: usize,
+    max_id: u64,
+    max_width: u64,
+) -> Database {
+    let ranges = (0..ranges)
+        .map(|_| {
+            let start = rng.random_range(1..max_id);
+            (
+                start,
+                start
+                    .saturating_add(rng.random_range(0..=max_width))
+                    .min(max_id),
+            )
+        })
+        .collect();
+    let ids = (0..ids).map(|_| rng.random_range(1..=max_id)).collect();
+    Database { ranges, ids }
+}
+
+/// Renders the database in the puzzle format: the ranges, a blank line, then
+/// one ID per line.
+pub fn render(database: &Database) -> String {
+    let ranges = database
+        .ranges
+        .iter()
+        .map(|(start, end)| format!("{start}-{end}"));
+    let ids = database.ids.iter().map(u64::to_string);
+    ranges
+        .chain(std::iter::once(String::new()))
+        .chain(ids)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,56 @@
+use rand::Rng;
+
+/// One problem of the worksheet: its numbers top to bottom, whether they are
+/// aligned to the left of the problem's columns, and its operator.
+#[derive(Debug, Clone)]
+pub struct Problem {
+    pub numbers: Vec<u64>,
+    pub left_aligned: bool,
+    pub op: char,
+}
+
+/// Generates `count` problems of `rows` numbers, each `1..=max_digits` long.
+pub fn problems(rng: &mut impl Rng, count: usize, rows: usize, max_digits: u32) -> Vec<Problem> {
+    (0..count)
+        .map(|_| Problem {
+            numbers: (0..rows)
+                .map(|_| {
+                    let digits = rng.random_range(1..=max_digits);
+                    rng.random_range(10u64.pow(digits - 1)..10u64.pow(digits))
+                })
+                .collect(),
+            left_aligned: rng.random_bool(0.5),
+            op: if rng.random_bool(0.5) { '+' } else { '*' },
+        })
+        .collect()
+}
+
+/// Renders problems side by side in the puzzle format, one space between
+/// them and the operators on the last line.
+pub fn render(problems: &[Problem]) -> String {
+    let rows = problems.iter().map(|p| p.numbers.len()).max().unwrap_or(0);
+    let mut grid = vec![String::new(); rows + 1];
+    for (i, problem) in problems.iter().enumerate() {
+        let width = problem
+            .numbers
+            .iter()
+            .map(|n| n.to_string().len())
+            .max()
+            .unwrap_or(1);
+        for (row, line) in grid.iter_mut().enumerate() {
+            if i > 0 {
+                line.push(' ');
+            }
+            let cell = match problem.numbers.get(row) {
+                Some(n) => n.to_string(),
+                None if row == rows => problem.op.to_string(),
+                None => String::new(),
+            };
+            match problem.left_aligned || row == rows {
+                true => line.push_str(&format!("{cell:<width$}")),
+                false => line.push_str(&format!("{cell:>width$}")),
+            }
+        }
+    }
+    grid.join("\n")
+}
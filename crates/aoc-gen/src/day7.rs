@@ -0,0 +1,29 @@
+use rand::Rng;
+
+/// Generates a `width` by `height` manifold with the source in the middle of
+/// the top row.
+///
+/// Splitters only sit on every other row below the source, as in the puzzle,
+/// each cell holding one with probability `density`.
+pub fn manifold(rng: &mut impl Rng, width: usize, height: usize, density: f64) -> Vec<Vec<u8>> {
+    let mut grid = vec![vec![b'.'; width]; height];
+    if let Some(top) = grid.first_mut() {
+        top[width / 2] = b'S';
+    }
+    for row in grid.iter_mut().skip(2).step_by(2) {
+        for cell in row.iter_mut() {
+            if rng.random_bool(density) {
+                *cell = b'^';
+            }
+        }
+    }
+    grid
+}
+
+/// Renders the manifold in the puzzle format.
+pub fn render(grid: &[Vec<u8>]) -> String {
+    grid.iter()
+        .map(|row| String::from_utf8_lossy(row).into_owned())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
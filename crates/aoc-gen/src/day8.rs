@@ -0,0 +1,23 @@
+use rand::Rng;
+
+/// Generates `count` junction boxes with coordinates in `0..max`.
+pub fn points(rng: &mut impl Rng, count: usize, max: u32) -> Vec<(u32, u32, u32)> {
+    (0..count)
+        .map(|_| {
+            (
+                rng.random_range(0..max),
+                rng.random_range(0..max),
+                rng.random_range(0..max),
+            )
+        })
+        .collect()
+}
+
+/// Renders the boxes in the puzzle format (one `x,y,z` per line).
+pub fn render(points: &[(u32, u32, u32)]) -> String {
+    points
+        .iter()
+        .map(|(x, y, z)| format!("{x},{y},{z}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,74 @@
+use rand::Rng;
+
+/// Generates the red tiles of a random rectilinear polygon, in boundary order.
+///
+/// The polygon is a run of `1..=max_columns` vertical strips laid side by side,
+/// each spanning its own `[bottom, top]` interval, with neighbouring strips
+/// overlapping so the outline never pinches. This yields notches on both the
+/// top and the bottom edge, which is where the interior checks tend to break.
+/// Collinear vertices are dropped, so consecutive edges always turn.
+pub fn polygon(rng: &mut impl Rng, max_columns: usize, max_step: i64) -> Vec<(i64, i64)> {
+    let columns = rng.random_range(1..=max_columns);
+    let height = max_step * 2;
+
+    let mut xs = vec![0i64];
+    for _ in 0..columns {
+        let last = *xs.last().unwrap();
+        xs.push(last + rng.random_range(1..=max_step));
+    }
+
+    let mut spans: Vec<(i64, i64)> = Vec::with_capacity(columns);
+    let bottom = rng.random_range(0..height);
+    spans.push((bottom, rng.random_range(bottom + 1..=height)));
+    for _ in 1..columns {
+        let (prev_bottom, prev_top) = *spans.last().unwrap();
+        let bottom = rng.random_range(0..prev_top);
+        let top = rng.random_range(prev_bottom.max(bottom) + 1..=height);
+        spans.push((bottom, top));
+    }
+
+    let mut outline = Vec::with_capacity(columns * 4);
+    for (i, &(bottom, _)) in spans.iter().enumerate() {
+        outline.push((xs[i], bottom));
+        outline.push((xs[i + 1], bottom));
+    }
+    for (i, &(_, top)) in spans.iter().enumerate().rev() {
+        outline.push((xs[i + 1], top));
+        outline.push((xs[i], top));
+    }
+
+    simplify(outline)
+}
+
+/// Removes repeated and collinear vertices from a closed outline.
+fn simplify(mut outline: Vec<(i64, i64)>) -> Vec<(i64, i64)> {
+    outline.dedup();
+    if outline.first() == outline.last() {
+        outline.pop();
+    }
+
+    loop {
+        let n = outline.len();
+        let collinear = (0..n).find(|&i| {
+            let (ax, ay) = outline[(i + n - 1) % n];
+            let (bx, by) = outline[i];
+            let (cx, cy) = outline[(i + 1) % n];
+            (ax == bx && bx == cx) || (ay == by && by == cy)
+        });
+        match collinear {
+            Some(i) => {
+                outline.remove(i);
+            }
+            None => return outline,
+        }
+    }
+}
+
+/// Renders the points in the puzzle format (one `x,y` per line).
+pub fn render(points: &[(i64, i64)]) -> String {
+    points
+        .iter()
+        .map(|(x, y)| format!("{x},{y}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,296 @@
+//! Golden answers: what every registered day must answer on its real input.
+//!
+//! The expected answers live in `answers.toml` at the workspace root, one
+//! table per day:
+//!
+//! ```toml
+//! [2025.1]
+//! part1 = 1234
+//! part2 = "ABC"
+//! ```
+//!
+//! A part without an answer is not checked, which leaves room for days that
+//! are only half solved, but every registered day needs a table. Days marked
+//! `slow = true` take too long for every `cargo test` and are only checked by
+//! the ignored `slow_days_match_answers` test. Inputs are read from the same
+//! store the day binaries load them from, see
+//! [`aoc_runtime::Day::input_path`]; `just import-inputs` seeds it. Days whose
+//! input is not stored are skipped, see [`has_input`].
+//!
+//! A day may also set `budget_ms`, the wall-clock time both parts together
+//! may take in a release build. The budgets are only checked by the ignored
+//! tests in `tests/budgets.rs`, since timings depend on the machine; see
+//! [`budget`] for scaling or overriding them.
+
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use aoc_common::answer::Answer;
+use aoc_common::solution::Part;
+use aoc_runtime::Day;
+use miette::*;
+use toml_edit::{DocumentMut, Item, Value};
+
+/// The expected answer of one part of one day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Golden {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub answer: Answer,
+    /// Whether the day is marked `slow`.
+    pub slow: bool,
+    /// How long both parts of the day may take, from `budget_ms`.
+    pub budget: Option<Duration>,
+}
+
+/// The workspace root, where `answers.toml` is.
+pub fn workspace_root() -> &'static Path {
+    Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../.."))
+}
+
+/// Reads the workspace's `answers.toml`.
+pub fn load() -> Result<Vec<Golden>> {
+    let path = workspace_root().join("answers.toml");
+    let contents = std::fs::read_to_string(&path)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+    parse(&contents).wrap_err_with(|| format!("Invalid {}", path.display()))
+}
+
+/// Parses the answers of an `answers.toml`, ordered by year, day and part.
+pub fn parse(contents: &str) -> Result<Vec<Golden>> {
+    let document = contents.parse::<DocumentMut>().into_diagnostic()?;
+    let mut goldens = Vec::new();
+    for (year, days) in document.iter() {
+        let year = year
+            .parse::<u16>()
+            .map_err(|_| miette!("{:?} is not a year", year))?;
+        let days = days
+            .as_table_like()
+            .ok_or_else(|| miette!("[{}] must be a table of days", year))?;
+        for (day, parts) in days.iter() {
+            let day = day
+                .parse::<u8>()
+                .map_err(|_| miette!("{:?} in [{}] is not a day", day, year))?;
+            let parts = parts
+                .as_table_like()
+                .ok_or_else(|| miette!("[{}.{}] must be a table of parts", year, day))?;
+            let slow = match parts.get("slow") {
+                Some(slow) => slow
+                    .as_bool()
+                    .ok_or_else(|| miette!("[{}.{}] slow must be a boolean", year, day))?,
+                None => false,
+            };
+            let budget = match parts.get("budget_ms") {
+                Some(budget) => Some(
+                    budget
+                        .as_integer()
+                        .and_then(|ms| u64::try_from(ms).ok())
+                        .map(Duration::from_millis)
+                        .ok_or_else(|| {
+                            miette!("[{}.{}] budget_ms must be a whole number", year, day)
+                        })?,
+                ),
+                None => None,
+            };
+            for (key, value) in parts.iter() {
+                let part = match key {
+                    "part1" => Part::One,
+                    "part2" => Part::Two,
+                    "slow" | "budget_ms" => continue,
+                    _ => bail!("Unknown key {:?} in [{}.{}]", key, year, day),
+                };
+                let answer = answer_of(value).ok_or_else(|| {
+                    miette!("[{}.{}] {} must be an integer or a string", year, day, key)
+                })?;
+                goldens.push(Golden {
+                    year,
+                    day,
+                    part,
+                    answer,
+                    slow,
+                    budget,
+                });
+            }
+        }
+    }
+    goldens.sort_by_key(|golden| (golden.year, golden.day, golden.part));
+    Ok(goldens)
+}
+
+fn answer_of(item: &Item) -> Option<Answer> {
+    match item.as_value()? {
+        Value::Integer(value) => Some(Answer::from(*value.value())),
+        // Answers past i64::MAX can only be written as strings.
+        Value::String(value) => Some(Answer::parse(value.value())),
+        _ => None,
+    }
+}
+
+/// The day of `golden`, whose input in the store both parts are solved on.
+pub fn day(golden: &Golden) -> Day {
+    Day {
+        year: golden.year,
+        day: golden.day,
+        crate_dir: "",
+    }
+}
+
+/// Whether the input of `golden`'s day is in the store.
+pub fn has_input(golden: &Golden) -> bool {
+    day(golden).input_path().is_ok_and(|path| path.exists())
+}
+
+/// Solves `golden`'s part on the real input and compares the answers.
+pub fn check(golden: &Golden) -> Result<()> {
+    time(golden).map(|_| ())
+}
+
+/// Like [`check`], but returns how long solving took, reading the input
+/// aside.
+pub fn time(golden: &Golden) -> Result<Duration> {
+    let entry = aoc_registry::find(golden.year, golden.day).ok_or_else(|| {
+        miette!(
+            "No solution is registered for {} day {}",
+            golden.year,
+            golden.day
+        )
+    })?;
+    let input = day(golden).load_input()?;
+    let start = Instant::now();
+    let answer = entry.run(&input, golden.part)?;
+    let elapsed = start.elapsed();
+    if answer != golden.answer {
+        bail!("Expected {}, got {}", golden.answer, answer);
+    }
+    Ok(elapsed)
+}
+
+/// The time budget of a day, after applying the environment `var` reads.
+///
+/// `AOC_BUDGET_<year>_<day>` (e.g. `AOC_BUDGET_2025_8=500`) replaces the
+/// day's budget, in milliseconds, and `AOC_BUDGET_SCALE` multiplies every
+/// budget, for slower machines or debug builds. `None` when the day has no
+/// budget at all.
+pub fn budget(
+    year: u16,
+    day: u8,
+    configured: Option<Duration>,
+    var: impl Fn(&str) -> Option<String>,
+) -> Result<Option<Duration>> {
+    let name = format!("AOC_BUDGET_{year}_{day}");
+    let budget = match var(&name) {
+        Some(ms) => Some(Duration::from_millis(ms.trim().parse().map_err(|_| {
+            miette!("{name} must be a whole number of milliseconds, not {ms:?}")
+        })?)),
+        None => configured,
+    };
+    let scale = match var("AOC_BUDGET_SCALE") {
+        Some(scale) => scale
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|scale| scale.is_finite() && *scale > 0.0)
+            .ok_or_else(|| miette!("AOC_BUDGET_SCALE must be a positive number, not {scale:?}"))?,
+        None => 1.0,
+    };
+    Ok(budget.map(|budget| budget.mul_f64(scale)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_answers() -> Result<()> {
+        let goldens = parse(
+            "[2025.2]\npart2 = 7\npart1 = \"18446744073709551615\"\nslow = true\n\n[2025.1]\npart1 = \"ABC\"\n",
+        )?;
+        let summary = goldens
+            .iter()
+            .map(|golden| {
+                (
+                    golden.day,
+                    golden.part,
+                    golden.answer.to_string(),
+                    golden.slow,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            [
+                (1, Part::One, "ABC".to_string(), false),
+                (2, Part::One, u64::MAX.to_string(), true),
+                (2, Part::Two, "7".to_string(), true),
+            ]
+        );
+        assert_eq!(goldens[1].answer, Answer::U64(u64::MAX));
+        Ok(())
+    }
+
+    #[test]
+    fn budgets_apply_to_every_part_of_the_day() -> Result<()> {
+        let goldens =
+            parse("[2025.8]\nbudget_ms = 250\npart1 = 1\npart2 = 2\n\n[2025.9]\npart1 = 3\n")?;
+        let budgets = goldens
+            .iter()
+            .map(|golden| golden.budget)
+            .collect::<Vec<_>>();
+        let quarter = Some(Duration::from_millis(250));
+        assert_eq!(budgets, [quarter, quarter, None]);
+        Ok(())
+    }
+
+    #[test]
+    fn environment_scales_and_overrides_budgets() -> Result<()> {
+        let env = |vars: &'static [(&str, &str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(var, _)| *var == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        let configured = Some(Duration::from_millis(100));
+        assert_eq!(budget(2025, 8, configured, env(&[]))?, configured);
+        assert_eq!(
+            budget(2025, 8, configured, env(&[("AOC_BUDGET_SCALE", "2.5")]))?,
+            Some(Duration::from_millis(250))
+        );
+        assert_eq!(
+            budget(
+                2025,
+                8,
+                None,
+                env(&[("AOC_BUDGET_2025_8", "40"), ("AOC_BUDGET_SCALE", "2")])
+            )?,
+            Some(Duration::from_millis(80))
+        );
+        assert_eq!(
+            budget(2025, 9, None, env(&[("AOC_BUDGET_2025_8", "40")]))?,
+            None
+        );
+        assert!(budget(2025, 8, configured, env(&[("AOC_BUDGET_SCALE", "-1")])).is_err());
+        assert!(budget(2025, 8, configured, env(&[("AOC_BUDGET_2025_8", "fast")])).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_malformed_answers() {
+        assert!(parse("[2025.1]\npart3 = 1\n").is_err());
+        assert!(parse("[2025.1]\npart1 = 1.5\n").is_err());
+        assert!(parse("[twenty.1]\npart1 = 1\n").is_err());
+        assert!(parse("[2025]\n1 = 1\n").is_err());
+        assert!(parse("[2025.1]\nslow = \"yes\"\n").is_err());
+        assert!(parse("[2025.1]\nbudget_ms = -5\n").is_err());
+    }
+
+    #[test]
+    fn inputs_come_from_the_store() -> Result<()> {
+        let goldens = parse("[2025.3]\npart2 = 1\n")?;
+        let path = day(&goldens[0]).input_path()?;
+        assert!(path.ends_with("aoc/2025/3/input.txt"));
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+//! Opt-in checks that every day still solves its real input within the
+//! `budget_ms` of `answers.toml`, so an accidental algorithmic regression fails
+//! loudly instead of just feeling slow. Run them in release:
+//!
+//! ```sh
+//! just budgets
+//! ```
+
+use std::time::Duration;
+
+/// Solves both parts of a day and fails if they took longer than its budget.
+fn within_budget(year: u16, day: u8) {
+    let goldens = aoc_golden::load()
+        .unwrap()
+        .into_iter()
+        .filter(|golden| golden.year == year && golden.day == day)
+        .collect::<Vec<_>>();
+    assert!(!goldens.is_empty(), "answers.toml has no {year} day {day}");
+    let configured = goldens[0].budget;
+    let budget = aoc_golden::budget(year, day, configured, |var| std::env::var(var).ok())
+        .unwrap()
+        .unwrap_or_else(|| panic!("{year} day {day} has no budget_ms in answers.toml"));
+
+    let elapsed = goldens
+        .iter()
+        .map(|golden| aoc_golden::time(golden).unwrap())
+        .sum::<Duration>();
+    assert!(
+        elapsed <= budget,
+        "{year} day {day} took {elapsed:.2?}, over its {budget:.2?} budget"
+    );
+}
+
+/// Declares one ignored budget test per day.
+macro_rules! budgets {
+    ($($name:ident => $year:literal day $day:literal,)+) => {
+        $(
+            #[test]
+            #[ignore = "timing depends on the machine; run `just budgets` in release"]
+            fn $name() {
+                within_budget($year, $day);
+            }
+        )+
+    };
+}
+
+budgets! {
+    day_2024_01 => 2024 day 1,
+    day_2024_02 => 2024 day 2,
+    day_2024_03 => 2024 day 3,
+    day_2024_04 => 2024 day 4,
+    day_2024_05 => 2024 day 5,
+    day_2024_06 => 2024 day 6,
+    day_2024_07 => 2024 day 7,
+    day_2024_08 => 2024 day 8,
+    day_2024_09 => 2024 day 9,
+    day_2024_10 => 2024 day 10,
+    day_2024_11 => 2024 day 11,
+    day_2024_12 => 2024 day 12,
+    day_2024_13 => 2024 day 13,
+    day_2024_14 => 2024 day 14,
+    day_2024_15 => 2024 day 15,
+    day_2024_16 => 2024 day 16,
+    day_2024_17 => 2024 day 17,
+    day_2024_18 => 2024 day 18,
+    day_2024_19 => 2024 day 19,
+    day_2024_20 => 2024 day 20,
+    day_2024_21 => 2024 day 21,
+    day_2024_22 => 2024 day 22,
+    day_2024_23 => 2024 day 23,
+    day_2024_24 => 2024 day 24,
+    day_2024_25 => 2024 day 25,
+    day_2025_01 => 2025 day 1,
+    day_2025_02 => 2025 day 2,
+    day_2025_03 => 2025 day 3,
+    day_2025_04 => 2025 day 4,
+    day_2025_05 => 2025 day 5,
+    day_2025_06 => 2025 day 6,
+    day_2025_07 => 2025 day 7,
+    day_2025_08 => 2025 day 8,
+    day_2025_09 => 2025 day 9,
+    day_2025_10 => 2025 day 10,
+    day_2025_11 => 2025 day 11,
+    day_2025_12 => 2025 day 12,
+}
@@ -0,0 +1,70 @@
+use aoc_golden::Golden;
+
+/// Checks every answer `select` picks, reporting all mismatches at once.
+fn check_all(select: impl Fn(&Golden) -> bool) {
+    let goldens = aoc_golden::load()
+        .unwrap()
+        .into_iter()
+        .filter(select)
+        .collect::<Vec<_>>();
+    assert!(!goldens.is_empty(), "answers.toml lists no such answers");
+
+    // A fresh checkout has no inputs, so days missing from the store are
+    // skipped rather than failed.
+    let (goldens, missing): (Vec<_>, Vec<_>) = goldens.into_iter().partition(aoc_golden::has_input);
+    if !missing.is_empty() {
+        eprintln!(
+            "skipped {} of {} answers with no stored input; run `just import-inputs`",
+            missing.len(),
+            goldens.len() + missing.len()
+        );
+    }
+
+    let failures = goldens
+        .iter()
+        .filter_map(|golden| {
+            let error = aoc_golden::check(golden).err()?;
+            Some(format!(
+                "{} day {} part {}: {:?}",
+                golden.year, golden.day, golden.part, error
+            ))
+        })
+        .collect::<Vec<_>>();
+    assert!(
+        failures.is_empty(),
+        "{} of {} answers changed:\n{}",
+        failures.len(),
+        goldens.len(),
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn every_registered_day_has_answers() {
+    let goldens = aoc_golden::load().unwrap();
+    let missing = aoc_registry::solutions()
+        .iter()
+        .filter(|entry| {
+            !goldens
+                .iter()
+                .any(|golden| golden.year == entry.year && golden.day == entry.day)
+        })
+        .map(|entry| format!("{} day {}", entry.year, entry.day))
+        .collect::<Vec<_>>();
+    assert!(
+        missing.is_empty(),
+        "answers.toml has no answers for:\n{}",
+        missing.join("\n")
+    );
+}
+
+#[test]
+fn registered_days_match_answers() {
+    check_all(|golden| !golden.slow);
+}
+
+#[test]
+#[ignore = "minutes even in release; run `just golden-slow`"]
+fn slow_days_match_answers() {
+    check_all(|golden| golden.slow);
+}
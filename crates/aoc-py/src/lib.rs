@@ -0,0 +1,297 @@
+//! Python bindings to the solutions and the solvers behind them, for
+//! cross-checking them against numpy or scipy from a notebook.
+//!
+//! Build and install into the active virtualenv with
+//! [maturin](https://www.maturin.rs): `maturin develop -m crates/aoc-py/Cargo.toml`.
+//!
+//! ```python
+//! import os
+//! import aoc_py
+//!
+//! # Inputs are read from the same store as the binaries.
+//! text = open(os.path.expanduser("~/.local/share/aoc/2025/10/input.txt")).read()
+//! aoc_py.solve(2025, 10, 2, text)
+//!
+//! # The joltage systems of day 10, against scipy's MILP.
+//! for a, b in aoc_py.day10_joltages(text):
+//!     assert aoc_py.milp_min_presses(a, b) == round(milp(...).fun)
+//! ```
+//!
+//! Matrices go in and out as lists of rows, which `numpy.array` takes as
+//! they are. Bits over GF(2) are the integers 0 and 1.
+
+use aoc_common::solution::Part;
+use aoc_solutions::y2025::day10::{part1, part2};
+use gf2::{Gf2Matrix, Row};
+use miette::{GraphicalReportHandler, GraphicalTheme, Report};
+use nalgebra::{DMatrix, DVector};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// A matrix as a list of rows.
+type Matrix<T> = Vec<Vec<T>>;
+
+/// `a x = b`, as `(a, b)`.
+type System<T> = (Matrix<T>, Vec<T>);
+
+/// A consistent GF(2) system's solutions: `particular` XOR any combination of
+/// the `basis` vectors.
+#[pyclass(get_all, frozen)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gf2Solution {
+    pub particular: Vec<u32>,
+    pub basis: Matrix<u32>,
+    /// The fewest ones of any solution.
+    pub min_weight: usize,
+}
+
+#[pymethods]
+impl Gf2Solution {
+    fn __repr__(&self) -> String {
+        format!(
+            "Gf2Solution(particular={:?}, basis={:?}, min_weight={})",
+            self.particular, self.basis, self.min_weight
+        )
+    }
+}
+
+/// Solves one part of a registered day and returns the answer as a string.
+#[pyfunction]
+fn solve(year: u16, day: u8, part: u8, input: &str) -> PyResult<String> {
+    solve_str(year, day, part, input).map_err(PyValueError::new_err)
+}
+
+/// The registered days, as `(year, day)` pairs.
+#[pyfunction]
+fn days() -> Vec<(u16, u8)> {
+    aoc_solutions::solutions()
+        .iter()
+        .map(|entry| (entry.year, entry.day))
+        .collect()
+}
+
+/// Solves `a x = b` over GF(2), or returns `None` when it has no solution.
+#[pyfunction]
+fn gf2_solve(a: Matrix<u32>, b: Vec<u32>) -> PyResult<Option<Gf2Solution>> {
+    let matrix = gf2_matrix(&a).map_err(PyValueError::new_err)?;
+    if b.len() != matrix.nrows() {
+        return Err(PyValueError::new_err(format!(
+            "b has {} entries but a has {} rows",
+            b.len(),
+            matrix.nrows()
+        )));
+    }
+    let b = row(&b).map_err(PyValueError::new_err)?;
+    let Some(space) = matrix.solve(&b) else {
+        return Ok(None);
+    };
+    let min_weight = space
+        .min_weight()
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(Some(Gf2Solution {
+        particular: bits(space.particular()),
+        basis: space.basis().iter().map(bits).collect(),
+        min_weight,
+    }))
+}
+
+/// The rank of `a` over GF(2).
+#[pyfunction]
+fn gf2_rank(a: Matrix<u32>) -> PyResult<usize> {
+    Ok(gf2_matrix(&a).map_err(PyValueError::new_err)?.rank())
+}
+
+/// A basis of the null space of `a` over GF(2).
+#[pyfunction]
+fn gf2_nullspace(a: Matrix<u32>) -> PyResult<Matrix<u32>> {
+    let matrix = gf2_matrix(&a).map_err(PyValueError::new_err)?;
+    Ok(matrix.nullspace().iter().map(bits).collect())
+}
+
+/// Day 10's light systems over GF(2), one `(a, b)` per machine: a column of
+/// `a` per button, a row per light, and `b` the lights to turn on.
+#[pyfunction]
+fn day10_lights(input: &str) -> PyResult<Vec<System<u32>>> {
+    let machines = part1::parse(input).map_err(|report| PyValueError::new_err(render(&report)))?;
+    Ok(machines
+        .iter()
+        .map(|machine| {
+            let system = machine.system();
+            let a = (0..system.nrows())
+                .map(|r| {
+                    (0..system.ncols())
+                        .map(|c| system.get(r, c) as u32)
+                        .collect()
+                })
+                .collect();
+            (a, bits(machine.lights()))
+        })
+        .collect())
+}
+
+/// Day 10's joltage systems, one `(a, b)` per machine: a column of `a` per
+/// button, a row per counter, and `b` the counters' targets. Every press
+/// costs one.
+#[pyfunction]
+fn day10_joltages(input: &str) -> PyResult<Vec<System<f64>>> {
+    let systems = part2::parse(input).map_err(|report| PyValueError::new_err(render(&report)))?;
+    Ok(systems
+        .iter()
+        .map(|sys| {
+            let a = sys
+                .a
+                .row_iter()
+                .map(|row| row.iter().copied().collect())
+                .collect();
+            (a, sys.b.iter().copied().collect())
+        })
+        .collect())
+}
+
+/// The fewest button presses, in non-negative integers, that make `a x = b`,
+/// found by day 10's branch and bound; `None` when there is none.
+#[pyfunction]
+fn milp_min_presses(a: Matrix<f64>, b: Vec<f64>) -> PyResult<Option<usize>> {
+    let sys = linear_system(&a, &b).map_err(PyValueError::new_err)?;
+    Ok(part2::min_presses(&sys, &part2::Simplex))
+}
+
+#[pymodule]
+fn aoc_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Gf2Solution>()?;
+    m.add_function(wrap_pyfunction!(solve, m)?)?;
+    m.add_function(wrap_pyfunction!(days, m)?)?;
+    m.add_function(wrap_pyfunction!(gf2_solve, m)?)?;
+    m.add_function(wrap_pyfunction!(gf2_rank, m)?)?;
+    m.add_function(wrap_pyfunction!(gf2_nullspace, m)?)?;
+    m.add_function(wrap_pyfunction!(day10_lights, m)?)?;
+    m.add_function(wrap_pyfunction!(day10_joltages, m)?)?;
+    m.add_function(wrap_pyfunction!(milp_min_presses, m)?)?;
+    Ok(())
+}
+
+/// [`solve`] with the error as the text Python will see.
+fn solve_str(year: u16, day: u8, part: u8, input: &str) -> Result<String, String> {
+    let entry = aoc_solutions::find(year, day)
+        .ok_or_else(|| format!("No solution is registered for {year} day {day}"))?;
+    let part = match part {
+        1 => Part::One,
+        2 => Part::Two,
+        _ => return Err(format!("There is no part {part}; pick 1 or 2")),
+    };
+    entry
+        .run(input, part)
+        .map(|answer| answer.to_string())
+        .map_err(|report| render(&report))
+}
+
+/// Draws a report the way the terminal would, minus the colors.
+fn render(report: &Report) -> String {
+    let mut out = String::new();
+    let handler = GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor());
+    match handler.render_report(&mut out, report.as_ref()) {
+        Ok(()) => out,
+        Err(_) => format!("{report}"),
+    }
+}
+
+/// The number of columns of a matrix given as rows, if they all agree.
+fn width<T>(rows: &[Vec<T>]) -> Result<usize, String> {
+    let cols = rows.first().map_or(0, Vec::len);
+    match rows.iter().position(|row| row.len() != cols) {
+        Some(r) => Err(format!(
+            "row {r} has {} entries but row 0 has {cols}",
+            rows[r].len()
+        )),
+        None => Ok(cols),
+    }
+}
+
+fn row(values: &[u32]) -> Result<Row, String> {
+    values
+        .iter()
+        .map(|&bit| match bit {
+            0 | 1 => Ok(bit == 1),
+            _ => Err(format!("{bit} is not a bit; GF(2) entries are 0 or 1")),
+        })
+        .collect()
+}
+
+fn bits(row: &Row) -> Vec<u32> {
+    row.iter().by_vals().map(u32::from).collect()
+}
+
+fn gf2_matrix(rows: &[Vec<u32>]) -> Result<Gf2Matrix, String> {
+    let cols = width(rows)?;
+    let rows = rows.iter().map(|r| row(r)).collect::<Result<_, _>>()?;
+    Ok(Gf2Matrix::from_rows(cols, rows))
+}
+
+fn linear_system(a: &[Vec<f64>], b: &[f64]) -> Result<part2::LinearSystem, String> {
+    let cols = width(a)?;
+    if b.len() != a.len() {
+        return Err(format!(
+            "b has {} entries but a has {} rows",
+            b.len(),
+            a.len()
+        ));
+    }
+    let a = DMatrix::from_fn(a.len(), cols, |r, c| a[r][c]);
+    Ok(part2::LinearSystem::new(a, DVector::from_column_slice(b)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}";
+
+    #[test]
+    fn solves_registered_days() {
+        assert_eq!(solve_str(2025, 10, 1, EXAMPLE), Ok("7".to_string()));
+        assert!(solve_str(2025, 10, 3, EXAMPLE).is_err());
+        assert!(days().contains(&(2025, 10)));
+    }
+
+    #[test]
+    fn light_systems_agree_with_part1() -> PyResult<()> {
+        let presses = day10_lights(EXAMPLE)?
+            .into_iter()
+            .map(|(a, b)| Ok(gf2_solve(a, b)?.unwrap().min_weight))
+            .collect::<PyResult<Vec<_>>>()?;
+        assert_eq!(presses, [2, 3, 2]);
+        Ok(())
+    }
+
+    #[test]
+    fn joltage_systems_agree_with_part2() -> PyResult<()> {
+        let presses = day10_joltages(EXAMPLE)?
+            .into_iter()
+            .map(|(a, b)| milp_min_presses(a, b))
+            .collect::<PyResult<Vec<_>>>()?;
+        assert_eq!(presses, [Some(10), Some(12), Some(11)]);
+        assert_eq!(milp_min_presses(vec![vec![2.0]], vec![3.0])?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn gf2_helpers() -> PyResult<()> {
+        // x0 + x1 = 1, x1 + x2 = 0: x = 100 + t * 111.
+        let a = vec![vec![1, 1, 0], vec![0, 1, 1]];
+        assert_eq!(gf2_rank(a.clone())?, 2);
+        assert_eq!(gf2_nullspace(a.clone())?, [vec![1, 1, 1]]);
+        let solution = gf2_solve(a.clone(), vec![1, 0])?.unwrap();
+        assert_eq!(solution.min_weight, 1);
+        assert!(gf2_solve(vec![vec![0]], vec![1])?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_malformed_matrices() {
+        assert!(gf2_matrix(&[vec![1, 0], vec![1]]).is_err());
+        assert!(gf2_matrix(&[vec![2]]).is_err());
+        assert!(linear_system(&[vec![1.0]], &[1.0, 2.0]).is_err());
+    }
+}
@@ -0,0 +1,97 @@
+//! Links every day's crate, so the [`Entry`] each one submits through
+//! [`aoc_common::solution!`] is there for the tooling to enumerate.
+//!
+//! `aoc new` adds the days it creates here.
+
+use std::sync::OnceLock;
+
+use aoc_common::solution::{self, Entry};
+
+use aoc2024_day_1 as _;
+use aoc2024_day_10 as _;
+use aoc2024_day_11 as _;
+use aoc2024_day_12 as _;
+use aoc2024_day_13 as _;
+use aoc2024_day_14 as _;
+use aoc2024_day_15 as _;
+use aoc2024_day_16 as _;
+use aoc2024_day_17 as _;
+use aoc2024_day_18 as _;
+use aoc2024_day_19 as _;
+use aoc2024_day_2 as _;
+use aoc2024_day_20 as _;
+use aoc2024_day_21 as _;
+use aoc2024_day_22 as _;
+use aoc2024_day_23 as _;
+use aoc2024_day_24 as _;
+use aoc2024_day_25 as _;
+use aoc2024_day_3 as _;
+use aoc2024_day_4 as _;
+use aoc2024_day_5 as _;
+use aoc2024_day_6 as _;
+use aoc2024_day_7 as _;
+use aoc2024_day_8 as _;
+use aoc2024_day_9 as _;
+use aoc2025_day_1 as _;
+use aoc2025_day_10 as _;
+use aoc2025_day_11 as _;
+use aoc2025_day_12 as _;
+use aoc2025_day_2 as _;
+use aoc2025_day_3 as _;
+use aoc2025_day_4 as _;
+use aoc2025_day_5 as _;
+use aoc2025_day_6 as _;
+use aoc2025_day_7 as _;
+use aoc2025_day_8 as _;
+use aoc2025_day_9 as _;
+
+/// The registered solutions, ordered by year and day.
+pub fn solutions() -> &'static [&'static Entry] {
+    static SOLUTIONS: OnceLock<Vec<&'static Entry>> = OnceLock::new();
+    SOLUTIONS.get_or_init(|| {
+        let mut entries: Vec<_> = solution::entries().collect();
+        entries.sort_by_key(|entry| (entry.year, entry.day));
+        entries
+    })
+}
+
+/// The solution registered for `year` and `day`.
+pub fn find(year: u16, day: u8) -> Option<&'static Entry> {
+    solutions()
+        .iter()
+        .copied()
+        .find(|entry| entry.year == year && entry.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_are_ordered_and_unique() {
+        assert!(!solutions().is_empty());
+        // Strictly increasing, so no day is registered twice.
+        assert!(solutions()
+            .windows(2)
+            .all(|pair| (pair[0].year, pair[0].day) < (pair[1].year, pair[1].day)));
+        for &entry in solutions() {
+            let found = find(entry.year, entry.day);
+            assert!(
+                found.is_some_and(|found| std::ptr::eq(found, entry)),
+                "find({}, {}) does not resolve {}",
+                entry.year,
+                entry.day,
+                entry.package
+            );
+        }
+        assert_eq!(
+            find(2025, 8).map(|entry| entry.package),
+            Some("aoc2025-day-8")
+        );
+        assert_eq!(
+            find(2024, 25).map(|entry| entry.package),
+            Some("aoc2024-day-25")
+        );
+        assert!(find(2025, 26).is_none());
+    }
+}
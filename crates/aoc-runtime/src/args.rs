@@ -0,0 +1,189 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use aoc_common::answer::Answer;
+use aoc_common::solution::{Entry, Part, Timed};
+use clap::Parser;
+use miette::*;
+
+use crate::input::{self, Day};
+
+/// Command line flags understood by every solution binary.
+#[derive(Debug, Default, Parser)]
+pub struct Args {
+    /// Read the puzzle input from this file instead of the stored one, or
+    /// from stdin if it is `-`.
+    #[arg(short, long, value_name = "PATH")]
+    pub input: Option<PathBuf>,
+    /// Same as `--input`, for `part1 big.txt` or `gen | part1 -`.
+    #[arg(value_name = "PATH", conflicts_with_all = ["input", "example"])]
+    pub path: Option<PathBuf>,
+    /// Run against the day's `example.txt` instead of the puzzle input.
+    #[arg(short, long, conflicts_with = "input")]
+    pub example: bool,
+    /// How to print the answer.
+    #[arg(long, value_enum, default_value_t)]
+    pub format: Format,
+    /// Print the time spent parsing, building and solving on stderr after
+    /// the answer.
+    #[arg(long)]
+    pub phases: bool,
+}
+
+/// How a binary prints its answer.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// `Result: <answer>`.
+    #[default]
+    Text,
+    /// One JSON object with the answer, where it came from and how long each
+    /// stage took.
+    Json,
+}
+
+impl Format {
+    /// Renders the timed answer of `part` of `entry`.
+    pub fn render(self, entry: &Entry, part: Part, timed: &Timed) -> String {
+        match self {
+            Format::Text => format!("Result: {}", timed.answer),
+            Format::Json => format!(
+                "{{\"year\":{},\"day\":{},\"part\":{},\"answer\":{},\"parse_ms\":{},\"solve_ms\":{},\"items_parsed\":{}}}",
+                entry.year,
+                entry.day,
+                part,
+                timed.answer.to_json(),
+                millis(timed.parse),
+                millis(timed.solve),
+                timed
+                    .items
+                    .map_or_else(|| "null".to_string(), |items| items.to_string()),
+            ),
+        }
+    }
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64() * 1e3)
+}
+
+impl Args {
+    /// Parses the flags of the current process, exiting on invalid usage.
+    pub fn from_env() -> Self {
+        Self::parse()
+    }
+
+    /// Resolves the input selected by the flags, falling back to the day's
+    /// input in the store.
+    pub fn load(&self, day: &Day) -> Result<String> {
+        match (self.input.as_ref().or(self.path.as_ref()), self.example) {
+            (Some(path), _) => input::read(path),
+            (None, true) => input::read(&day.example_path()),
+            (None, false) => day.load_input(),
+        }
+    }
+
+    /// Solves `part` of a registered day and prints the answer in the
+    /// format selected by the flags.
+    pub fn solve(&self, entry: &Entry, part: Part, input: &str) -> Result<()> {
+        crate::phases::take();
+        let timed = entry.run_timed(input, part)?;
+        println!("{}", self.format.render(entry, part, &timed));
+        if self.phases {
+            eprint!("{}", crate::phases::table(&crate::phases::take()));
+        }
+        Ok(())
+    }
+
+    /// Prints an answer computed outside the registry, for the alternative
+    /// implementations a day ships next to its
+    /// [`Solution`](aoc_common::solution::Solution).
+    ///
+    /// Without an [`Entry`] there is nothing to time or place, so the JSON
+    /// only carries the part and the answer. Numeric answers are written as
+    /// numbers, including the ones days hand over already formatted.
+    pub fn report(&self, part: u8, answer: impl Into<Answer>) {
+        let answer = match answer.into() {
+            Answer::Text(text) => Answer::parse(&text),
+            answer => answer,
+        };
+        match self.format {
+            Format::Text => println!("Result: {}", answer),
+            Format::Json => println!("{{\"part\":{},\"answer\":{}}}", part, answer.to_json()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn flags_parse() {
+        let args = Args::try_parse_from(["part1", "--example", "--format", "json"]).unwrap();
+        assert!(args.example && args.format == Format::Json);
+        assert!(Args::try_parse_from(["part1", "--example", "--input", "x"]).is_err());
+        assert!(Args::try_parse_from(["part1", "--format", "yaml"]).is_err());
+
+        let args = Args::try_parse_from(["part1", "-"]).unwrap();
+        assert_eq!(args.path.as_deref(), Some(Path::new("-")));
+        assert!(Args::try_parse_from(["part1", "-i", "x", "y"]).is_err());
+        assert!(Args::try_parse_from(["part1", "--example", "y"]).is_err());
+    }
+
+    #[test]
+    fn renders_timed_answers() {
+        struct Fixed;
+
+        impl aoc_common::solution::Solution for Fixed {
+            type Parsed = ();
+
+            fn parse(_: &str) -> Result<()> {
+                Ok(())
+            }
+
+            fn part1(_: &()) -> Result<Answer> {
+                Ok(Answer::U64(42))
+            }
+
+            fn part2(_: &()) -> Result<Answer> {
+                Ok(Answer::Text("ABC".into()))
+            }
+        }
+
+        const ENTRY: Entry = Entry::new::<Fixed>("aoc2025-day-3");
+        let timed = Timed {
+            answer: Answer::U64(42),
+            parse: Duration::from_micros(1500),
+            solve: Duration::from_millis(2),
+            items: Some(7),
+        };
+        assert_eq!(Format::Text.render(&ENTRY, Part::One, &timed), "Result: 42");
+        assert_eq!(
+            Format::Json.render(&ENTRY, Part::One, &timed),
+            "{\"year\":2025,\"day\":3,\"part\":1,\"answer\":42,\"parse_ms\":1.500,\"solve_ms\":2.000,\"items_parsed\":7}"
+        );
+
+        let timed = Timed {
+            answer: Answer::Text("ABC".into()),
+            items: None,
+            ..timed
+        };
+        assert!(Format::Json
+            .render(&ENTRY, Part::Two, &timed)
+            .contains("\"part\":2,\"answer\":\"ABC\","));
+        assert!(Format::Json
+            .render(&ENTRY, Part::Two, &timed)
+            .ends_with("\"items_parsed\":null}"));
+    }
+
+    #[test]
+    fn missing_example_is_reported() {
+        let args = Args {
+            example: true,
+            ..Args::default()
+        };
+        let day = Day::from_package("aoc2025-day-1", env!("CARGO_MANIFEST_DIR")).unwrap();
+        assert!(args.load(&day).is_err());
+    }
+}
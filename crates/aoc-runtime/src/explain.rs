@@ -0,0 +1,153 @@
+use std::fmt::{self, Write as _};
+use std::io::Write as _;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+/// Prints the [`aoc_common::explain`] events as a numbered walkthrough.
+///
+/// Each step shows the fields of its enclosing spans, its message and then
+/// its own fields:
+///
+/// ```text
+///    3. [machine=1] branch on x2 = 2.5
+/// ```
+pub struct Walkthrough<W> {
+    writer: W,
+    step: AtomicUsize,
+}
+
+impl<W> Walkthrough<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            step: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl<S, W> Layer<S> for Walkthrough<W>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + 'static,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut fields = Fields::default();
+        attrs.record(&mut fields);
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(fields);
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+
+        let mut line = format!("{:>4}. ", self.step.fetch_add(1, Ordering::Relaxed) + 1);
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope.from_root() {
+                let extensions = span.extensions();
+                match extensions.get::<Fields>() {
+                    Some(labels) if !labels.rest.is_empty() => {
+                        let _ = write!(line, "[{}] ", labels.rest.join(" "));
+                    }
+                    _ => {
+                        let _ = write!(line, "[{}] ", span.name());
+                    }
+                }
+            }
+        }
+        line.push_str(&fields.message);
+        if !fields.rest.is_empty() {
+            let _ = write!(line, " ({})", fields.rest.join(", "));
+        }
+
+        let _ = writeln!(self.writer.make_writer(), "{line}");
+    }
+}
+
+/// The recorded fields of a step or span, as `name=value` pairs.
+#[derive(Default)]
+struct Fields {
+    message: String,
+    rest: Vec<String>,
+}
+
+impl Visit for Fields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            self.rest.push(format!("{}={}", field.name(), value));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{value:?}");
+        } else {
+            self.rest.push(format!("{}={:?}", field.name(), value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    use aoc_common::{explain, explain_span};
+    use tracing_subscriber::filter::Targets;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for Buffer {
+        type Writer = Buffer;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[test]
+    fn numbers_steps_within_their_spans() {
+        let buffer = Buffer::default();
+        let subscriber =
+            tracing_subscriber::registry().with(Walkthrough::new(buffer.clone()).with_filter(
+                Targets::new().with_target(aoc_common::explain::TARGET, tracing::Level::DEBUG),
+            ));
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("not part of the walkthrough");
+            explain!("start");
+            let _machine = explain_span!("machine", machine = 1).entered();
+            explain!(cost = 4, "branch on x{} = {}", 2, 2.5);
+        });
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert_eq!(
+            output,
+            "   1. start\n   2. [machine=1] branch on x2 = 2.5 (cost=4)\n"
+        );
+    }
+}
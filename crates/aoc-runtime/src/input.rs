@@ -0,0 +1,139 @@
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use miette::*;
+
+/// Environment variable overriding the base of the input store.
+pub const DATA_HOME_VAR: &str = "XDG_DATA_HOME";
+
+/// The puzzle a binary solves, resolved from its package at compile time.
+///
+/// Build it with [`day!`](crate::day), which reads the calling crate's
+/// `CARGO_PKG_NAME` and `CARGO_MANIFEST_DIR`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    /// Directory of the day's crate, where `example.txt` lives.
+    pub crate_dir: &'static str,
+}
+
+impl Day {
+    /// Reads the year and day out of a package name such as `aoc2025-day-7`.
+    pub fn from_package(package: &str, crate_dir: &'static str) -> Result<Self> {
+        let (year, day) = package
+            .strip_prefix("aoc")
+            .and_then(|rest| rest.split_once("-day-"))
+            .ok_or_else(|| miette!("Package {} is not named aoc<year>-day-<day>", package))?;
+        Ok(Self {
+            year: year.parse().into_diagnostic()?,
+            day: day.parse().into_diagnostic()?,
+            crate_dir,
+        })
+    }
+
+    /// Location of the day's input in the store.
+    pub fn input_path(&self) -> Result<PathBuf> {
+        Ok(data_dir()?
+            .join(self.year.to_string())
+            .join(self.day.to_string())
+            .join("input.txt"))
+    }
+
+    /// Location of the day's example input.
+    pub fn example_path(&self) -> PathBuf {
+        Path::new(self.crate_dir).join("example.txt")
+    }
+
+    /// Reads the day's input from the store.
+    pub fn load_input(&self) -> Result<String> {
+        let path = self.input_path()?;
+        read(&path).wrap_err_with(|| {
+            format!(
+                "No input for {} day {}; fetch it with scripts/get-aoc-input.py {} day-{} or run just import-inputs",
+                self.year, self.day, self.year, self.day
+            )
+        })
+    }
+}
+
+/// Expands to the [`Day`] of the calling crate.
+#[macro_export]
+macro_rules! day {
+    () => {
+        $crate::Day::from_package(env!("CARGO_PKG_NAME"), env!("CARGO_MANIFEST_DIR"))
+    };
+}
+
+/// Expands to the calling crate's input from the store, as an
+/// `Option<&'static str>` for benchmarks.
+///
+/// The input is read once per process and shared by every benchmark. When it
+/// is not stored the reason is printed and the result is `None`, so the
+/// benchmarks can be skipped instead of failing on a fresh checkout.
+#[macro_export]
+macro_rules! bench_input {
+    () => {
+        $crate::bench_input(|| $crate::day!())
+    };
+}
+
+/// Backs [`bench_input!`](macro@crate::bench_input), which resolves `day`
+/// in the calling crate.
+#[doc(hidden)]
+pub fn bench_input(day: impl FnOnce() -> Result<Day>) -> Option<&'static str> {
+    static INPUT: OnceLock<Option<String>> = OnceLock::new();
+    INPUT
+        .get_or_init(|| match day().and_then(|day| day.load_input()) {
+            Ok(input) => Some(input),
+            Err(error) => {
+                eprintln!("skipping benchmarks: {error:?}");
+                None
+            }
+        })
+        .as_deref()
+}
+
+/// Root of the input store: `$XDG_DATA_HOME/aoc`, or `~/.local/share/aoc`.
+pub fn data_dir() -> Result<PathBuf> {
+    let base = match std::env::var_os(DATA_HOME_VAR).filter(|dir| !dir.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => std::env::var_os("HOME")
+            .map(|home| Path::new(&home).join(".local/share"))
+            .ok_or_else(|| miette!("Neither {} nor HOME is set", DATA_HOME_VAR))?,
+    };
+    Ok(base.join("aoc"))
+}
+
+/// Reads an input file, or stdin if `path` is `-`.
+pub(crate) fn read(path: &Path) -> Result<String> {
+    if path == Path::new("-") {
+        return std::io::read_to_string(std::io::stdin())
+            .into_diagnostic()
+            .wrap_err("Failed to read input from stdin");
+    }
+    std::fs::read_to_string(path)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("Failed to read input from {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day_comes_from_the_package_name() -> Result<()> {
+        let day = Day::from_package("aoc2025-day-12", "/crate")?;
+        assert_eq!((day.year, day.day), (2025, 12));
+        assert_eq!(day.example_path(), Path::new("/crate/example.txt"));
+        assert!(Day::from_package("aoc-runtime", "/crate").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn inputs_live_under_the_data_dir() -> Result<()> {
+        let day = Day::from_package("aoc2024-day-3", "/crate")?;
+        assert!(day.input_path()?.ends_with("aoc/2024/3/input.txt"));
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+//! Process-level plumbing shared by the solution binaries.
+
+mod args;
+mod explain;
+mod input;
+pub mod phases;
+
+use std::sync::Once;
+
+use miette::MietteHandlerOpts;
+use tracing_subscriber::filter::{LevelFilter, Targets};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::Layer;
+
+pub use args::{Args, Format};
+pub use input::{bench_input, data_dir, Day, DATA_HOME_VAR};
+
+/// Environment variable that caps the size of the global rayon pool.
+pub const THREADS_VAR: &str = "AOC_THREADS";
+
+/// Environment variable that, when set, swaps the logs for the walkthrough the
+/// solvers narrate through [`aoc_common::explain!`].
+pub const EXPLAIN_VAR: &str = "AOC_EXPLAIN";
+
+static INIT: Once = Once::new();
+
+/// Performs the one-time setup every binary needs before solving anything.
+///
+/// Installs miette's graphical report and panic hooks, the tracing
+/// subscriber (or the walkthrough printer under [`EXPLAIN_VAR`]) along with
+/// the [`phases`] timer, and sizes the global rayon pool from [`THREADS_VAR`]
+/// when it is set. Only the first call has any effect.
+pub fn init() {
+    INIT.call_once(|| {
+        let _ = miette::set_hook(Box::new(|_| {
+            Box::new(MietteHandlerOpts::new().context_lines(2).build())
+        }));
+        miette::set_panic_hook();
+        if std::env::var_os(EXPLAIN_VAR).is_some() {
+            let walkthrough = explain::Walkthrough::new(std::io::stderr).with_filter(
+                Targets::new().with_target(aoc_common::explain::TARGET, tracing::Level::DEBUG),
+            );
+            let _ = tracing_subscriber::registry()
+                .with(walkthrough)
+                .with(phases::PhaseTimer.with_filter(LevelFilter::INFO))
+                .try_init();
+        } else {
+            let _ = tracing_subscriber::registry()
+                .with(tracing_subscriber::fmt::layer().with_filter(LevelFilter::INFO))
+                .with(phases::PhaseTimer.with_filter(LevelFilter::INFO))
+                .try_init();
+        }
+        if let Some(threads) = thread_count() {
+            let _ = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build_global();
+        }
+    });
+}
+
+fn thread_count() -> Option<usize> {
+    std::env::var(THREADS_VAR)
+        .ok()?
+        .parse()
+        .ok()
+        .filter(|&threads| threads > 0)
+}
@@ -0,0 +1,144 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use aoc_common::solution::PHASES;
+use tracing::span::{Attributes, Id};
+use tracing::Subscriber;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+/// Time spent in one of the [`PHASES`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Phase {
+    pub name: &'static str,
+    /// How many spans of this phase closed.
+    pub spans: usize,
+    /// Wall-clock time in those spans, minus the time in phases nested in
+    /// them.
+    pub time: Duration,
+}
+
+static TOTALS: Mutex<[(usize, Duration); PHASES.len()]> =
+    Mutex::new([(0, Duration::ZERO); PHASES.len()]);
+
+/// The time recorded for every phase since the last call, in [`PHASES`]
+/// order, leaving the totals at zero.
+pub fn take() -> Vec<Phase> {
+    let totals = std::mem::take(&mut *TOTALS.lock().unwrap());
+    PHASES
+        .iter()
+        .zip(totals)
+        .map(|(&name, (spans, time))| Phase { name, spans, time })
+        .collect()
+}
+
+/// Renders `phases` as a table, leaving out the ones with no spans.
+pub fn table(phases: &[Phase]) -> String {
+    let mut table = format!("{:<8} {:>6} {:>12}\n", "phase", "spans", "time");
+    for phase in phases.iter().filter(|phase| phase.spans > 0) {
+        table += &format!(
+            "{:<8} {:>6} {:>12}\n",
+            phase.name,
+            phase.spans,
+            format!("{:.2?}", phase.time)
+        );
+    }
+    table
+}
+
+/// When a phase span opened, and how long the phases nested in it took.
+struct Timing {
+    phase: usize,
+    start: Instant,
+    nested: Duration,
+}
+
+/// Adds up the time spent in the spans named after [`PHASES`].
+///
+/// Spans are timed from creation to close, so a span entered on several
+/// threads counts once. Phases opened on other threads, as rayon does, have
+/// no parent here and count on their own.
+pub(crate) struct PhaseTimer;
+
+impl<S> Layer<S> for PhaseTimer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let name = attrs.metadata().name();
+        let Some(phase) = PHASES.iter().position(|&phase| phase == name) else {
+            return;
+        };
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(Timing {
+                phase,
+                start: Instant::now(),
+                nested: Duration::ZERO,
+            });
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some((phase, elapsed, nested)) = span
+            .extensions()
+            .get::<Timing>()
+            .map(|timing| (timing.phase, timing.start.elapsed(), timing.nested))
+        else {
+            return;
+        };
+        {
+            let mut totals = TOTALS.lock().unwrap();
+            totals[phase].0 += 1;
+            totals[phase].1 += elapsed.saturating_sub(nested);
+        }
+        if let Some(parent) = span
+            .scope()
+            .skip(1)
+            .find(|ancestor| ancestor.extensions().get::<Timing>().is_some())
+        {
+            if let Some(timing) = parent.extensions_mut().get_mut::<Timing>() {
+                timing.nested += elapsed;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn nested_phases_count_once() {
+        let subscriber = tracing_subscriber::registry().with(PhaseTimer);
+        tracing::subscriber::with_default(subscriber, || {
+            take();
+            tracing::info_span!("solve").in_scope(|| {
+                tracing::info_span!("build")
+                    .in_scope(|| std::thread::sleep(Duration::from_millis(20)));
+                tracing::info_span!("other")
+                    .in_scope(|| std::thread::sleep(Duration::from_millis(5)));
+            });
+            let phases = take();
+
+            assert_eq!(
+                phases.iter().map(|p| (p.name, p.spans)).collect::<Vec<_>>(),
+                [("parse", 0), ("build", 1), ("solve", 1)]
+            );
+            let (build, solve) = (phases[1].time, phases[2].time);
+            assert!(build >= Duration::from_millis(20), "{build:?}");
+            assert!(solve >= Duration::from_millis(5), "{solve:?}");
+            assert!(solve < Duration::from_millis(20), "{solve:?}");
+            assert!(take().iter().all(|phase| phase.spans == 0));
+
+            let table = table(&phases);
+            assert!(table.starts_with("phase"));
+            assert!(!table.contains("parse"));
+            assert!(table.contains("build"));
+        });
+    }
+}
@@ -0,0 +1,59 @@
+//! Every day's crate behind a single dependency, as `y<year>::day<day>`.
+//!
+//! ```
+//! use aoc_solutions as aoc;
+//!
+//! let answer = aoc::y2025::day1::part1::process("L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82");
+//! assert_eq!(answer.unwrap(), "3");
+//! ```
+//!
+//! The registry comes along too, for running a day picked by number.
+//!
+//! `aoc new` adds the days it creates here.
+
+pub use aoc_registry::{find, solutions};
+
+/// The 2024 puzzles.
+pub mod y2024 {
+    pub use aoc2024_day_1 as day1;
+    pub use aoc2024_day_10 as day10;
+    pub use aoc2024_day_11 as day11;
+    pub use aoc2024_day_12 as day12;
+    pub use aoc2024_day_13 as day13;
+    pub use aoc2024_day_14 as day14;
+    pub use aoc2024_day_15 as day15;
+    pub use aoc2024_day_16 as day16;
+    pub use aoc2024_day_17 as day17;
+    pub use aoc2024_day_18 as day18;
+    pub use aoc2024_day_19 as day19;
+    pub use aoc2024_day_2 as day2;
+    pub use aoc2024_day_20 as day20;
+    pub use aoc2024_day_21 as day21;
+    pub use aoc2024_day_22 as day22;
+    pub use aoc2024_day_23 as day23;
+    pub use aoc2024_day_24 as day24;
+    pub use aoc2024_day_25 as day25;
+    pub use aoc2024_day_3 as day3;
+    pub use aoc2024_day_4 as day4;
+    pub use aoc2024_day_5 as day5;
+    pub use aoc2024_day_6 as day6;
+    pub use aoc2024_day_7 as day7;
+    pub use aoc2024_day_8 as day8;
+    pub use aoc2024_day_9 as day9;
+}
+
+/// The 2025 puzzles.
+pub mod y2025 {
+    pub use aoc2025_day_1 as day1;
+    pub use aoc2025_day_10 as day10;
+    pub use aoc2025_day_11 as day11;
+    pub use aoc2025_day_12 as day12;
+    pub use aoc2025_day_2 as day2;
+    pub use aoc2025_day_3 as day3;
+    pub use aoc2025_day_4 as day4;
+    pub use aoc2025_day_5 as day5;
+    pub use aoc2025_day_6 as day6;
+    pub use aoc2025_day_7 as day7;
+    pub use aoc2025_day_8 as day8;
+    pub use aoc2025_day_9 as day9;
+}
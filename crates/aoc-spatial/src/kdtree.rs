@@ -0,0 +1,203 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use glam::DVec3;
+
+/// A point found by [`KdTree::nearest`].
+#[derive(Debug, Clone, Copy)]
+pub struct Neighbor {
+    /// Index of the point in the slice the tree was built from.
+    pub index: usize,
+    pub distance_squared: f64,
+}
+
+impl Neighbor {
+    /// Orders by distance, then by index, so that equally distant points
+    /// always come out in the same order.
+    fn key_cmp(&self, other: &Self) -> Ordering {
+        self.distance_squared
+            .total_cmp(&other.distance_squared)
+            .then(self.index.cmp(&other.index))
+    }
+}
+
+impl PartialEq for Neighbor {
+    fn eq(&self, other: &Self) -> bool {
+        self.key_cmp(other).is_eq()
+    }
+}
+
+impl Eq for Neighbor {}
+
+impl PartialOrd for Neighbor {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Neighbor {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key_cmp(other)
+    }
+}
+
+/// A static 3D k-d tree over a set of points.
+///
+/// The tree is implicit: `order` holds the point indices arranged so that
+/// every subslice's middle element splits the rest on the axis of its depth,
+/// smaller coordinates to the left.
+#[derive(Debug, Clone)]
+pub struct KdTree {
+    points: Vec<DVec3>,
+    order: Vec<usize>,
+}
+
+impl KdTree {
+    pub fn new(points: &[DVec3]) -> Self {
+        let mut order = (0..points.len()).collect::<Vec<_>>();
+        build(points, &mut order, 0);
+        Self {
+            points: points.to_vec(),
+            order,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// The point at `index` in the slice the tree was built from.
+    pub fn point(&self, index: usize) -> DVec3 {
+        self.points[index]
+    }
+
+    /// The `k` points closest to `query`, closest first.
+    ///
+    /// Ties are broken by index, so the result for `k` is always a prefix of
+    /// the result for any larger `k`. A point at `query` itself is included.
+    pub fn nearest(&self, query: DVec3, k: usize) -> Vec<Neighbor> {
+        if k == 0 {
+            return Vec::new();
+        }
+        let mut best = BinaryHeap::with_capacity(k + 1);
+        self.search(&self.order, 0, query, k, &mut best);
+        best.into_sorted_vec()
+    }
+
+    /// Collects into the max-heap `best` the `k` closest points of the
+    /// subtree `nodes`, whose splits start at `depth`.
+    fn search(
+        &self,
+        nodes: &[usize],
+        depth: usize,
+        query: DVec3,
+        k: usize,
+        best: &mut BinaryHeap<Neighbor>,
+    ) {
+        if nodes.is_empty() {
+            return;
+        }
+        let mid = nodes.len() / 2;
+        let index = nodes[mid];
+        let point = self.points[index];
+        let candidate = Neighbor {
+            index,
+            distance_squared: query.distance_squared(point),
+        };
+        if best.len() < k {
+            best.push(candidate);
+        } else if best.peek().is_some_and(|worst| candidate < *worst) {
+            best.pop();
+            best.push(candidate);
+        }
+
+        let axis = depth % 3;
+        let offset = query[axis] - point[axis];
+        let (near, far) = if offset < 0.0 {
+            (&nodes[..mid], &nodes[mid + 1..])
+        } else {
+            (&nodes[mid + 1..], &nodes[..mid])
+        };
+        self.search(near, depth + 1, query, k, best);
+        // Everything across the splitting plane is at least `offset` away.
+        // Equal distances still matter, as a smaller index wins the tie.
+        let reachable = best.len() < k
+            || best
+                .peek()
+                .is_some_and(|worst| offset * offset <= worst.distance_squared);
+        if reachable {
+            self.search(far, depth + 1, query, k, best);
+        }
+    }
+}
+
+/// Arranges `order` into an implicit k-d tree, splitting on `depth`'s axis.
+fn build(points: &[DVec3], order: &mut [usize], depth: usize) {
+    if order.len() <= 1 {
+        return;
+    }
+    let axis = depth % 3;
+    let mid = order.len() / 2;
+    order.select_nth_unstable_by(mid, |&a, &b| points[a][axis].total_cmp(&points[b][axis]));
+    let (left, right) = order.split_at_mut(mid);
+    build(points, left, depth + 1);
+    build(points, &mut right[1..], depth + 1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn brute_force(points: &[DVec3], query: DVec3, k: usize) -> Vec<Neighbor> {
+        let mut all = points
+            .iter()
+            .enumerate()
+            .map(|(index, &point)| Neighbor {
+                index,
+                distance_squared: query.distance_squared(point),
+            })
+            .collect::<Vec<_>>();
+        all.sort();
+        all.truncate(k);
+        all
+    }
+
+    #[test]
+    fn nearest_matches_brute_force() {
+        let mut rng = StdRng::seed_from_u64(515);
+        // A small coordinate range makes plenty of ties.
+        let points = (0..300)
+            .map(|_| {
+                DVec3::new(
+                    rng.random_range(0..20) as f64,
+                    rng.random_range(0..20) as f64,
+                    rng.random_range(0..20) as f64,
+                )
+            })
+            .collect::<Vec<_>>();
+        let tree = KdTree::new(&points);
+        for _ in 0..50 {
+            let query = points[rng.random_range(0..points.len())];
+            for k in [0, 1, 7, 64, 300, 400] {
+                let found = tree.nearest(query, k);
+                let expected = brute_force(&points, query, k);
+                let indices =
+                    |neighbors: &[Neighbor]| neighbors.iter().map(|n| n.index).collect::<Vec<_>>();
+                assert_eq!(indices(&found), indices(&expected), "k = {k}");
+            }
+        }
+    }
+
+    #[test]
+    fn empty_tree_finds_nothing() {
+        let tree = KdTree::new(&[]);
+        assert!(tree.is_empty());
+        assert!(tree.nearest(DVec3::ZERO, 3).is_empty());
+    }
+}
@@ -0,0 +1,11 @@
+//! Spatial indexes for the days that search point clouds.
+//!
+//! [`KdTree`] answers k-nearest-neighbor queries, and [`ClosestPairs`] builds
+//! on it to stream every pair of points from the closest outwards, producing
+//! only as many candidate edges as the caller consumes.
+
+mod kdtree;
+mod pairs;
+
+pub use kdtree::{KdTree, Neighbor};
+pub use pairs::ClosestPairs;
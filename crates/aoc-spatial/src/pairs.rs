@@ -0,0 +1,163 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+use glam::DVec3;
+
+use crate::{KdTree, Neighbor};
+
+/// Neighbors fetched per point on its first query; later queries double it.
+const FIRST_BATCH: usize = 8;
+
+/// The next unseen neighbor of `from`, as queued in [`ClosestPairs`].
+///
+/// Ordered by distance, then by `(from, neighbor)`, which is the order the
+/// pairs are reported in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Candidate {
+    neighbor: Neighbor,
+    from: usize,
+    /// Position of `neighbor` in `from`'s neighbor list.
+    rank: usize,
+}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.neighbor
+            .distance_squared
+            .total_cmp(&other.neighbor.distance_squared)
+            .then(self.from.cmp(&other.from))
+            .then(self.neighbor.index.cmp(&other.neighbor.index))
+    }
+}
+
+/// Every pair of points, closest first, as `(i, j, distance_squared)` with
+/// `i < j`.
+///
+/// Pairs are generated lazily: each point walks its own neighbors outwards
+/// through the [`KdTree`], fetching them in doubling batches, and a heap
+/// merges those walks. Taking the first `m` pairs thus costs about
+/// `O((n + m) log n)` rather than building and sorting all `n²/2` of them.
+///
+/// Equally distant pairs come out ordered by `(i, j)`, so the sequence is
+/// fully determined by the points.
+#[derive(Debug, Clone)]
+pub struct ClosestPairs {
+    tree: KdTree,
+    /// Neighbors of each point fetched so far, closest first, without itself.
+    neighbors: Vec<Vec<Neighbor>>,
+    /// Whether each point's list already holds every other point.
+    complete: Vec<bool>,
+    heap: BinaryHeap<Reverse<Candidate>>,
+}
+
+impl ClosestPairs {
+    pub fn new(points: &[DVec3]) -> Self {
+        let tree = KdTree::new(points);
+        let mut pairs = Self {
+            tree,
+            neighbors: vec![Vec::new(); points.len()],
+            complete: vec![false; points.len()],
+            heap: BinaryHeap::with_capacity(points.len()),
+        };
+        for from in 0..points.len() {
+            pairs.queue(from, 0);
+        }
+        pairs
+    }
+
+    /// Queues the `rank`-th closest neighbor of `from`, fetching more
+    /// neighbors if the list runs out.
+    fn queue(&mut self, from: usize, rank: usize) {
+        if rank == self.neighbors[from].len() && !self.complete[from] {
+            self.fetch(from, (2 * rank).max(FIRST_BATCH));
+        }
+        if let Some(&neighbor) = self.neighbors[from].get(rank) {
+            self.heap.push(Reverse(Candidate {
+                neighbor,
+                from,
+                rank,
+            }));
+        }
+    }
+
+    /// Replaces the neighbor list of `from` with its `k` closest neighbors.
+    ///
+    /// Ties are broken the same way on every query, so the old list is a
+    /// prefix of the new one and ranks already queued stay valid.
+    fn fetch(&mut self, from: usize, k: usize) {
+        let others = self.tree.len() - 1;
+        let k = k.min(others);
+        let mut neighbors = self.tree.nearest(self.tree.point(from), k + 1);
+        neighbors.retain(|neighbor| neighbor.index != from);
+        neighbors.truncate(k);
+        self.complete[from] = k == others;
+        self.neighbors[from] = neighbors;
+    }
+}
+
+impl Iterator for ClosestPairs {
+    type Item = (usize, usize, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Each pair is reached from both ends; only the walk from the smaller
+        // index reports it.
+        loop {
+            let Reverse(candidate) = self.heap.pop()?;
+            self.queue(candidate.from, candidate.rank + 1);
+            let Candidate { neighbor, from, .. } = candidate;
+            if from < neighbor.index {
+                return Some((from, neighbor.index, neighbor.distance_squared));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn random_points(rng: &mut StdRng, n: usize, range: i32) -> Vec<DVec3> {
+        (0..n)
+            .map(|_| {
+                DVec3::new(
+                    rng.random_range(0..range) as f64,
+                    rng.random_range(0..range) as f64,
+                    rng.random_range(0..range) as f64,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn streams_every_pair_in_order() {
+        let mut rng = StdRng::seed_from_u64(515);
+        for (n, range) in [(0, 10), (1, 10), (2, 10), (60, 5), (200, 1000)] {
+            let points = random_points(&mut rng, n, range);
+            let mut expected = Vec::new();
+            for i in 0..n {
+                for j in i + 1..n {
+                    expected.push((i, j, points[i].distance_squared(points[j])));
+                }
+            }
+            expected.sort_by(|a, b| a.2.total_cmp(&b.2).then((a.0, a.1).cmp(&(b.0, b.1))));
+
+            let streamed = ClosestPairs::new(&points).collect::<Vec<_>>();
+            assert_eq!(streamed, expected, "{n} points in 0..{range}");
+        }
+    }
+
+    #[test]
+    fn duplicate_points_pair_at_zero() {
+        let points = [DVec3::ONE, DVec3::ZERO, DVec3::ONE];
+        let pairs = ClosestPairs::new(&points).collect::<Vec<_>>();
+        assert_eq!(pairs, [(0, 2, 0.0), (0, 1, 3.0), (1, 2, 3.0)]);
+    }
+}
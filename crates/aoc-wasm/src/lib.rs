@@ -0,0 +1,85 @@
+//! The registered solutions compiled to WebAssembly, for running them from
+//! JavaScript.
+//!
+//! Build with `wasm-pack build crates/aoc-wasm --target web` (or plain
+//! `cargo build --target wasm32-unknown-unknown -p aoc-wasm`). Without the
+//! `parallel` feature the days that use rayon run on the calling thread, as
+//! `wasm32-unknown-unknown` has no threads of its own.
+//!
+//! ```js
+//! import init, { solve } from "./pkg/aoc_wasm.js";
+//! await init();
+//! console.log(solve(2025, 1, 1, input));
+//! ```
+
+use aoc_common::solution::Part;
+use miette::{GraphicalReportHandler, GraphicalTheme, Report};
+use wasm_bindgen::prelude::*;
+
+/// Solves one part of a registered day, returning the answer as a string.
+///
+/// Throws an `Error` for an unknown day or part, or when the solution fails;
+/// a parse error's message includes the offending line, drawn without
+/// colors.
+#[wasm_bindgen]
+pub fn solve(year: u16, day: u8, part: u8, input: &str) -> Result<String, JsError> {
+    solve_str(year, day, part, input).map_err(|message| JsError::new(&message))
+}
+
+/// The registered days, as `"<year>-<day>"` with the day padded to two digits.
+#[wasm_bindgen]
+pub fn days() -> Vec<String> {
+    aoc_registry::solutions()
+        .iter()
+        .copied()
+        .map(|entry| format!("{}-{:02}", entry.year, entry.day))
+        .collect()
+}
+
+/// [`solve`] with the error as the text JavaScript will see, so it can be
+/// exercised off the wasm target too.
+pub fn solve_str(year: u16, day: u8, part: u8, input: &str) -> Result<String, String> {
+    let entry = aoc_registry::find(year, day)
+        .ok_or_else(|| format!("No solution is registered for {year} day {day}"))?;
+    let part = match part {
+        1 => Part::One,
+        2 => Part::Two,
+        _ => return Err(format!("There is no part {part}; pick 1 or 2")),
+    };
+    entry
+        .run(input, part)
+        .map(|answer| answer.to_string())
+        .map_err(|report| render(&report))
+}
+
+/// Draws a report the way the terminal would, minus the colors.
+fn render(report: &Report) -> String {
+    let mut out = String::new();
+    let handler = GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor());
+    match handler.render_report(&mut out, report.as_ref()) {
+        Ok(()) => out,
+        Err(_) => format!("{report}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solves_registered_days() {
+        let input = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
+        assert_eq!(solve_str(2025, 1, 1, input), Ok("3".to_string()));
+        assert!(days().contains(&"2025-01".to_string()));
+    }
+
+    #[test]
+    fn explains_what_went_wrong() {
+        assert!(solve_str(2025, 26, 1, "")
+            .unwrap_err()
+            .contains("2025 day 26"));
+        assert!(solve_str(2025, 1, 3, "").unwrap_err().contains("part 3"));
+        let error = solve_str(2025, 1, 1, "L68\nX30").unwrap_err();
+        assert!(error.contains("X30"), "{error}");
+    }
+}
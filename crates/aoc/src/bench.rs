@@ -0,0 +1,347 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use aoc_common::solution::{Entry, Part};
+use miette::*;
+use owo_colors::{OwoColorize, Stream};
+
+/// Arguments of `aoc bench`.
+///
+/// Every selected day is solved in-process from its stored input, so all of
+/// them go through the same harness. Build in release mode for meaningful
+/// numbers, e.g. through `just bench-all`.
+#[derive(Debug, clap::Args)]
+pub struct BenchArgs {
+    /// Benchmark every registered day.
+    #[arg(long, required_unless_present = "day")]
+    all: bool,
+    /// Only benchmark days of this year.
+    #[arg(short, long)]
+    year: Option<u16>,
+    /// Only benchmark this day.
+    #[arg(short, long)]
+    day: Option<u8>,
+    /// Untimed runs per part before measuring.
+    #[arg(short, long, default_value_t = 3)]
+    warmup: u32,
+    /// Timed runs per part.
+    #[arg(short = 'n', long, default_value_t = 20, value_parser = clap::value_parser!(u32).range(1..))]
+    iterations: u32,
+    /// Also write the results as JSON to this file, as a baseline for
+    /// `--compare`.
+    #[arg(long, visible_alias = "json", value_name = "PATH")]
+    save: Option<PathBuf>,
+    /// Compare the medians against a baseline written by `--save`.
+    #[arg(long, value_name = "PATH")]
+    compare: Option<PathBuf>,
+    /// Changes in the median within this percentage count as noise.
+    #[arg(long, default_value_t = 5.0, requires = "compare")]
+    threshold: f64,
+}
+
+/// Timings of one part over all its timed runs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Stats {
+    min: Duration,
+    median: Duration,
+    p95: Duration,
+    /// Median time spent parsing, included in the totals above.
+    parse: Duration,
+}
+
+impl Stats {
+    /// Summarizes `(parse, solve)` pairs, one per run.
+    fn new(runs: &[(Duration, Duration)]) -> Self {
+        let mut totals = runs
+            .iter()
+            .map(|&(parse, solve)| parse + solve)
+            .collect::<Vec<_>>();
+        let mut parses = runs.iter().map(|&(parse, _)| parse).collect::<Vec<_>>();
+        totals.sort_unstable();
+        parses.sort_unstable();
+        Self {
+            min: totals[0],
+            median: totals[totals.len() / 2],
+            p95: totals[(totals.len() * 95).div_ceil(100) - 1],
+            parse: parses[parses.len() / 2],
+        }
+    }
+}
+
+/// The outcome of benchmarking one part of one day.
+struct Row {
+    entry: &'static Entry,
+    part: Part,
+    result: Result<Stats>,
+}
+
+fn measure(entry: &Entry, input: &str, part: Part, warmup: u32, iterations: u32) -> Result<Stats> {
+    for _ in 0..warmup {
+        entry.run(input, part)?;
+    }
+    let mut answer = None;
+    let mut runs = Vec::with_capacity(iterations as usize);
+    for _ in 0..iterations {
+        let timed = entry.run_timed(input, part)?;
+        if answer.get_or_insert_with(|| timed.answer.clone()) != &timed.answer {
+            bail!(
+                "Not deterministic: answered {} and then {}",
+                answer.unwrap(),
+                timed.answer
+            );
+        }
+        runs.push((timed.parse, timed.solve));
+    }
+    Ok(Stats::new(&runs))
+}
+
+/// The stored input of `entry`'s day.
+pub(crate) fn input_of(entry: &Entry) -> Result<String> {
+    let path = aoc_runtime::data_dir()?
+        .join(entry.year.to_string())
+        .join(entry.day.to_string())
+        .join("input.txt");
+    std::fs::read_to_string(&path)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("No input at {}", path.display()))
+}
+
+fn print_table(rows: &[Row]) {
+    println!(
+        "{:<6} {:>3} {:>4}  {:>12} {:>12} {:>12} {:>12}",
+        "year", "day", "part", "median", "p95", "min", "parse"
+    );
+    for row in rows {
+        print!(
+            "{:<6} {:>3} {:>4}  ",
+            row.entry.year, row.entry.day, row.part
+        );
+        match &row.result {
+            Ok(stats) => println!(
+                "{:>12} {:>12} {:>12} {:>12}",
+                format!("{:.2?}", stats.median),
+                format!("{:.2?}", stats.p95),
+                format!("{:.2?}", stats.min),
+                format!("{:.2?}", stats.parse),
+            ),
+            Err(error) => println!("skipped: {error}"),
+        }
+    }
+}
+
+fn to_json(rows: &[Row], iterations: u32) -> serde_json::Value {
+    let nanos = |duration: Duration| duration.as_nanos() as u64;
+    let days = rows
+        .iter()
+        .map(|row| {
+            let mut value = serde_json::json!({
+                "year": row.entry.year,
+                "day": row.entry.day,
+                "part": part_number(row.part),
+            });
+            match &row.result {
+                Ok(stats) => {
+                    value["min_ns"] = nanos(stats.min).into();
+                    value["median_ns"] = nanos(stats.median).into();
+                    value["p95_ns"] = nanos(stats.p95).into();
+                    value["parse_ns"] = nanos(stats.parse).into();
+                }
+                Err(error) => value["error"] = error.to_string().into(),
+            }
+            value
+        })
+        .collect::<Vec<_>>();
+    serde_json::json!({ "iterations": iterations, "results": days })
+}
+
+/// Which way a part's median moved against the baseline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Change {
+    Regression,
+    Improvement,
+    Noise,
+}
+
+impl Change {
+    /// Classifies a change of `delta` percent, ignoring anything within
+    /// `threshold` percent.
+    fn of(delta: f64, threshold: f64) -> Self {
+        if delta > threshold {
+            Self::Regression
+        } else if delta < -threshold {
+            Self::Improvement
+        } else {
+            Self::Noise
+        }
+    }
+}
+
+/// How much slower `now` is than `before`, in percent.
+fn delta(before: Duration, now: Duration) -> f64 {
+    let (before, now) = (before.as_nanos() as f64, now.as_nanos() as f64);
+    (now - before) / before * 100.0
+}
+
+/// The median of every part that succeeded in a file written by `--save`,
+/// keyed by year, day and part number.
+fn read_baseline(path: &Path) -> Result<HashMap<(u16, u8, u8), Duration>> {
+    let text = std::fs::read_to_string(path)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("Failed to read the baseline {}", path.display()))?;
+    let json = serde_json::from_str::<serde_json::Value>(&text)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("The baseline {} is not JSON", path.display()))?;
+    let results = json["results"]
+        .as_array()
+        .ok_or_else(|| miette!("The baseline {} has no results", path.display()))?;
+    Ok(results
+        .iter()
+        .filter_map(|result| {
+            let key = (
+                u16::try_from(result["year"].as_u64()?).ok()?,
+                u8::try_from(result["day"].as_u64()?).ok()?,
+                u8::try_from(result["part"].as_u64()?).ok()?,
+            );
+            Some((key, Duration::from_nanos(result["median_ns"].as_u64()?)))
+        })
+        .collect())
+}
+
+fn print_comparison(rows: &[Row], baseline: &HashMap<(u16, u8, u8), Duration>, threshold: f64) {
+    println!();
+    println!(
+        "{:<6} {:>3} {:>4}  {:>12} {:>12} {:>9}",
+        "year", "day", "part", "baseline", "median", "delta"
+    );
+    for row in rows {
+        let key = (row.entry.year, row.entry.day, part_number(row.part));
+        let (Ok(stats), Some(&before)) = (&row.result, baseline.get(&key)) else {
+            continue;
+        };
+        let delta = delta(before, stats.median);
+        let cell = format!("{delta:>+8.1}%");
+        let cell = match Change::of(delta, threshold) {
+            Change::Regression => cell
+                .if_supports_color(Stream::Stdout, |cell| cell.red())
+                .to_string(),
+            Change::Improvement => cell
+                .if_supports_color(Stream::Stdout, |cell| cell.green())
+                .to_string(),
+            Change::Noise => cell,
+        };
+        println!(
+            "{:<6} {:>3} {:>4}  {:>12} {:>12} {}",
+            row.entry.year,
+            row.entry.day,
+            row.part,
+            format!("{before:.2?}"),
+            format!("{:.2?}", stats.median),
+            cell,
+        );
+    }
+}
+
+fn part_number(part: Part) -> u8 {
+    match part {
+        Part::One => 1,
+        Part::Two => 2,
+    }
+}
+
+pub fn run(args: BenchArgs) -> Result<()> {
+    let baseline = args.compare.as_deref().map(read_baseline).transpose()?;
+    let entries = aoc_registry::solutions()
+        .iter()
+        .copied()
+        .filter(|entry| args.year.is_none_or(|year| entry.year == year))
+        .filter(|entry| args.day.is_none_or(|day| entry.day == day))
+        .collect::<Vec<_>>();
+    if entries.is_empty() {
+        bail!("No registered solution matches the selection");
+    }
+
+    let mut rows = Vec::new();
+    for entry in entries {
+        let input = input_of(entry);
+        for part in Part::ALL {
+            tracing::debug!("benchmarking {} part {}", entry.package, part);
+            let result = match &input {
+                Ok(input) => measure(entry, input, part, args.warmup, args.iterations),
+                Err(error) => Err(miette!("{error}")),
+            };
+            rows.push(Row {
+                entry,
+                part,
+                result,
+            });
+        }
+    }
+
+    print_table(&rows);
+    if let Some(baseline) = &baseline {
+        print_comparison(&rows, baseline, args.threshold);
+    }
+    if let Some(path) = &args.save {
+        let json =
+            serde_json::to_string_pretty(&to_json(&rows, args.iterations)).into_diagnostic()?;
+        std::fs::write(path, json + "\n")
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_over_runs() {
+        let ms = Duration::from_millis;
+        let runs = (1..=20).rev().map(|n| (ms(1), ms(n))).collect::<Vec<_>>();
+        let stats = Stats::new(&runs);
+        assert_eq!(stats.min, ms(2));
+        assert_eq!(stats.median, ms(12));
+        assert_eq!(stats.p95, ms(20));
+        assert_eq!(stats.parse, ms(1));
+
+        let single = Stats::new(&[(ms(1), ms(4))]);
+        assert_eq!((single.median, single.p95), (ms(5), ms(5)));
+    }
+
+    #[test]
+    fn saved_results_read_back_as_a_baseline() -> Result<()> {
+        let ms = Duration::from_millis;
+        let entry = aoc_registry::find(2025, 1).unwrap();
+        let rows = [
+            Row {
+                entry,
+                part: Part::One,
+                result: Ok(Stats::new(&[(ms(1), ms(3))])),
+            },
+            Row {
+                entry,
+                part: Part::Two,
+                result: Err(miette!("no input")),
+            },
+        ];
+        let path = std::env::temp_dir().join(format!("aoc-baseline-{}.json", std::process::id()));
+        std::fs::write(&path, to_json(&rows, 1).to_string()).into_diagnostic()?;
+        let baseline = read_baseline(&path);
+        std::fs::remove_file(&path).into_diagnostic()?;
+        assert_eq!(baseline?, HashMap::from([((2025, 1, 1), ms(4))]));
+        Ok(())
+    }
+
+    #[test]
+    fn changes_past_the_threshold_count() {
+        let ms = Duration::from_millis;
+        assert_eq!(delta(ms(100), ms(150)), 50.0);
+        assert_eq!(delta(ms(200), ms(100)), -50.0);
+        assert_eq!(Change::of(50.0, 5.0), Change::Regression);
+        assert_eq!(Change::of(-50.0, 5.0), Change::Improvement);
+        assert_eq!(Change::of(4.0, 5.0), Change::Noise);
+        assert_eq!(Change::of(-5.0, 5.0), Change::Noise);
+    }
+}
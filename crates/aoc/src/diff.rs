@@ -0,0 +1,270 @@
+use std::io::Read;
+use std::os::unix::process::ExitStatusExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+use std::time::{Duration, Instant};
+
+use aoc_common::answer::Answer;
+use miette::*;
+
+/// Arguments of `aoc diff`.
+///
+/// Both sides are built in release mode and run as separate processes, so
+/// the right-hand side can come from another git worktree as easily as from
+/// another binary of the same package.
+#[derive(Debug, clap::Args)]
+pub struct DiffArgs {
+    /// Package holding the implementations, e.g. `aoc2025-day-2`.
+    #[arg(short, long)]
+    package: String,
+    /// Binary used as the baseline, e.g. `part2`.
+    left: String,
+    /// Binary compared against the baseline; defaults to the baseline itself.
+    right: Option<String>,
+    /// Build the right-hand side from this worktree instead of the current one.
+    #[arg(short, long, value_name = "DIR")]
+    worktree: Option<PathBuf>,
+    /// Input handed to both sides through their `--input` flag.
+    #[arg(short, long, value_name = "PATH")]
+    input: Option<PathBuf>,
+    /// Timed runs per side.
+    #[arg(short, long, default_value_t = 5, value_parser = clap::value_parser!(u32).range(1..))]
+    runs: u32,
+}
+
+/// One side of the comparison.
+#[derive(Debug)]
+pub(crate) struct Target {
+    pub(crate) dir: PathBuf,
+    pub(crate) package: String,
+    pub(crate) bin: String,
+}
+
+impl Target {
+    fn label(&self) -> String {
+        format!("{}: {}/{}", self.dir.display(), self.package, self.bin)
+    }
+
+    /// Builds the binary and returns the path of the executable cargo produced.
+    pub(crate) fn build(&self) -> Result<PathBuf> {
+        let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+        let output = Command::new(cargo)
+            .current_dir(&self.dir)
+            .args(["build", "--release", "--message-format=json"])
+            .args(["--package", &self.package, "--bin", &self.bin])
+            .stderr(Stdio::inherit())
+            .output()
+            .into_diagnostic()
+            .wrap_err("Failed to invoke cargo")?;
+        if !output.status.success() {
+            bail!("Failed to build {}", self.label());
+        }
+        executable(&String::from_utf8_lossy(&output.stdout), &self.bin)
+            .ok_or_else(|| miette!("Cargo did not report an executable for {}", self.label()))
+    }
+}
+
+/// Finds the executable built for `bin` in cargo's JSON messages.
+fn executable(messages: &str, bin: &str) -> Option<PathBuf> {
+    messages
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|message| message["reason"] == "compiler-artifact")
+        .filter(|message| message["target"]["name"] == bin)
+        .find_map(|message| message["executable"].as_str().map(PathBuf::from))
+}
+
+/// Answer and resource usage of a single run.
+#[derive(Debug)]
+pub(crate) struct Run {
+    pub(crate) answer: Answer,
+    elapsed: Duration,
+    peak_rss_kib: u64,
+}
+
+/// Aggregate over all the timed runs of one side.
+#[derive(Debug)]
+struct Summary {
+    answer: Answer,
+    best: Duration,
+    median: Duration,
+    peak_rss_kib: u64,
+}
+
+pub(crate) fn run_once(executable: &Path, input: Option<&Path>) -> Result<Run> {
+    let mut command = Command::new(executable);
+    if let Some(input) = input {
+        command.arg("--input").arg(input);
+    }
+    let start = Instant::now();
+    let mut child = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .into_diagnostic()
+        .wrap_err_with(|| format!("Failed to run {}", executable.display()))?;
+    let mut stdout = String::new();
+    if let Some(mut pipe) = child.stdout.take() {
+        pipe.read_to_string(&mut stdout).into_diagnostic()?;
+    }
+    let (status, usage) = wait_with_usage(child.id())?;
+    let elapsed = start.elapsed();
+
+    if !status.success() {
+        bail!("{} exited with {}", executable.display(), status);
+    }
+    let answer = parse_answer(&stdout)
+        .ok_or_else(|| miette!("{} did not print an answer", executable.display()))?;
+    Ok(Run {
+        answer,
+        elapsed,
+        peak_rss_kib: u64::try_from(usage.ru_maxrss).unwrap_or_default(),
+    })
+}
+
+/// Reaps `pid`, returning its exit status together with its resource usage.
+fn wait_with_usage(pid: u32) -> Result<(ExitStatus, libc::rusage)> {
+    let mut status = 0;
+    // SAFETY: `rusage` is plain old data, so the all-zero value is valid.
+    let mut usage = unsafe { std::mem::zeroed::<libc::rusage>() };
+    // SAFETY: both out-pointers are valid for writes, and `pid` is a child of
+    // this process that has not been reaped yet.
+    let reaped = unsafe { libc::wait4(pid as libc::pid_t, &mut status, 0, &mut usage) };
+    if reaped < 0 {
+        return Err(std::io::Error::last_os_error()).into_diagnostic();
+    }
+    Ok((ExitStatus::from_raw(status), usage))
+}
+
+/// Extracts the answer from a bin's output, either `Result: N` or `--format json`.
+fn parse_answer(stdout: &str) -> Option<Answer> {
+    stdout.lines().rev().find_map(|line| {
+        if let Some(answer) = line.trim().strip_prefix("Result: ") {
+            return Some(Answer::parse(answer));
+        }
+        let value = serde_json::from_str::<serde_json::Value>(line).ok()?;
+        match &value["answer"] {
+            serde_json::Value::String(answer) => Some(Answer::parse(answer)),
+            serde_json::Value::Number(answer) => Some(Answer::parse(&answer.to_string())),
+            _ => None,
+        }
+    })
+}
+
+fn measure(executable: &Path, input: Option<&Path>, runs: u32) -> Result<Summary> {
+    let runs = (0..runs)
+        .map(|_| run_once(executable, input))
+        .collect::<Result<Vec<_>>>()?;
+    if let Some(run) = runs.iter().find(|run| run.answer != runs[0].answer) {
+        bail!(
+            "{} is not deterministic: answered {} and then {}",
+            executable.display(),
+            runs[0].answer,
+            run.answer
+        );
+    }
+    let mut times = runs.iter().map(|run| run.elapsed).collect::<Vec<_>>();
+    times.sort_unstable();
+    Ok(Summary {
+        answer: runs[0].answer.clone(),
+        best: times[0],
+        median: times[times.len() / 2],
+        peak_rss_kib: runs.iter().map(|run| run.peak_rss_kib).max().unwrap_or(0),
+    })
+}
+
+fn print_summary(side: &str, target: &Target, summary: &Summary) {
+    println!("{side:<5} {}", target.label());
+    println!("      answer  {}", summary.answer);
+    println!(
+        "      time    best {:.2?}, median {:.2?}",
+        summary.best, summary.median
+    );
+    println!("      memory  {} KiB peak RSS", summary.peak_rss_kib);
+}
+
+/// How the right-hand side compares to the left, as a ratio of median times.
+fn speedup(left: Duration, right: Duration) -> f64 {
+    left.as_secs_f64() / right.as_secs_f64().max(f64::EPSILON)
+}
+
+pub fn run(args: DiffArgs) -> Result<()> {
+    let current = std::env::current_dir().into_diagnostic()?;
+    let left = Target {
+        dir: current.clone(),
+        package: args.package.clone(),
+        bin: args.left.clone(),
+    };
+    let right = Target {
+        dir: args.worktree.unwrap_or(current),
+        package: args.package,
+        bin: args.right.unwrap_or(args.left),
+    };
+    if left.dir == right.dir && left.bin == right.bin {
+        bail!("Nothing to compare: pass a second binary or a --worktree");
+    }
+
+    let input = args.input.as_deref();
+    let left_summary = measure(&left.build()?, input, args.runs)?;
+    let right_summary = measure(&right.build()?, input, args.runs)?;
+
+    print_summary("left", &left, &left_summary);
+    print_summary("right", &right, &right_summary);
+    println!(
+        "speedup {:.2}x (median), memory {:+} KiB",
+        speedup(left_summary.median, right_summary.median),
+        right_summary.peak_rss_kib as i64 - left_summary.peak_rss_kib as i64
+    );
+
+    if left_summary.answer != right_summary.answer {
+        bail!(
+            "Answers differ: {} vs {}",
+            left_summary.answer,
+            right_summary.answer
+        );
+    }
+    println!("answers match");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_plain_and_json_answers() {
+        assert_eq!(parse_answer("noise\nResult: 42\n"), Some(Answer::U64(42)));
+        assert_eq!(
+            parse_answer("{\"part\":1,\"answer\":\"7\"}\n"),
+            Some(Answer::U64(7))
+        );
+        assert_eq!(
+            parse_answer("{\"part\":2,\"answer\":-3}\n"),
+            Some(Answer::I64(-3))
+        );
+        assert_eq!(
+            parse_answer("{\"year\":2025,\"day\":1,\"part\":1,\"answer\":5,\"parse_ms\":0.010,\"solve_ms\":1.000,\"items_parsed\":null}\n"),
+            Some(Answer::U64(5))
+        );
+        assert_eq!(parse_answer("nothing here"), None);
+    }
+
+    #[test]
+    fn finds_the_executable_of_the_requested_bin() {
+        let messages = r#"{"reason":"compiler-artifact","target":{"name":"day"},"executable":null}
+{"reason":"compiler-artifact","target":{"name":"part1"},"executable":"/t/part1"}
+{"reason":"compiler-artifact","target":{"name":"part2"},"executable":"/t/part2"}
+{"reason":"build-finished","success":true}"#;
+        assert_eq!(
+            executable(messages, "part2"),
+            Some(PathBuf::from("/t/part2"))
+        );
+        assert_eq!(executable(messages, "part3"), None);
+    }
+
+    #[test]
+    fn speedup_is_left_over_right() {
+        let speedup = speedup(Duration::from_millis(30), Duration::from_millis(10));
+        assert!((speedup - 3.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,64 @@
+use std::path::{Path, PathBuf};
+
+use miette::*;
+
+/// Arguments of `aoc gen`.
+///
+/// Writes seeded random inputs from [`aoc_gen`], for stress benchmarks (`aoc
+/// run -i`, `aoc profile -i`) or as a fuzzing corpus.
+#[derive(Debug, clap::Args)]
+pub struct GenArgs {
+    /// Year of the puzzle, e.g. `2025`.
+    year: u16,
+    /// Day of the puzzle.
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// How many records to generate: lines for most days, the side of the
+    /// grid for days 4 and 7.
+    #[arg(short, long, default_value_t = 1000)]
+    size: usize,
+    /// Seed of the first input; every seed gives the same input.
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+    /// How many inputs to generate, one per seed from `--seed` on.
+    #[arg(short = 'n', long, default_value_t = 1, requires = "output")]
+    count: u64,
+    /// Where to write the input; a directory when `--count` is more than one,
+    /// which gets one `<year>-<day>-<seed>.txt` per input. Defaults to stdout.
+    #[arg(short, long, value_name = "PATH")]
+    output: Option<PathBuf>,
+}
+
+pub fn run(args: GenArgs) -> Result<()> {
+    let generate = |seed| {
+        let input = match args.year {
+            2025 => aoc_gen::input(args.day, &mut aoc_gen::rng(seed), args.size),
+            _ => None,
+        };
+        input.ok_or_else(|| miette!("No generator for {} day {}", args.year, args.day))
+    };
+
+    let Some(output) = &args.output else {
+        println!("{}", generate(args.seed)?);
+        return Ok(());
+    };
+    if args.count == 1 {
+        return write(output, &generate(args.seed)?);
+    }
+
+    std::fs::create_dir_all(output)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("Failed to create {}", output.display()))?;
+    for seed in args.seed..args.seed + args.count {
+        let path = output.join(format!("{}-{:02}-{seed}.txt", args.year, args.day));
+        write(&path, &generate(seed)?)?;
+    }
+    eprintln!("Wrote {} inputs to {}", args.count, output.display());
+    Ok(())
+}
+
+fn write(path: &Path, input: &str) -> Result<()> {
+    std::fs::write(path, format!("{input}\n"))
+        .into_diagnostic()
+        .wrap_err_with(|| format!("Failed to write {}", path.display()))
+}
@@ -0,0 +1,70 @@
+//! Workspace runner for the Advent of Code solutions.
+
+mod bench;
+mod diff;
+mod gen;
+mod new;
+mod profile;
+mod run;
+mod serve;
+mod submit;
+
+use clap::{Parser, Subcommand};
+use miette::*;
+
+/// Lets `aoc profile --heap` see every allocation.
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "aoc",
+    about = "Tools for working on the Advent of Code solutions"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Time every part of the selected days under one harness.
+    Bench(bench::BenchArgs),
+    /// Run two implementations on the same input and compare them.
+    Diff(diff::DiffArgs),
+    /// Write seeded random inputs for stress testing.
+    Gen(gen::GenArgs),
+    /// Create the crate for a new day from `daily-template`.
+    New(new::NewArgs),
+    /// List the registered solutions.
+    List,
+    /// Profile one part of a registered day.
+    Profile(profile::ProfileArgs),
+    /// Solve a registered day from its stored input.
+    Run(run::RunArgs),
+    /// Serve the registered solutions over HTTP.
+    Serve(serve::ServeArgs),
+    /// Solve a puzzle and post the answer to adventofcode.com.
+    Submit(submit::SubmitArgs),
+}
+
+fn main() -> Result<()> {
+    aoc_runtime::init();
+    match Cli::parse().command {
+        Command::Bench(args) => bench::run(args),
+        Command::Diff(args) => diff::run(args),
+        Command::Gen(args) => gen::run(args),
+        Command::New(args) => new::run(args),
+        Command::List => {
+            for entry in aoc_registry::solutions() {
+                println!("{} day {:>2}  {}", entry.year, entry.day, entry.package);
+            }
+            Ok(())
+        }
+        Command::Profile(args) => profile::run(args),
+        Command::Run(args) => run::run(args),
+        Command::Serve(args) => serve::run(args),
+        Command::Submit(args) => submit::run(args),
+    }
+}
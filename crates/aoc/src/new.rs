@@ -0,0 +1,335 @@
+use std::path::Path;
+
+use miette::*;
+
+/// Directory, relative to the workspace root, holding the day template.
+const TEMPLATE_DIR: &str = "daily-template";
+
+/// Crate, relative to the workspace root, listing every day's solution.
+const REGISTRY_DIR: &str = "crates/aoc-registry";
+
+/// Crate, relative to the workspace root, re-exporting every day's crate.
+const SOLUTIONS_DIR: &str = "crates/aoc-solutions";
+
+/// Template entries that only drive `cargo generate` and are not copied.
+const TEMPLATE_ONLY: &[&str] = &["cargo-generate.toml", "scripts"];
+
+/// Arguments of `aoc new`.
+///
+/// Renders `daily-template` into `<year>/day-<day>`, makes sure the
+/// workspace picks the new crate up and adds it to the solution registry and
+/// the `aoc-solutions` facade. Run it from the workspace root.
+#[derive(Debug, clap::Args)]
+pub struct NewArgs {
+    /// Year of the puzzle, e.g. `2025`.
+    year: u16,
+    /// Day of the puzzle.
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+}
+
+/// The values substituted for the template's placeholders.
+struct Placeholders {
+    year: u16,
+    day: u8,
+}
+
+impl Placeholders {
+    /// Replaces every placeholder in `contents`, failing on any it does not
+    /// know rather than leaving it for the compiler to trip over.
+    fn render(&self, contents: &str) -> Result<String> {
+        let rendered = contents
+            .replace(
+                "{{crate_name | kebab_case}}",
+                &format!("aoc{}-day-{}", self.year, self.day),
+            )
+            .replace(
+                "{{crate_name}}",
+                &format!("aoc{}_day_{}", self.year, self.day),
+            )
+            .replace("{{year}}", &self.year.to_string())
+            .replace("{{day}}", &self.day.to_string());
+        if let Some(start) = rendered.find("{{") {
+            let placeholder = rendered[start..].lines().next().unwrap_or_default();
+            bail!("Unknown template placeholder in {:?}", placeholder);
+        }
+        Ok(rendered)
+    }
+}
+
+/// Renders every template file under `from` into `to`.
+fn render_dir(from: &Path, to: &Path, placeholders: &Placeholders) -> Result<()> {
+    std::fs::create_dir_all(to).into_diagnostic()?;
+    for entry in std::fs::read_dir(from).into_diagnostic()? {
+        let entry = entry.into_diagnostic()?;
+        let name = entry.file_name();
+        if TEMPLATE_ONLY.iter().any(|skip| name == *skip) {
+            continue;
+        }
+        let (source, target) = (entry.path(), to.join(&name));
+        if entry.file_type().into_diagnostic()?.is_dir() {
+            render_dir(&source, &target, placeholders)?;
+        } else {
+            let contents = std::fs::read_to_string(&source)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("Failed to read {}", source.display()))?;
+            let rendered = placeholders
+                .render(&contents)
+                .wrap_err_with(|| format!("Failed to render {}", source.display()))?;
+            std::fs::write(&target, rendered).into_diagnostic()?;
+        }
+    }
+    Ok(())
+}
+
+/// Whether a `members` entry such as `2025/day-*` covers `path`.
+fn covers(pattern: &str, path: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => path.starts_with(prefix),
+        None => pattern == path,
+    }
+}
+
+/// Adds `<year>/day-*` to the workspace members of `manifest`, unless an
+/// existing entry already covers `member`.
+///
+/// Returns `None` when nothing needs to change. The new entry goes after the
+/// last year, ahead of the shared crates.
+fn register(manifest: &str, year: u16, member: &str) -> Result<Option<String>> {
+    let lines = manifest.lines().collect::<Vec<_>>();
+    let start = lines
+        .iter()
+        .position(|line| line.trim_start().starts_with("members = ["))
+        .ok_or_else(|| miette!("The workspace manifest has no members list"))?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| line.trim() == "]")
+            .ok_or_else(|| miette!("The workspace members list is not closed"))?;
+
+    let entries = lines[start + 1..end]
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let entry = line.trim().strip_prefix('"')?.split('"').next()?;
+            Some((start + 1 + i, entry))
+        })
+        .collect::<Vec<_>>();
+    if entries.iter().any(|(_, entry)| covers(entry, member)) {
+        return Ok(None);
+    }
+
+    let at = entries
+        .iter()
+        .rev()
+        .find(|(_, entry)| entry.starts_with(|c: char| c.is_ascii_digit()))
+        .map(|&(line, _)| line + 1)
+        .unwrap_or(start + 1);
+    let entry = format!("  \"{year}/day-*\",");
+    let mut updated = lines[..at].to_vec();
+    updated.push(&entry);
+    updated.extend_from_slice(&lines[at..]);
+    Ok(Some(updated.join("\n") + "\n"))
+}
+
+/// Adds the day's crate to the dependencies of the registry's or the
+/// facade's `manifest`, after the days already there.
+fn add_dependency(manifest: &str, year: u16, day: u8) -> Result<String> {
+    let lines = manifest.lines().collect::<Vec<_>>();
+    let at = lines
+        .iter()
+        .rposition(|line| is_day_package(line))
+        .or_else(|| {
+            lines
+                .iter()
+                .position(|line| line.trim() == "[dependencies]")
+        })
+        .ok_or_else(|| miette!("The manifest has no [dependencies]"))?;
+    let dependency = format!("aoc{year}-day-{day} = {{ path = \"../../{year}/day-{day}\" }}");
+    let mut updated = lines[..=at].to_vec();
+    updated.push(&dependency);
+    updated.extend_from_slice(&lines[at + 1..]);
+    Ok(updated.join("\n") + "\n")
+}
+
+fn is_day_package(line: &str) -> bool {
+    line.strip_prefix("aoc")
+        .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+}
+
+/// Links the day's crate from the registry's `lib`, so the entry its
+/// `solution!` submits is registered. The imports stay in the order rustfmt
+/// sorts them in.
+fn add_entry(lib: &str, year: u16, day: u8) -> Result<String> {
+    let entry = format!("use aoc{year}_day_{day} as _;");
+    let lines = lib.lines().collect::<Vec<_>>();
+    let linked = |line: &&str| line.starts_with("use aoc") && line.ends_with(" as _;");
+    let last = lines
+        .iter()
+        .rposition(linked)
+        .ok_or_else(|| miette!("The registry links no days"))?;
+    let at = lines
+        .iter()
+        .position(|line| linked(line) && **line > *entry)
+        .unwrap_or(last + 1);
+    let mut updated = lines[..at].to_vec();
+    updated.push(&entry);
+    updated.extend_from_slice(&lines[at..]);
+    Ok(updated.join("\n") + "\n")
+}
+
+/// Re-exports the day's crate from the facade's `lib` as `y<year>::day<day>`,
+/// opening a module for the year if it has none yet. The re-exports stay in
+/// the order rustfmt sorts them in.
+fn add_module(lib: &str, year: u16, day: u8) -> Result<String> {
+    let header = format!("pub mod y{year} {{");
+    let entry = format!("    pub use aoc{year}_day_{day} as day{day};");
+    let lines = lib.lines().collect::<Vec<_>>();
+    let Some(start) = lines.iter().position(|line| *line == header) else {
+        let module = format!("/// The {year} puzzles.\n{header}\n{entry}\n}}\n");
+        return Ok(format!("{}\n\n{module}", lib.trim_end()));
+    };
+    let at = start
+        + 1
+        + lines[start + 1..]
+            .iter()
+            .position(|line| *line == "}" || (line.starts_with("    pub use ") && **line > *entry))
+            .ok_or_else(|| miette!("The y{} module is not closed", year))?;
+    let mut updated = lines[..at].to_vec();
+    updated.push(&entry);
+    updated.extend_from_slice(&lines[at..]);
+    Ok(updated.join("\n") + "\n")
+}
+
+/// Applies `edit` to the file at `path`.
+fn rewrite(path: &Path, edit: impl FnOnce(&str) -> Result<String>) -> Result<()> {
+    let contents = std::fs::read_to_string(path).into_diagnostic()?;
+    std::fs::write(path, edit(&contents)?).into_diagnostic()
+}
+
+/// Lists the new day in the registry crate and the facade.
+fn register_solution(root: &Path, year: u16, day: u8) -> Result<()> {
+    let registry = root.join(REGISTRY_DIR);
+    rewrite(&registry.join("Cargo.toml"), |manifest| {
+        add_dependency(manifest, year, day)
+    })?;
+    rewrite(&registry.join("src").join("lib.rs"), |lib| {
+        add_entry(lib, year, day)
+    })?;
+    let facade = root.join(SOLUTIONS_DIR);
+    rewrite(&facade.join("Cargo.toml"), |manifest| {
+        add_dependency(manifest, year, day)
+    })?;
+    rewrite(&facade.join("src").join("lib.rs"), |lib| {
+        add_module(lib, year, day)
+    })
+}
+
+pub fn run(args: NewArgs) -> Result<()> {
+    let root = std::env::current_dir().into_diagnostic()?;
+    let template = root.join(TEMPLATE_DIR);
+    if !template.is_dir() {
+        bail!(
+            "No {} in {}; run aoc new from the workspace root",
+            TEMPLATE_DIR,
+            root.display()
+        );
+    }
+
+    let member = format!("{}/day-{}", args.year, args.day);
+    let dest = root.join(&member);
+    if dest.exists() {
+        bail!("{} already exists", dest.display());
+    }
+    let placeholders = Placeholders {
+        year: args.year,
+        day: args.day,
+    };
+    render_dir(&template, &dest, &placeholders)?;
+    println!("created {}", dest.display());
+
+    let manifest_path = root.join("Cargo.toml");
+    let manifest = std::fs::read_to_string(&manifest_path).into_diagnostic()?;
+    if let Some(manifest) = register(&manifest, args.year, &member)? {
+        std::fs::write(&manifest_path, manifest).into_diagnostic()?;
+        println!("registered {}/day-* in the workspace", args.year);
+    }
+    register_solution(&root, args.year, args.day)?;
+    println!(
+        "added the solution to {} and {}",
+        REGISTRY_DIR, SOLUTIONS_DIR
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"[workspace]
+resolver = "2"
+members = [
+  # "2023/day-*",
+  "2025/day-*",
+  "crates/*",
+]
+
+[workspace.dependencies]
+"#;
+
+    #[test]
+    fn renders_placeholders() -> Result<()> {
+        let placeholders = Placeholders {
+            year: 2025,
+            day: 13,
+        };
+        assert_eq!(
+            placeholders.render(
+                "name = \"{{crate_name | kebab_case}}\"\nuse {{crate_name}}::part1;\n{{year}}-day-{{day}}"
+            )?,
+            "name = \"aoc2025-day-13\"\nuse aoc2025_day_13::part1;\n2025-day-13"
+        );
+        assert!(placeholders.render("{{project-name}}").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn registers_only_uncovered_years() -> Result<()> {
+        assert_eq!(register(MANIFEST, 2025, "2025/day-13")?, None);
+        let updated = register(MANIFEST, 2026, "2026/day-1")?.unwrap();
+        assert!(updated.contains("  \"2025/day-*\",\n  \"2026/day-*\",\n  \"crates/*\","));
+        assert_eq!(register(&updated, 2026, "2026/day-2")?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn adds_the_day_to_the_registry() -> Result<()> {
+        let manifest = "[dependencies]\naoc-common = { path = \"../aoc-common\" }\naoc2025-day-1 = { path = \"../../2025/day-1\" }\n";
+        assert!(add_dependency(manifest, 2025, 2)?.ends_with(
+            "aoc2025-day-1 = { path = \"../../2025/day-1\" }\naoc2025-day-2 = { path = \"../../2025/day-2\" }\n"
+        ));
+
+        let lib = "use aoc_common::solution::Entry;\n\nuse aoc2025_day_1 as _;\nuse aoc2025_day_9 as _;\n\npub fn solutions() {}\n";
+        assert_eq!(
+            add_entry(lib, 2025, 3)?,
+            "use aoc_common::solution::Entry;\n\nuse aoc2025_day_1 as _;\nuse aoc2025_day_3 as _;\nuse aoc2025_day_9 as _;\n\npub fn solutions() {}\n"
+        );
+        assert!(add_entry(lib, 2026, 1)?
+            .contains("use aoc2025_day_9 as _;\nuse aoc2026_day_1 as _;\n\n"));
+        assert!(add_entry("pub fn solutions() {}\n", 2025, 1).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn adds_the_day_to_the_facade() -> Result<()> {
+        let lib = "//! Docs.\n\n/// The 2025 puzzles.\npub mod y2025 {\n    pub use aoc2025_day_1 as day1;\n    pub use aoc2025_day_10 as day10;\n    pub use aoc2025_day_3 as day3;\n}\n";
+        assert_eq!(
+            add_module(lib, 2025, 2)?,
+            "//! Docs.\n\n/// The 2025 puzzles.\npub mod y2025 {\n    pub use aoc2025_day_1 as day1;\n    pub use aoc2025_day_10 as day10;\n    pub use aoc2025_day_2 as day2;\n    pub use aoc2025_day_3 as day3;\n}\n"
+        );
+        assert!(add_module(lib, 2026, 1)?.ends_with(
+            "}\n\n/// The 2026 puzzles.\npub mod y2026 {\n    pub use aoc2026_day_1 as day1;\n}\n"
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use std::path::PathBuf;
+
+use aoc_common::solution::Part;
+use miette::*;
+
+/// Arguments of `aoc profile`.
+///
+/// Solves one part of a registered day in-process under a profiler, so any
+/// day can be profiled without a bench file of its own.
+#[derive(Debug, clap::Args)]
+pub struct ProfileArgs {
+    /// Record every allocation with dhat and write a heap profile, viewable
+    /// in dhat's `dh_view.html`. Needs the `dhat-heap` feature.
+    #[arg(long, required = true)]
+    heap: bool,
+    /// Year of the puzzle, e.g. `2025`.
+    year: u16,
+    /// Day of the puzzle.
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// Part of the puzzle to profile.
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+    /// Read the puzzle input from this file instead of the stored one.
+    #[arg(short, long, value_name = "PATH")]
+    input: Option<PathBuf>,
+    /// Where to write the profile; defaults to
+    /// `dhat-heap-<year>-<day>-<part>.json`.
+    #[arg(short, long, value_name = "PATH")]
+    output: Option<PathBuf>,
+}
+
+pub fn run(args: ProfileArgs) -> Result<()> {
+    let entry = aoc_registry::find(args.year, args.day).ok_or_else(|| {
+        miette!(
+            "No solution is registered for {} day {}",
+            args.year,
+            args.day
+        )
+    })?;
+    let input = match &args.input {
+        Some(path) => std::fs::read_to_string(path)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Failed to read {}", path.display()))?,
+        None => crate::bench::input_of(entry)?,
+    };
+    let part = if args.part == 1 { Part::One } else { Part::Two };
+    let output = args.output.unwrap_or_else(|| {
+        format!("dhat-heap-{}-{:02}-{}.json", args.year, args.day, args.part).into()
+    });
+    heap(|| entry.run(&input, part), output)
+}
+
+/// Runs `solve` under dhat, writing the profile to `output` and summarizing
+/// it on stdout.
+#[cfg(feature = "dhat-heap")]
+fn heap(solve: impl FnOnce() -> Result<aoc_common::answer::Answer>, output: PathBuf) -> Result<()> {
+    let profiler = dhat::Profiler::builder().file_name(&output).build();
+    let answer = solve()?;
+    let stats = dhat::HeapStats::get();
+    // dhat writes the file, and its own summary on stderr, when dropped.
+    drop(profiler);
+
+    println!("Result: {answer}");
+    println!(
+        "{} bytes in {} allocations, at most {} bytes in {} blocks live at once",
+        stats.total_bytes, stats.total_blocks, stats.max_bytes, stats.max_blocks
+    );
+    println!("Heap profile written to {}", output.display());
+    Ok(())
+}
+
+#[cfg(not(feature = "dhat-heap"))]
+fn heap(_: impl FnOnce() -> Result<aoc_common::answer::Answer>, _: PathBuf) -> Result<()> {
+    Err(miette!(
+        help = "rebuild with `--features dhat-heap`, e.g. through `just profile-heap`",
+        "Heap profiling is not compiled in"
+    ))
+}
@@ -0,0 +1,61 @@
+use std::path::PathBuf;
+
+use aoc_common::solution::Part;
+use aoc_runtime::Format;
+use miette::*;
+
+/// Arguments of `aoc run`.
+///
+/// Solves a registered day in-process, the same way its binaries do, so the
+/// output matches theirs for every `--format`.
+#[derive(Debug, clap::Args)]
+pub struct RunArgs {
+    /// Year of the puzzle, e.g. `2025`.
+    year: u16,
+    /// Day of the puzzle.
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// Only solve this part.
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Read the puzzle input from this file instead of the stored one.
+    #[arg(short, long, value_name = "PATH")]
+    input: Option<PathBuf>,
+    /// How to print the answers.
+    #[arg(long, value_enum, default_value_t)]
+    format: Format,
+    /// Print the time spent parsing, building and solving each part on
+    /// stderr after its answer.
+    #[arg(long)]
+    phases: bool,
+}
+
+pub fn run(args: RunArgs) -> Result<()> {
+    let entry = aoc_registry::find(args.year, args.day).ok_or_else(|| {
+        miette!(
+            "No solution is registered for {} day {}",
+            args.year,
+            args.day
+        )
+    })?;
+    let input = match &args.input {
+        Some(path) => std::fs::read_to_string(path)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Failed to read {}", path.display()))?,
+        None => crate::bench::input_of(entry)?,
+    };
+    let parts = match args.part {
+        Some(1) => &[Part::One][..],
+        Some(_) => &[Part::Two],
+        None => &Part::ALL,
+    };
+    for &part in parts {
+        aoc_runtime::phases::take();
+        let timed = entry.run_timed(&input, part)?;
+        println!("{}", args.format.render(entry, part, &timed));
+        if args.phases {
+            eprint!("{}", aoc_runtime::phases::table(&aoc_runtime::phases::take()));
+        }
+    }
+    Ok(())
+}
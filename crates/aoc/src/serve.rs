@@ -0,0 +1,133 @@
+use std::net::SocketAddr;
+
+use aoc_common::solution::Part;
+use aoc_runtime::Format;
+use axum::extract::{DefaultBodyLimit, Path};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::Router;
+use miette::*;
+
+/// Arguments of `aoc serve`.
+///
+/// `POST /solve/{year}/{day}/{part}` with the raw puzzle input as the body
+/// answers with the same JSON object as `aoc run --format json`; failures
+/// come back as `{"error": "..."}`. `GET /days` lists the registered days.
+#[derive(Debug, clap::Args)]
+pub struct ServeArgs {
+    /// Address to listen on.
+    #[arg(short, long, default_value = "127.0.0.1:3000")]
+    addr: SocketAddr,
+}
+
+/// Generated stress inputs run far past axum's default 2 MB limit.
+const MAX_INPUT: usize = 256 * 1024 * 1024;
+
+pub fn run(args: ServeArgs) -> Result<()> {
+    let runtime = tokio::runtime::Runtime::new().into_diagnostic()?;
+    runtime.block_on(async {
+        let listener = tokio::net::TcpListener::bind(args.addr)
+            .await
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Failed to listen on {}", args.addr))?;
+        eprintln!("Listening on http://{}", args.addr);
+        axum::serve(listener, router()).await.into_diagnostic()
+    })
+}
+
+fn router() -> Router {
+    Router::new()
+        .route("/days", get(days))
+        .route("/solve/{year}/{day}/{part}", post(solve))
+        .layer(DefaultBodyLimit::max(MAX_INPUT))
+}
+
+async fn days() -> Response {
+    let days = aoc_registry::solutions()
+        .iter()
+        .copied()
+        .map(|entry| serde_json::json!({ "year": entry.year, "day": entry.day }))
+        .collect::<Vec<_>>();
+    json(StatusCode::OK, serde_json::Value::from(days).to_string())
+}
+
+async fn solve(Path((year, day, part)): Path<(u16, u8, u8)>, input: String) -> Response {
+    let Some(entry) = aoc_registry::find(year, day) else {
+        let message = format!("No solution is registered for {year} day {day}");
+        return error(StatusCode::NOT_FOUND, &message);
+    };
+    let part = match part {
+        1 => Part::One,
+        2 => Part::Two,
+        _ => return error(StatusCode::NOT_FOUND, &format!("There is no part {part}")),
+    };
+    // Solving is CPU-bound; keep it off the threads serving connections.
+    match tokio::task::spawn_blocking(move || entry.run_timed(&input, part)).await {
+        Ok(Ok(timed)) => json(StatusCode::OK, Format::Json.render(entry, part, &timed)),
+        Ok(Err(report)) => error(StatusCode::UNPROCESSABLE_ENTITY, &render(&report)),
+        Err(_) => error(StatusCode::INTERNAL_SERVER_ERROR, "The solution panicked"),
+    }
+}
+
+fn json(status: StatusCode, body: String) -> Response {
+    (status, [(header::CONTENT_TYPE, "application/json")], body).into_response()
+}
+
+fn error(status: StatusCode, message: &str) -> Response {
+    json(status, serde_json::json!({ "error": message }).to_string())
+}
+
+/// Draws a report the way the terminal would, minus the colors.
+fn render(report: &Report) -> String {
+    let mut out = String::new();
+    let handler = GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor());
+    match handler.render_report(&mut out, report.as_ref()) {
+        Ok(()) => out,
+        Err(_) => format!("{report}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    async fn post(uri: &str, input: &str) -> (StatusCode, serde_json::Value) {
+        let request = Request::post(uri)
+            .body(Body::from(input.to_string()))
+            .unwrap();
+        let response = router().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn solves_posted_inputs() {
+        let input = "987654321111111\n811111111111119\n234234234234278\n818181911112111\n";
+        let (status, body) = post("/solve/2025/3/1", input).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["answer"], 357);
+        assert_eq!(
+            (body["year"].as_u64(), body["part"].as_u64()),
+            (Some(2025), Some(1))
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_failures_as_json() {
+        let (status, body) = post("/solve/2025/10/1", "junk\n").await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(body["error"].is_string());
+
+        let (status, body) = post("/solve/2025/26/1", "").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["error"], "No solution is registered for 2025 day 26");
+
+        let (status, _) = post("/solve/2025/3/3", "").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,405 @@
+use std::fmt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use aoc_common::answer::Answer;
+use miette::*;
+
+use crate::diff::{run_once, Target};
+
+/// Environment variable holding the adventofcode.com session cookie.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Arguments of `aoc submit`.
+///
+/// The answer comes from the day's release binary run on the stored input.
+/// Every verdict the site gives is recorded next to that input, and answers
+/// they already rule out are refused before anything is posted.
+#[derive(Debug, clap::Args)]
+pub struct SubmitArgs {
+    /// Year of the puzzle, e.g. `2025`.
+    year: u16,
+    /// Day of the puzzle.
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// Part to answer.
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+    /// Input handed to the solver instead of the stored one.
+    #[arg(short, long, value_name = "PATH")]
+    input: Option<PathBuf>,
+    /// Solve and check the answer against past verdicts, without posting it.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+/// What adventofcode.com said about an answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// Answered too recently; the site asks to wait this long.
+    Wait(Duration),
+    /// The part is not the one currently open, usually because it is solved.
+    WrongLevel,
+}
+
+impl Verdict {
+    /// Keyword stored in the history, `None` for verdicts that say nothing
+    /// about the answer itself.
+    fn keyword(self) -> Option<&'static str> {
+        match self {
+            Verdict::Correct => Some("correct"),
+            Verdict::TooHigh => Some("too-high"),
+            Verdict::TooLow => Some("too-low"),
+            Verdict::Wrong => Some("wrong"),
+            Verdict::Wait(_) | Verdict::WrongLevel => None,
+        }
+    }
+
+    fn from_keyword(keyword: &str) -> Option<Self> {
+        match keyword {
+            "correct" => Some(Verdict::Correct),
+            "too-high" => Some(Verdict::TooHigh),
+            "too-low" => Some(Verdict::TooLow),
+            "wrong" => Some(Verdict::Wrong),
+            _ => None,
+        }
+    }
+
+    /// Reads the verdict out of the page returned for a submission.
+    fn parse(page: &str) -> Option<Self> {
+        let text = main_text(page);
+        if text.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if text.contains("That's not the right answer") {
+            Some(if text.contains("too high") {
+                Verdict::TooHigh
+            } else if text.contains("too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            })
+        } else if text.contains("You gave an answer too recently") {
+            Some(Verdict::Wait(wait_time(&text).unwrap_or_default()))
+        } else if text.contains("You don't seem to be solving the right level") {
+            Some(Verdict::WrongLevel)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => f.write_str("correct"),
+            Verdict::TooHigh => f.write_str("too high"),
+            Verdict::TooLow => f.write_str("too low"),
+            Verdict::Wrong => f.write_str("wrong"),
+            Verdict::Wait(wait) => write!(f, "rate limited, try again in {wait:?}"),
+            Verdict::WrongLevel => f.write_str("not for the open part, is it already solved?"),
+        }
+    }
+}
+
+/// The text of the page's `<article>`, where the site puts its verdict, with
+/// the markup stripped.
+fn main_text(page: &str) -> String {
+    // Past `<article` we are still inside the opening tag.
+    let (article, mut in_tag) = match page
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+    {
+        Some((article, _)) => (article, true),
+        None => (page, false),
+    };
+    let mut text = String::with_capacity(article.len());
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Parses the `You have 1m 5s left to wait` part of a rate limit message.
+fn wait_time(text: &str) -> Option<Duration> {
+    let (_, rest) = text.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+    wait.split_whitespace()
+        .try_fold(Duration::ZERO, |total, unit| {
+            let (amount, unit) = unit.split_at(unit.find(|c: char| !c.is_ascii_digit())?);
+            let amount = amount.parse::<u64>().ok()?;
+            let seconds = match unit {
+                "h" => amount * 3600,
+                "m" => amount * 60,
+                "s" => amount,
+                _ => return None,
+            };
+            Some(total + Duration::from_secs(seconds))
+        })
+}
+
+/// A past submission and the verdict it got.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Submission {
+    part: u8,
+    verdict: Verdict,
+    answer: Answer,
+}
+
+/// The submissions made for one day, one `part<TAB>verdict<TAB>answer` line
+/// each.
+#[derive(Debug, Default)]
+struct History {
+    submissions: Vec<Submission>,
+}
+
+impl History {
+    fn path(year: u16, day: u8) -> Result<PathBuf> {
+        Ok(aoc_runtime::data_dir()?
+            .join(year.to_string())
+            .join(day.to_string())
+            .join("submissions.tsv"))
+    }
+
+    fn load(path: &Path) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => Self::parse(&contents)
+                .wrap_err_with(|| format!("Failed to read submissions from {}", path.display())),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error).into_diagnostic(),
+        }
+    }
+
+    fn parse(contents: &str) -> Result<Self> {
+        let submissions = contents
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let mut fields = line.splitn(3, '\t');
+                let (Some(part), Some(verdict), Some(answer)) =
+                    (fields.next(), fields.next(), fields.next())
+                else {
+                    bail!("Malformed submission {:?}", line);
+                };
+                Ok(Submission {
+                    part: part.parse().into_diagnostic()?,
+                    verdict: Verdict::from_keyword(verdict)
+                        .ok_or_else(|| miette!("Unknown verdict {:?}", verdict))?,
+                    answer: Answer::parse(answer),
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { submissions })
+    }
+
+    fn append(path: &Path, submission: &Submission) -> Result<()> {
+        let Some(keyword) = submission.verdict.keyword() else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).into_diagnostic()?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Failed to open {}", path.display()))?;
+        writeln!(
+            file,
+            "{}\t{}\t{}",
+            submission.part, keyword, submission.answer
+        )
+        .into_diagnostic()
+    }
+
+    /// What the recorded verdicts already say about `answer` for `part`.
+    ///
+    /// Besides exact repeats, a known correct answer rules out every other
+    /// one, and a numeric answer at or beyond a known bound is too high or
+    /// too low as well.
+    fn judge(&self, part: u8, answer: &Answer) -> Option<Verdict> {
+        let past = self.submissions.iter().filter(|s| s.part == part);
+        let mut verdict = None;
+        for submission in past {
+            if submission.answer == *answer {
+                return Some(submission.verdict);
+            }
+            let beyond = |ordering| {
+                numeric(answer)
+                    .zip(numeric(&submission.answer))
+                    .is_some_and(|(answer, known)| answer.cmp(&known) != ordering)
+            };
+            verdict = match submission.verdict {
+                Verdict::Correct => Some(Verdict::Wrong),
+                Verdict::TooHigh if beyond(std::cmp::Ordering::Less) => Some(Verdict::TooHigh),
+                Verdict::TooLow if beyond(std::cmp::Ordering::Greater) => Some(Verdict::TooLow),
+                _ => verdict,
+            };
+        }
+        verdict
+    }
+}
+
+/// The answer as a number, for comparing against known bounds.
+fn numeric(answer: &Answer) -> Option<i128> {
+    match answer {
+        Answer::U64(n) => Some(*n as i128),
+        Answer::I64(n) => Some(*n as i128),
+        Answer::U128(n) => i128::try_from(*n).ok(),
+        Answer::Text(_) => None,
+    }
+}
+
+/// Posts `answer` and returns the page the site responds with.
+///
+/// The session cookie goes to curl through stdin so it never shows up in
+/// the process list.
+fn post(year: u16, day: u8, part: u8, answer: &Answer, session: &str) -> Result<String> {
+    let url = format!("https://adventofcode.com/{year}/day/{day}/answer");
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail"])
+        .args(["--user-agent", "github.com/hadronomy/advent-of-code"])
+        .args(["--header", "@-"])
+        .arg("--data-urlencode")
+        .arg(format!("level={part}"))
+        .arg("--data-urlencode")
+        .arg(format!("answer={answer}"))
+        .arg(&url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .into_diagnostic()
+        .wrap_err("Failed to invoke curl")?;
+    if let Some(mut stdin) = child.stdin.take() {
+        writeln!(stdin, "Cookie: session={session}").into_diagnostic()?;
+    }
+    let output = child.wait_with_output().into_diagnostic()?;
+    if !output.status.success() {
+        bail!("Posting to {} failed with {}", url, output.status);
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+pub fn run(args: SubmitArgs) -> Result<()> {
+    let target = Target {
+        dir: std::env::current_dir().into_diagnostic()?,
+        package: format!("aoc{}-day-{}", args.year, args.day),
+        bin: format!("part{}", args.part),
+    };
+    let answer = run_once(&target.build()?, args.input.as_deref())?.answer;
+    println!("answer  {answer}");
+
+    let path = History::path(args.year, args.day)?;
+    let history = History::load(&path)?;
+    match history.judge(args.part, &answer) {
+        Some(Verdict::Correct) => {
+            println!("already accepted");
+            return Ok(());
+        }
+        Some(verdict) => bail!("Not submitting {}: it is known to be {}", answer, verdict),
+        None if args.dry_run => {
+            println!(
+                "not ruled out by {} past submissions",
+                history.submissions.len()
+            );
+            return Ok(());
+        }
+        None => {}
+    }
+
+    let session = std::env::var(SESSION_VAR)
+        .into_diagnostic()
+        .wrap_err_with(|| {
+            format!(
+                "Set {} to your adventofcode.com session cookie",
+                SESSION_VAR
+            )
+        })?;
+    let page = post(args.year, args.day, args.part, &answer, &session)?;
+    let verdict = Verdict::parse(&page).ok_or_else(|| {
+        miette!(
+            "Could not find a verdict in the response:\n{}",
+            main_text(&page)
+        )
+    })?;
+    History::append(
+        &path,
+        &Submission {
+            part: args.part,
+            verdict,
+            answer: answer.clone(),
+        },
+    )?;
+
+    if verdict != Verdict::Correct {
+        bail!("{} is {}", answer, verdict);
+    }
+    println!("correct!");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_verdicts_from_the_response() {
+        let page = |message: &str| format!("<main><article><p>{message}</p></article></main>");
+        assert_eq!(
+            Verdict::parse(&page(
+                "That's the right answer! You are <em>one gold star</em> closer."
+            )),
+            Some(Verdict::Correct)
+        );
+        assert_eq!(
+            Verdict::parse(&page(
+                "That's not the right answer; your answer is too high."
+            )),
+            Some(Verdict::TooHigh)
+        );
+        assert_eq!(
+            Verdict::parse(&page("That's not the right answer. If you're stuck...")),
+            Some(Verdict::Wrong)
+        );
+        assert_eq!(
+            Verdict::parse(&page(
+                "You gave an answer too recently. You have 1m 5s left to wait."
+            )),
+            Some(Verdict::Wait(Duration::from_secs(65)))
+        );
+        assert_eq!(
+            Verdict::parse(&page("You don't seem to be solving the right level.")),
+            Some(Verdict::WrongLevel)
+        );
+        assert_eq!(Verdict::parse("<html></html>"), None);
+    }
+
+    #[test]
+    fn history_rules_out_known_answers() -> Result<()> {
+        let history = History::parse("1\ttoo-high\t100\n1\ttoo-low\t10\n1\twrong\tabc\n")?;
+        assert_eq!(history.judge(1, &Answer::U64(100)), Some(Verdict::TooHigh));
+        assert_eq!(history.judge(1, &Answer::U64(150)), Some(Verdict::TooHigh));
+        assert_eq!(history.judge(1, &Answer::I64(-4)), Some(Verdict::TooLow));
+        assert_eq!(history.judge(1, &Answer::from("abc")), Some(Verdict::Wrong));
+        assert_eq!(history.judge(1, &Answer::U64(50)), None);
+        assert_eq!(history.judge(2, &Answer::U64(150)), None);
+
+        let solved = History::parse("2\tcorrect\t42\n")?;
+        assert_eq!(solved.judge(2, &Answer::U64(42)), Some(Verdict::Correct));
+        assert_eq!(solved.judge(2, &Answer::U64(43)), Some(Verdict::Wrong));
+        assert!(History::parse("1\tmaybe\t3\n").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,19 @@
+//! Linear algebra over GF(2), the field of bits, where addition is XOR.
+//!
+//! [`Gf2Matrix`] brings systems to reduced row echelon form to find their
+//! rank, null space and solutions. A consistent system's solutions form a
+//! [`SolutionSpace`]: one particular solution plus any combination of the
+//! null space basis, which can be walked in Gray-code order or searched for
+//! the solution of minimum Hamming weight.
+
+mod matrix;
+mod space;
+
+use bitvec::prelude::*;
+
+pub use matrix::Gf2Matrix;
+pub use space::{SolutionSpace, Solutions, TooManySolutions};
+
+/// A bit vector backed by `usize` words with Least Significant Bit first ordering.
+/// This aligns with standard CPU integer operations for maximum performance.
+pub type Row = BitVec<usize, Lsb0>;
@@ -0,0 +1,322 @@
+use bitvec::prelude::*;
+
+use crate::{Row, SolutionSpace};
+
+/// A dense matrix over GF(2), stored as one bit vector per row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gf2Matrix {
+    rows: Vec<Row>,
+    cols: usize,
+}
+
+/// An augmented matrix `[A | b]` in reduced row echelon form.
+struct Echelon {
+    matrix: Vec<Row>,
+    cols: usize,
+    /// Maps column index (variable) to pivot row index
+    col_to_pivot: Vec<Option<usize>>,
+    /// Indices of free variables (columns without pivots)
+    free_vars: Vec<usize>,
+    /// Whether no row reads `0 = 1`.
+    consistent: bool,
+}
+
+impl Gf2Matrix {
+    /// An all-zero matrix.
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self {
+            rows: vec![Row::repeat(false, cols); rows],
+            cols,
+        }
+    }
+
+    /// A matrix with the given rows, each `cols` bits long.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a row has a different length.
+    pub fn from_rows(cols: usize, rows: Vec<Row>) -> Self {
+        assert!(
+            rows.iter().all(|row| row.len() == cols),
+            "every row must have {cols} columns"
+        );
+        Self { rows, cols }
+    }
+
+    /// A matrix with the given columns, each `rows` bits long.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a column has a different length.
+    pub fn from_columns(rows: usize, columns: &[Row]) -> Self {
+        let mut matrix = Self::zeros(rows, columns.len());
+        for (c, column) in columns.iter().enumerate() {
+            assert_eq!(column.len(), rows, "every column must have {rows} rows");
+            for r in column.iter_ones() {
+                matrix.rows[r].set(c, true);
+            }
+        }
+        matrix
+    }
+
+    pub fn nrows(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn ncols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> bool {
+        self.rows[row][col]
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: bool) {
+        self.rows[row].set(col, value);
+    }
+
+    pub fn row(&self, row: usize) -> &BitSlice<usize, Lsb0> {
+        &self.rows[row]
+    }
+
+    /// The product `A x`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` does not have one bit per column.
+    pub fn mul_vec(&self, x: &BitSlice<usize, Lsb0>) -> Row {
+        assert_eq!(x.len(), self.cols, "x must have one bit per column");
+        self.rows
+            .iter()
+            .map(|row| {
+                let mut product = row.clone();
+                product &= x;
+                product.count_ones() % 2 == 1
+            })
+            .collect()
+    }
+
+    /// The dimension of the column (and row) space.
+    pub fn rank(&self) -> usize {
+        self.cols
+            - self
+                .echelon(&Row::repeat(false, self.nrows()))
+                .free_vars
+                .len()
+    }
+
+    /// A basis of the null space, the `x` with `A x = 0`, with one vector per
+    /// free variable.
+    pub fn nullspace(&self) -> Vec<Row> {
+        self.echelon(&Row::repeat(false, self.nrows()))
+            .extract_solution_space()
+            .1
+    }
+
+    /// Every solution of `A x = b`, or `None` when there is none.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `b` does not have one bit per row.
+    pub fn solve(&self, b: &BitSlice<usize, Lsb0>) -> Option<SolutionSpace> {
+        assert_eq!(b.len(), self.nrows(), "b must have one bit per row");
+        let echelon = self.echelon(b);
+        if !echelon.consistent {
+            return None;
+        }
+        let (particular, basis) = echelon.extract_solution_space();
+        let pivots = (0..self.cols)
+            .filter(|&c| echelon.col_to_pivot[c].is_some())
+            .collect();
+        Some(SolutionSpace::new(particular, basis, pivots))
+    }
+
+    /// Reduces the augmented matrix `[A | b]`.
+    fn echelon(&self, b: &BitSlice<usize, Lsb0>) -> Echelon {
+        let matrix = self
+            .rows
+            .iter()
+            .zip(b.iter())
+            .map(|(row, bit)| {
+                let mut augmented = row.clone();
+                augmented.push(*bit);
+                augmented
+            })
+            .collect();
+        let mut echelon = Echelon {
+            matrix,
+            cols: self.cols,
+            col_to_pivot: vec![None; self.cols],
+            free_vars: Vec::new(),
+            consistent: false,
+        };
+        echelon.consistent = echelon.rref();
+        echelon
+    }
+}
+
+impl Echelon {
+    /// Performs Gaussian Elimination to transform the matrix into Reduced Row Echelon Form (RREF).
+    fn rref(&mut self) -> bool {
+        let num_eqs = self.matrix.len();
+        let mut pivot_row = 0;
+
+        for c in 0..self.cols {
+            if pivot_row >= num_eqs {
+                self.free_vars.push(c);
+                continue;
+            }
+
+            // Find a row with a 1 in the current column (pivot)
+            let Some(r) = (pivot_row..num_eqs).find(|&r| self.matrix[r][c]) else {
+                self.free_vars.push(c);
+                continue;
+            };
+
+            self.matrix.swap(pivot_row, r);
+            self.col_to_pivot[c] = Some(pivot_row);
+
+            // Clone pivot row to avoid multiple mutable borrows
+            let pivot_vec = self.matrix[pivot_row].clone();
+
+            // XOR eliminate other rows (both below AND above for RREF)
+            for i in 0..num_eqs {
+                if i != pivot_row && self.matrix[i][c] {
+                    let row = &mut self.matrix[i];
+                    *row ^= &pivot_vec;
+                }
+            }
+            pivot_row += 1;
+        }
+
+        // Check for consistency: 0 = 1?
+        // If a row is all zeros except the augmented column, no solution exists.
+        (pivot_row..num_eqs).all(|r| !self.matrix[r][self.cols])
+    }
+
+    /// Extracts the particular solution and the basis of the null space.
+    fn extract_solution_space(&self) -> (Row, Vec<Row>) {
+        // Particular Solution (x_p)
+        // Set all free variables to 0. Since matrix is in RREF,
+        // the pivot variables simply take the value of the augmented column.
+        let mut x_p = Row::repeat(false, self.cols);
+        for (c, &pivot_row) in self.col_to_pivot.iter().enumerate() {
+            if let Some(r) = pivot_row {
+                if self.matrix[r][self.cols] {
+                    x_p.set(c, true);
+                }
+            }
+        }
+
+        // Null Space Basis
+        // For each free variable f, set x_f = 1, others = 0, and solve for pivots.
+        let mut basis = Vec::with_capacity(self.free_vars.len());
+
+        for &f in &self.free_vars {
+            let mut v = Row::repeat(false, self.cols);
+            v.set(f, true);
+
+            // In RREF a pivot row only involves its pivot and free variables,
+            // so every pivot before `f` takes its row's entry in column `f`.
+            for c in 0..f {
+                if let Some(r) = self.col_to_pivot[c] {
+                    if self.matrix[r][f] {
+                        v.set(c, true);
+                    }
+                }
+            }
+            basis.push(v);
+        }
+
+        (x_p, basis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn random_matrix(rng: &mut StdRng) -> Gf2Matrix {
+        let rows = rng.random_range(0..=6);
+        let cols = rng.random_range(0..=9);
+        let density = rng.random_range(0.1..0.9);
+        Gf2Matrix::from_rows(
+            cols,
+            (0..rows)
+                .map(|_| (0..cols).map(|_| rng.random_bool(density)).collect())
+                .collect(),
+        )
+    }
+
+    /// Every `x` of `cols` bits, the naive way to search the whole space.
+    fn every_vector(cols: usize) -> impl Iterator<Item = Row> {
+        (0..1usize << cols).map(move |bits| (0..cols).map(|c| bits >> c & 1 == 1).collect())
+    }
+
+    #[test]
+    fn rank_counts_the_column_space() {
+        let mut rng = StdRng::seed_from_u64(525);
+        for _ in 0..100 {
+            let a = random_matrix(&mut rng);
+            let mut images = every_vector(a.ncols())
+                .map(|x| a.mul_vec(&x))
+                .collect::<Vec<_>>();
+            images.sort();
+            images.dedup();
+            assert_eq!(1 << a.rank(), images.len(), "{a:?}");
+        }
+    }
+
+    #[test]
+    fn nullspace_spans_every_kernel_vector() {
+        let mut rng = StdRng::seed_from_u64(525);
+        for _ in 0..100 {
+            let a = random_matrix(&mut rng);
+            let basis = a.nullspace();
+            assert_eq!(basis.len(), a.ncols() - a.rank());
+            for v in &basis {
+                assert!(a.mul_vec(v).not_any(), "{a:?}");
+            }
+            let kernel = every_vector(a.ncols())
+                .filter(|x| a.mul_vec(x).not_any())
+                .count();
+            assert_eq!(kernel, 1 << basis.len(), "{a:?}");
+        }
+    }
+
+    #[test]
+    fn solve_finds_exactly_the_solutions() {
+        let mut rng = StdRng::seed_from_u64(525);
+        for _ in 0..100 {
+            let a = random_matrix(&mut rng);
+            let b = (0..a.nrows())
+                .map(|_| rng.random_bool(0.5))
+                .collect::<Row>();
+            let mut expected = every_vector(a.ncols())
+                .filter(|x| a.mul_vec(x) == b)
+                .collect::<Vec<_>>();
+            expected.sort();
+
+            match a.solve(&b) {
+                None => assert!(expected.is_empty(), "{a:?} {b}"),
+                Some(space) => {
+                    let mut found = space.iter().unwrap().collect::<Vec<_>>();
+                    found.sort();
+                    assert_eq!(found, expected, "{a:?} {b}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn shapes_are_checked() {
+        let a =
+            Gf2Matrix::from_columns(2, &[bitvec![usize, Lsb0; 1, 0], bitvec![usize, Lsb0; 1, 1]]);
+        assert_eq!((a.nrows(), a.ncols()), (2, 2));
+        assert!(a.get(0, 1) && a.get(1, 1) && !a.get(1, 0));
+        assert_eq!(a.rank(), 2);
+        assert!(std::panic::catch_unwind(|| a.solve(&bitvec![usize, Lsb0; 1])).is_err());
+    }
+}
@@ -0,0 +1,359 @@
+use std::fmt;
+
+use crate::Row;
+
+/// Null spaces up to this many free variables are walked solution by
+/// solution; larger ones meet in the middle when they can.
+const GRAY_CODE_MAX_FREE: usize = 20;
+
+/// Meeting in the middle tabulates every pattern of the pivot variables, so
+/// it needs few of them.
+const MEET_IN_THE_MIDDLE_MAX_PIVOTS: usize = 24;
+
+/// The error for a null space with more solutions than can be counted, let
+/// alone searched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TooManySolutions {
+    /// The number of free variables.
+    pub dimension: usize,
+}
+
+impl fmt::Display for TooManySolutions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "a null space of {} dimensions has too many solutions to search",
+            self.dimension
+        )
+    }
+}
+
+impl std::error::Error for TooManySolutions {}
+
+/// The solutions of a consistent system `A x = b`: a particular solution
+/// XOR any combination of the null space basis.
+///
+/// Built by [`Gf2Matrix::solve`](crate::Gf2Matrix::solve), whose basis sets
+/// one free variable per vector and otherwise only pivot variables.
+#[derive(Debug, Clone)]
+pub struct SolutionSpace {
+    particular: Row,
+    basis: Vec<Row>,
+    /// The pivot columns, those not free.
+    pivots: Vec<usize>,
+}
+
+impl SolutionSpace {
+    pub(crate) fn new(particular: Row, basis: Vec<Row>, pivots: Vec<usize>) -> Self {
+        Self {
+            particular,
+            basis,
+            pivots,
+        }
+    }
+
+    /// The solution with every free variable at zero.
+    pub fn particular(&self) -> &Row {
+        &self.particular
+    }
+
+    /// The null space basis, one vector per free variable.
+    pub fn basis(&self) -> &[Row] {
+        &self.basis
+    }
+
+    /// The number of free variables; there are `2^dimension` solutions.
+    pub fn dimension(&self) -> usize {
+        self.basis.len()
+    }
+
+    /// Every solution, the particular one first, then walking the null
+    /// space in Gray-code order so each step is a single XOR.
+    ///
+    /// Fails when there are too many solutions to count in a `usize`.
+    pub fn iter(&self) -> Result<Solutions<'_>, TooManySolutions> {
+        Ok(Solutions {
+            current: self.particular.clone(),
+            basis: &self.basis,
+            step: 0,
+            end: self.walk_len()?,
+        })
+    }
+
+    /// The minimum Hamming weight of any solution.
+    ///
+    /// Walks every solution while the null space is small, and meets in the
+    /// middle past 20 free variables when there are few enough pivots. Fails
+    /// when neither can cover the null space.
+    pub fn min_weight(&self) -> Result<usize, TooManySolutions> {
+        if self.meets_in_the_middle() {
+            Ok(self.min_weight_meet_in_the_middle())
+        } else {
+            Ok(self.lightest_step()?.0)
+        }
+    }
+
+    /// A solution of [minimum weight](Self::min_weight).
+    pub fn min_weight_solution(&self) -> Result<Row, TooManySolutions> {
+        if self.meets_in_the_middle() {
+            return Ok(self.meet_in_the_middle().1);
+        }
+        // The walk only kept the step; its Gray code says which basis
+        // vectors were XORed in by then.
+        let (_, step) = self.lightest_step()?;
+        let gray = step ^ step >> 1;
+        let mut solution = self.particular.clone();
+        for i in (0..self.basis.len()).filter(|&i| gray >> i & 1 == 1) {
+            solution ^= &self.basis[i];
+        }
+        Ok(solution)
+    }
+
+    fn meets_in_the_middle(&self) -> bool {
+        // Each half of the basis is walked by subset, so the larger one must
+        // be countable too.
+        let half = self.basis.len() - self.basis.len() / 2;
+        self.basis.len() > GRAY_CODE_MAX_FREE
+            && self.pivots.len() <= MEET_IN_THE_MIDDLE_MAX_PIVOTS
+            && half < usize::BITS as usize
+    }
+
+    /// The number of solutions, if it fits in a `usize`.
+    fn walk_len(&self) -> Result<usize, TooManySolutions> {
+        u32::try_from(self.basis.len())
+            .ok()
+            .and_then(|free| 1usize.checked_shl(free))
+            .ok_or(TooManySolutions {
+                dimension: self.basis.len(),
+            })
+    }
+
+    /// The minimum weight over every solution, and the first step of the
+    /// Gray-code walk reaching it.
+    ///
+    /// Unlike [`Self::iter`], XORs each step into a single row and counts its
+    /// bits in place, so the walk allocates nothing.
+    fn lightest_step(&self) -> Result<(usize, usize), TooManySolutions> {
+        let end = self.walk_len()?;
+        let mut current = self.particular.clone();
+        let mut best = (current.count_ones(), 0);
+        for step in 1..end {
+            current ^= &self.basis[step.trailing_zeros() as usize];
+            let weight = current.count_ones();
+            if weight < best.0 {
+                best = (weight, step);
+            }
+        }
+        Ok(best)
+    }
+
+    /// The minimum Hamming weight in `O(2^(k/2) + p 2^p)` for `k` free
+    /// variables and `p` pivots, rather than `O(2^k)`.
+    ///
+    /// Each basis vector sets its own free variable and otherwise only
+    /// pivots, so choosing a set `S` of them weighs `|S|` plus the pivot bits
+    /// of `x_p ^ Σ S`. Splitting the basis in two halves `A` and `B`, the
+    /// first half is tabulated by pivot pattern, the table is spread over the
+    /// Hamming cube so each entry holds the cheapest way to come within any
+    /// distance of it, and the second half is walked against it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are too many pivots to tabulate, or too many free
+    /// variables to walk either half of the basis.
+    pub fn min_weight_meet_in_the_middle(&self) -> usize {
+        self.meet_in_the_middle().0
+    }
+
+    /// The minimum weight and a solution with it, by meeting in the middle.
+    fn meet_in_the_middle(&self) -> (usize, Row) {
+        assert!(
+            self.pivots.len() < usize::BITS as usize,
+            "too many pivots to tabulate"
+        );
+        let pattern = |x: &Row| {
+            self.pivots
+                .iter()
+                .enumerate()
+                .filter(|&(_, &c)| x[c])
+                .fold(0usize, |mask, (bit, _)| mask | 1 << bit)
+        };
+        let target = pattern(&self.particular);
+        let patterns = self.basis.iter().map(pattern).collect::<Vec<_>>();
+        let (first, second) = patterns.split_at(patterns.len() / 2);
+
+        // nearest[y]: fewest presses from the first half, plus the pivot bits
+        // left differing from `y`. Both are bounded by the basis half and the
+        // pivots, so a `u16` holds them and keeps the table at 32 MiB for 24
+        // pivots.
+        assert!(
+            first.len() + self.pivots.len() < u16::MAX as usize,
+            "too many free variables to tabulate"
+        );
+        let mut nearest = vec![u16::MAX; 1 << self.pivots.len()];
+        for (_, mask, weight) in subsets(first) {
+            let entry = &mut nearest[target ^ mask];
+            *entry = (*entry).min(weight as u16);
+        }
+        for bit in 0..self.pivots.len() {
+            for y in 0..nearest.len() {
+                let flipped = nearest[y ^ 1 << bit].saturating_add(1);
+                nearest[y] = nearest[y].min(flipped);
+            }
+        }
+
+        let (second_set, second_mask, weight) = subsets(second)
+            .map(|(set, mask, weight)| (set, mask, weight + nearest[mask] as usize))
+            .min_by_key(|&(_, _, weight)| weight)
+            .expect("the empty subset is always there");
+
+        // The table only kept the weight; one more walk of the first half
+        // finds a subset reaching it.
+        let (first_set, _, _) = subsets(first)
+            .map(|(set, mask, weight)| {
+                let differing = (target ^ mask ^ second_mask).count_ones() as usize;
+                (set, mask, weight + differing)
+            })
+            .min_by_key(|&(_, _, weight)| weight)
+            .expect("the empty subset is always there");
+
+        let mut solution = self.particular.clone();
+        let chosen = (0..first.len()).filter(|&i| first_set >> i & 1 == 1).chain(
+            (0..second.len())
+                .filter(|&i| second_set >> i & 1 == 1)
+                .map(|i| first.len() + i),
+        );
+        for i in chosen {
+            solution ^= &self.basis[i];
+        }
+        debug_assert_eq!(solution.count_ones(), weight);
+        (weight, solution)
+    }
+}
+
+/// Every subset of `patterns` in Gray-code order, as a bit set of the
+/// patterns chosen, the XOR of those patterns and their number.
+fn subsets(patterns: &[usize]) -> impl Iterator<Item = (usize, usize, usize)> + '_ {
+    let mut mask = 0;
+    (0..1usize << patterns.len()).map(move |step| {
+        if step > 0 {
+            mask ^= patterns[step.trailing_zeros() as usize];
+        }
+        let set = step ^ step >> 1;
+        (set, mask, set.count_ones() as usize)
+    })
+}
+
+/// Lazy iterator over a [`SolutionSpace`], returned by
+/// [`SolutionSpace::iter`].
+pub struct Solutions<'a> {
+    current: Row,
+    /// Null space basis; every solution is `current` XOR some combination.
+    basis: &'a [Row],
+    step: usize,
+    end: usize,
+}
+
+impl Iterator for Solutions<'_> {
+    type Item = Row;
+
+    fn next(&mut self) -> Option<Row> {
+        if self.step >= self.end {
+            return None;
+        }
+        // Gray Code Iteration:
+        // The bit that changes between gray(i-1) and gray(i) is the position
+        // of the lowest set bit in i, so one XOR moves to the next solution.
+        if self.step > 0 {
+            let basis_idx = self.step.trailing_zeros() as usize;
+            self.current ^= &self.basis[basis_idx];
+        }
+        self.step += 1;
+        Some(self.current.clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.step;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for Solutions<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Gf2Matrix;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn minimum_weights_agree() {
+        let mut rng = StdRng::seed_from_u64(525);
+        for _ in 0..100 {
+            let rows = rng.random_range(1..=6);
+            let cols = rng.random_range(1..=14);
+            let a = Gf2Matrix::from_rows(
+                cols,
+                (0..rows)
+                    .map(|_| (0..cols).map(|_| rng.random_bool(0.4)).collect())
+                    .collect(),
+            );
+            let b = (0..rows).map(|_| rng.random_bool(0.5)).collect::<Row>();
+            let Some(space) = a.solve(&b) else {
+                continue;
+            };
+            let walked = space.iter().unwrap().map(|x| x.count_ones()).min();
+            let walked = walked.unwrap();
+            assert_eq!(space.min_weight(), Ok(walked), "{a:?} {b}");
+            assert_eq!(space.min_weight_meet_in_the_middle(), walked, "{a:?} {b}");
+            let solution = space.min_weight_solution().unwrap();
+            for x in [solution, space.meet_in_the_middle().1] {
+                assert_eq!(x.count_ones(), walked, "{a:?} {b}");
+                assert_eq!(a.mul_vec(&x), b, "{a:?} {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn large_spaces_meet_in_the_middle() {
+        // 30 columns over 3 rows leave 27 free variables, too many to walk,
+        // yet a single column reaches the target.
+        let columns = (0..30)
+            .map(|i| (0..3).map(|r| r == i % 3).collect())
+            .collect::<Vec<Row>>();
+        let a = Gf2Matrix::from_columns(3, &columns);
+        let space = a
+            .solve(&[true, false, false].into_iter().collect::<Row>())
+            .unwrap();
+        assert_eq!(space.dimension(), 27);
+        assert_eq!(space.min_weight(), Ok(1));
+    }
+
+    #[test]
+    fn refuses_spaces_too_large_to_search() {
+        // 100 columns over 30 rows leave 30 pivots, too many to tabulate,
+        // and 70 free variables, too many to walk.
+        let columns = (0..100)
+            .map(|i| (0..30).map(|r| r == i % 30).collect())
+            .collect::<Vec<Row>>();
+        let a = Gf2Matrix::from_columns(30, &columns);
+        let space = a.solve(&Row::repeat(false, 30)).unwrap();
+        let error = TooManySolutions { dimension: 70 };
+        assert_eq!(space.dimension(), 70);
+        assert_eq!(space.iter().err(), Some(error));
+        assert_eq!(space.min_weight(), Err(error));
+        assert_eq!(space.min_weight_solution(), Err(error));
+    }
+
+    #[test]
+    fn iterates_every_combination_once() {
+        let a = Gf2Matrix::from_rows(3, vec![[true, true, true].into_iter().collect()]);
+        let space = a.solve(&[true].into_iter().collect::<Row>()).unwrap();
+        let solutions = space.iter().unwrap();
+        assert_eq!(solutions.len(), 4);
+        let mut weights = solutions.map(|x| x.count_ones()).collect::<Vec<_>>();
+        weights.sort_unstable();
+        assert_eq!(weights, [1, 1, 1, 3]);
+    }
+}
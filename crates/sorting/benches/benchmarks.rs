@@ -0,0 +1,81 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use sorting::algorithm::*;
+use sorting::SortAlgorithm;
+
+fn main() {
+    divan::main();
+}
+
+const SIZES: &[usize] = &[100, 1_000, 10_000];
+
+fn random_input(len: usize) -> Vec<u32> {
+    let mut rng = StdRng::seed_from_u64(539);
+    (0..len).map(|_| rng.random()).collect()
+}
+
+fn sorted_input(len: usize) -> Vec<u32> {
+    (0..len as u32).collect()
+}
+
+fn reversed_input(len: usize) -> Vec<u32> {
+    (0..len as u32).rev().collect()
+}
+
+/// Ascending runs of 32 that keep dropping back to zero.
+fn sawtooth_input(len: usize) -> Vec<u32> {
+    (0..len as u32).map(|i| i % 32).collect()
+}
+
+/// Random values drawn from only eight distinct ones.
+fn few_unique_input(len: usize) -> Vec<u32> {
+    let mut rng = StdRng::seed_from_u64(539);
+    (0..len).map(|_| rng.random_range(0..8)).collect()
+}
+
+fn bench<A: SortAlgorithm<u32> + Default>(bencher: divan::Bencher, input: Vec<u32>) {
+    bencher
+        .counter(divan::counter::ItemsCount::new(input.len()))
+        .with_inputs(|| input.clone())
+        .bench_local_refs(|data| A::default().sort(divan::black_box(data)));
+}
+
+#[divan::bench(
+    types = [SelectionSort, TopDownMergeSort, BottomUpMergeSort, QuickSort, HeapSort],
+    args = SIZES,
+)]
+fn random<A: SortAlgorithm<u32> + Default>(bencher: divan::Bencher, len: usize) {
+    bench::<A>(bencher, random_input(len));
+}
+
+#[divan::bench(
+    types = [SelectionSort, TopDownMergeSort, BottomUpMergeSort, QuickSort, HeapSort],
+    args = SIZES,
+)]
+fn sorted<A: SortAlgorithm<u32> + Default>(bencher: divan::Bencher, len: usize) {
+    bench::<A>(bencher, sorted_input(len));
+}
+
+#[divan::bench(
+    types = [SelectionSort, TopDownMergeSort, BottomUpMergeSort, QuickSort, HeapSort],
+    args = SIZES,
+)]
+fn reversed<A: SortAlgorithm<u32> + Default>(bencher: divan::Bencher, len: usize) {
+    bench::<A>(bencher, reversed_input(len));
+}
+
+#[divan::bench(
+    types = [SelectionSort, TopDownMergeSort, BottomUpMergeSort, QuickSort, HeapSort],
+    args = SIZES,
+)]
+fn sawtooth<A: SortAlgorithm<u32> + Default>(bencher: divan::Bencher, len: usize) {
+    bench::<A>(bencher, sawtooth_input(len));
+}
+
+#[divan::bench(
+    types = [SelectionSort, TopDownMergeSort, BottomUpMergeSort, QuickSort, HeapSort],
+    args = SIZES,
+)]
+fn few_unique<A: SortAlgorithm<u32> + Default>(bencher: divan::Bencher, len: usize) {
+    bench::<A>(bencher, few_unique_input(len));
+}
@@ -0,0 +1,70 @@
+use crate::{less, swap, Observer, SortAlgorithm};
+
+/// Heapsort: builds a max-heap in place, then repeatedly swaps its root to
+/// the end. `O(n log n)` in every case with no extra memory, but not stable.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeapSort;
+
+impl<T: Ord> SortAlgorithm<T> for HeapSort {
+    fn name(&self) -> &'static str {
+        "heap"
+    }
+
+    fn sort(&self, data: &mut [T]) {
+        sort(data, &mut ());
+    }
+
+    fn sort_observed(&self, data: &mut [T], observer: &mut dyn Observer) {
+        sort(data, observer);
+    }
+}
+
+fn sort<T: Ord, O: Observer + ?Sized>(data: &mut [T], observer: &mut O) {
+    for root in (0..data.len() / 2).rev() {
+        sift_down(data, root, observer);
+    }
+    for end in (1..data.len()).rev() {
+        swap(observer, data, 0, end);
+        sift_down(&mut data[..end], 0, observer);
+    }
+}
+
+/// Restores the heap property below `root`, assuming both of its subtrees
+/// already hold it.
+fn sift_down<T: Ord, O: Observer + ?Sized>(heap: &mut [T], mut root: usize, observer: &mut O) {
+    loop {
+        let left = 2 * root + 1;
+        if left >= heap.len() {
+            return;
+        }
+        let right = left + 1;
+        let child = if right < heap.len() && less(observer, &heap[left], &heap[right]) {
+            right
+        } else {
+            left
+        };
+        if !less(observer, &heap[root], &heap[child]) {
+            return;
+        }
+        swap(observer, heap, root, child);
+        root = child;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+
+    #[test]
+    fn sorts_like_std() {
+        agrees_with_std(&HeapSort);
+    }
+
+    #[test]
+    fn edge_cases() {
+        sorts_duplicates(&HeapSort);
+        sorts_sorted(&HeapSort);
+        sorts_reversed(&HeapSort);
+    }
+}
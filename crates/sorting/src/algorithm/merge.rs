@@ -0,0 +1,164 @@
+use crate::{less, swap, Observer, SortAlgorithm};
+
+/// Recursive merge sort: sorts each half, then merges them. Stable, and
+/// `O(n log n)` whatever the input, at the cost of a buffer of `n / 2`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TopDownMergeSort;
+
+/// Iterative merge sort: merges runs of 1, 2, 4, ... elements until a
+/// single run is left, without recursing.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BottomUpMergeSort;
+
+impl<T: Ord + Clone> SortAlgorithm<T> for TopDownMergeSort {
+    fn name(&self) -> &'static str {
+        "merge (top-down)"
+    }
+
+    fn sort(&self, data: &mut [T]) {
+        top_down(data, &mut Vec::with_capacity(data.len() / 2), &mut ());
+    }
+
+    fn sort_observed(&self, data: &mut [T], observer: &mut dyn Observer) {
+        top_down(data, &mut Vec::with_capacity(data.len() / 2), observer);
+    }
+}
+
+impl<T: Ord + Clone> SortAlgorithm<T> for BottomUpMergeSort {
+    fn name(&self) -> &'static str {
+        "merge (bottom-up)"
+    }
+
+    fn sort(&self, data: &mut [T]) {
+        bottom_up(data, &mut ());
+    }
+
+    fn sort_observed(&self, data: &mut [T], observer: &mut dyn Observer) {
+        bottom_up(data, observer);
+    }
+}
+
+fn top_down<T: Ord + Clone, O: Observer + ?Sized>(
+    data: &mut [T],
+    buffer: &mut Vec<T>,
+    observer: &mut O,
+) {
+    if data.len() <= 1 {
+        return;
+    }
+    let mid = data.len() / 2;
+    observer.descend();
+    top_down(&mut data[..mid], buffer, observer);
+    top_down(&mut data[mid..], buffer, observer);
+    observer.ascend();
+    merge(data, mid, buffer, observer);
+}
+
+fn bottom_up<T: Ord + Clone, O: Observer + ?Sized>(data: &mut [T], observer: &mut O) {
+    let mut buffer = Vec::with_capacity(data.len() / 2);
+    let mut width = 1;
+    while width < data.len() {
+        for start in (0..data.len()).step_by(2 * width) {
+            let end = (start + 2 * width).min(data.len());
+            if start + width < end {
+                merge(&mut data[start..end], width, &mut buffer, observer);
+            }
+        }
+        width *= 2;
+    }
+}
+
+/// Merges the sorted runs `data[..mid]` and `data[mid..]` in place.
+///
+/// Only the left run is copied out: the write position never overtakes the
+/// right run's read position, so its elements can be swapped down in place.
+fn merge<T: Ord + Clone, O: Observer + ?Sized>(
+    data: &mut [T],
+    mid: usize,
+    buffer: &mut Vec<T>,
+    observer: &mut O,
+) {
+    if !less(observer, &data[mid], &data[mid - 1]) {
+        return;
+    }
+    buffer.clear();
+    buffer.extend_from_slice(&data[..mid]);
+    let (mut left, mut right, mut out) = (0, mid, 0);
+    while left < buffer.len() && right < data.len() {
+        // Taking from the left on ties keeps the sort stable.
+        if less(observer, &data[right], &buffer[left]) {
+            swap(observer, data, out, right);
+            right += 1;
+        } else {
+            data[out] = buffer[left].clone();
+            left += 1;
+        }
+        out += 1;
+    }
+    for element in &buffer[left..] {
+        data[out] = element.clone();
+        out += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+
+    #[test]
+    fn sorts_like_std() {
+        agrees_with_std(&TopDownMergeSort);
+        agrees_with_std(&BottomUpMergeSort);
+    }
+
+    #[test]
+    fn edge_cases() {
+        for algorithm in [
+            &TopDownMergeSort as &dyn SortAlgorithm<i32>,
+            &BottomUpMergeSort,
+        ] {
+            sorts_duplicates(algorithm);
+            sorts_sorted(algorithm);
+            sorts_reversed(algorithm);
+        }
+    }
+
+    #[test]
+    fn merges_are_stable() {
+        /// Ordered by key alone, so equal keys tell their tags apart.
+        #[derive(Debug, Clone)]
+        struct Tagged(u8, usize);
+        impl PartialEq for Tagged {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+        impl Eq for Tagged {}
+        impl PartialOrd for Tagged {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for Tagged {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+
+        let data = (0..100)
+            .map(|i| Tagged((i * 37 % 5) as u8, i))
+            .collect::<Vec<_>>();
+        let mut expected = data.clone();
+        expected.sort();
+        for algorithm in [
+            &TopDownMergeSort as &dyn SortAlgorithm<Tagged>,
+            &BottomUpMergeSort,
+        ] {
+            let mut sorted = data.clone();
+            algorithm.sort(&mut sorted);
+            let tags = |v: &[Tagged]| v.iter().map(|t| t.1).collect::<Vec<_>>();
+            assert_eq!(tags(&sorted), tags(&expected), "{}", algorithm.name());
+        }
+    }
+}
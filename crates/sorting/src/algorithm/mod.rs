@@ -0,0 +1,41 @@
+//! The algorithms themselves, one module each.
+
+mod heap;
+mod merge;
+pub(crate) mod quick;
+mod selection;
+
+pub use heap::HeapSort;
+pub use merge::{BottomUpMergeSort, TopDownMergeSort};
+pub use quick::QuickSort;
+pub use selection::SelectionSort;
+
+use crate::SortAlgorithm;
+
+/// Every algorithm in the crate, for running them side by side.
+pub fn all<T: Ord + Clone>() -> Vec<Box<dyn SortAlgorithm<T>>> {
+    vec![
+        Box::new(SelectionSort),
+        Box::new(TopDownMergeSort),
+        Box::new(BottomUpMergeSort),
+        Box::new(QuickSort),
+        Box::new(HeapSort),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_unique() {
+        let mut names = all::<i32>()
+            .iter()
+            .map(|algorithm| algorithm.name())
+            .collect::<Vec<_>>();
+        let count = names.len();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), count);
+    }
+}
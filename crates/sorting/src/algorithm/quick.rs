@@ -0,0 +1,109 @@
+use crate::{less, swap, Observer, SortAlgorithm};
+
+/// Quicksort with a median-of-three pivot and Hoare partitioning.
+///
+/// The median of the first, middle and last elements keeps sorted and
+/// reversed inputs at `O(n log n)`, and Hoare's scans stop on elements equal
+/// to the pivot, so runs of duplicates split evenly instead of degrading to
+/// `O(n^2)`. Recursing into the smaller side bounds the stack at `O(log n)`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct QuickSort;
+
+impl<T: Ord> SortAlgorithm<T> for QuickSort {
+    fn name(&self) -> &'static str {
+        "quick"
+    }
+
+    fn sort(&self, data: &mut [T]) {
+        sort(data, &mut ());
+    }
+
+    fn sort_observed(&self, data: &mut [T], observer: &mut dyn Observer) {
+        sort(data, observer);
+    }
+}
+
+fn sort<T: Ord, O: Observer + ?Sized>(mut data: &mut [T], observer: &mut O) {
+    while data.len() > 1 {
+        let pivot = partition(data, observer);
+        let (left, right) = data.split_at_mut(pivot);
+        let right = &mut right[1..];
+        let (smaller, larger) = if left.len() < right.len() {
+            (left, right)
+        } else {
+            (right, left)
+        };
+        observer.descend();
+        sort(smaller, observer);
+        observer.ascend();
+        data = larger;
+    }
+}
+
+/// Moves the median of the first, middle and last elements to the front.
+fn median_of_three<T: Ord, O: Observer + ?Sized>(data: &mut [T], observer: &mut O) {
+    let (first, mid, last) = (0, data.len() / 2, data.len() - 1);
+    if less(observer, &data[mid], &data[first]) {
+        swap(observer, data, mid, first);
+    }
+    if less(observer, &data[last], &data[mid]) {
+        swap(observer, data, last, mid);
+        if less(observer, &data[mid], &data[first]) {
+            swap(observer, data, mid, first);
+        }
+    }
+    swap(observer, data, first, mid);
+}
+
+/// Partitions `data` around a median-of-three pivot and returns the pivot's
+/// final index: everything before it is no greater, everything after no
+/// smaller.
+pub(crate) fn partition<T: Ord, O: Observer + ?Sized>(data: &mut [T], observer: &mut O) -> usize {
+    median_of_three(data, observer);
+    let (pivot, rest) = data.split_first_mut().expect("partition needs data");
+    let (mut i, mut j) = (0, rest.len());
+    loop {
+        while i < j && less(observer, &rest[i], pivot) {
+            i += 1;
+        }
+        while i < j && less(observer, pivot, &rest[j - 1]) {
+            j -= 1;
+        }
+        if i >= j {
+            break;
+        }
+        swap(observer, rest, i, j - 1);
+        i += 1;
+        j -= 1;
+    }
+    // `rest[..i]` is no greater than the pivot and `rest[i..]` no smaller,
+    // so the pivot belongs at `i` once `rest[i - 1]` takes its place.
+    swap(observer, data, 0, i);
+    i
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+
+    #[test]
+    fn sorts_like_std() {
+        agrees_with_std(&QuickSort);
+    }
+
+    #[test]
+    fn edge_cases() {
+        sorts_duplicates(&QuickSort);
+        sorts_sorted(&QuickSort);
+        sorts_reversed(&QuickSort);
+    }
+
+    #[test]
+    fn partitions_around_the_pivot() {
+        let mut data = [5, 1, 9, 3, 7, 3, 8];
+        let pivot = partition(&mut data, &mut ());
+        assert!(data[..pivot].iter().all(|&x| x <= data[pivot]));
+        assert!(data[pivot + 1..].iter().all(|&x| x >= data[pivot]));
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{less, swap, Observer, SortAlgorithm};
+
+/// Selection sort: repeatedly swaps the smallest remaining element to the
+/// front. Always `O(n^2)` comparisons, but at most `n - 1` swaps.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SelectionSort;
+
+impl<T: Ord> SortAlgorithm<T> for SelectionSort {
+    fn name(&self) -> &'static str {
+        "selection"
+    }
+
+    fn sort(&self, data: &mut [T]) {
+        sort(data, &mut ());
+    }
+
+    fn sort_observed(&self, data: &mut [T], observer: &mut dyn Observer) {
+        sort(data, observer);
+    }
+}
+
+fn sort<T: Ord, O: Observer + ?Sized>(data: &mut [T], observer: &mut O) {
+    for i in 0..data.len() {
+        let smallest = (i + 1..data.len()).fold(i, |smallest, j| {
+            if less(observer, &data[j], &data[smallest]) {
+                j
+            } else {
+                smallest
+            }
+        });
+        if smallest != i {
+            swap(observer, data, i, smallest);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+
+    #[test]
+    fn sorts_like_std() {
+        agrees_with_std(&SelectionSort);
+    }
+
+    #[test]
+    fn edge_cases() {
+        sorts_duplicates(&SelectionSort);
+        sorts_sorted(&SelectionSort);
+        sorts_reversed(&SelectionSort);
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{Observer, SortAlgorithm};
+
+/// What one sort did, as counted by a [`CountingSorter`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SortStats {
+    pub comparisons: u64,
+    /// Merge sort's copies out of its buffer are moves, not swaps, and go
+    /// uncounted.
+    pub swaps: u64,
+    /// The deepest nesting of recursive calls below the first one; zero for
+    /// iterative algorithms.
+    pub max_depth: usize,
+}
+
+/// Wraps any algorithm to count what it does while it sorts.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CountingSorter<A> {
+    algorithm: A,
+}
+
+impl<A> CountingSorter<A> {
+    pub fn new(algorithm: A) -> Self {
+        Self { algorithm }
+    }
+
+    pub fn algorithm(&self) -> &A {
+        &self.algorithm
+    }
+
+    /// Sorts `data` with the wrapped algorithm and returns its tally.
+    pub fn sort<T: Ord>(&self, data: &mut [T]) -> SortStats
+    where
+        A: SortAlgorithm<T>,
+    {
+        let mut counter = Counter::default();
+        self.algorithm.sort_observed(data, &mut counter);
+        debug_assert_eq!(counter.depth, 0, "unbalanced descend and ascend");
+        counter.stats
+    }
+}
+
+#[derive(Default)]
+struct Counter {
+    stats: SortStats,
+    depth: usize,
+}
+
+impl Observer for Counter {
+    fn compare(&mut self) {
+        self.stats.comparisons += 1;
+    }
+
+    fn swap(&mut self) {
+        self.stats.swaps += 1;
+    }
+
+    fn descend(&mut self) {
+        self.depth += 1;
+        self.stats.max_depth = self.stats.max_depth.max(self.depth);
+    }
+
+    fn ascend(&mut self) {
+        self.depth -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::*;
+
+    #[test]
+    fn selection_sort_counts_are_exact() {
+        let mut data = [4, 2, 3, 1, 0];
+        let stats = CountingSorter::new(SelectionSort).sort(&mut data);
+        assert_eq!(data, [0, 1, 2, 3, 4]);
+        assert_eq!(
+            stats,
+            SortStats {
+                comparisons: 10,
+                swaps: 3,
+                max_depth: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn sorted_input_merges_for_free() {
+        // Every merge sees its halves already in order after one comparison.
+        let mut data = (0..8).collect::<Vec<_>>();
+        let stats = CountingSorter::new(TopDownMergeSort).sort(&mut data);
+        assert_eq!(
+            stats,
+            SortStats {
+                comparisons: 7,
+                swaps: 0,
+                max_depth: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn quicksort_recursion_stays_logarithmic() {
+        for data in [
+            (0..1024).collect::<Vec<_>>(),
+            (0..1024).rev().collect(),
+            vec![0; 1024],
+        ] {
+            let mut data = data;
+            let stats = CountingSorter::new(QuickSort).sort(&mut data);
+            assert!(data.is_sorted());
+            assert!(stats.max_depth <= 10, "{stats:?}");
+        }
+    }
+
+    #[test]
+    fn counts_are_deterministic() {
+        let data = (0..200).map(|i| i * 7919 % 211).collect::<Vec<_>>();
+        for algorithm in all::<i32>() {
+            let counter = CountingSorter::new(algorithm);
+            let (mut first, mut second) = (data.clone(), data.clone());
+            let stats = counter.sort(&mut first);
+            assert_eq!(stats, counter.sort(&mut second));
+            assert!(stats.comparisons > 0, "{}", counter.algorithm().name());
+        }
+    }
+}
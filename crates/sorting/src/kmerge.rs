@@ -0,0 +1,172 @@
+//! Lazily merging any number of sorted iterators into one.
+
+use std::iter::FusedIterator;
+
+/// The ordering [`kmerge`] merges by: `a < b`.
+pub type Ascending<T> = fn(&T, &T) -> bool;
+
+/// Merges `streams`, each already sorted, into a single sorted iterator.
+///
+/// Equal items come out in the order of the streams they came from, so
+/// merging is stable. Each item costs `O(log k)` for `k` streams.
+pub fn kmerge<I>(streams: impl IntoIterator<Item = I>) -> KMerge<I::IntoIter, Ascending<I::Item>>
+where
+    I: IntoIterator,
+    I::Item: Ord,
+{
+    kmerge_by(streams, PartialOrd::lt)
+}
+
+/// Like [`kmerge`], for streams sorted by `less` rather than by [`Ord`].
+pub fn kmerge_by<I, F>(streams: impl IntoIterator<Item = I>, less: F) -> KMerge<I::IntoIter, F>
+where
+    I: IntoIterator,
+    F: FnMut(&I::Item, &I::Item) -> bool,
+{
+    let mut streams = streams
+        .into_iter()
+        .map(IntoIterator::into_iter)
+        .collect::<Vec<_>>();
+    let heads = streams
+        .iter_mut()
+        .enumerate()
+        .filter_map(|(index, stream)| Some((stream.next()?, index)))
+        .collect();
+    let mut merge = KMerge {
+        streams,
+        heads,
+        less,
+    };
+    for root in (0..merge.heads.len() / 2).rev() {
+        merge.sift_down(root);
+    }
+    merge
+}
+
+/// The iterator returned by [`kmerge`] and [`kmerge_by`].
+pub struct KMerge<I: Iterator, F> {
+    streams: Vec<I>,
+    /// The next item of every stream not yet exhausted, with the stream's
+    /// index, as a min-heap.
+    heads: Vec<(I::Item, usize)>,
+    less: F,
+}
+
+impl<I, F> KMerge<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item, &I::Item) -> bool,
+{
+    /// Whether head `a` comes out before head `b`, ties going to the earlier
+    /// stream.
+    fn before(&mut self, a: usize, b: usize) -> bool {
+        let ((x, i), (y, j)) = (&self.heads[a], &self.heads[b]);
+        (self.less)(x, y) || (!(self.less)(y, x) && i < j)
+    }
+
+    fn sift_down(&mut self, mut root: usize) {
+        loop {
+            let left = 2 * root + 1;
+            if left >= self.heads.len() {
+                return;
+            }
+            let right = left + 1;
+            let child = if right < self.heads.len() && self.before(right, left) {
+                right
+            } else {
+                left
+            };
+            if !self.before(child, root) {
+                return;
+            }
+            self.heads.swap(root, child);
+            root = child;
+        }
+    }
+}
+
+impl<I, F> Iterator for KMerge<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item, &I::Item) -> bool,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let (_, stream) = self.heads.first()?;
+        let item = match self.streams[*stream].next() {
+            Some(next) => std::mem::replace(&mut self.heads[0].0, next),
+            None => self.heads.swap_remove(0).0,
+        };
+        self.sift_down(0);
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.heads
+            .iter()
+            .map(|(_, stream)| self.streams[*stream].size_hint())
+            .fold(
+                (self.heads.len(), Some(self.heads.len())),
+                |(lo, hi), (l, h)| {
+                    (
+                        lo.saturating_add(l),
+                        hi.zip(h).and_then(|(a, b)| a.checked_add(b)),
+                    )
+                },
+            )
+    }
+}
+
+impl<I, F> FusedIterator for KMerge<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item, &I::Item) -> bool,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn merges_like_sorting_the_concatenation() {
+        let mut rng = StdRng::seed_from_u64(542);
+        for _ in 0..200 {
+            let streams = (0..rng.random_range(0..8))
+                .map(|_| {
+                    let mut stream = (0..rng.random_range(0..20))
+                        .map(|_| rng.random_range(0..30))
+                        .collect::<Vec<u32>>();
+                    stream.sort_unstable();
+                    stream
+                })
+                .collect::<Vec<_>>();
+            let mut expected = streams.concat();
+            expected.sort_unstable();
+
+            let merged = kmerge(streams.clone());
+            assert_eq!(merged.size_hint(), (expected.len(), Some(expected.len())));
+            assert_eq!(merged.collect::<Vec<_>>(), expected, "{streams:?}");
+        }
+    }
+
+    #[test]
+    fn ties_keep_stream_order() {
+        let streams = [
+            vec![(1, 'a'), (2, 'a')],
+            vec![(1, 'b')],
+            vec![(0, 'c'), (1, 'c')],
+        ];
+        let merged = kmerge_by(streams, |x, y| x.0 < y.0).collect::<Vec<_>>();
+        assert_eq!(merged, [(0, 'c'), (1, 'a'), (1, 'b'), (1, 'c'), (2, 'a')]);
+    }
+
+    #[test]
+    fn merges_lazily() {
+        let merged = kmerge([(0..).step_by(2), (1..).step_by(2)]);
+        assert_eq!(merged.take(5).collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+    }
+}
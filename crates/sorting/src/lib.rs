@@ -0,0 +1,121 @@
+//! Classic comparison sorts, written out by hand to study and compare them.
+//!
+//! Every algorithm lives under [`algorithm`] and implements
+//! [`SortAlgorithm`], so they can be swapped for one another and walked as a
+//! whole with [`algorithm::all`]. Each one reports its comparisons, swaps
+//! and recursion to an [`Observer`], which [`CountingSorter`] uses to tally
+//! them into [`SortStats`]. When only a few elements are wanted in order,
+//! [`select`] finds them without sorting the rest, and [`kmerge`] merges
+//! streams that are sorted already.
+
+pub mod algorithm;
+mod counting;
+mod kmerge;
+pub mod select;
+
+pub use counting::{CountingSorter, SortStats};
+pub use kmerge::{kmerge, kmerge_by, Ascending, KMerge};
+
+/// An in-place sort of any totally ordered element type.
+pub trait SortAlgorithm<T: Ord> {
+    /// A short human-readable name, for tables and benchmark labels.
+    fn name(&self) -> &'static str;
+
+    /// Sorts `data` in ascending order.
+    fn sort(&self, data: &mut [T]) {
+        self.sort_observed(data, &mut ());
+    }
+
+    /// Sorts `data` in ascending order, reporting every step to `observer`.
+    fn sort_observed(&self, data: &mut [T], observer: &mut dyn Observer);
+}
+
+impl<T: Ord, A: SortAlgorithm<T> + ?Sized> SortAlgorithm<T> for Box<A> {
+    fn name(&self) -> &'static str {
+        (**self).name()
+    }
+
+    fn sort(&self, data: &mut [T]) {
+        (**self).sort(data);
+    }
+
+    fn sort_observed(&self, data: &mut [T], observer: &mut dyn Observer) {
+        (**self).sort_observed(data, observer);
+    }
+}
+
+/// Hooks an algorithm calls as it works. Every hook does nothing by default,
+/// and `()` observes nothing at all.
+pub trait Observer {
+    /// Two elements were compared.
+    fn compare(&mut self) {}
+
+    /// Two elements were swapped.
+    fn swap(&mut self) {}
+
+    /// The algorithm recursed one level deeper.
+    fn descend(&mut self) {}
+
+    /// The algorithm returned from a recursive call.
+    fn ascend(&mut self) {}
+}
+
+impl Observer for () {}
+
+/// `a < b`, reported to `observer`.
+pub(crate) fn less<T: Ord, O: Observer + ?Sized>(observer: &mut O, a: &T, b: &T) -> bool {
+    observer.compare();
+    a < b
+}
+
+/// Swaps `data[i]` and `data[j]`, reported to `observer`.
+pub(crate) fn swap<T, O: Observer + ?Sized>(observer: &mut O, data: &mut [T], i: usize, j: usize) {
+    observer.swap();
+    data.swap(i, j);
+}
+
+#[cfg(test)]
+pub(crate) mod testing {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use crate::SortAlgorithm;
+
+    /// Checks `algorithm` against the standard library on random inputs of
+    /// every length up to 64, some of them full of duplicates.
+    pub fn agrees_with_std(algorithm: &dyn SortAlgorithm<i32>) {
+        let mut rng = StdRng::seed_from_u64(535);
+        for len in 0..=64 {
+            for range in [4, 1000] {
+                let mut data = (0..len)
+                    .map(|_| rng.random_range(0..range))
+                    .collect::<Vec<i32>>();
+                let mut expected = data.clone();
+                expected.sort();
+                algorithm.sort(&mut data);
+                assert_eq!(data, expected, "{}", algorithm.name());
+            }
+        }
+    }
+
+    fn sorts(algorithm: &dyn SortAlgorithm<i32>, mut data: Vec<i32>) {
+        let mut expected = data.clone();
+        expected.sort();
+        algorithm.sort(&mut data);
+        assert_eq!(data, expected, "{}", algorithm.name());
+    }
+
+    /// Long runs of equal elements, including a slice of one repeated value.
+    pub fn sorts_duplicates(algorithm: &dyn SortAlgorithm<i32>) {
+        sorts(algorithm, vec![7; 100]);
+        sorts(algorithm, (0..500).map(|i| i * 7 % 3).collect());
+    }
+
+    pub fn sorts_sorted(algorithm: &dyn SortAlgorithm<i32>) {
+        sorts(algorithm, (0..500).collect());
+    }
+
+    pub fn sorts_reversed(algorithm: &dyn SortAlgorithm<i32>) {
+        sorts(algorithm, (0..500).rev().collect());
+    }
+}
@@ -0,0 +1,95 @@
+//! Finding the `k` smallest or largest elements without sorting them all.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::algorithm::quick::partition;
+
+/// Reorders `data` so the element at `n` is the one sorted order would put
+/// there, everything before it no greater and everything after no smaller,
+/// and returns it.
+///
+/// Quickselect: partitions like [`QuickSort`](crate::algorithm::QuickSort)
+/// but only follows the side holding `n`, for `O(n)` expected time.
+///
+/// # Panics
+///
+/// Panics if `n` is out of bounds.
+pub fn select_nth<T: Ord>(data: &mut [T], n: usize) -> &mut T {
+    assert!(n < data.len(), "index {n} out of bounds for {}", data.len());
+    let (mut lo, mut hi) = (0, data.len());
+    while hi - lo > 1 {
+        let pivot = lo + partition(&mut data[lo..hi], &mut ());
+        match n.cmp(&pivot) {
+            std::cmp::Ordering::Less => hi = pivot,
+            std::cmp::Ordering::Equal => break,
+            std::cmp::Ordering::Greater => lo = pivot + 1,
+        }
+    }
+    &mut data[n]
+}
+
+/// The `k` smallest items, smallest first.
+///
+/// Keeps a max-heap of the best `k` seen so far, evicting its top whenever a
+/// smaller item comes along: `O(n log k)` time and `O(k)` memory, in a single
+/// pass over an iterator of any length.
+pub fn bottom_k<T: Ord>(items: impl IntoIterator<Item = T>, k: usize) -> Vec<T> {
+    if k == 0 {
+        return Vec::new();
+    }
+    let mut heap = BinaryHeap::with_capacity(k);
+    for item in items {
+        if heap.len() < k {
+            heap.push(item);
+        } else if let Some(mut top) = heap.peek_mut() {
+            if item < *top {
+                *top = item;
+            }
+        }
+    }
+    heap.into_sorted_vec()
+}
+
+/// The `k` largest items, largest first, on the same bounded heap as
+/// [`bottom_k`].
+pub fn top_k<T: Ord>(items: impl IntoIterator<Item = T>, k: usize) -> Vec<T> {
+    bottom_k(items.into_iter().map(Reverse), k)
+        .into_iter()
+        .map(|Reverse(item)| item)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn selects_what_sorting_would() {
+        let mut rng = StdRng::seed_from_u64(541);
+        for len in 1..=64usize {
+            let data = (0..len)
+                .map(|_| rng.random_range(0..len as u32 / 2 + 1))
+                .collect::<Vec<u32>>();
+            let mut sorted = data.clone();
+            sorted.sort_unstable();
+            for n in 0..len {
+                let mut selected = data.clone();
+                assert_eq!(*select_nth(&mut selected, n), sorted[n]);
+                assert!(selected[..n].iter().all(|x| *x <= sorted[n]));
+                assert!(selected[n + 1..].iter().all(|x| *x >= sorted[n]));
+            }
+        }
+    }
+
+    #[test]
+    fn keeps_the_k_extremes_in_order() {
+        let data = [5, 1, 9, 3, 7, 3, 8];
+        assert_eq!(bottom_k(data, 3), [1, 3, 3]);
+        assert_eq!(top_k(data, 3), [9, 8, 7]);
+        assert_eq!(top_k(data, 0), Vec::<i32>::new());
+        assert_eq!(top_k(data, 10), [9, 8, 7, 5, 3, 3, 1]);
+    }
+}
@@ -0,0 +1,36 @@
+use {{crate_name}}::{part1, part2};
+use gungraun::{Dhat, LibraryBenchmarkConfig, library_benchmark, library_benchmark_group, main};
+use std::hint::black_box;
+
+/// The stored input, read before the measured function runs. Without one the
+/// benchmarks measure nothing.
+fn input() -> Option<&'static str> {
+    aoc_runtime::bench_input!()
+}
+
+#[library_benchmark]
+#[bench::part1(input())]
+fn bench_part1(input: Option<&str>) {
+    if let Some(input) = input {
+        black_box(part1::process(black_box(input)).unwrap());
+    }
+}
+
+#[library_benchmark]
+#[bench::part2(input())]
+fn bench_part2(input: Option<&str>) {
+    if let Some(input) = input {
+        black_box(part2::process(black_box(input)).unwrap());
+    }
+}
+
+library_benchmark_group!(
+    name = day_{{day}}_group;
+    benchmarks = bench_part1, bench_part2
+);
+
+main!(
+    config = LibraryBenchmarkConfig::default()
+        .tool(Dhat::default());
+    library_benchmark_groups = day_{{day}}_group
+);
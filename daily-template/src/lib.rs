@@ -1,2 +1,27 @@
+use aoc_common::answer::Answer;
+use aoc_common::solution::Solution;
+use miette::Result;
+
 pub mod part1;
 pub mod part2;
+
+/// Both parts solve from the same parsed input.
+pub struct Day{{day}};
+
+impl Solution for Day{{day}} {
+    type Parsed = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        part1::parse(input)
+    }
+
+    fn part1(items: &Self::Parsed) -> Result<Answer> {
+        part1::solve(items)
+    }
+
+    fn part2(items: &Self::Parsed) -> Result<Answer> {
+        part2::solve(items)
+    }
+}
+
+aoc_common::solution!(Day{{day}});
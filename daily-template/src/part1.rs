@@ -1,9 +1,20 @@
+use aoc_common::answer::Answer;
 use chumsky::prelude::*;
 use miette::*;
 
+/// Parses the puzzle input, one item per line.
+pub fn parse(input: &str) -> Result<Vec<String>> {
+    Ok(input.lines().map(str::to_owned).collect())
+}
+
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    Ok(String::from(""))
+    Ok(solve(&parse(input)?)?.to_string())
+}
+
+#[tracing::instrument(skip(_items))]
+pub fn solve(_items: &[String]) -> Result<Answer> {
+    Ok(Answer::Text(String::new()))
 }
 
 #[cfg(test)]
@@ -1,9 +1,17 @@
+use aoc_common::answer::Answer;
 use chumsky::prelude::*;
 use miette::*;
 
+use crate::part1::parse;
+
 #[tracing::instrument]
 pub fn process(input: &str) -> Result<String> {
-    Ok(String::from(""))
+    Ok(solve(&parse(input)?)?.to_string())
+}
+
+#[tracing::instrument(skip(_items))]
+pub fn solve(_items: &[String]) -> Result<Answer> {
+    Ok(Answer::Text(String::new()))
 }
 
 #[cfg(test)]
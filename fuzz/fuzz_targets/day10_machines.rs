@@ -0,0 +1,16 @@
+#![no_main]
+
+use aoc2025_day_10::part1::{parse, Machine};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(machines) = parse(input) {
+        let printed = machines
+            .iter()
+            .map(Machine::to_string)
+            .collect::<Vec<_>>()
+            .join("\n");
+        let reparsed = parse(&printed).expect("printed machines parse");
+        assert_eq!(reparsed, machines, "{printed:?}");
+    }
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use aoc2025_day_12::part1::parse;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(puzzle) = parse(input) {
+        let printed = puzzle.to_string();
+        let reparsed = parse(&printed).expect("a printed puzzle parses");
+        assert_eq!(reparsed, puzzle, "{printed:?}");
+    }
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+use aoc2025_day_4::diagram::Diagram;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(diagram) = input.parse::<Diagram>() {
+        let printed = diagram.to_string();
+        let reparsed = printed
+            .parse::<Diagram>()
+            .expect("a printed diagram parses");
+        assert_eq!(reparsed, diagram, "{printed:?}");
+    }
+});
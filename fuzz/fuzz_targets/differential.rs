@@ -0,0 +1,10 @@
+#![no_main]
+
+use aoc_differential::{day2, day9, seed_from_bytes};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let seed = seed_from_bytes(data);
+    day2::check(seed);
+    day9::check(seed);
+});
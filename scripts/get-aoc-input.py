@@ -87,6 +87,17 @@ def validate_day(day: str) -> str:
     return day
 
 
+def data_dir() -> Path:
+    """
+    Root of the input store the solution binaries read from.
+
+    Returns:
+        Path: `$XDG_DATA_HOME/aoc`, or `~/.local/share/aoc` when unset.
+    """
+    base = os.getenv("XDG_DATA_HOME") or Path.home() / ".local" / "share"
+    return Path(base) / "aoc"
+
+
 def get_aoc_input(
     year: str = typer.Argument(
         ..., help="Year of the Advent of Code event", callback=validate_year
@@ -95,7 +106,7 @@ def get_aoc_input(
         ..., help="Day of the Advent of Code event", callback=validate_day
     ),
     cwd: Path = typer.Option(
-        Path.cwd(), help="Current working directory to save the input files"
+        Path.cwd(), help="Workspace root to write the README into"
     ),
     session: str = typer.Option(
         None,
@@ -113,7 +124,7 @@ def get_aoc_input(
     ),
 ):
     """
-    Fetch the Advent of Code input for a given year and day, and save it to files.
+    Fetch the Advent of Code input for a given year and day into the input store.
     """
     configure_logging(log_level)
 
@@ -195,13 +206,11 @@ def get_aoc_input(
         log.info(f"Wrote 'README.md' for {year}/{day}")
         log.debug(f"Wrote {readme_path}")
 
-    for filename in ["input1.txt", "input2.txt"]:
-        file_path = cwd / year / day / filename
-        file_path.parent.mkdir(parents=True, exist_ok=True)
-        with open(file_path, "w") as file:
-            file.write(input_data)
-            log.info(f"Wrote '{filename}' for {year}/{day}")
-            log.debug(f"Wrote {file_path}")
+    store_path = data_dir() / year / day_number / "input.txt"
+    store_path.parent.mkdir(parents=True, exist_ok=True)
+    with open(store_path, "w") as file:
+        file.write(input_data)
+        log.info(f"Stored input for {year}/{day} in {store_path}")
 
 
 if __name__ == "__main__":